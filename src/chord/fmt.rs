@@ -0,0 +1,696 @@
+use std::fmt;
+use crate::chord::Chord;
+use crate::interval::Number;
+use crate::{Interval, Pitch};
+
+/// The notation used when formatting a [`Chord`] as a chord symbol.
+///
+/// Every style except [`Verbose`](Self::Verbose) can be parsed back into the same chord,
+/// as long as the chord's intervals could have come from a chord symbol in the first place.
+///
+/// | Chord | `Ascii` | `Jazz` | `Berklee` |
+/// |-------|---------|--------|-----------|
+/// | C minor seventh | `Cm7` | `C-7` | `C-7` |
+/// | C major seventh | `Cmaj7` | `CΔ7` | `Cmaj7` |
+/// | C half-diminished seventh | `Cm7b5` | `Cø7` | `C-7(♭5)` |
+/// | C diminished seventh | `Cdim7` | `C°7` | `Co7` |
+/// | C augmented triad | `Caug` | `C+` | `C+` |
+/// | C dominant thirteenth, sharp eleventh | `C13#11` | `C13♯11` | `C7(9,♯11,13)` |
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolStyle {
+    /// Plain ASCII, such as `F#m7b5` or `Bbmaj9`. This is what [`Display`](fmt::Display) uses.
+    #[default]
+    Ascii,
+    /// Jazz lead sheet glyphs, such as `F♯ø7`, `B♭Δ9`, or `C°7`.
+    Jazz,
+    /// Berklee style, where every tension of a seventh chord is listed in parentheses,
+    /// such as `F♯-7(♭5)` or `B♭maj7(9)`.
+    Berklee,
+    /// An English description, such as "C dominant seventh, first inversion".
+    Verbose,
+}
+
+/// Helper type for displaying chords as chord symbols.
+///
+/// Obtained via [`Chord::display_symbol()`].
+///
+/// # Examples
+/// ```
+/// # use music_theory::Pitch;
+/// # use music_theory::chord::{Chord, SymbolStyle};
+/// # use music_theory::chord::types::ChordType;
+/// let chord = Chord::from_type(ChordType::HalfDiminishedSeventh, Pitch::F_SHARP, 2).unwrap();
+///
+/// assert_eq!(chord.display_symbol(SymbolStyle::Ascii).to_string(), "F#m7b5/C");
+/// assert_eq!(chord.display_symbol(SymbolStyle::Jazz).to_string(), "F♯ø7/C");
+/// assert_eq!(chord.display_symbol(SymbolStyle::Berklee).to_string(), "F♯-7(♭5)/C");
+/// assert_eq!(
+///     chord.display_symbol(SymbolStyle::Verbose).to_string(),
+///     "F♯ half-diminished seventh, second inversion",
+/// );
+/// ```
+pub struct DisplaySymbol<'a> {
+    chord: &'a Chord,
+    style: SymbolStyle,
+}
+
+impl Chord {
+    /// Returns a helper to display the chord as a chord symbol in the given [`SymbolStyle`].
+    ///
+    /// Works for any chord, including ones without a [`ChordType`](crate::chord::types::ChordType).
+    /// Inverted chords are written with slash notation, or with the inversion's name when verbose.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Pitch, Interval};
+    /// # use music_theory::chord::{Chord, SymbolStyle};
+    /// let intervals = vec![
+    ///     Interval::PERFECT_UNISON,
+    ///     Interval::MAJOR_THIRD,
+    ///     Interval::MINOR_SEVENTH,
+    ///     Interval::MINOR_NINTH,
+    ///     Interval::MINOR_THIRTEENTH,
+    /// ];
+    ///
+    /// let chord = Chord::from_intervals(intervals, Pitch::G, 0).unwrap();
+    ///
+    /// assert_eq!(chord.display_symbol(SymbolStyle::Ascii).to_string(), "G7b9b13no5");
+    /// assert_eq!(chord.display_symbol(SymbolStyle::Berklee).to_string(), "G7(♭9,♭13,no5)");
+    /// assert_eq!(
+    ///     chord.display_symbol(SymbolStyle::Verbose).to_string(),
+    ///     "G dominant seventh with flat ninth and flat thirteenth, without fifth",
+    /// );
+    /// ```
+    pub fn display_symbol(&self, style: SymbolStyle) -> DisplaySymbol<'_> {
+        DisplaySymbol { chord: self, style }
+    }
+}
+
+impl fmt::Display for Chord {
+    /// Formats the chord as a chord symbol, in the [`Ascii`](SymbolStyle::Ascii) style.
+    ///
+    /// See [`Chord::display_symbol`] for other styles.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::chord::types::ChordType;
+    /// let chord = Chord::from_type(ChordType::DominantSeventh, Pitch::B_FLAT, 1).unwrap();
+    /// assert_eq!(chord.to_string(), "Bb7/D");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_symbol(SymbolStyle::default()).fmt(f)
+    }
+}
+
+impl fmt::Display for DisplaySymbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = Symbol::analyze(self.chord.intervals());
+        let bass = (self.chord.inversion() != 0).then(|| self.chord.pitches()[0]);
+
+        match self.style {
+            SymbolStyle::Ascii => symbol.write_compact(f, self.chord.root, bass, true),
+            SymbolStyle::Jazz => symbol.write_compact(f, self.chord.root, bass, false),
+            SymbolStyle::Berklee => symbol.write_berklee(f, self.chord.root, bass),
+            SymbolStyle::Verbose => symbol.write_verbose(f, self.chord.root, self.chord.inversion()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Base {
+    Major,
+    Minor,
+    Diminished,
+    HalfDiminished,
+    Augmented,
+    Power,
+}
+
+/// A chord broken down into the parts of a chord symbol.
+///
+/// Every interval of the chord ends up in exactly one part, so that parsing the
+/// written symbol gives back the same intervals.
+struct Symbol {
+    base: Base,
+    seventh: Option<Interval>,
+    // the highest stacked tension, e.g. 13 in "C13"
+    extension: Option<i16>,
+    sixth: bool,
+    six_nine: bool,
+    sus: Vec<Interval>,
+    // altered tones that replace the unaltered tone, like the "b9" in "C7b9"
+    alterations: Vec<Interval>,
+    // tones added on top, like the "add9" in "Cadd9"
+    additions: Vec<Interval>,
+    omissions: Vec<i16>,
+}
+
+fn take(rest: &mut Vec<Interval>, ivl: Interval) -> bool {
+    match rest.iter().position(|&i| i == ivl) {
+        Some(idx) => {
+            rest.remove(idx);
+            true
+        }
+        None => false,
+    }
+}
+
+fn natural(degree: i16) -> Interval {
+    Interval::new_maj_or_perfect(Number::new(degree).expect("degrees aren't zero"))
+}
+
+// how many semitones an interval is raised or lowered from its major or perfect version
+fn alteration(ivl: Interval) -> i16 {
+    ivl.semitones().0 - natural(ivl.number().get()).semitones().0
+}
+
+impl Symbol {
+    fn analyze(intervals: &[Interval]) -> Self {
+        use Interval as I;
+
+        let mut rest = intervals.to_vec();
+
+        let has_root = take(&mut rest, I::PERFECT_UNISON);
+
+        let third = [I::MAJOR_THIRD, I::MINOR_THIRD].into_iter().find(|&ivl| take(&mut rest, ivl));
+        let fifth = [I::PERFECT_FIFTH, I::DIMINISHED_FIFTH, I::AUGMENTED_FIFTH].into_iter().find(|&ivl| take(&mut rest, ivl));
+
+        let mut base = match (third, fifth) {
+            (Some(I::MINOR_THIRD), Some(I::DIMINISHED_FIFTH)) => Base::Diminished,
+            (Some(I::MAJOR_THIRD), Some(I::AUGMENTED_FIFTH)) => Base::Augmented,
+            (Some(I::MINOR_THIRD), _) => Base::Minor,
+            _ => Base::Major,
+        };
+
+        let mut sevenths = vec![I::MINOR_SEVENTH, I::MAJOR_SEVENTH];
+
+        if base == Base::Diminished {
+            sevenths.push(I::DIMINISHED_SEVENTH);
+        }
+
+        let seventh = sevenths.into_iter().find(|&ivl| take(&mut rest, ivl));
+
+        if base == Base::Diminished && seventh == Some(I::MINOR_SEVENTH) {
+            base = Base::HalfDiminished;
+        }
+
+        let mut omissions = Vec::new();
+
+        if !has_root {
+            omissions.push(1);
+        }
+
+        let sus = if third.is_none() {
+            [I::PERFECT_FOURTH, I::MAJOR_SECOND].into_iter()
+                .filter(|&ivl| take(&mut rest, ivl))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let sixth = seventh.is_none() && take(&mut rest, I::MAJOR_SIXTH);
+        let six_nine = sixth && take(&mut rest, I::MAJOR_NINTH);
+
+        if third.is_none() && sus.is_empty() {
+            if seventh.is_none() && !sixth {
+                base = Base::Power;
+            } else {
+                omissions.push(3);
+            }
+        }
+
+        if fifth.is_none() {
+            omissions.push(5);
+        }
+
+        let mut extension = None;
+
+        if seventh.is_some() {
+            // the extension stacks up every tension below it, so it can only go as high as
+            // every tension is there, and the top one has to be unaltered
+            for degree in [9, 11, 13] {
+                if !rest.iter().any(|ivl| ivl.number().get() == degree) {
+                    break;
+                }
+
+                if rest.contains(&natural(degree)) {
+                    extension = Some(degree);
+                }
+            }
+
+            for degree in [9, 11, 13].into_iter().filter(|&d| Some(d) <= extension) {
+                take(&mut rest, natural(degree));
+            }
+        }
+
+        // the symbol's triad implies a fifth, which an altered fifth replaces
+        if let Some(fifth) = fifth {
+            let implied = match base {
+                Base::Diminished | Base::HalfDiminished => I::DIMINISHED_FIFTH,
+                Base::Augmented => I::AUGMENTED_FIFTH,
+                _ => I::PERFECT_FIFTH,
+            };
+
+            if fifth != implied {
+                rest.push(fifth);
+            }
+        }
+
+        rest.sort_by_key(|ivl| (ivl.number(), ivl.semitones()));
+
+        // an alteration replaces the unaltered tone when parsed, so it can only be
+        // written as one if the chord doesn't also have the unaltered tone
+        let (alterations, additions) = rest.into_iter()
+            .partition(|&ivl| {
+                let number = ivl.number().get();
+
+                let allowed = match alteration(ivl) {
+                    1 => [5, 9, 11].contains(&number),
+                    -1 => [5, 6, 9, 13].contains(&number),
+                    _ => false,
+                };
+
+                allowed && !intervals.contains(&natural(number))
+            });
+
+        Self {
+            base,
+            seventh,
+            extension,
+            sixth,
+            six_nine,
+            sus,
+            alterations,
+            additions,
+            omissions,
+        }
+    }
+
+    fn is_major_seventh(&self) -> bool {
+        self.seventh == Some(Interval::MAJOR_SEVENTH)
+    }
+
+    fn write_compact(&self, f: &mut fmt::Formatter<'_>, root: Pitch, bass: Option<Pitch>, ascii: bool) -> fmt::Result {
+        let mut s = String::new();
+
+        let quality = match (self.base, ascii) {
+            (Base::Major, _) => "",
+            (Base::Minor | Base::HalfDiminished, true) => "m",
+            (Base::Minor, false) => "-",
+            (Base::Diminished, true) => "dim",
+            (Base::Diminished, false) => "°",
+            (Base::HalfDiminished, false) => "ø",
+            (Base::Augmented, true) => "aug",
+            (Base::Augmented, false) => "+",
+            (Base::Power, _) => "5",
+        };
+
+        s.push_str(quality);
+
+        if self.seventh.is_some() {
+            if self.is_major_seventh() {
+                s.push_str(match (self.base, ascii) {
+                    (Base::Major, true) => "maj",
+                    (_, true) => "Maj",
+                    (_, false) => "Δ",
+                });
+            }
+
+            s.push_str(&self.extension.unwrap_or(7).to_string());
+
+            if ascii && self.base == Base::HalfDiminished {
+                s.push_str("b5");
+            }
+        }
+
+        if self.sixth {
+            s.push('6');
+        }
+
+        if self.six_nine {
+            s.push_str("/9");
+        }
+
+        for &ivl in &self.sus {
+            s.push_str(&format!("sus{}", ivl.number()));
+        }
+
+        if !self.alterations.is_empty() {
+            let alterations = self.alterations.iter()
+                .map(|&ivl| degree_symbol(ivl, ascii))
+                .collect::<String>();
+
+            // an alteration right after the root would be read as the root's accidental
+            if s.is_empty() {
+                s = format!("({alterations})");
+            } else {
+                s.push_str(&alterations);
+            }
+        }
+
+        for &ivl in &self.additions {
+            s.push_str(&format!("add{}", degree_symbol(ivl, ascii)));
+        }
+
+        for &degree in &self.omissions {
+            s.push_str(&format!("no{degree}"));
+        }
+
+        write_pitch(f, root, ascii)?;
+        write!(f, "{s}")?;
+
+        if let Some(bass) = bass {
+            write!(f, "/")?;
+            write_pitch(f, bass, ascii)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_berklee(&self, f: &mut fmt::Formatter<'_>, root: Pitch, bass: Option<Pitch>) -> fmt::Result {
+        let mut tensions = Vec::new();
+
+        let quality = match self.base {
+            Base::Major => "",
+            Base::Minor | Base::HalfDiminished => "-",
+            Base::Diminished => "o",
+            Base::Augmented => "+",
+            Base::Power => "5",
+        };
+
+        let mut seventh = "";
+
+        if self.seventh.is_some() {
+            if !self.is_major_seventh() {
+                seventh = "7";
+            } else if self.base == Base::Major {
+                seventh = "maj7";
+            } else {
+                tensions.push("maj7".to_owned());
+            }
+        }
+
+        if self.base == Base::HalfDiminished {
+            tensions.push("♭5".to_owned());
+        }
+
+        if self.six_nine {
+            tensions.push("9".to_owned());
+        }
+
+        let stacked = [9, 11, 13].into_iter()
+            .filter(|&d| Some(d) <= self.extension)
+            .map(natural);
+
+        let mut altered = stacked.chain(self.alterations.iter().copied()).collect::<Vec<_>>();
+        altered.sort_by_key(|ivl| (ivl.number(), ivl.semitones()));
+
+        tensions.extend(altered.into_iter().map(|ivl| degree_symbol(ivl, false)));
+        tensions.extend(self.additions.iter().map(|&ivl| format!("add{}", degree_symbol(ivl, false))));
+        tensions.extend(self.omissions.iter().map(|degree| format!("no{degree}")));
+
+        write!(f, "{root}{quality}{seventh}")?;
+
+        if self.sixth {
+            write!(f, "6")?;
+        }
+
+        for &ivl in &self.sus {
+            write!(f, "sus{}", ivl.number())?;
+        }
+
+        if !tensions.is_empty() {
+            write!(f, "({})", tensions.join(","))?;
+        }
+
+        if let Some(bass) = bass {
+            write!(f, "/{bass}")?;
+        }
+
+        Ok(())
+    }
+
+    fn write_verbose(&self, f: &mut fmt::Formatter<'_>, root: Pitch, inversion: u8) -> fmt::Result {
+        let quality = match self.base {
+            Base::Major => "major",
+            Base::Minor => "minor",
+            Base::Diminished => "diminished",
+            Base::HalfDiminished => "half-diminished",
+            Base::Augmented => "augmented",
+            Base::Power => "power",
+        };
+
+        let name = match (self.base, self.seventh) {
+            (_, Some(seventh)) => {
+                let family = match (self.base, seventh == Interval::MAJOR_SEVENTH) {
+                    (Base::Major, false) => "dominant".to_owned(),
+                    (Base::Major, true) => "major".to_owned(),
+                    (Base::Minor, true) => "minor-major".to_owned(),
+                    (_, true) => format!("{quality} major"),
+                    (_, false) => quality.to_owned(),
+                };
+
+                format!("{family} {}", ordinal(self.extension.unwrap_or(7)))
+            }
+            (Base::Power, None) => "power chord".to_owned(),
+            (_, None) if self.six_nine => format!("{quality} six-nine"),
+            (_, None) if self.sixth => format!("{quality} sixth"),
+            (_, None) if !self.sus.is_empty() => "suspended".to_owned(),
+            (_, None) => format!("{quality} triad"),
+        };
+
+        write!(f, "{root} {name}")?;
+
+        if !self.sus.is_empty() {
+            let sus = self.sus.iter()
+                .rev()
+                .map(|ivl| ordinal(ivl.number().get()))
+                .collect::<Vec<_>>();
+
+            if self.seventh.is_some() || self.sixth {
+                write!(f, " suspended")?;
+            }
+
+            write!(f, " {}", join_english(&sus))?;
+        }
+
+        let with = self.alterations.iter()
+            .map(|&ivl| degree_name(ivl))
+            .chain(self.additions.iter().map(|&ivl| format!("added {}", degree_name(ivl))))
+            .collect::<Vec<_>>();
+
+        if !with.is_empty() {
+            write!(f, " with {}", join_english(&with))?;
+        }
+
+        if !self.omissions.is_empty() {
+            let without = self.omissions.iter()
+                .map(|&degree| ordinal(degree))
+                .collect::<Vec<_>>();
+
+            write!(f, ", without {}", join_english(&without))?;
+        }
+
+        if inversion != 0 {
+            write!(f, ", {} inversion", ordinal_number(inversion as _))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_pitch(f: &mut fmt::Formatter<'_>, pitch: Pitch, ascii: bool) -> fmt::Result {
+    if !ascii {
+        return write!(f, "{pitch}");
+    }
+
+    let offset = pitch.accidental().offset;
+    let symbol = if offset > 0 { "#" } else { "b" };
+
+    write!(f, "{}{}", pitch.letter(), symbol.repeat(offset.unsigned_abs() as _))
+}
+
+// e.g. "b9" or "♯11"
+fn degree_symbol(ivl: Interval, ascii: bool) -> String {
+    let offset = alteration(ivl);
+
+    let symbol = match (offset > 0, ascii) {
+        (true, true) => "#",
+        (true, false) => "♯",
+        (false, true) => "b",
+        (false, false) => "♭",
+    };
+
+    format!("{}{}", symbol.repeat(offset.unsigned_abs() as _), ivl.number())
+}
+
+// e.g. "flat ninth" or "sharp eleventh"
+fn degree_name(ivl: Interval) -> String {
+    let accidental = match alteration(ivl) {
+        0 => "",
+        1 => "sharp ",
+        -1 => "flat ",
+        2 => "double sharp ",
+        -2 => "double flat ",
+        n if n > 0 => "augmented ",
+        _ => "diminished ",
+    };
+
+    format!("{accidental}{}", ordinal(ivl.number().get()))
+}
+
+fn ordinal(degree: i16) -> String {
+    match degree {
+        1 => "root".to_owned(),
+        2 => "second".to_owned(),
+        3 => "third".to_owned(),
+        4 => "fourth".to_owned(),
+        5 => "fifth".to_owned(),
+        6 => "sixth".to_owned(),
+        7 => "seventh".to_owned(),
+        9 => "ninth".to_owned(),
+        11 => "eleventh".to_owned(),
+        13 => "thirteenth".to_owned(),
+        n => ordinal_number(n),
+    }
+}
+
+fn ordinal_number(n: i16) -> String {
+    match n {
+        1 => "first".to_owned(),
+        2 => "second".to_owned(),
+        3 => "third".to_owned(),
+        4 => "fourth".to_owned(),
+        5 => "fifth".to_owned(),
+        6 => "sixth".to_owned(),
+        7 => "seventh".to_owned(),
+        8 => "eighth".to_owned(),
+        9 => "ninth".to_owned(),
+        n => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+
+            format!("{n}{suffix}")
+        }
+    }
+}
+
+// "a", "a and b", or "a, b, and c"
+fn join_english(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [a, b] => format!("{a} and {b}"),
+        [init @ .., last] => format!("{}, and {last}", init.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+    use super::*;
+    use crate::chord::types::ChordType;
+
+    fn symbol(s: &str, style: SymbolStyle) -> String {
+        s.parse::<Chord>()
+            .unwrap_or_else(|err| panic!("failed to parse {s:?}: {err}"))
+            .display_symbol(style)
+            .to_string()
+    }
+
+    #[test]
+    fn round_trip() {
+        let roots = [Pitch::C, Pitch::F_SHARP, Pitch::B_FLAT, Pitch::E_DOUBLE_FLAT, Pitch::G_DOUBLE_SHARP];
+
+        for ty in ChordType::iter() {
+            for root in roots {
+                for inversion in 0..ty.intervals().len() as u8 {
+                    let chord = Chord::from_type(ty, root, inversion).unwrap();
+
+                    for style in [SymbolStyle::Ascii, SymbolStyle::Jazz, SymbolStyle::Berklee] {
+                        let symbol = chord.display_symbol(style).to_string();
+
+                        let parsed = Chord::from_str_strict(&symbol)
+                            .unwrap_or_else(|err| panic!("failed to parse {symbol:?} from {ty:?}: {err}"));
+
+                        assert_eq!(parsed, chord, "{symbol:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_symbols() {
+        let symbols = [
+            "Bb13#11", "C7b9#9", "C7alt", "Cm11b5", "Csus2sus4", "Cmaj9sus4", "C7add6no5", "C5add9",
+            "Caddb3", "Cm6/9", "C6b9", "Cdim7b13", "CdimMaj7", "Cm7#5", "C9b9add9", "C7b5add5", "C13no1",
+            "G/F", "Am/G", "D7sus4/C",
+        ];
+
+        for s in symbols {
+            let chord = s.parse::<Chord>().unwrap();
+
+            for style in [SymbolStyle::Ascii, SymbolStyle::Jazz, SymbolStyle::Berklee] {
+                let symbol = chord.display_symbol(style).to_string();
+                assert_eq!(Chord::from_str_strict(&symbol), Ok(chord.clone()), "{s} -> {symbol}");
+            }
+        }
+    }
+
+    #[test]
+    fn styles() {
+        use SymbolStyle as S;
+
+        let cases = [
+            ("C", "C", "C", "C", "C major triad"),
+            ("Cm", "Cm", "C-", "C-", "C minor triad"),
+            ("C°", "Cdim", "C°", "Co", "C diminished triad"),
+            ("C+", "Caug", "C+", "C+", "C augmented triad"),
+            ("C5", "C5", "C5", "C5", "C power chord"),
+            ("Csus4", "Csus4", "Csus4", "Csus4", "C suspended fourth"),
+            ("C7sus4", "C7sus4", "C7sus4", "C7sus4", "C dominant seventh suspended fourth"),
+            ("C6", "C6", "C6", "C6", "C major sixth"),
+            ("Cm6/9", "Cm6/9", "C-6/9", "C-6(9)", "C minor six-nine"),
+            ("C7", "C7", "C7", "C7", "C dominant seventh"),
+            ("Cmaj9", "Cmaj9", "CΔ9", "Cmaj7(9)", "C major ninth"),
+            ("CmMaj7", "CmMaj7", "C-Δ7", "C-(maj7)", "C minor-major seventh"),
+            ("Cdim7", "Cdim7", "C°7", "Co7", "C diminished seventh"),
+            ("Cm7b5", "Cm7b5", "Cø7", "C-7(♭5)", "C half-diminished seventh"),
+            ("C+7", "Caug7", "C+7", "C+7", "C augmented seventh"),
+            ("Cmaj7#5", "CaugMaj7", "C+Δ7", "C+(maj7)", "C augmented major seventh"),
+            ("C13", "C13", "C13", "C7(9,11,13)", "C dominant thirteenth"),
+            ("C7#9", "C7#9", "C7♯9", "C7(♯9)", "C dominant seventh with sharp ninth"),
+            ("C7b5", "C7b5", "C7♭5", "C7(♭5)", "C dominant seventh with flat fifth"),
+            ("C(b5)", "C(b5)", "C(♭5)", "C(♭5)", "C major triad with flat fifth"),
+            ("Cadd9", "Cadd9", "Cadd9", "C(add9)", "C major triad with added ninth"),
+            ("Cmb6", "Cmb6", "C-♭6", "C-(♭6)", "C minor triad with flat sixth"),
+            ("C7no3", "C7no3", "C7no3", "C7(no3)", "C dominant seventh, without third"),
+            (
+                "C7b9#11no5/E",
+                "C7b9#11no5/E", "C7♭9♯11no5/E", "C7(♭9,♯11,no5)/E",
+                "C dominant seventh with flat ninth and sharp eleventh, without fifth, first inversion",
+            ),
+            (
+                "Ebm11/Db",
+                "Ebm11/Db", "E♭-11/D♭", "E♭-7(9,11)/D♭",
+                "E♭ minor eleventh, third inversion",
+            ),
+        ];
+
+        for (s, ascii, jazz, berklee, verbose) in cases {
+            assert_eq!(symbol(s, S::Ascii), ascii);
+            assert_eq!(symbol(s, S::Jazz), jazz);
+            assert_eq!(symbol(s, S::Berklee), berklee);
+            assert_eq!(symbol(s, S::Verbose), verbose);
+        }
+    }
+}
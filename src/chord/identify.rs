@@ -0,0 +1,283 @@
+use strum::IntoEnumIterator;
+use crate::chord::Chord;
+use crate::chord::types::ChordType;
+use crate::{EnharmonicEq, EnharmonicOrd, Interval, Note, Pitch};
+
+/// A possible name for a set of pitches, returned by [`Chord::identify`].
+///
+/// See [`Chord::identify`] for how candidates are scored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChordCandidate {
+    /// The chord, inverted so that the bass is the lowest tone if it's a chord tone.
+    pub chord: Chord,
+    /// Tones of the chord that weren't in the input, spelled relative to the chord's root.
+    pub missing: Vec<Pitch>,
+    /// Pitches from the input that aren't part of the chord.
+    pub extra: Vec<Pitch>,
+    /// How unlikely this interpretation is. Lower is better.
+    pub score: u32,
+}
+
+impl ChordCandidate {
+    /// Score added for each missing tone, other than the ones below.
+    pub const MISSING_TONE_COST: u32 = 3;
+    /// Score added for a missing perfect fifth, or a missing eleventh in a thirteenth chord,
+    /// since these are commonly left out of voicings.
+    pub const OMITTABLE_TONE_COST: u32 = 2;
+    /// Score added for each input pitch that isn't part of the chord.
+    pub const EXTRA_TONE_COST: u32 = 4;
+    /// Score added if the bass isn't the chord's root.
+    pub const INVERSION_COST: u32 = 1;
+}
+
+impl Chord {
+    /// Names the chord formed by a set of pitches, returning every plausible candidate,
+    /// best first. The first pitch is treated as the bass.
+    ///
+    /// Every distinct pitch of the input is tried as a root against the intervals of every
+    /// [`ChordType`]. Pitches are compared with [`EnharmonicEq`], so the input's spelling
+    /// doesn't matter, but the root of each candidate is spelled as it was in the input.
+    ///
+    /// Each candidate's score is the sum of:
+    /// - [`ChordCandidate::MISSING_TONE_COST`] for each chord tone not in the input, except
+    /// - [`ChordCandidate::OMITTABLE_TONE_COST`] for a missing perfect fifth, or eleventh of a thirteenth chord,
+    /// - [`ChordCandidate::EXTRA_TONE_COST`] for each input pitch that isn't a chord tone, and
+    /// - [`ChordCandidate::INVERSION_COST`] if the bass isn't the root.
+    ///
+    /// Candidates with an equal score are ordered by how many tones the chord has, fewest first,
+    /// then by where their root first appears in the input, then by the order of [`ChordType`]'s
+    /// variants. This means that exact matches always
+    /// come first, and root position interpretations are preferred, so `C E G A` is
+    /// `C6` rather than `Am7/C`.
+    ///
+    /// To avoid implausible results, a candidate must contain at least two pitches of the
+    /// input, can't be missing more than two tones, and must have more tones present than missing.
+    /// [`ChordType`]s with the same intervals as an earlier variant are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::chord::types::ChordType;
+    /// let candidates = Chord::identify(&[Pitch::C, Pitch::E, Pitch::G, Pitch::A]);
+    ///
+    /// assert_eq!(candidates[0].chord.root, Pitch::C);
+    /// assert_eq!(candidates[0].chord.chord_type(), Some(ChordType::MajorSixth));
+    ///
+    /// assert_eq!(candidates[1].chord.root, Pitch::A);
+    /// assert_eq!(candidates[1].chord.chord_type(), Some(ChordType::MinorSeventh));
+    /// assert_eq!(candidates[1].chord.inversion(), 1);
+    ///
+    /// // a dominant seventh without its fifth
+    /// let best = &Chord::identify(&[Pitch::G, Pitch::F, Pitch::B])[0];
+    /// assert_eq!(best.chord.chord_type(), Some(ChordType::DominantSeventh));
+    /// assert_eq!(best.missing, [Pitch::D]);
+    /// ```
+    pub fn identify(pitches: &[Pitch]) -> Vec<ChordCandidate> {
+        let Some(&bass) = pitches.first() else {
+            return Vec::new();
+        };
+
+        let mut distinct: Vec<Pitch> = Vec::with_capacity(pitches.len());
+
+        for &pitch in pitches {
+            if !distinct.iter().any(|p| p.eq_enharmonic(&pitch)) {
+                distinct.push(pitch);
+            }
+        }
+
+        let mut templates: Vec<(ChordType, Vec<Interval>)> = Vec::new();
+
+        for ty in ChordType::iter() {
+            let mut intervals = ty.intervals();
+            intervals.sort();
+
+            if !templates.iter().any(|(_, ivls)| *ivls == intervals) {
+                templates.push((ty, intervals));
+            }
+        }
+
+        let mut candidates = Vec::new();
+
+        for &root in &distinct {
+            for (ty, intervals) in &templates {
+                if let Some(candidate) = Self::candidate(*ty, intervals, root, bass, &distinct) {
+                    candidates.push((intervals.len(), candidate));
+                }
+            }
+        }
+
+        // stable, so ties stay in order of root, then chord type
+        candidates.sort_by_key(|(len, candidate)| (candidate.score, *len));
+
+        candidates.into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// Names the chord formed by a set of notes, returning every plausible candidate, best first.
+    ///
+    /// This works the same as [`Chord::identify`], except that the lowest note is treated as
+    /// the bass, so the notes can be given in any order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::chord::types::ChordType;
+    /// let notes = [
+    ///     Note::new(Pitch::F_SHARP, 4),
+    ///     Note::new(Pitch::D, 4),
+    ///     Note::new(Pitch::A, 3),
+    /// ];
+    ///
+    /// let best = &Chord::identify_notes(&notes)[0];
+    ///
+    /// assert_eq!(best.chord.root, Pitch::D);
+    /// assert_eq!(best.chord.chord_type(), Some(ChordType::MajorTriad));
+    /// // A is in the bass
+    /// assert_eq!(best.chord.inversion(), 2);
+    /// ```
+    pub fn identify_notes(notes: &[Note]) -> Vec<ChordCandidate> {
+        let Some(bass) = notes.iter().min_by(|a, b| a.cmp_enharmonic(b)) else {
+            return Vec::new();
+        };
+
+        let pitches = std::iter::once(bass.pitch)
+            .chain(notes.iter().map(|n| n.pitch))
+            .collect::<Vec<_>>();
+
+        Self::identify(&pitches)
+    }
+
+    fn candidate(ty: ChordType, intervals: &[Interval], root: Pitch, bass: Pitch, input: &[Pitch]) -> Option<ChordCandidate> {
+        let has_thirteenth = intervals.contains(&Interval::MAJOR_THIRTEENTH);
+
+        let mut score = 0;
+        let mut missing = Vec::new();
+
+        for &ivl in intervals {
+            let tone = root.transpose(ivl);
+
+            if input.iter().any(|p| p.eq_enharmonic(&tone)) {
+                continue;
+            }
+
+            let omittable = ivl == Interval::PERFECT_FIFTH
+                || (ivl == Interval::PERFECT_ELEVENTH && has_thirteenth);
+
+            score += if omittable { ChordCandidate::OMITTABLE_TONE_COST } else { ChordCandidate::MISSING_TONE_COST };
+            missing.push(tone);
+        }
+
+        let present = intervals.len() - missing.len();
+
+        if present < 2 || missing.len() > 2 || present <= missing.len() {
+            return None;
+        }
+
+        let extra = input.iter()
+            .copied()
+            .filter(|p| !intervals.iter().any(|&ivl| root.transpose(ivl).eq_enharmonic(p)))
+            .collect::<Vec<_>>();
+
+        score += ChordCandidate::EXTRA_TONE_COST * extra.len() as u32;
+
+        if !bass.eq_enharmonic(&root) {
+            score += ChordCandidate::INVERSION_COST;
+        }
+
+        let mut chord = Chord::from_type(ty, root, 0)
+            .expect("root position is always valid");
+
+        let bass_idx = chord.intervals()
+            .iter()
+            .position(|&ivl| root.transpose(ivl).eq_enharmonic(&bass));
+
+        if let Some(idx) = bass_idx {
+            chord.set_inversion(idx as _)
+                .expect("index is in range of intervals");
+        }
+
+        Some(ChordCandidate { chord, missing, extra, score })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(pitches: &[Pitch]) -> (Pitch, Option<ChordType>, u8) {
+        let candidate = &Chord::identify(pitches)[0];
+
+        (candidate.chord.root, candidate.chord.chord_type(), candidate.chord.inversion())
+    }
+
+    #[test]
+    fn exact_matches() {
+        use Pitch as P;
+        use ChordType as C;
+
+        assert_eq!(best(&[P::C, P::E, P::G]), (P::C, Some(C::MajorTriad), 0));
+        assert_eq!(best(&[P::E, P::G, P::C]), (P::C, Some(C::MajorTriad), 1));
+        assert_eq!(best(&[P::A, P::C, P::E, P::G]), (P::A, Some(C::MinorSeventh), 0));
+        assert_eq!(best(&[P::C, P::E, P::G, P::A]), (P::C, Some(C::MajorSixth), 0));
+        assert_eq!(best(&[P::C, P::E, P::G, P::B_FLAT, P::D]), (P::C, Some(C::DominantNinth), 0));
+        assert_eq!(best(&[P::B, P::D, P::F, P::A]), (P::B, Some(C::HalfDiminishedSeventh), 0));
+        assert_eq!(best(&[P::F, P::B, P::D, P::G_SHARP]), (P::F, Some(C::DiminishedSeventh), 0));
+        assert_eq!(best(&[P::C, P::F, P::G]), (P::C, Some(C::Suspended4Triad), 0));
+        assert_eq!(best(&[P::E, P::G_SHARP, P::C]), (P::E, Some(C::AugmentedTriad), 0));
+    }
+
+    #[test]
+    fn enharmonic_input() {
+        let candidates = Chord::identify(&[Pitch::C, Pitch::F_FLAT, Pitch::G]);
+
+        assert_eq!(candidates[0].chord.chord_type(), Some(ChordType::MajorTriad));
+        assert_eq!(candidates[0].chord.pitches(), [Pitch::C, Pitch::E, Pitch::G]);
+        assert!(candidates[0].extra.is_empty());
+
+        // duplicates, even if spelled differently, are ignored
+        assert_eq!(
+            Chord::identify(&[Pitch::C, Pitch::E, Pitch::G, Pitch::B_SHARP, Pitch::F_FLAT]),
+            Chord::identify(&[Pitch::C, Pitch::E, Pitch::G]),
+        );
+    }
+
+    #[test]
+    fn missing_and_extra() {
+        // thirteenth chord without its fifth or eleventh
+        let candidates = Chord::identify(&[Pitch::C, Pitch::E, Pitch::B_FLAT, Pitch::D, Pitch::A]);
+
+        assert_eq!(candidates[0].chord.chord_type(), Some(ChordType::DominantThirteenth));
+        assert_eq!(candidates[0].missing, [Pitch::G, Pitch::F]);
+        assert_eq!(candidates[0].score, 2 * ChordCandidate::OMITTABLE_TONE_COST);
+
+        // a non-chord tone in the bass
+        let candidates = Chord::identify(&[Pitch::D, Pitch::C, Pitch::E, Pitch::G]);
+        let slash = candidates.iter()
+            .find(|c| c.chord.chord_type() == Some(ChordType::MajorTriad))
+            .unwrap();
+
+        assert_eq!(slash.chord.root, Pitch::C);
+        assert_eq!(slash.chord.inversion(), 0);
+        assert_eq!(slash.extra, [Pitch::D]);
+        assert_eq!(slash.score, ChordCandidate::EXTRA_TONE_COST + ChordCandidate::INVERSION_COST);
+    }
+
+    #[test]
+    fn sorted_and_filtered() {
+        let candidates = Chord::identify(&[Pitch::C, Pitch::E, Pitch::G, Pitch::B, Pitch::D, Pitch::F_SHARP]);
+
+        assert!(candidates.windows(2).all(|w| w[0].score <= w[1].score));
+
+        for candidate in &candidates {
+            let present = candidate.chord.intervals().len() - candidate.missing.len();
+            assert!(candidate.missing.len() <= 2 && present > candidate.missing.len());
+        }
+
+        assert!(Chord::identify(&[]).is_empty());
+        assert!(Chord::identify(&[Pitch::C]).is_empty());
+        assert!(Chord::identify_notes(&[]).is_empty());
+    }
+}
@@ -5,6 +5,19 @@ pub mod quality;
 pub mod size;
 pub mod types;
 mod eq;
+mod fmt;
+mod identify;
+mod parse;
+mod voicing;
+mod voice_leading;
+mod neo_riemannian;
+
+pub use fmt::*;
+pub use identify::*;
+pub use parse::*;
+pub use voicing::*;
+pub use voice_leading::*;
+pub use neo_riemannian::*;
 
 #[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
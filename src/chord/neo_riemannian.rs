@@ -0,0 +1,603 @@
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+use crate::chord::Chord;
+use crate::chord::types::ChordType;
+use crate::{Pitch, PitchClass};
+
+/// A Neo-Riemannian transformation between major and minor triads.
+///
+/// The three basic transformations, [`P`](Self::Parallel), [`L`](Self::LeadingToneExchange), and
+/// [`R`](Self::Relative), each keep two tones of the triad and move the third by step.
+/// The others are made by combining them.
+///
+/// # Examples
+/// ```
+/// # use music_theory::chord::{Chord, NeoRiemannian};
+/// let c_major = "C".parse::<Chord>().unwrap();
+///
+/// assert_eq!(NeoRiemannian::Parallel.apply(&c_major).unwrap().to_string(), "Cm");
+/// assert_eq!(NeoRiemannian::LeadingToneExchange.apply(&c_major).unwrap().to_string(), "Em");
+/// assert_eq!(NeoRiemannian::Relative.apply(&c_major).unwrap().to_string(), "Am");
+/// assert_eq!(NeoRiemannian::Slide.apply(&c_major).unwrap().to_string(), "C#m");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NeoRiemannian {
+    /// `P`, which changes a triad to the parallel major or minor, like C major and C minor.
+    Parallel,
+    /// `L`, which moves the root of a major triad down a semitone, like C major and E minor.
+    LeadingToneExchange,
+    /// `R`, which changes a triad to the relative major or minor, like C major and A minor.
+    Relative,
+    /// `N`, or `RLP`, which changes a major triad to the minor triad a fourth above,
+    /// like C major and F minor.
+    Nebenverwandt,
+    /// `S`, or `LPR`, which keeps the third and moves the other tones by a semitone,
+    /// like C major and C♯ minor.
+    Slide,
+    /// `H`, the hexatonic pole, which changes a major triad to the minor triad a major third below,
+    /// like C major and A♭ minor. This is `LPL`, up to enharmonic spelling.
+    HexatonicPole,
+}
+
+/// Error returned when a [`Chord`] used with a Neo-Riemannian transformation
+/// isn't a major or minor triad.
+///
+/// # Examples
+/// ```
+/// # use music_theory::chord::{Chord, NeoRiemannian, NotMajorOrMinorTriad};
+/// let seventh = "G7".parse::<Chord>().unwrap();
+///
+/// assert_eq!(NeoRiemannian::Parallel.apply(&seventh), Err(NotMajorOrMinorTriad));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("Neo-Riemannian transformations only apply to major and minor triads")]
+pub struct NotMajorOrMinorTriad;
+
+// the root of a major or minor triad, and whether it's major
+fn triad(chord: &Chord) -> Result<(Pitch, bool), NotMajorOrMinorTriad> {
+    [true, false]
+        .into_iter()
+        .find(|&major| chord.intervals() == triad_type(major).intervals())
+        .map(|major| (chord.root, major))
+        .ok_or(NotMajorOrMinorTriad)
+}
+
+fn triad_type(major: bool) -> ChordType {
+    if major { ChordType::MajorTriad } else { ChordType::MinorTriad }
+}
+
+impl NeoRiemannian {
+    /// Returns the basic transformations that make up this one, in the order they're applied.
+    ///
+    /// Applying them gives the same triad as the transformation, but possibly spelled differently.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::NeoRiemannian;
+    /// use NeoRiemannian as NR;
+    ///
+    /// assert_eq!(NR::Parallel.decompose(), [NR::Parallel]);
+    /// assert_eq!(NR::Slide.decompose(), [NR::LeadingToneExchange, NR::Parallel, NR::Relative]);
+    /// ```
+    pub fn decompose(self) -> &'static [Self] {
+        use NeoRiemannian as NR;
+
+        match self {
+            NR::Parallel => &[NR::Parallel],
+            NR::LeadingToneExchange => &[NR::LeadingToneExchange],
+            NR::Relative => &[NR::Relative],
+            NR::Nebenverwandt => &[NR::Relative, NR::LeadingToneExchange, NR::Parallel],
+            NR::Slide => &[NR::LeadingToneExchange, NR::Parallel, NR::Relative],
+            NR::HexatonicPole => &[NR::LeadingToneExchange, NR::Parallel, NR::LeadingToneExchange],
+        }
+    }
+
+    /// Returns the letter used for the transformation.
+    pub fn letter(self) -> char {
+        match self {
+            Self::Parallel => 'P',
+            Self::LeadingToneExchange => 'L',
+            Self::Relative => 'R',
+            Self::Nebenverwandt => 'N',
+            Self::Slide => 'S',
+            Self::HexatonicPole => 'H',
+        }
+    }
+
+    /// Returns the transformation with the given letter, if there is one.
+    pub fn from_letter(letter: char) -> Option<Self> {
+        use strum::IntoEnumIterator;
+
+        Self::iter().find(|t| t.letter() == letter)
+    }
+
+    // how far the root of a triad moves in the Tonnetz
+    fn root_step(self, major: bool) -> TonnetzCoord {
+        let step = match self {
+            Self::Parallel => TonnetzCoord::new(0, 0),
+            Self::LeadingToneExchange => TonnetzCoord::MAJOR_THIRD,
+            Self::Relative => TonnetzCoord::MAJOR_THIRD - TonnetzCoord::FIFTH,
+            Self::Nebenverwandt => -TonnetzCoord::FIFTH,
+            Self::Slide => TonnetzCoord::MAJOR_THIRD * 2 - TonnetzCoord::FIFTH,
+            Self::HexatonicPole => -TonnetzCoord::MAJOR_THIRD,
+        };
+
+        // every transformation is its own inverse, so minor triads move back
+        if major { step } else { -step }
+    }
+
+    // transforms the root of a triad, moving along the line of fifths
+    fn apply_root(self, root: Pitch, major: bool) -> (Pitch, bool) {
+        let step = self.root_step(major);
+
+        (root.transpose_fifths(step.fifths + 4 * step.thirds), !major)
+    }
+
+    /// Applies the transformation to a major or minor triad, keeping its inversion.
+    ///
+    /// The new triad is spelled by moving its root by the interval the transformation moves it,
+    /// so `L` changes C major to E minor rather than F♭ minor, and `S` changes C minor to C♭ major.
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if the chord isn't a major or minor triad.
+    pub fn apply(self, chord: &Chord) -> Result<Chord, NotMajorOrMinorTriad> {
+        let (root, major) = triad(chord)?;
+        let (root, major) = self.apply_root(root, major);
+
+        Ok(Chord::from_type(triad_type(major), root, chord.inversion()).expect("triads have three inversions"))
+    }
+}
+
+impl fmt::Display for NeoRiemannian {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// A sequence of [Neo-Riemannian transformations](NeoRiemannian), applied from left to right.
+///
+/// # Examples
+/// ```
+/// # use music_theory::chord::{Chord, NeoRiemannianPath};
+/// let path = "PLR".parse::<NeoRiemannianPath>().unwrap();
+/// let c_major = "C".parse::<Chord>().unwrap();
+///
+/// // C major, then C minor, then A♭ major, then F minor
+/// assert_eq!(path.apply(&c_major).unwrap().to_string(), "Fm");
+/// assert_eq!(path.to_string(), "PLR");
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeoRiemannianPath(pub Vec<NeoRiemannian>);
+
+impl NeoRiemannianPath {
+    /// Applies every transformation in order, returning each triad along the way,
+    /// starting with the given chord.
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if the chord isn't a major or minor triad.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::{Chord, NeoRiemannianPath};
+    /// let path = "LR".parse::<NeoRiemannianPath>().unwrap();
+    /// let c_major = "C".parse::<Chord>().unwrap();
+    ///
+    /// let chords = path.chords(&c_major).unwrap().iter().map(Chord::to_string).collect::<Vec<_>>();
+    /// assert_eq!(chords, ["C", "Em", "G"]);
+    /// ```
+    pub fn chords(&self, chord: &Chord) -> Result<Vec<Chord>, NotMajorOrMinorTriad> {
+        triad(chord)?;
+
+        let mut chords = vec![chord.clone()];
+
+        for transformation in &self.0 {
+            let next = transformation.apply(chords.last().expect("starts with the chord"))?;
+            chords.push(next);
+        }
+
+        Ok(chords)
+    }
+
+    /// Applies every transformation in order, returning the last triad.
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if the chord isn't a major or minor triad.
+    pub fn apply(&self, chord: &Chord) -> Result<Chord, NotMajorOrMinorTriad> {
+        self.0.iter().try_fold(chord.clone(), |chord, t| t.apply(&chord))
+    }
+
+    /// Finds a shortest sequence of `P`, `L`, and `R` transformations from one triad to another,
+    /// ignoring enharmonic spelling.
+    ///
+    /// Every major and minor triad can be reached from any other with these transformations.
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if either chord isn't a major or minor triad.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::{Chord, NeoRiemannianPath};
+    /// let c_major = "C".parse::<Chord>().unwrap();
+    /// let g_sharp_minor = "G#m".parse::<Chord>().unwrap();
+    ///
+    /// let path = NeoRiemannianPath::shortest(&c_major, &g_sharp_minor).unwrap();
+    /// // C major, then C minor, then A♭ major, then A♭ minor
+    /// assert_eq!(path.to_string(), "PLP");
+    /// ```
+    pub fn shortest(from: &Chord, to: &Chord) -> Result<Self, NotMajorOrMinorTriad> {
+        use NeoRiemannian as NR;
+
+        Self::shortest_with(from, to, &[NR::Parallel, NR::LeadingToneExchange, NR::Relative])
+            .map(|path| path.expect("P, L, and R reach every triad"))
+    }
+
+    /// Finds a shortest sequence of the given transformations from one triad to another,
+    /// ignoring enharmonic spelling, or `None` if there's no such sequence.
+    ///
+    /// Ties are broken by preferring transformations earlier in `transformations`.
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if either chord isn't a major or minor triad.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::{Chord, NeoRiemannian, NeoRiemannianPath};
+    /// let c_major = "C".parse::<Chord>().unwrap();
+    /// let c_minor = "Cm".parse::<Chord>().unwrap();
+    /// let d_major = "D".parse::<Chord>().unwrap();
+    ///
+    /// let hexatonic = [NeoRiemannian::Parallel, NeoRiemannian::LeadingToneExchange];
+    ///
+    /// let path = NeoRiemannianPath::shortest_with(&c_major, &c_minor, &hexatonic).unwrap();
+    /// assert_eq!(path.map(|p| p.to_string()), Some("P".to_owned()));
+    ///
+    /// // D major isn't in C major's hexatonic cycle
+    /// assert_eq!(NeoRiemannianPath::shortest_with(&c_major, &d_major, &hexatonic), Ok(None));
+    /// ```
+    pub fn shortest_with(from: &Chord, to: &Chord, transformations: &[NeoRiemannian]) -> Result<Option<Self>, NotMajorOrMinorTriad> {
+        let (from_root, from_major) = triad(from)?;
+        let (to_root, to_major) = triad(to)?;
+
+        let key = |root: Pitch, major: bool| (root.as_pitch_class(), major);
+        let target = key(to_root, to_major);
+
+        // how each triad was first reached, as the previous triad and the transformation
+        let mut previous = HashMap::<(PitchClass, bool), Option<((PitchClass, bool), NeoRiemannian)>>::new();
+        previous.insert(key(from_root, from_major), None);
+
+        let mut queue = VecDeque::from([(from_root, from_major)]);
+
+        while let Some((root, major)) = queue.pop_front() {
+            let current = key(root, major);
+
+            if current == target {
+                let mut path = Vec::new();
+                let mut at = current;
+
+                while let Some(&Some((before, transformation))) = previous.get(&at) {
+                    path.push(transformation);
+                    at = before;
+                }
+
+                path.reverse();
+                return Ok(Some(Self(path)));
+            }
+
+            for &transformation in transformations {
+                let (next_root, next_major) = transformation.apply_root(root, major);
+                let next = key(next_root, next_major);
+
+                if let Entry::Vacant(entry) = previous.entry(next) {
+                    entry.insert(Some((current, transformation)));
+                    queue.push_back((next_root, next_major));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl fmt::Display for NeoRiemannianPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for transformation in &self.0 {
+            write!(f, "{transformation}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`NeoRiemannianPath`] fails,
+/// with the character that isn't a transformation.
+///
+/// # Examples
+/// ```
+/// # use music_theory::chord::{NeoRiemannianPath, ParseNeoRiemannianError};
+/// assert_eq!("PXL".parse::<NeoRiemannianPath>(), Err(ParseNeoRiemannianError('X')));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("Unknown transformation '{0}', expected P, L, R, N, S, or H")]
+pub struct ParseNeoRiemannianError(pub char);
+
+impl FromStr for NeoRiemannianPath {
+    type Err = ParseNeoRiemannianError;
+
+    /// Parses a sequence of transformations from their letters, like `PLR`.
+    ///
+    /// Letters can be upper or lower case, and whitespace is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| NeoRiemannian::from_letter(c.to_ascii_uppercase()).ok_or(ParseNeoRiemannianError(c)))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// A position in the Tonnetz, the lattice of pitches where one axis is perfect fifths
+/// and the other is major thirds.
+///
+/// A position's pitch is the pitch `fifths` perfect fifths and `thirds` major thirds above C,
+/// so moving along the thirds axis is the same as moving four fifths on the
+/// [line of fifths](Pitch::as_fifths_from_c). Minor thirds go up a fifth and down a major third.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Pitch;
+/// # use music_theory::chord::TonnetzCoord;
+/// let e = TonnetzCoord::from_pitch(Pitch::E);
+/// assert_eq!(e, TonnetzCoord::new(0, 1));
+///
+/// assert_eq!((e + TonnetzCoord::FIFTH).pitch(), Pitch::B);
+/// assert_eq!((e + TonnetzCoord::MINOR_THIRD).pitch(), Pitch::G);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonnetzCoord {
+    /// The position along the perfect fifths axis.
+    pub fifths: i16,
+    /// The position along the major thirds axis.
+    pub thirds: i16,
+}
+
+impl TonnetzCoord {
+    /// The step along the Tonnetz for a perfect fifth.
+    pub const FIFTH: Self = Self::new(1, 0);
+
+    /// The step along the Tonnetz for a major third.
+    pub const MAJOR_THIRD: Self = Self::new(0, 1);
+
+    /// The step along the Tonnetz for a minor third.
+    pub const MINOR_THIRD: Self = Self::new(1, -1);
+
+    /// Creates a position from its coordinates along each axis.
+    pub const fn new(fifths: i16, thirds: i16) -> Self {
+        Self { fifths, thirds }
+    }
+
+    /// Returns the position of a pitch, in the band of four fifths from F to D.
+    ///
+    /// Pitches have many positions in the Tonnetz, since four fifths up is a major third up.
+    /// This chooses the one whose `fifths` is from -1 to 2, so the naturals are F, C, G, and D,
+    /// with A, E, B and F♯ a third above them, and D♭, A♭, E♭, and B♭ a third below.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::TonnetzCoord;
+    /// assert_eq!(TonnetzCoord::from_pitch(Pitch::C), TonnetzCoord::new(0, 0));
+    /// assert_eq!(TonnetzCoord::from_pitch(Pitch::A), TonnetzCoord::new(-1, 1));
+    /// assert_eq!(TonnetzCoord::from_pitch(Pitch::B_FLAT), TonnetzCoord::new(2, -1));
+    ///
+    /// // enharmonic pitches are in different places
+    /// assert_eq!(TonnetzCoord::from_pitch(Pitch::G_SHARP), TonnetzCoord::new(0, 2));
+    /// assert_eq!(TonnetzCoord::from_pitch(Pitch::A_FLAT), TonnetzCoord::new(0, -1));
+    /// ```
+    pub fn from_pitch(pitch: Pitch) -> Self {
+        let fifths_from_c = pitch.as_fifths_from_c();
+        let fifths = (fifths_from_c + 1).rem_euclid(4) - 1;
+
+        Self::new(fifths, (fifths_from_c - fifths) / 4)
+    }
+
+    /// Returns the pitch at the position.
+    pub fn pitch(self) -> Pitch {
+        Pitch::from_fifths_from_c(self.fifths + 4 * self.thirds)
+    }
+
+    /// Returns the six positions next to this one, a fifth, major third or minor third up or down,
+    /// in that order.
+    pub fn neighbors(self) -> [Self; 6] {
+        [Self::FIFTH, Self::MAJOR_THIRD, Self::MINOR_THIRD].map(|step| [self + step, self - step])
+            .as_flattened()
+            .try_into()
+            .expect("three steps, up and down")
+    }
+}
+
+impl Add for TonnetzCoord {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.fifths + rhs.fifths, self.thirds + rhs.thirds)
+    }
+}
+
+impl Sub for TonnetzCoord {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Mul<i16> for TonnetzCoord {
+    type Output = Self;
+
+    fn mul(self, rhs: i16) -> Self::Output {
+        Self::new(self.fifths * rhs, self.thirds * rhs)
+    }
+}
+
+impl Neg for TonnetzCoord {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.fifths, -self.thirds)
+    }
+}
+
+/// A major or minor triad as a triangle in the Tonnetz.
+///
+/// Major triads have their fifth to the right of their root, and their third above it. Minor
+/// triads have their fifth to the right of their root, and their third below the fifth. The
+/// [Neo-Riemannian transformations](NeoRiemannian) flip the triangle over one of its edges,
+/// so applying them with [`transform`](Self::transform) walks the lattice without jumping.
+///
+/// # Examples
+/// ```
+/// # use music_theory::chord::{Chord, NeoRiemannian, TonnetzCoord, TonnetzTriad};
+/// let c_major = TonnetzTriad::from_chord(&"C".parse::<Chord>().unwrap()).unwrap();
+///
+/// let e_minor = c_major.transform(NeoRiemannian::LeadingToneExchange);
+/// assert_eq!(e_minor.chord().to_string(), "Em");
+///
+/// // E and G stay in place
+/// assert_eq!(c_major.vertices()[1..], [TonnetzCoord::new(0, 1), TonnetzCoord::new(1, 0)]);
+/// assert_eq!(e_minor.vertices()[..2], [TonnetzCoord::new(0, 1), TonnetzCoord::new(1, 0)]);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonnetzTriad {
+    /// The position of the triad's root.
+    pub root: TonnetzCoord,
+    /// Whether the triad is major, rather than minor.
+    pub major: bool,
+}
+
+impl TonnetzTriad {
+    /// Returns the triangle of a major or minor triad, with its root placed by [`TonnetzCoord::from_pitch`].
+    ///
+    /// # Errors
+    /// Returns [`NotMajorOrMinorTriad`] if the chord isn't a major or minor triad.
+    pub fn from_chord(chord: &Chord) -> Result<Self, NotMajorOrMinorTriad> {
+        let (root, major) = triad(chord)?;
+
+        Ok(Self { root: TonnetzCoord::from_pitch(root), major })
+    }
+
+    /// Returns the triad as a root position [`Chord`].
+    pub fn chord(self) -> Chord {
+        Chord::from_type(triad_type(self.major), self.root.pitch(), 0).expect("root position is valid")
+    }
+
+    /// Returns the positions of the triad's root, third, and fifth.
+    pub fn vertices(self) -> [TonnetzCoord; 3] {
+        let third = if self.major { TonnetzCoord::MAJOR_THIRD } else { TonnetzCoord::MINOR_THIRD };
+
+        [self.root, self.root + third, self.root + TonnetzCoord::FIFTH]
+    }
+
+    /// Returns the triangle reached by applying the transformation, which shares an edge
+    /// with this one for `P`, `L`, and `R`.
+    pub fn transform(self, transformation: NeoRiemannian) -> Self {
+        Self {
+            root: self.root + transformation.root_step(self.major),
+            major: !self.major,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+    use super::*;
+
+    #[test]
+    fn transformations() {
+        let cases = [
+            ("C", "Cm", "Em", "Am", "Fm", "C#m", "Abm"),
+            ("Cm", "C", "Ab", "Eb", "G", "Cb", "E"),
+            ("G", "Gm", "Bm", "Em", "Cm", "G#m", "Ebm"),
+            ("Am", "A", "F", "C", "E", "Ab", "C#"),
+        ];
+
+        for (from, p, l, r, n, s, h) in cases {
+            let chord = from.parse::<Chord>().unwrap();
+
+            for (transformation, expected) in NeoRiemannian::iter().zip([p, l, r, n, s, h]) {
+                let result = transformation.apply(&chord).unwrap();
+                assert_eq!(result.to_string(), expected, "{transformation} {from}");
+
+                // the basic and compound transformations are involutions
+                assert_eq!(transformation.apply(&result), Ok(chord.clone()), "{transformation} {from}");
+            }
+        }
+
+        let inverted = "C/E".parse::<Chord>().unwrap();
+        assert_eq!(NeoRiemannian::Relative.apply(&inverted).unwrap().to_string(), "Am/C");
+        assert_eq!("PLRX".parse::<NeoRiemannianPath>(), Err(ParseNeoRiemannianError('X')));
+        assert_eq!("p l r".parse::<NeoRiemannianPath>().unwrap().to_string(), "PLR");
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let roots = ["C", "Db", "D", "Eb", "E", "F", "F#", "G", "Ab", "A", "Bb", "B"];
+
+        let triads = roots.iter()
+            .flat_map(|root| [root.to_string(), format!("{root}m")])
+            .map(|s| s.parse::<Chord>().unwrap())
+            .collect::<Vec<_>>();
+
+        for from in &triads {
+            for to in &triads {
+                let path = NeoRiemannianPath::shortest(from, to).unwrap();
+                let result = path.apply(from).unwrap();
+
+                assert!(result.pitches().iter().zip(to.pitches()).all(|(a, b)| a.as_pitch_class() == b.as_pitch_class()));
+
+                // every triad is at most five steps away
+                assert!(path.0.len() <= 5, "{from} {to} {path}");
+                assert_eq!(path.0.is_empty(), from == to);
+            }
+        }
+    }
+
+    #[test]
+    fn tonnetz() {
+        for fifths_from_c in -20..20 {
+            let pitch = Pitch::from_fifths_from_c(fifths_from_c);
+
+            assert_eq!(TonnetzCoord::from_pitch(pitch).pitch(), pitch);
+        }
+
+        let triad = TonnetzTriad::from_chord(&"Am".parse::<Chord>().unwrap()).unwrap();
+
+        for transformation in NeoRiemannian::iter() {
+            let moved = triad.transform(transformation);
+
+            assert_eq!(Ok(moved.chord()), transformation.apply(&triad.chord()), "{transformation}");
+
+            let shared = moved.vertices().iter().filter(|v| triad.vertices().contains(v)).count();
+
+            let expected = match transformation {
+                NeoRiemannian::Parallel | NeoRiemannian::LeadingToneExchange | NeoRiemannian::Relative => 2,
+                NeoRiemannian::Nebenverwandt | NeoRiemannian::Slide => 1,
+                NeoRiemannian::HexatonicPole => 0,
+            };
+
+            assert_eq!(shared, expected, "{transformation}");
+        }
+
+        assert_eq!(TonnetzCoord::new(0, 0).neighbors().map(TonnetzCoord::pitch), [
+            Pitch::G, Pitch::F, Pitch::E, Pitch::A_FLAT, Pitch::E_FLAT, Pitch::A,
+        ]);
+    }
+}
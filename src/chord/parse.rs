@@ -0,0 +1,851 @@
+use std::ops::Range;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use crate::chord::Chord;
+use crate::chord::types::ChordType;
+use crate::interval::{Number, Quality};
+use crate::note::{parse_letter_and_accidental, ParseNoteError};
+use crate::{EnharmonicEq, EnharmonicOrd, Interval, Pitch};
+
+/// An error returned when a chord symbol can't be parsed.
+///
+/// The [`span`](Self::span) is the byte range of the input responsible for the error,
+/// so it can be used to underline the offending part of the symbol.
+#[derive(Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("{kind} (at {}..{})", span.start, span.end)]
+pub struct ParseChordError {
+    pub kind: ParseChordErrorKind,
+    pub span: Range<usize>,
+}
+
+/// The reason a chord symbol couldn't be parsed.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseChordErrorKind {
+    /// The input was empty, or only whitespace.
+    #[error("The chord symbol was empty")]
+    Empty,
+    /// The root wasn't a valid pitch.
+    #[error("The root wasn't a valid pitch")]
+    InvalidRoot,
+    /// The bass note after the `/` wasn't a valid pitch.
+    #[error("The bass note wasn't a valid pitch")]
+    InvalidBass,
+    /// A character that isn't part of any known quality or modifier.
+    #[error("Unexpected character in chord symbol")]
+    UnexpectedCharacter,
+    /// A degree that can't be used in this position, such as `add8` or `13` after `sus`.
+    #[error("Invalid degree for this modifier")]
+    InvalidDegree,
+    /// An opening parenthesis without a closing one, or vice versa.
+    #[error("Unbalanced parentheses")]
+    UnbalancedParentheses,
+    /// Strict only: the same modifier (or tone) was given more than once.
+    #[error("The modifier was already applied")]
+    DuplicateModifier,
+    /// Strict only: a tone that isn't in the chord was omitted.
+    #[error("Can't omit a tone that isn't in the chord")]
+    OmittedMissingTone,
+    /// Every tone of the chord was omitted.
+    #[error("Every tone of the chord was omitted")]
+    NoTones,
+}
+
+impl Chord {
+    /// Parses a chord symbol, rejecting anything ambiguous or redundant.
+    ///
+    /// This accepts the same symbols as [`FromStr`], except:
+    /// - Whitespace isn't allowed anywhere.
+    /// - Roots and bass notes must start with an uppercase letter.
+    /// - Words must be written in lowercase, except for the capitalized `Maj`, `Min`, `Dim`, and `Aug`.
+    /// - Repeating a modifier, or adding a tone that's already in the chord, is an error.
+    /// - Omitting a tone that isn't in the chord is an error.
+    /// - The bass note must be spelled exactly as a chord tone to count as an inversion.
+    ///
+    /// # Errors
+    /// Returns a [`ParseChordError`] with the byte range that couldn't be parsed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::{Chord, ParseChordErrorKind};
+    /// # use music_theory::chord::types::ChordType;
+    /// let chord = Chord::from_str_strict("Dm7").unwrap();
+    /// assert_eq!(chord.chord_type(), Some(ChordType::MinorSeventh));
+    ///
+    /// // accepted by FromStr, but not in strict mode
+    /// assert!("C MAJ7".parse::<Chord>().is_ok());
+    /// let err = Chord::from_str_strict("C MAJ7").unwrap_err();
+    /// assert_eq!(err.kind, ParseChordErrorKind::UnexpectedCharacter);
+    /// assert_eq!(err.span, 1..2);
+    ///
+    /// let err = Chord::from_str_strict("C7b9b9").unwrap_err();
+    /// assert_eq!(err.kind, ParseChordErrorKind::DuplicateModifier);
+    /// assert_eq!(err.span, 4..6);
+    /// ```
+    pub fn from_str_strict(s: &str) -> Result<Self, ParseChordError> {
+        parse(s, true)
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseChordError;
+
+    /// Parses a lead-sheet chord symbol, such as `F#m7b5/C`, `Bb13(#11)`, or `G7alt`.
+    ///
+    /// A symbol is a root, an optional quality, an optional extension,
+    /// any number of modifiers, and an optional slash bass note.
+    ///
+    /// | Part | Accepted |
+    /// |------|----------|
+    /// | Root | A pitch, like `C`, `F#`, `Bb`, or `E♭` |
+    /// | Major | (nothing), `maj`, `ma`, `M`, `Δ` (`Δ` alone means a major seventh) |
+    /// | Minor | `m`, `min`, `mi`, `-` |
+    /// | Diminished | `dim`, `°`, `o` |
+    /// | Half-diminished | `ø` (implies a seventh) |
+    /// | Augmented | `aug`, `+` |
+    /// | Power chord | `5` |
+    /// | Extension | `6`, `6/9` or `69`, `7`, `9`, `11`, `13` |
+    /// | Alterations | `b5`, `#5`, `b6`, `b9`, `#9`, `#11`, `b13` (also with `♭`/`♯` or `-`/`+`) |
+    /// | Additions | `add` and a degree from 2 to 7, or 9, 11, or 13, optionally altered (`add9`, `add#11`, `addb3`) |
+    /// | Tensions | `9`, `11`, or `13` in parentheses, as in `C7(9, 13)` |
+    /// | Suspensions | `sus`, `sus2`, `sus4` |
+    /// | Omissions | `no3`, `omit5`, and so on for any chord tone |
+    /// | Other | `alt`, and `maj7`/`M7`/`Δ7` after a minor quality, like `m(maj7)` |
+    /// | Bass | `/` followed by a pitch |
+    ///
+    /// Extensions imply the tones below them, so `C13` contains the 7th, 9th and 11th.
+    /// The seventh is minor, unless a major marker was given (`maj9`), or the chord is
+    /// diminished (`dim7`), in which case it's diminished.
+    ///
+    /// `alt` is written as a dominant seventh with the fifth replaced by every altered tension:
+    /// the root, M3, m7, m9, A9, A11, and m13.
+    ///
+    /// Modifiers can be wrapped in parentheses and separated by commas, as in `C7(b9, #11)`.
+    ///
+    /// If the bass note is a chord tone, the chord is inverted so that tone is in the bass.
+    /// Otherwise, the bass note is added to the chord and then inverted to, so `Am/G` is the
+    /// same as `Am7/G`. If the tones match a [`ChordType`], [`Chord::chord_type`] will return it.
+    ///
+    /// This is the lenient parser: it ignores whitespace between parts, accepts words in
+    /// any case (`MAJ7`, `Sus4`), ignores repeated modifiers, and matches the bass note
+    /// enharmonically. See [`Chord::from_str_strict`] for the strict version.
+    ///
+    /// # Errors
+    /// Returns a [`ParseChordError`] with the byte range that couldn't be parsed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Pitch, Interval};
+    /// # use music_theory::chord::{Chord, ParseChordErrorKind};
+    /// # use music_theory::chord::types::ChordType;
+    /// let chord = "F#m7b5/C".parse::<Chord>().unwrap();
+    /// assert_eq!(chord.root, Pitch::F_SHARP);
+    /// assert_eq!(chord.chord_type(), Some(ChordType::HalfDiminishedSeventh));
+    /// assert_eq!(chord.pitches(), [Pitch::C, Pitch::E, Pitch::F_SHARP, Pitch::A]);
+    ///
+    /// let chord = "Csus4add9".parse::<Chord>().unwrap();
+    /// assert_eq!(chord.pitches(), [Pitch::C, Pitch::F, Pitch::G, Pitch::D]);
+    ///
+    /// // synonyms
+    /// assert_eq!("C-7".parse::<Chord>(), "Cm7".parse());
+    /// assert_eq!("CΔ".parse::<Chord>(), "Cmaj7".parse());
+    /// assert_eq!("C°7".parse::<Chord>(), "Cdim7".parse());
+    ///
+    /// let err = "Cmaj7(#11".parse::<Chord>().unwrap_err();
+    /// assert_eq!(err.kind, ParseChordErrorKind::UnbalancedParentheses);
+    /// assert_eq!(err.span, 5..6);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, false)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Triad {
+    Major,
+    Minor,
+    Diminished,
+    HalfDiminished,
+    Augmented,
+    Power,
+}
+
+impl Triad {
+    fn intervals(self) -> Vec<Interval> {
+        use Interval as I;
+
+        match self {
+            Self::Major => vec![I::PERFECT_UNISON, I::MAJOR_THIRD, I::PERFECT_FIFTH],
+            Self::Minor => vec![I::PERFECT_UNISON, I::MINOR_THIRD, I::PERFECT_FIFTH],
+            Self::Diminished | Self::HalfDiminished => vec![I::PERFECT_UNISON, I::MINOR_THIRD, I::DIMINISHED_FIFTH],
+            Self::Augmented => vec![I::PERFECT_UNISON, I::MAJOR_THIRD, I::AUGMENTED_FIFTH],
+            Self::Power => vec![I::PERFECT_UNISON, I::PERFECT_FIFTH],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Modifier {
+    Alter(Interval),
+    Add(Interval),
+    Sus(Interval),
+    Omit(i16),
+    MajorSeventh,
+    Alt,
+}
+
+/// The tones of a chord while it's being parsed, without duplicates.
+struct Tones(Vec<Interval>);
+
+impl Tones {
+    fn insert(&mut self, ivl: Interval) -> bool {
+        if self.0.contains(&ivl) {
+            false
+        } else {
+            self.0.push(ivl);
+            true
+        }
+    }
+
+    fn has_number(&self, number: i16) -> bool {
+        self.0.iter().any(|ivl| ivl.number().get() == number)
+    }
+
+    fn remove_number(&mut self, number: i16) -> bool {
+        let len = self.0.len();
+        self.0.retain(|ivl| ivl.number().get() != number);
+        self.0.len() != len
+    }
+
+    // replaces the unaltered tone of the same degree if there is one,
+    // so "7b9#9" keeps both ninths, but "9b9" only has the flat ninth
+    fn alter(&mut self, ivl: Interval) -> bool {
+        let unaltered = self.0.iter()
+            .position(|i| i.number() == ivl.number() && matches!(i.quality(), Quality::Major | Quality::Perfect));
+
+        match unaltered {
+            Some(idx) => {
+                self.0[idx] = ivl;
+                true
+            }
+            None => self.insert(ivl),
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    strict: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos == self.src.len()
+    }
+
+    fn error(&self, kind: ParseChordErrorKind, span: Range<usize>) -> ParseChordError {
+        ParseChordError { kind, span }
+    }
+
+    // the span of the next character, or an empty span at the end of the input
+    fn next_char_span(&self) -> Range<usize> {
+        self.pos..self.pos + self.peek().map_or(0, char::len_utf8)
+    }
+
+    fn unexpected(&self) -> ParseChordError {
+        self.error(ParseChordErrorKind::UnexpectedCharacter, self.next_char_span())
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if self.rest().starts_with(symbol) {
+            self.pos += symbol.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    // words are case-insensitive when lenient, and must match exactly when strict
+    fn eat_word(&mut self, words: &[&str]) -> bool {
+        let rest = self.rest();
+
+        let found = words.iter().find(|word| match self.strict {
+            true => rest.starts_with(*word),
+            false => rest.get(..word.len()).is_some_and(|s| s.eq_ignore_ascii_case(word)),
+        });
+
+        if let Some(word) = found {
+            self.pos += word.len();
+        }
+
+        found.is_some()
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), ParseChordError> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            if self.strict {
+                return Err(self.unexpected());
+            }
+
+            self.pos += self.peek().map_or(0, char::len_utf8);
+        }
+
+        Ok(())
+    }
+
+    fn number(&mut self) -> Option<(u16, Range<usize>)> {
+        let start = self.pos;
+        let len = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+
+        if len == 0 {
+            return None;
+        }
+
+        self.pos += len;
+
+        // numbers too large for u16 aren't a valid degree either
+        let number = self.src[start..self.pos].parse().unwrap_or(u16::MAX);
+
+        Some((number, start..self.pos))
+    }
+
+    fn pitch(&mut self, kind: ParseChordErrorKind) -> Result<Pitch, ParseChordError> {
+        let start = self.pos;
+
+        if self.strict && !self.peek().is_some_and(|c| c.is_ascii_uppercase()) {
+            return Err(self.error(kind, self.next_char_span()));
+        }
+
+        match parse_letter_and_accidental(self.rest()) {
+            Ok((pitch, rest)) => {
+                self.pos = self.src.len() - rest.len();
+                Ok(pitch)
+            }
+            Err(ParseNoteError::InvalidAccidental) => {
+                let letter_len = self.peek().map_or(0, char::len_utf8);
+
+                let accidentals_len = self.rest()[letter_len..]
+                    .chars()
+                    .take_while(|c| matches!(c, '#' | '♯' | 'x' | '𝄪' | 'b' | '♭' | '𝄫' | '♮'))
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+
+                Err(self.error(kind, start..start + letter_len + accidentals_len))
+            }
+            Err(_) => Err(self.error(kind, self.next_char_span())),
+        }
+    }
+
+    // returns the triad, whether a major seventh was specified, and whether a seventh is implied
+    fn quality(&mut self) -> (Triad, bool, bool) {
+        // the minor words are checked before a bare "M", which would otherwise take the "M" of "Min"
+        let triad = if self.eat_word(&["maj", "Maj"])
+            || (!self.rest().get(..4).is_some_and(|s| s.eq_ignore_ascii_case("madd")) && self.eat_word(&["ma", "Ma"]))
+        {
+            return (Triad::Major, true, false);
+        } else if self.eat_word(&["min", "Min", "mi", "Mi"]) {
+            Triad::Minor
+        } else if self.eat("M") {
+            return (Triad::Major, true, false);
+        } else if self.eat("Δ") {
+            return (Triad::Major, true, true);
+        } else if self.eat("m") || self.eat("-") {
+            Triad::Minor
+        } else if self.eat_word(&["dim", "Dim"]) || self.eat("°") || (!self.rest().starts_with("omit") && self.eat("o")) {
+            Triad::Diminished
+        } else if self.eat("ø") || self.eat("Ø") {
+            return (Triad::HalfDiminished, false, true);
+        } else if self.eat_word(&["aug", "Aug"]) || self.eat("+") {
+            Triad::Augmented
+        } else if self.rest().starts_with('5') && !self.rest()[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            self.pos += 1;
+            return (Triad::Power, false, false);
+        } else {
+            return (Triad::Major, false, false);
+        };
+
+        // a major seventh can follow any other quality, e.g. "mMaj7", "-Δ7", or "augM7"
+        if self.eat_word(&["maj", "Maj"]) || self.eat("M") {
+            (triad, true, false)
+        } else if self.eat("Δ") {
+            (triad, true, true)
+        } else {
+            (triad, false, false)
+        }
+    }
+
+    fn degree(&mut self, start: usize, allowed: &[u16]) -> Result<i16, ParseChordError> {
+        match self.number() {
+            Some((n, _)) if allowed.contains(&n) => Ok(n as _),
+            Some((_, span)) => Err(self.error(ParseChordErrorKind::InvalidDegree, span)),
+            None => Err(self.error(ParseChordErrorKind::InvalidDegree, start..self.next_char_span().end)),
+        }
+    }
+
+    // a sharp or flat before a degree, e.g. the "b" in "b9"
+    fn alteration_sign(&mut self) -> Option<i16> {
+        if self.eat("#") || self.eat("♯") || self.eat("+") {
+            Some(1)
+        } else if self.eat("b") || self.eat("♭") || self.eat("-") {
+            Some(-1)
+        } else {
+            None
+        }
+    }
+
+    fn modifier(&mut self, in_parens: bool) -> Result<Modifier, ParseChordError> {
+        let start = self.pos;
+
+        // a bare tension is only unambiguous in parentheses, as in "C7(9, 13)"
+        if in_parens && self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            let degree = self.degree(start, &[9, 11, 13])?;
+
+            return Ok(Modifier::Add(altered(degree, 0).expect("natural degrees are valid")));
+        }
+
+        if self.eat_word(&["sus"]) {
+            return match self.number() {
+                None | Some((4, _)) => Ok(Modifier::Sus(Interval::PERFECT_FOURTH)),
+                Some((2, _)) => Ok(Modifier::Sus(Interval::MAJOR_SECOND)),
+                Some((_, span)) => Err(self.error(ParseChordErrorKind::InvalidDegree, span)),
+            };
+        }
+
+        if self.eat_word(&["add"]) {
+            let sign = self.alteration_sign().unwrap_or(0);
+            let degree = self.degree(start, &[2, 3, 4, 5, 6, 7, 9, 11, 13])?;
+
+            return altered(degree, sign)
+                .map(Modifier::Add)
+                .ok_or_else(|| self.error(ParseChordErrorKind::InvalidDegree, start..self.pos));
+        }
+
+        if self.eat_word(&["alt"]) {
+            return Ok(Modifier::Alt);
+        }
+
+        if self.eat_word(&["omit", "no"]) {
+            return self.degree(start, &[1, 3, 5, 7, 9, 11, 13]).map(Modifier::Omit);
+        }
+
+        if self.eat_word(&["maj", "Maj"]) || self.eat("M") || self.eat("Δ") {
+            return match self.number() {
+                None | Some((7, _)) => Ok(Modifier::MajorSeventh),
+                Some((_, span)) => Err(self.error(ParseChordErrorKind::InvalidDegree, span)),
+            };
+        }
+
+        if let Some(sign) = self.alteration_sign() {
+            let allowed: &[u16] = if sign > 0 { &[5, 9, 11] } else { &[5, 6, 9, 13] };
+
+            let degree = self.degree(start, allowed)?;
+
+            return Ok(Modifier::Alter(altered(degree, sign).expect("allowed degrees can be altered by one")));
+        }
+
+        Err(self.unexpected())
+    }
+}
+
+// the major or perfect interval of the degree, raised or lowered by `sign` semitones
+fn altered(degree: i16, sign: i16) -> Option<Interval> {
+    let number = Number::new(degree).expect("degrees aren't zero");
+
+    let quality = match (sign, number.is_perfect()) {
+        (0, true) => Quality::Perfect,
+        (0, false) => Quality::Major,
+        (1.., _) => Quality::AUGMENTED,
+        (..0, true) => Quality::DIMINISHED,
+        (..0, false) => Quality::Minor,
+    };
+
+    Interval::new(quality, number)
+}
+
+fn parse(src: &str, strict: bool) -> Result<Chord, ParseChordError> {
+    use ParseChordErrorKind as K;
+
+    if src.trim().is_empty() {
+        return Err(ParseChordError { kind: K::Empty, span: 0..src.len() });
+    }
+
+    let mut p = Parser { src, pos: 0, strict };
+
+    p.skip_whitespace()?;
+
+    let root = p.pitch(K::InvalidRoot)?;
+
+    p.skip_whitespace()?;
+
+    let (triad, major_seventh, implied_seventh) = p.quality();
+
+    let seventh = match (triad, major_seventh) {
+        (_, true) => Interval::MAJOR_SEVENTH,
+        (Triad::Diminished, false) => Interval::DIMINISHED_SEVENTH,
+        (_, false) => Interval::MINOR_SEVENTH,
+    };
+
+    let mut tones = Tones(triad.intervals());
+
+    let extension = if triad == Triad::Power { None } else { p.number() };
+
+    match extension {
+        None if implied_seventh => {
+            tones.insert(seventh);
+        }
+        None => {}
+        Some((6, _)) => {
+            tones.insert(Interval::MAJOR_SIXTH);
+
+            let is_six_nine = p.rest().starts_with("/9")
+                && !p.rest()[2..].starts_with(|c: char| c.is_ascii_digit());
+
+            if is_six_nine {
+                p.pos += 2;
+                tones.insert(Interval::MAJOR_NINTH);
+            }
+        }
+        Some((69, _)) => {
+            tones.insert(Interval::MAJOR_SIXTH);
+            tones.insert(Interval::MAJOR_NINTH);
+        }
+        Some((n @ (7 | 9 | 11 | 13), _)) => {
+            tones.insert(seventh);
+
+            let extensions = [Interval::MAJOR_NINTH, Interval::PERFECT_ELEVENTH, Interval::MAJOR_THIRTEENTH];
+
+            for ivl in extensions.into_iter().filter(|ivl| ivl.number().get() <= n as i16) {
+                tones.insert(ivl);
+            }
+        }
+        Some((_, span)) => return Err(p.error(K::InvalidDegree, span)),
+    }
+
+    let mut applied = Vec::new();
+    let mut open_paren = None;
+
+    loop {
+        p.skip_whitespace()?;
+
+        if p.is_done() || (open_paren.is_none() && p.peek() == Some('/')) {
+            break;
+        }
+
+        let start = p.pos;
+
+        if p.eat("(") {
+            if open_paren.is_some() {
+                return Err(p.error(K::UnbalancedParentheses, start..p.pos));
+            }
+
+            open_paren = Some(start);
+            continue;
+        }
+
+        if p.eat(")") {
+            if open_paren.take().is_none() {
+                return Err(p.error(K::UnbalancedParentheses, start..p.pos));
+            }
+
+            continue;
+        }
+
+        if open_paren.is_some() && p.eat(",") {
+            continue;
+        }
+
+        let modifier = p.modifier(open_paren.is_some())?;
+        let span = start..p.pos;
+
+        if strict && applied.contains(&modifier) {
+            return Err(p.error(K::DuplicateModifier, span));
+        }
+
+        applied.push(modifier);
+
+        let changed = match modifier {
+            Modifier::Alter(ivl) => tones.alter(ivl),
+            Modifier::Add(ivl) => tones.insert(ivl),
+            Modifier::Sus(ivl) => {
+                tones.remove_number(3);
+                tones.insert(ivl)
+            }
+            Modifier::Omit(degree) => {
+                if !tones.remove_number(degree) && strict {
+                    return Err(p.error(K::OmittedMissingTone, span));
+                }
+
+                true
+            }
+            Modifier::MajorSeventh => {
+                tones.remove_number(7);
+                tones.insert(Interval::MAJOR_SEVENTH)
+            }
+            Modifier::Alt => {
+                if !tones.has_number(7) {
+                    tones.insert(Interval::MINOR_SEVENTH);
+                }
+
+                tones.remove_number(5);
+                tones.remove_number(9);
+
+                for ivl in [Interval::MINOR_NINTH, Interval::AUGMENTED_NINTH, Interval::AUGMENTED_ELEVENTH, Interval::MINOR_THIRTEENTH] {
+                    tones.alter(ivl);
+                }
+
+                true
+            }
+        };
+
+        if strict && !changed {
+            return Err(p.error(K::DuplicateModifier, span));
+        }
+    }
+
+    if let Some(open) = open_paren {
+        return Err(p.error(K::UnbalancedParentheses, open..open + 1));
+    }
+
+    let bass = if p.eat("/") {
+        p.skip_whitespace()?;
+        let bass = p.pitch(K::InvalidBass)?;
+        p.skip_whitespace()?;
+        Some(bass)
+    } else {
+        None
+    };
+
+    if !p.is_done() {
+        return Err(p.unexpected());
+    }
+
+    let mut intervals = tones.0;
+
+    if intervals.is_empty() {
+        return Err(p.error(K::NoTones, 0..src.len()));
+    }
+
+    intervals.sort_by(Interval::cmp_enharmonic);
+
+    let inversion = match bass {
+        None => 0,
+        Some(bass) => {
+            let find = |intervals: &[Interval]| {
+                intervals.iter()
+                    .position(|&ivl| root.transpose(ivl) == bass)
+                    .or_else(|| {
+                        intervals.iter()
+                            .position(|&ivl| !strict && root.transpose(ivl).eq_enharmonic(&bass))
+                    })
+            };
+
+            match find(&intervals) {
+                Some(idx) => idx,
+                None => {
+                    intervals.push(root.distance_to(bass));
+                    intervals.sort_by(Interval::cmp_enharmonic);
+
+                    find(&intervals).expect("bass was just added to the chord")
+                }
+            }
+        }
+    };
+
+    let mut sorted = intervals.clone();
+    sorted.sort();
+
+    let ty = ChordType::iter().find(|ty| {
+        let mut ty_intervals = ty.intervals();
+        ty_intervals.sort();
+
+        ty_intervals == sorted
+    });
+
+    let inversion = u8::try_from(inversion).expect("a chord symbol can't have more than 255 tones");
+
+    Ok(
+        Chord::from_intervals_inner(ty, intervals, root, inversion)
+            .expect("inversion is an index into the intervals")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ParseChordErrorKind as K;
+
+    fn parse_type(s: &str) -> Option<ChordType> {
+        s.parse::<Chord>()
+            .unwrap_or_else(|err| panic!("failed to parse {s:?}: {err}"))
+            .chord_type()
+    }
+
+    fn err(s: &str) -> (ParseChordErrorKind, Range<usize>) {
+        let err = s.parse::<Chord>().expect_err(s);
+        (err.kind, err.span)
+    }
+
+    fn err_strict(s: &str) -> (ParseChordErrorKind, Range<usize>) {
+        let err = Chord::from_str_strict(s).expect_err(s);
+        (err.kind, err.span)
+    }
+
+    #[test]
+    fn chord_types() {
+        use ChordType as C;
+
+        let cases = [
+            ("C", C::MajorTriad), ("CM", C::MajorTriad), ("Cmaj", C::MajorTriad),
+            ("Cm", C::MinorTriad), ("Cmin", C::MinorTriad), ("C-", C::MinorTriad), ("Cmi", C::MinorTriad),
+            ("C+", C::AugmentedTriad), ("Caug", C::AugmentedTriad),
+            ("C°", C::DiminishedTriad), ("Co", C::DiminishedTriad), ("Cdim", C::DiminishedTriad),
+            ("Csus2", C::Suspended2Triad), ("Csus4", C::Suspended4Triad), ("Csus", C::Suspended4Triad),
+            ("C5", C::Fifth), ("Cno3", C::Fifth),
+            ("C6", C::MajorSixth), ("Cm6", C::MinorSixth), ("Cmb6", C::MinorFlatSixth), ("Cm(b6)", C::MinorFlatSixth),
+            ("Cdim7", C::DiminishedSeventh), ("C°7", C::DiminishedSeventh), ("Co7", C::DiminishedSeventh),
+            ("Cø", C::HalfDiminishedSeventh), ("Cø7", C::HalfDiminishedSeventh), ("Cm7b5", C::HalfDiminishedSeventh),
+            ("Cm7", C::MinorSeventh), ("C-7", C::MinorSeventh), ("Cmin7", C::MinorSeventh),
+            ("CmMaj7", C::MinorMajorSeventh), ("Cm(maj7)", C::MinorMajorSeventh), ("C-Δ7", C::MinorMajorSeventh), ("CmM7", C::MinorMajorSeventh),
+            ("C7", C::DominantSeventh),
+            ("Cmaj7", C::MajorSeventh), ("CM7", C::MajorSeventh), ("CΔ", C::MajorSeventh), ("CΔ7", C::MajorSeventh), ("Cma7", C::MajorSeventh),
+            ("C+7", C::AugmentedSeventh), ("Caug7", C::AugmentedSeventh), ("C7#5", C::AugmentedSeventh),
+            ("C+maj7", C::AugmentedMajorSeventh), ("Cmaj7#5", C::AugmentedMajorSeventh),
+            ("C7b5", C::DominantSeventhFlatFive),
+            ("C9", C::DominantNinth), ("C11", C::DominantEleventh), ("C13", C::DominantThirteenth),
+            ("Cmaj7#11", C::Lydian), ("C7#5#11", C::LydianAugmented),
+            ("C7b9", C::SeventhMinorNinth), ("C7#9", C::SeventhSharpNinth), ("C7(#9)", C::SeventhSharpNinth),
+            ("C9#11", C::SeventhAugmentedEleventh), ("C13b13", C::SeventhDiminishedThirteenth),
+            ("Cadd2", C::AddTwo), ("Cadd4", C::AddFourth), ("Cadd9", C::AddNine), ("C(add9)", C::AddNine),
+            ("C6/9", C::SixNine), ("C69", C::SixNine),
+            ("C9sus4", C::JazzSus),
+        ];
+
+        for (symbol, ty) in cases {
+            assert_eq!(parse_type(symbol), Some(ty), "{symbol}");
+        }
+
+        assert_eq!(parse_type("Csus4add9"), None);
+        assert_eq!(parse_type("C7alt"), None);
+    }
+
+    #[test]
+    fn roots_and_bass() {
+        let chord = "Bb13(#11)".parse::<Chord>().unwrap();
+        assert_eq!(chord.root, Pitch::B_FLAT);
+        assert_eq!(
+            chord.intervals(),
+            [
+                Interval::PERFECT_UNISON, Interval::MAJOR_THIRD, Interval::PERFECT_FIFTH, Interval::MINOR_SEVENTH,
+                Interval::MAJOR_NINTH, Interval::AUGMENTED_ELEVENTH, Interval::MAJOR_THIRTEENTH,
+            ]
+        );
+
+        let chord = "F#m7b5/C".parse::<Chord>().unwrap();
+        assert_eq!(chord.root, Pitch::F_SHARP);
+        assert_eq!(chord.inversion(), 2);
+
+        assert_eq!("C/E".parse::<Chord>().unwrap().inversion(), 1);
+        assert_eq!("C7/Bb".parse::<Chord>().unwrap().inversion(), 3);
+        assert_eq!("Ebmaj7/G".parse::<Chord>().unwrap().pitches()[0], Pitch::G);
+
+        // a bass note outside the chord is added to it
+        let chord = "Am/G".parse::<Chord>().unwrap();
+        assert_eq!(chord, "Am7/G".parse().unwrap());
+        assert_eq!(chord.chord_type(), Some(ChordType::MinorSeventh));
+
+        // enharmonic bass notes only match when lenient
+        assert_eq!("C/Fb".parse::<Chord>().unwrap().pitches()[0], Pitch::E);
+        assert_eq!(Chord::from_str_strict("C/Fb").unwrap().pitches()[0], Pitch::F_FLAT);
+        assert_eq!(Chord::from_str_strict("C/Fb").unwrap().intervals().len(), 4);
+    }
+
+    #[test]
+    fn alt() {
+        let chord = "G7alt".parse::<Chord>().unwrap();
+
+        assert_eq!(
+            chord.pitches(),
+            [Pitch::G, Pitch::B, Pitch::F, Pitch::A_FLAT, Pitch::A_SHARP, Pitch::C_SHARP, Pitch::E_FLAT]
+        );
+
+        assert_eq!(chord, "Galt".parse().unwrap());
+    }
+
+    #[test]
+    fn minor_spellings() {
+        for quality in ["m", "-", "min", "Min", "mi", "Mi"] {
+            for (suffix, ty) in [("", ChordType::MinorTriad), ("7", ChordType::MinorSeventh), ("Maj7", ChordType::MinorMajorSeventh)] {
+                let symbol = format!("C{quality}{suffix}");
+
+                assert_eq!(parse_type(&symbol), Some(ty), "{symbol}");
+                assert_eq!(Chord::from_str_strict(&symbol).ok().and_then(|chord| chord.chord_type()), Some(ty), "{symbol}");
+            }
+        }
+
+        for symbol in ["CMI", "CMI7", "CMIN7", "CmIn7"] {
+            assert_eq!(symbol.parse::<Chord>().unwrap().chord_type().map(|ty| ty.intervals()[1]), Some(Interval::MINOR_THIRD), "{symbol}");
+        }
+
+        assert_eq!(parse_type("CM"), Some(ChordType::MajorTriad));
+        assert_eq!(parse_type("CMadd9"), Some(ChordType::AddNine));
+        assert_eq!(err_strict("CMI7"), (K::UnexpectedCharacter, 2..3));
+    }
+
+    #[test]
+    fn lenient() {
+        assert_eq!(" c  MAJ7 ".parse::<Chord>(), "Cmaj7".parse());
+        assert_eq!("CSUS4".parse::<Chord>(), "Csus4".parse());
+        assert_eq!("C7b9b9".parse::<Chord>(), "C7b9".parse());
+        assert_eq!("C7 (b9, #11)".parse::<Chord>(), "C7b9#11".parse());
+        assert_eq!("Cno7".parse::<Chord>(), "C".parse());
+        assert_eq!("C7(9)".parse::<Chord>(), "C9".parse());
+        assert_eq!("C(addb3)".parse::<Chord>().unwrap().chord_type(), Some(ChordType::MixedThird));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(err(""), (K::Empty, 0..0));
+        assert_eq!(err("   "), (K::Empty, 0..3));
+        assert_eq!(err("H7"), (K::InvalidRoot, 0..1));
+        assert_eq!(err("C#b7"), (K::InvalidRoot, 0..3));
+        assert_eq!(err("C7/"), (K::InvalidBass, 3..3));
+        assert_eq!(err("C7/Q"), (K::InvalidBass, 3..4));
+        assert_eq!(err("Cmaj7?"), (K::UnexpectedCharacter, 5..6));
+        assert_eq!(err("C♯m7😀"), (K::UnexpectedCharacter, 6..10));
+        assert_eq!(err("C8"), (K::InvalidDegree, 1..2));
+        assert_eq!(err("Cadd8"), (K::InvalidDegree, 4..5));
+        assert_eq!(err("Cadd"), (K::InvalidDegree, 1..4));
+        assert_eq!(err("C7#13"), (K::InvalidDegree, 3..5));
+        assert_eq!(err("Csus3"), (K::InvalidDegree, 4..5));
+        assert_eq!(err("C7(7)"), (K::InvalidDegree, 3..4));
+        assert_eq!(err("C79"), (K::InvalidDegree, 1..3));
+        assert_eq!(err("C7(b9"), (K::UnbalancedParentheses, 2..3));
+        assert_eq!(err("C7b9)"), (K::UnbalancedParentheses, 4..5));
+        assert_eq!(err("C7((b9))"), (K::UnbalancedParentheses, 3..4));
+        assert_eq!(err("C5no1no5"), (K::NoTones, 0..8));
+        assert_eq!(err("C/E/G"), (K::UnexpectedCharacter, 3..4));
+    }
+
+    #[test]
+    fn strict_errors() {
+        assert_eq!(err_strict(" C"), (K::UnexpectedCharacter, 0..1));
+        assert_eq!(err_strict("c"), (K::InvalidRoot, 0..1));
+        assert_eq!(err_strict("C/e"), (K::InvalidBass, 2..3));
+        assert_eq!(err_strict("CMAJ7"), (K::UnexpectedCharacter, 2..3));
+        assert_eq!(err_strict("Cno7"), (K::OmittedMissingTone, 1..4));
+        assert_eq!(err_strict("C9add9"), (K::DuplicateModifier, 2..6));
+        assert_eq!(err_strict("Csus4sus4"), (K::DuplicateModifier, 5..9));
+
+        assert!(Chord::from_str_strict("C7(b9,#11)").is_ok());
+        assert!(Chord::from_str_strict("Bbm7b5/Fb").is_ok());
+    }
+}
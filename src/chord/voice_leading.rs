@@ -0,0 +1,414 @@
+use crate::chord::Chord;
+use crate::chord::voicing::{at_or_above, height};
+use crate::{EnharmonicEq, Note, Pitch, Semitones};
+
+/// Constraints on the voicings chosen by [`lead_voices`].
+///
+/// Voices are numbered by their position in the starting voicing.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch, Semitones};
+/// # use music_theory::chord::VoiceLeadingConstraints;
+/// let constraints = VoiceLeadingConstraints {
+///     range: Some((Note::new(Pitch::C, 3), Note::new(Pitch::C, 6))),
+///     max_leap: Some(Semitones(4)),
+///     retain_common_tones: true,
+///     .. Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VoiceLeadingConstraints {
+    /// The number of voices. If set, the starting voicing must have exactly this many notes.
+    /// Otherwise, there are as many voices as there are notes in the starting voicing.
+    pub voice_count: Option<usize>,
+    /// The lowest and highest note any voice can have, inclusive.
+    pub range: Option<(Note, Note)>,
+    /// The lowest and highest note of each voice, inclusive. Voices without an entry
+    /// are only limited by [`range`](Self::range).
+    pub voice_ranges: Vec<(Note, Note)>,
+    /// Whether a voice can move above the voice after it, or below the voice before it.
+    /// If not, the starting voicing must be ordered from lowest to highest.
+    pub allow_crossing: bool,
+    /// Whether a voice whose note is in the next chord must keep that note.
+    pub retain_common_tones: bool,
+    /// The largest interval any voice can move in one step.
+    /// If not set, voices move at most an octave.
+    pub max_leap: Option<Semitones>,
+}
+
+/// The cost of moving from one voicing to the next.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepCost {
+    /// How far each voice moved, negative if it moved down.
+    pub motion: Vec<Semitones>,
+    /// The total number of semitones moved by all voices. This is what's minimized.
+    pub total: u32,
+    /// How many voices kept the same pitch.
+    pub common_tones: usize,
+    /// The largest distance moved by a single voice.
+    pub largest_leap: Semitones,
+}
+
+/// The voicings chosen by [`lead_voices`], and the cost of each step between them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VoiceLeading {
+    /// The voicing of each chord, with the starting voicing first. Each voicing lists
+    /// the notes of each voice in the same order as the starting voicing.
+    pub voicings: Vec<Vec<Note>>,
+    /// The cost of moving from each voicing to the next, so there's one less than voicings.
+    pub costs: Vec<StepCost>,
+}
+
+/// An error returned by [`lead_voices`].
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum VoiceLeadingError {
+    /// The starting voicing didn't have the number of voices given by
+    /// [`VoiceLeadingConstraints::voice_count`].
+    #[error("Expected {expected} voices in the starting voicing, but found {found}")]
+    WrongVoiceCount {
+        expected: usize,
+        found: usize,
+    },
+    /// A voice of the starting voicing was outside of its range.
+    #[error("Voice {voice} of the starting voicing is out of range")]
+    StartOutOfRange {
+        voice: usize,
+    },
+    /// The starting voicing wasn't ordered from lowest to highest, but crossing isn't allowed.
+    #[error("The starting voicing has crossed voices")]
+    StartCrossed,
+    /// There was no voicing for the chord at this index that met the constraints.
+    #[error("No voicing for chord {step} meets the constraints")]
+    NoValidVoicing {
+        step: usize,
+    },
+}
+
+impl VoiceLeadingConstraints {
+    // the range of heights a voice can have
+    fn range_of(&self, voice: usize) -> (i32, i32) {
+        [self.range.as_ref(), self.voice_ranges.get(voice)]
+            .into_iter()
+            .flatten()
+            .fold((i32::MIN, i32::MAX), |(low, high), &(l, h)| {
+                (low.max(height(l)), high.min(height(h)))
+            })
+    }
+}
+
+/// Voices a chord progression, starting from the given voicing of the first chord and
+/// choosing each next voicing to move the voices as little as possible.
+///
+/// Each step chooses the voicing of the next chord with the smallest total motion, measured
+/// in semitones with [`Note::semitones_to`], that meets the [constraints](VoiceLeadingConstraints).
+/// Every voicing uses as many distinct tones of its chord as it can. When a chord has more
+/// tones than there are voices, any of them can be left out, and when it has fewer, some
+/// are doubled. Ties are broken in favor of the lower voicing.
+///
+/// The first chord isn't checked against the starting voicing, so the starting voicing
+/// can be anything.
+///
+/// # Errors
+/// Returns a [`VoiceLeadingError`] if the starting voicing doesn't meet the constraints,
+/// or if no voicing of some chord does.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch, Semitones};
+/// # use music_theory::chord::{Chord, VoiceLeadingConstraints, lead_voices};
+/// let chords = ["C", "F", "G7", "C"].map(|s| s.parse::<Chord>().unwrap());
+///
+/// let start = vec![
+///     Note::new(Pitch::C, 4),
+///     Note::new(Pitch::E, 4),
+///     Note::new(Pitch::G, 4),
+///     Note::new(Pitch::C, 5),
+/// ];
+///
+/// let result = lead_voices(&chords, start, &VoiceLeadingConstraints::default()).unwrap();
+///
+/// // both Cs stay, E moves up to F, and G moves up to A
+/// assert_eq!(
+///     result.voicings[1],
+///     [
+///         Note::new(Pitch::C, 4),
+///         Note::new(Pitch::F, 4),
+///         Note::new(Pitch::A, 4),
+///         Note::new(Pitch::C, 5),
+///     ]
+/// );
+///
+/// assert_eq!(result.costs[0].total, 3);
+/// assert_eq!(result.costs[0].common_tones, 2);
+/// assert_eq!(result.costs[0].motion, [Semitones(0), Semitones(1), Semitones(2), Semitones(0)]);
+/// ```
+pub fn lead_voices(chords: &[Chord], start: Vec<Note>, constraints: &VoiceLeadingConstraints) -> Result<VoiceLeading, VoiceLeadingError> {
+    if let Some(expected) = constraints.voice_count
+        && start.len() != expected
+    {
+        return Err(VoiceLeadingError::WrongVoiceCount { expected, found: start.len() });
+    }
+
+    for (voice, &note) in start.iter().enumerate() {
+        let (low, high) = constraints.range_of(voice);
+
+        if !(low..=high).contains(&height(note)) {
+            return Err(VoiceLeadingError::StartOutOfRange { voice });
+        }
+    }
+
+    if !constraints.allow_crossing && !start.windows(2).all(|w| height(w[0]) <= height(w[1])) {
+        return Err(VoiceLeadingError::StartCrossed);
+    }
+
+    let mut voicings = vec![start];
+    let mut costs = Vec::new();
+
+    for (step, chord) in chords.iter().enumerate().skip(1) {
+        let current = voicings.last().expect("there's always a starting voicing");
+
+        let next = next_voicing(chord, current, constraints)
+            .ok_or(VoiceLeadingError::NoValidVoicing { step })?;
+
+        let motion = current.iter()
+            .zip(&next)
+            .map(|(&from, &to)| from.semitones_to(to))
+            .collect::<Vec<_>>();
+
+        costs.push(StepCost {
+            total: motion.iter().map(|s| s.0.unsigned_abs() as u32).sum(),
+            common_tones: motion.iter().filter(|s| s.0 == 0).count(),
+            largest_leap: motion.iter().map(|s| Semitones(s.0.abs())).max().unwrap_or_default(),
+            motion,
+        });
+
+        voicings.push(next);
+    }
+
+    Ok(VoiceLeading { voicings, costs })
+}
+
+fn next_voicing(chord: &Chord, current: &[Note], constraints: &VoiceLeadingConstraints) -> Option<Vec<Note>> {
+    let mut tones: Vec<Pitch> = Vec::new();
+
+    for pitch in chord.pitches() {
+        if !tones.contains(&pitch) {
+            tones.push(pitch);
+        }
+    }
+
+    let leap = constraints.max_leap.map_or(12, |s| s.0.unsigned_abs() as i32);
+
+    let candidates = current.iter()
+        .enumerate()
+        .map(|(voice, &note)| {
+            let h = height(note);
+            let (low, high) = constraints.range_of(voice);
+            let (low, high) = (low.max(h - leap), high.min(h + leap));
+
+            let common = tones.iter().find(|t| t.eq_enharmonic(&note.pitch));
+
+            let mut notes = match common {
+                Some(&tone) if constraints.retain_common_tones => vec![at_or_above(tone, h)],
+                _ => tones.iter()
+                    .flat_map(|&tone| {
+                        let mut note = at_or_above(tone, low);
+                        let mut notes = Vec::new();
+
+                        while height(note) <= high {
+                            notes.push(note);
+                            note.octave += 1;
+                        }
+
+                        notes
+                    })
+                    .collect(),
+            };
+
+            notes.sort_by_key(|&n| ((height(n) - h).abs(), height(n)));
+            notes
+        })
+        .collect::<Vec<_>>();
+
+    let mut search = Search {
+        candidates: &candidates,
+        current,
+        needed: tones.len().min(current.len()),
+        allow_crossing: constraints.allow_crossing,
+        chosen: Vec::with_capacity(current.len()),
+        best: None,
+    };
+
+    search.run(0);
+
+    search.best.map(|(_, notes)| notes)
+}
+
+struct Search<'a> {
+    candidates: &'a [Vec<Note>],
+    current: &'a [Note],
+    // how many distinct tones the voicing needs
+    needed: usize,
+    allow_crossing: bool,
+    chosen: Vec<Note>,
+    best: Option<(u32, Vec<Note>)>,
+}
+
+impl Search<'_> {
+    fn covered(&self) -> usize {
+        let mut pitches = self.chosen.iter().map(|n| n.pitch).collect::<Vec<_>>();
+        pitches.sort();
+        pitches.dedup();
+        pitches.len()
+    }
+
+    fn run(&mut self, cost: u32) {
+        if self.best.as_ref().is_some_and(|(best, _)| cost >= *best) {
+            return;
+        }
+
+        let voice = self.chosen.len();
+
+        if self.covered() + (self.current.len() - voice) < self.needed {
+            return;
+        }
+
+        if voice == self.current.len() {
+            self.best = Some((cost, self.chosen.clone()));
+            return;
+        }
+
+        for &note in &self.candidates[voice] {
+            let crosses = self.chosen.last().is_some_and(|&below| height(note) < height(below));
+
+            if crosses && !self.allow_crossing {
+                continue;
+            }
+
+            let motion = self.current[voice].semitones_to(note).0.unsigned_abs() as u32;
+
+            self.chosen.push(note);
+            self.run(cost + motion);
+            self.chosen.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chords(symbols: &[&str]) -> Vec<Chord> {
+        symbols.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn notes(s: &str) -> Vec<Note> {
+        s.split(' ').map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn ii_v_i() {
+        let progression = chords(&["Dm7", "G7", "Cmaj7"]);
+        let result = lead_voices(&progression, notes("D3 F3 A3 C4"), &Default::default()).unwrap();
+
+        assert_eq!(result.voicings[1], notes("D3 F3 G3 B3"));
+        assert_eq!(result.voicings[2], notes("C3 E3 G3 B3"));
+
+        assert_eq!(result.costs.len(), 2);
+        assert_eq!(result.costs[0].total, 3);
+        assert_eq!(result.costs[1].total, 3);
+        assert_eq!(result.costs[1].largest_leap, Semitones(2));
+    }
+
+    #[test]
+    fn every_tone_is_used() {
+        let progression = chords(&["C", "Bdim", "C"]);
+        let result = lead_voices(&progression, notes("C4 E4 G4"), &Default::default()).unwrap();
+
+        for (voicing, chord) in result.voicings.iter().zip(&progression) {
+            for pitch in chord.pitches() {
+                assert!(voicing.iter().any(|n| n.pitch == pitch), "{pitch} missing from {voicing:?}");
+            }
+        }
+
+        // fewer voices than tones
+        let progression = chords(&["C", "G9"]);
+        let result = lead_voices(&progression, notes("C4 E4"), &Default::default()).unwrap();
+
+        assert_eq!(result.voicings[1], notes("B3 F4"));
+    }
+
+    #[test]
+    fn constraints() {
+        let progression = chords(&["C", "Ab"]);
+        let start = notes("C4 E4 G4");
+
+        let free = lead_voices(&progression, start.clone(), &Default::default()).unwrap();
+        assert_eq!(free.voicings[1], notes("C4 E♭4 A♭4"));
+
+        let retained = VoiceLeadingConstraints { retain_common_tones: true, ..Default::default() };
+        let result = lead_voices(&progression, start.clone(), &retained).unwrap();
+        assert_eq!(result.voicings[1][0], Note::new(Pitch::C, 4));
+
+        // the top voice can't go above G4, so it has to go down
+        let ranged = VoiceLeadingConstraints {
+            voice_ranges: vec![(notes("C3")[0], notes("C5")[0]); 2].into_iter()
+                .chain([(notes("C4")[0], notes("G4")[0])])
+                .collect(),
+            ..Default::default()
+        };
+
+        let result = lead_voices(&progression, start.clone(), &ranged).unwrap();
+        assert!(result.voicings[1].iter().all(|n| height(*n) <= height(notes("G4")[0])));
+
+        let leaps = VoiceLeadingConstraints { max_leap: Some(Semitones(0)), ..Default::default() };
+
+        assert_eq!(
+            lead_voices(&progression, start.clone(), &leaps),
+            Err(VoiceLeadingError::NoValidVoicing { step: 1 }),
+        );
+    }
+
+    #[test]
+    fn crossing() {
+        let progression = chords(&["C", "C"]);
+
+        let crossed = VoiceLeadingConstraints { allow_crossing: true, ..Default::default() };
+
+        assert_eq!(
+            lead_voices(&progression, notes("G4 C4 E4"), &Default::default()),
+            Err(VoiceLeadingError::StartCrossed),
+        );
+
+        let result = lead_voices(&progression, notes("G4 C4 E4"), &crossed).unwrap();
+        assert_eq!(result.voicings[1], notes("G4 C4 E4"));
+        assert_eq!(result.costs[0].common_tones, 3);
+    }
+
+    #[test]
+    fn start_errors() {
+        let progression = chords(&["C"]);
+
+        let counted = VoiceLeadingConstraints { voice_count: Some(4), ..Default::default() };
+
+        assert_eq!(
+            lead_voices(&progression, notes("C4 E4 G4"), &counted),
+            Err(VoiceLeadingError::WrongVoiceCount { expected: 4, found: 3 }),
+        );
+
+        let ranged = VoiceLeadingConstraints { range: Some((notes("D4")[0], notes("C5")[0])), ..Default::default() };
+
+        assert_eq!(
+            lead_voices(&progression, notes("C4 E4 G4"), &ranged),
+            Err(VoiceLeadingError::StartOutOfRange { voice: 0 }),
+        );
+
+        let result = lead_voices(&progression, notes("C4 E4 G4"), &Default::default()).unwrap();
+        assert_eq!(result.voicings.len(), 1);
+        assert!(result.costs.is_empty());
+    }
+}
@@ -0,0 +1,568 @@
+use std::collections::VecDeque;
+use crate::chord::Chord;
+use crate::{Note, Pitch};
+
+/// A strategy for arranging the tones of a [`Chord`] into notes.
+///
+/// Styles which start from a close voicing (like the drop voicings) start from the
+/// chord's [inversion](Chord::inversion), so the same style gives a different voicing
+/// for each inversion.
+///
+/// | Style | C major seventh |
+/// |-------|-----------------|
+/// | [`Close`](Self::Close) | C E G B |
+/// | [`Open`](Self::Open) | C G E B |
+/// | [`Drop2`](Self::Drop2) | G C E B |
+/// | [`Drop2And4`](Self::Drop2And4) | C G E B |
+/// | [`Drop3`](Self::Drop3) | E C G B |
+/// | [`Shell`](Self::Shell) | C E B |
+/// | [`RootlessA`](Self::RootlessA) | E G B (D, if the chord has a ninth) |
+/// | [`RootlessB`](Self::RootlessB) | B (D) E G |
+/// | [`Quartal`](Self::Quartal) | G C B E |
+/// | [`SpreadTriad`](Self::SpreadTriad) | C G E |
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoicingStyle {
+    /// Every tone, stacked as closely as possible from the bass.
+    Close,
+    /// Every tone, with every other tone of the close voicing moved up an octave.
+    Open,
+    /// The close voicing, with the second voice from the top dropped an octave.
+    /// Needs at least three tones.
+    Drop2,
+    /// The close voicing, with the second and fourth voices from the top dropped an octave.
+    /// Needs at least four tones.
+    Drop2And4,
+    /// The close voicing, with the third voice from the top dropped an octave.
+    /// Needs at least four tones.
+    Drop3,
+    /// The root, third, and seventh (1-3-7). Sixth chords use the sixth, and suspended chords
+    /// use the suspended tone in place of the third.
+    Shell,
+    /// A rootless voicing built up from the third: 3-5-7-9.
+    /// If the chord has a thirteenth, it replaces the fifth.
+    RootlessA,
+    /// A rootless voicing built up from the seventh: 7-9-3-5.
+    /// If the chord has a thirteenth, it replaces the fifth.
+    RootlessB,
+    /// Every tone, stacked in fourths wherever possible, starting from whichever tone gives
+    /// the most fourths. This ignores the chord's inversion, but prefers to keep its bass.
+    Quartal,
+    /// Only the root, third, and fifth, spread out as root, fifth, and the third an octave up.
+    /// If the third or fifth is in the bass, it's voiced from there instead.
+    SpreadTriad,
+}
+
+/// A chord tone to double, in addition to the tones of a [`VoicingStyle`].
+///
+/// The doubled tone is added on top of the voicing.
+/// If the chord doesn't have the tone, nothing is doubled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Doubling {
+    /// Double the chord's root.
+    Root,
+    /// Double the chord's third.
+    Third,
+    /// Double the chord's fifth.
+    Fifth,
+    /// Double whichever tone is in the bass of the chord's inversion.
+    Bass,
+}
+
+/// A [`VoicingStyle`], and optionally a tone to double.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// # use music_theory::chord::{Chord, Doubling, Voicing, VoicingStyle};
+/// # use music_theory::chord::types::ChordType;
+/// let chord = Chord::from_type(ChordType::MajorTriad, Pitch::G, 0).unwrap();
+/// let voicing = Voicing::new(VoicingStyle::Close).with_doubling(Doubling::Root);
+///
+/// let notes = chord.voice(voicing, Note::new(Pitch::C, 3), Note::new(Pitch::C, 5)).unwrap();
+///
+/// assert_eq!(
+///     notes,
+///     [
+///         Note::new(Pitch::G, 3),
+///         Note::new(Pitch::B, 3),
+///         Note::new(Pitch::D, 4),
+///         Note::new(Pitch::G, 4),
+///     ]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Voicing {
+    pub style: VoicingStyle,
+    pub doubling: Option<Doubling>,
+}
+
+impl Voicing {
+    /// Creates a voicing of the given style, without any doubling.
+    pub fn new(style: VoicingStyle) -> Self {
+        Self { style, doubling: None }
+    }
+
+    /// Returns the same voicing, doubling the given tone.
+    pub fn with_doubling(self, doubling: Doubling) -> Self {
+        Self { doubling: Some(doubling), ..self }
+    }
+
+    // the pitches of the voicing from the bottom up, each to be placed as close as possible above the last
+    fn stack(self, chord: &Chord) -> Option<Vec<Pitch>> {
+        let close = chord.pitches();
+        let len = close.len();
+
+        let mut stack = match self.style {
+            VoicingStyle::Close => close,
+            VoicingStyle::Open => {
+                let (even, odd): (Vec<_>, Vec<_>) = close.iter().enumerate().partition(|(i, _)| i % 2 == 0);
+
+                even.into_iter().chain(odd).map(|(_, &p)| p).collect()
+            }
+            VoicingStyle::Drop2 if len >= 3 => drop_voices(&close, &[2]),
+            VoicingStyle::Drop2And4 if len >= 4 => drop_voices(&close, &[2, 4]),
+            VoicingStyle::Drop3 if len >= 4 => drop_voices(&close, &[3]),
+            VoicingStyle::Drop2 | VoicingStyle::Drop2And4 | VoicingStyle::Drop3 => return None,
+            VoicingStyle::Shell => vec![
+                chord.tone(&[1])?,
+                chord.tone(&[3, 4, 2])?,
+                chord.tone(&[7, 6])?,
+            ],
+            VoicingStyle::RootlessA | VoicingStyle::RootlessB => {
+                let third = chord.tone(&[3, 4, 2])?;
+                let seventh = chord.tone(&[7, 6])?;
+                let ninth = chord.tone(&[9, 2]);
+
+                let thirteenth = chord.tone(&[13])
+                    .or_else(|| chord.tone(&[7]).and(chord.tone(&[6])));
+
+                let fifth = thirteenth.or_else(|| chord.tone(&[5]));
+
+                let order = match self.style {
+                    VoicingStyle::RootlessA => [Some(third), fifth, Some(seventh), ninth],
+                    _ => [Some(seventh), ninth, Some(third), fifth],
+                };
+
+                order.into_iter().flatten().collect()
+            }
+            VoicingStyle::Quartal if len >= 2 => quartal(&close),
+            VoicingStyle::Quartal => return None,
+            VoicingStyle::SpreadTriad => {
+                let root = chord.tone(&[1])?;
+                let third = chord.tone(&[3, 4, 2])?;
+                let fifth = chord.tone(&[5])?;
+
+                let bass = close[0];
+
+                if bass == third {
+                    vec![third, root, fifth]
+                } else if bass == fifth {
+                    vec![fifth, third, root]
+                } else {
+                    vec![root, fifth, third]
+                }
+            }
+        };
+
+        if let Some(doubling) = self.doubling {
+            let doubled = match doubling {
+                Doubling::Root => chord.tone(&[1]),
+                Doubling::Third => chord.tone(&[3]),
+                Doubling::Fifth => chord.tone(&[5]),
+                Doubling::Bass => Some(chord.pitches()[0]),
+            };
+
+            stack.extend(doubled);
+        }
+
+        Some(stack)
+    }
+}
+
+impl From<VoicingStyle> for Voicing {
+    fn from(style: VoicingStyle) -> Self {
+        Self::new(style)
+    }
+}
+
+// drops the voices at the given positions from the top (1 being the top) an octave,
+// moving them below the rest in the same order
+fn drop_voices(close: &[Pitch], from_top: &[usize]) -> Vec<Pitch> {
+    let is_dropped = |i: usize| from_top.contains(&(close.len() - i));
+
+    let dropped = close.iter().enumerate().filter(|&(i, _)| is_dropped(i));
+    let kept = close.iter().enumerate().filter(|&(i, _)| !is_dropped(i));
+
+    dropped.chain(kept).map(|(_, &p)| p).collect()
+}
+
+// stacks perfect or augmented fourths greedily from each tone, keeping the stack with the most
+// fourths between adjacent voices. When no unused tone is a fourth above the top of the stack, the
+// next tone starts a new run of fourths if it can, and is otherwise the lowest unused tone in close
+// position. Each pitch class keeps a queue of its unused tones, so each step only looks at the
+// twelve pitch classes, and the whole search takes quadratic time.
+fn quartal(close: &[Pitch]) -> Vec<Pitch> {
+    const FOURTHS: [usize; 2] = [5, 6];
+
+    let class = |pitch: Pitch| pitch.semitones_offset_from_c().0.rem_euclid(12) as usize;
+
+    let stack = |start: usize| {
+        let mut unused = [(); 12].map(|_| VecDeque::new());
+
+        for (i, &pitch) in close.iter().enumerate().filter(|&(i, _)| i != start) {
+            unused[class(pitch)].push_back(i);
+        }
+
+        let mut fourths = 0;
+        let mut stack = vec![close[start]];
+
+        while stack.len() < close.len() {
+            let top = class(*stack.last().expect("the stack starts with a tone"));
+
+            let next_class = match FOURTHS.into_iter().map(|step| (top + step) % 12).find(|&c| !unused[c].is_empty()) {
+                Some(next_class) => {
+                    fourths += 1;
+                    next_class
+                }
+                None => {
+                    let unused_classes = (0..12).filter(|&c| !unused[c].is_empty());
+                    let starts_run = |&c: &usize| FOURTHS.iter().any(|step| !unused[(c + step) % 12].is_empty());
+
+                    unused_classes.clone()
+                        .filter(starts_run)
+                        .min_by_key(|&c| unused[c][0])
+                        .or_else(|| unused_classes.min_by_key(|&c| unused[c][0]))
+                        .expect("the stack is missing a tone")
+                }
+            };
+
+            let next = unused[next_class].pop_front().expect("the pitch class has an unused tone");
+            stack.push(close[next]);
+        }
+
+        (fourths, stack)
+    };
+
+    // the first stack with the most fourths wins, so ties favor the chord's bass
+    (0..close.len())
+        .map(stack)
+        .rev()
+        .max_by_key(|&(fourths, _)| fourths)
+        .expect("quartal voicings need at least two tones")
+        .1
+}
+
+// the height of a note in semitones from C0
+pub(super) fn height(note: Note) -> i32 {
+    note.octave as i32 * 12 + note.pitch.semitones_offset_from_c().0 as i32
+}
+
+// the lowest instance of the pitch at or above the given height
+pub(super) fn at_or_above(pitch: Pitch, min_height: i32) -> Note {
+    let offset = pitch.semitones_offset_from_c().0 as i32;
+    let octave = (min_height - offset).div_euclid(12) + ((min_height - offset).rem_euclid(12) != 0) as i32;
+
+    Note::new(pitch, octave as _)
+}
+
+impl Chord {
+    // the first pitch whose interval from the root has one of the given numbers,
+    // trying each number in order
+    fn tone(&self, numbers: &[i16]) -> Option<Pitch> {
+        numbers.iter()
+            .find_map(|&n| self.intervals.iter().find(|ivl| ivl.number().get() == n))
+            .map(|&ivl| self.root.transpose(ivl))
+    }
+
+    /// Voices the chord in the given [`Voicing`], as low as possible with every note
+    /// between `low` and `high` inclusive.
+    ///
+    /// The notes are returned from lowest to highest. Returns `None` if the chord doesn't
+    /// have the tones the style needs (like a third for [`Shell`](VoicingStyle::Shell)), or the
+    /// voicing doesn't fit in the range.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::chord::{Chord, VoicingStyle};
+    /// let chord = "Cmaj7".parse::<Chord>().unwrap();
+    /// let (low, high) = (Note::new(Pitch::C, 3), Note::new(Pitch::C, 6));
+    ///
+    /// assert_eq!(
+    ///     chord.voice(VoicingStyle::Drop2.into(), low, high).unwrap(),
+    ///     [
+    ///         Note::new(Pitch::G, 3),
+    ///         Note::new(Pitch::C, 4),
+    ///         Note::new(Pitch::E, 4),
+    ///         Note::new(Pitch::B, 4),
+    ///     ]
+    /// );
+    ///
+    /// // a major seventh has no ninth, so only three notes
+    /// assert_eq!(chord.voice(VoicingStyle::RootlessA.into(), low, high).unwrap().len(), 3);
+    ///
+    /// // the range is too small
+    /// assert_eq!(chord.voice(VoicingStyle::Open.into(), low, Note::new(Pitch::C, 4)), None);
+    /// ```
+    pub fn voice(&self, voicing: Voicing, low: Note, high: Note) -> Option<Vec<Note>> {
+        let stack = voicing.stack(self)?;
+
+        let mut notes = Vec::with_capacity(stack.len());
+        let mut min_height = height(low);
+
+        for pitch in stack {
+            let note = at_or_above(pitch, min_height);
+
+            min_height = height(note) + 1;
+            notes.push(note);
+        }
+
+        let fits = notes.last().is_none_or(|&top| height(top) <= height(high));
+
+        fits.then_some(notes)
+    }
+
+    /// Returns an iterator over every voicing of the chord with every note between `low` and
+    /// `high` inclusive, useful for searching for the best voicing by some measure.
+    ///
+    /// Each voicing has each distinct tone of the chord exactly once (plus the doubled tone,
+    /// if given), no two notes sound the same pitch, and the chord's bass is the lowest note.
+    /// The notes of each voicing are returned from lowest to highest.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::chord::{Chord, Doubling};
+    /// let chord = "C/E".parse::<Chord>().unwrap();
+    /// let (low, high) = (Note::new(Pitch::C, 4), Note::new(Pitch::B, 5));
+    ///
+    /// let voicings = chord.voicings(None, low, high).collect::<Vec<_>>();
+    ///
+    /// // E4 in the bass, C5 above it, and G4 or G5
+    /// assert_eq!(voicings.len(), 2);
+    /// assert!(voicings.iter().all(|notes| notes[0].pitch == Pitch::E));
+    ///
+    /// let doubled = chord.voicings(Some(Doubling::Root), low, high).collect::<Vec<_>>();
+    /// assert!(doubled.iter().all(|notes| notes.len() == 4));
+    /// ```
+    pub fn voicings(&self, doubling: Option<Doubling>, low: Note, high: Note) -> Voicings {
+        let mut tones: Vec<Pitch> = Vec::new();
+
+        for pitch in self.pitches() {
+            if !tones.contains(&pitch) {
+                tones.push(pitch);
+            }
+        }
+
+        let doubled = match doubling {
+            None => None,
+            Some(Doubling::Root) => self.tone(&[1]),
+            Some(Doubling::Third) => self.tone(&[3]),
+            Some(Doubling::Fifth) => self.tone(&[5]),
+            Some(Doubling::Bass) => tones.first().copied(),
+        };
+
+        tones.extend(doubled);
+
+        let (low, high) = (height(low), height(high));
+
+        let candidates = tones.iter()
+            .map(|&pitch| {
+                let mut note = at_or_above(pitch, low);
+                let mut notes = Vec::new();
+
+                while height(note) <= high {
+                    notes.push(note);
+                    note.octave += 1;
+                }
+
+                notes
+            })
+            .collect::<Vec<_>>();
+
+        let done = candidates.iter().any(Vec::is_empty);
+
+        Voicings {
+            bass: self.pitches()[0],
+            doubled: doubled.is_some(),
+            indices: vec![0; candidates.len()],
+            candidates,
+            done,
+        }
+    }
+}
+
+/// An iterator over the voicings of a chord within a range.
+///
+/// Created by [`Chord::voicings`].
+#[derive(Clone, Debug)]
+pub struct Voicings {
+    bass: Pitch,
+    // if the last tone is a doubling of another tone
+    doubled: bool,
+    // the notes each tone can be, from lowest to highest
+    candidates: Vec<Vec<Note>>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl Voicings {
+    fn advance(&mut self) {
+        for (idx, candidates) in self.indices.iter_mut().zip(&self.candidates).rev() {
+            *idx += 1;
+
+            if *idx < candidates.len() {
+                return;
+            }
+
+            *idx = 0;
+        }
+
+        self.done = true;
+    }
+
+    fn current(&self) -> Option<Vec<Note>> {
+        let mut notes = self.indices.iter()
+            .zip(&self.candidates)
+            .map(|(&idx, candidates)| candidates[idx])
+            .collect::<Vec<_>>();
+
+        notes.sort_by_key(|&n| height(n));
+
+        let unique = notes.windows(2).all(|w| height(w[0]) != height(w[1]));
+
+        if !unique || notes[0].pitch != self.bass {
+            return None;
+        }
+
+        // the doubled tone is the same as another tone, so only keep one ordering of the two
+        if self.doubled {
+            let doubled = *self.candidates.last()?.get(*self.indices.last()?)?;
+
+            let original = self.indices.iter()
+                .zip(&self.candidates)
+                .find(|(_, candidates)| candidates[0].pitch == doubled.pitch)
+                .map(|(&idx, candidates)| candidates[idx])?;
+
+            if height(doubled) < height(original) {
+                return None;
+            }
+        }
+
+        Some(notes)
+    }
+}
+
+impl Iterator for Voicings {
+    type Item = Vec<Note>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let current = self.current();
+            self.advance();
+
+            if current.is_some() {
+                return current;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(symbol: &str, style: VoicingStyle) -> Option<Vec<String>> {
+        let chord = symbol.parse::<Chord>().unwrap();
+
+        chord.voice(style.into(), Note::new(Pitch::C, 3), Note::new(Pitch::C, 7))
+            .map(|notes| notes.iter().map(Note::to_string).collect())
+    }
+
+    #[test]
+    fn styles() {
+        use VoicingStyle as S;
+
+        let cases = [
+            ("Cmaj7", S::Close, vec!["C3", "E3", "G3", "B3"]),
+            ("Cmaj7/E", S::Close, vec!["E3", "G3", "B3", "C4"]),
+            ("Cmaj7", S::Open, vec!["C3", "G3", "E4", "B4"]),
+            ("C", S::Open, vec!["C3", "G3", "E4"]),
+            ("Cmaj7", S::Drop2, vec!["G3", "C4", "E4", "B4"]),
+            ("Cmaj7", S::Drop2And4, vec!["C3", "G3", "E4", "B4"]),
+            ("Cmaj7", S::Drop3, vec!["E3", "C4", "G4", "B4"]),
+            ("C", S::Drop2, vec!["E3", "C4", "G4"]),
+            ("Cmaj7", S::Shell, vec!["C3", "E3", "B3"]),
+            ("C6", S::Shell, vec!["C3", "E3", "A3"]),
+            ("C7sus4", S::Shell, vec!["C3", "F3", "B♭3"]),
+            ("Dm9", S::RootlessA, vec!["F3", "A3", "C4", "E4"]),
+            ("Dm9", S::RootlessB, vec!["C3", "E3", "F3", "A3"]),
+            ("G13", S::RootlessA, vec!["B3", "E4", "F4", "A4"]),
+            ("G13", S::RootlessB, vec!["F3", "A3", "B3", "E4"]),
+            ("Em11", S::Quartal, vec!["F♯3", "B3", "E4", "A4", "D5", "G5"]),
+            ("Cmaj7", S::Quartal, vec!["G3", "C4", "B4", "E5"]),
+            ("C", S::SpreadTriad, vec!["C3", "G3", "E4"]),
+            ("C/E", S::SpreadTriad, vec!["E3", "C4", "G4"]),
+            ("Cmaj7", S::SpreadTriad, vec!["C3", "G3", "E4"]),
+        ];
+
+        for (symbol, style, expected) in cases {
+            assert_eq!(voice(symbol, style), Some(expected.into_iter().map(str::to_owned).collect()), "{symbol} {style:?}");
+        }
+
+        assert_eq!(voice("C5", S::Shell), None);
+        assert_eq!(voice("C", S::Drop3), None);
+        assert_eq!(voice("C5", S::Drop2), None);
+        assert_eq!(voice("Csus4", S::SpreadTriad), Some(vec!["C3".to_owned(), "G3".to_owned(), "F4".to_owned()]));
+    }
+
+    #[test]
+    fn doubling_and_range() {
+        let chord = "C".parse::<Chord>().unwrap();
+        let voicing = Voicing::new(VoicingStyle::Close).with_doubling(Doubling::Fifth);
+
+        let notes = chord.voice(voicing, Note::new(Pitch::D, 3), Note::new(Pitch::C, 6)).unwrap();
+
+        assert_eq!(
+            notes,
+            [Note::new(Pitch::C, 4), Note::new(Pitch::E, 4), Note::new(Pitch::G, 4), Note::new(Pitch::G, 5)]
+        );
+
+        assert_eq!(chord.voice(voicing, Note::new(Pitch::D, 3), Note::new(Pitch::F, 5)), None);
+
+        let b_sharp = Chord::from_type(crate::chord::types::ChordType::MajorTriad, Pitch::B_SHARP, 0).unwrap();
+        let notes = b_sharp.voice(VoicingStyle::Close.into(), Note::new(Pitch::C, 4), Note::new(Pitch::C, 6)).unwrap();
+
+        assert_eq!(notes[0], Note::new(Pitch::B_SHARP, 3));
+    }
+
+    #[test]
+    fn voicings() {
+        let chord = "C7".parse::<Chord>().unwrap();
+        let (low, high) = (Note::new(Pitch::C, 3), Note::new(Pitch::B, 4));
+
+        let voicings = chord.voicings(None, low, high).collect::<Vec<_>>();
+
+        // C3 in the bass with three other tones in either octave, or C4 and everything above it
+        assert_eq!(voicings.len(), 8 + 1);
+
+        for notes in &voicings {
+            assert_eq!(notes.len(), 4);
+            assert_eq!(notes[0].pitch, Pitch::C);
+            assert!(notes.windows(2).all(|w| height(w[0]) < height(w[1])));
+        }
+
+        let doubled = chord.voicings(Some(Doubling::Root), low, high).collect::<Vec<_>>();
+
+        // only C3 in the bass, with C4 doubled, and the three others in either octave
+        assert_eq!(doubled.len(), 8);
+        assert!(doubled.iter().all(|notes| notes.len() == 5));
+
+        assert_eq!(chord.voicings(None, high, low).count(), 0);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::counterpoint::{cadential_pitches, Counterpoint, CounterpointNote, Placement, Species};
+use crate::harmony::{Key, ScaleDegree};
+use crate::{Note, Semitones};
+
+impl Counterpoint {
+    /// The most notes [`Counterpoint::generate`] tries before giving up.
+    pub const SEARCH_LIMIT: usize = 200_000;
+
+    /// Generates a counterpoint line in the given species against a cantus firmus, using a
+    /// backtracking search.
+    ///
+    /// Notes are chosen from the key, with the raised sixth and seventh scale degrees in the second
+    /// to last measure, and are at most a twelfth away from the cantus firmus, without crossing it.
+    /// Stepwise motion is preferred, but the order candidates are tried in is randomized with
+    /// `seed`, so different seeds give different lines, while the same seed always gives the same line.
+    /// For fifth species, the rhythm of each measure is also chosen randomly. If the search gets stuck,
+    /// it restarts with a new random order, and for fifth species, a new rhythm.
+    ///
+    /// Every note is checked against the rules used by [`Counterpoint::validate`] as it's added,
+    /// so a generated line always validates. Returns `None` if there's no valid line, or if one
+    /// isn't found within [`SEARCH_LIMIT`](Self::SEARCH_LIMIT) notes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::harmony::Key;
+    /// # use music_theory::counterpoint::{Counterpoint, Placement, Species};
+    /// let n = |s: &str| s.parse::<Note>().unwrap();
+    ///
+    /// let cantus = ["C4", "D4", "F4", "E4", "D4", "C4"].map(n);
+    /// let key = Key::major(Pitch::C);
+    ///
+    /// let line = Counterpoint::generate(&cantus, key, Species::First, Placement::Above, 42).unwrap();
+    ///
+    /// assert_eq!(line.notes.len(), 6);
+    /// assert!(line.validate(&cantus, key).is_empty());
+    ///
+    /// // the same seed gives the same line
+    /// assert_eq!(Counterpoint::generate(&cantus, key, Species::First, Placement::Above, 42), Some(line));
+    /// ```
+    pub fn generate(cantus: &[Note], key: Key, species: Species, placement: Placement, seed: u64) -> Option<Self> {
+        const FIRST_ATTEMPT_LIMIT: usize = 1_000;
+
+        if cantus.is_empty() {
+            return None;
+        }
+
+        let mut rng = Rng::new(seed);
+        let pool = pool(cantus, key);
+
+        let mut remaining = Self::SEARCH_LIMIT;
+        let mut limit = FIRST_ATTEMPT_LIMIT;
+
+        while remaining > 0 {
+            let (rest, slots) = rhythm(species, cantus.len(), &mut rng);
+
+            let attempt_limit = limit.min(remaining);
+
+            let mut search = Search {
+                cantus,
+                key,
+                slots: &slots,
+                pool: &pool,
+                line: Self { species, placement, rest, notes: Vec::with_capacity(slots.len()) },
+                rng: &mut rng,
+                remaining: attempt_limit,
+            };
+
+            if search.run() {
+                return Some(search.line);
+            }
+
+            remaining -= attempt_limit;
+            limit *= 2;
+        }
+
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Slot {
+    duration: u8,
+    // whether the slot continues the previous note
+    continues: bool,
+    tied: bool,
+}
+
+impl Slot {
+    fn new(duration: u8) -> Self {
+        Self { duration, continues: false, tied: false }
+    }
+
+    fn tied(self) -> Self {
+        Self { tied: true, .. self }
+    }
+
+    fn continues(self) -> Self {
+        Self { continues: true, .. self }
+    }
+}
+
+// the rest before the line, and the rhythm of its notes
+fn rhythm(species: Species, measures: usize, rng: &mut Rng) -> (u8, Vec<Slot>) {
+    let interior = measures - 1;
+    let whole = Slot::new(4);
+
+    if interior == 0 {
+        return (0, vec![whole]);
+    }
+
+    match species {
+        Species::First => (0, vec![whole; measures]),
+        Species::Second => (0, [Slot::new(2); 2].repeat(interior).into_iter().chain([whole]).collect()),
+        Species::Third => (0, [Slot::new(1); 4].repeat(interior).into_iter().chain([whole]).collect()),
+        Species::Fourth => {
+            let mut slots = Vec::new();
+
+            for measure in 0..interior {
+                if measure > 0 {
+                    slots.push(Slot::new(2).continues());
+                }
+
+                let weak = Slot::new(2);
+                slots.push(if measure + 1 < interior { weak.tied() } else { weak });
+            }
+
+            slots.push(whole);
+
+            (2, slots)
+        }
+        Species::Fifth => {
+            let mut slots = Vec::new();
+            let mut tied = false;
+
+            for measure in 0..interior {
+                let cadence = measure + 1 == interior;
+
+                let choices: &[&[u8]] = match (measure, tied, cadence) {
+                    (0, _, _) => &[&[2]],
+                    (_, true, true) => &[&[2]],
+                    (_, true, false) => &[&[2], &[1, 1]],
+                    (_, false, true) => &[&[2, 2], &[1, 1, 2]],
+                    (_, false, false) => &[&[2, 2], &[2, 1, 1], &[1, 1, 2], &[1, 1, 1, 1]],
+                };
+
+                if tied {
+                    slots.push(Slot::new(2).continues());
+                }
+
+                slots.extend(choices[rng.below(choices.len())].iter().map(|&d| Slot::new(d)));
+
+                // ties into the next measure from a half note on the weak beat
+                tied = !cadence && slots.last().is_some_and(|s| s.duration == 2) && rng.below(2) == 0;
+
+                if let Some(last) = slots.last_mut().filter(|_| tied) {
+                    *last = last.tied();
+                }
+            }
+
+            slots.push(whole);
+
+            (2, slots)
+        }
+    }
+}
+
+// every note a line against this cantus could use
+fn pool(cantus: &[Note], key: Key) -> Vec<Note> {
+    const RANGE: i16 = 19;
+
+    let low = cantus.iter().min_by_key(|n| Note::MIDDLE_C.semitones_to(**n)).expect("not empty");
+    let high = cantus.iter().max_by_key(|n| Note::MIDDLE_C.semitones_to(**n)).expect("not empty");
+
+    let pitches = (1..=7)
+        .map(|n| key.relative_pitch(ScaleDegree::from_num(n).expect("in range")))
+        .chain(cadential_pitches(key));
+
+    let mut pool = pitches
+        .flat_map(|pitch| (low.octave.saturating_sub(3)..=high.octave.saturating_add(3)).map(move |octave| Note::new(pitch, octave)))
+        .filter(|n| low.semitones_to(*n).0 >= -RANGE && n.semitones_to(*high).0 >= -RANGE)
+        .collect::<Vec<_>>();
+
+    pool.sort();
+    pool.dedup();
+
+    pool
+}
+
+struct Search<'a> {
+    cantus: &'a [Note],
+    key: Key,
+    slots: &'a [Slot],
+    pool: &'a [Note],
+    line: Counterpoint,
+    rng: &'a mut Rng,
+    remaining: usize,
+}
+
+impl Search<'_> {
+    // a twelfth
+    const MAX_DISTANCE: i16 = 19;
+
+    fn run(&mut self) -> bool {
+        let index = self.line.notes.len();
+
+        let Some(&slot) = self.slots.get(index) else {
+            return true;
+        };
+
+        let candidates = match self.line.notes.last() {
+            Some(prev) if slot.continues => vec![prev.note],
+            prev => self.candidates(index, prev.map(|p| p.note)),
+        };
+
+        for note in candidates {
+            if self.remaining == 0 {
+                return false;
+            }
+
+            self.remaining -= 1;
+
+            self.line.notes.push(CounterpointNote { note, duration: slot.duration, tied: slot.tied });
+
+            let complete = index + 1 == self.slots.len();
+
+            if self.line.check(self.cantus, self.key, complete).is_empty() && self.run() {
+                return true;
+            }
+
+            self.line.notes.pop();
+        }
+
+        false
+    }
+
+    fn candidates(&mut self, index: usize, prev: Option<Note>) -> Vec<Note> {
+        let onset = self.line.rest as usize
+            + self.slots[..index].iter().map(|s| s.duration as usize).sum::<usize>();
+
+        let cantus = self.cantus[onset / 4];
+
+        let mut candidates = self.pool.iter()
+            .copied()
+            .filter(|&note| {
+                let distance = match self.line.placement {
+                    Placement::Above => cantus.semitones_to(note),
+                    Placement::Below => note.semitones_to(cantus),
+                };
+
+                (0..=Self::MAX_DISTANCE).contains(&distance.0)
+            })
+            .map(|note| {
+                let motion = prev.map_or(Semitones(0), |p| p.semitones_to(note)).0.abs();
+                // prefer steps, then small leaps
+                (motion + self.rng.below(4) as i16, note)
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|&(key, _)| key);
+
+        candidates.into_iter().map(|(_, note)| note).collect()
+    }
+}
+
+// a small, seedable pseudorandom number generator (splitmix64)
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+    use super::*;
+    use crate::Pitch;
+    use crate::harmony::DiatonicMode;
+
+    fn notes(s: &str) -> Vec<Note> {
+        s.split(' ').map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn generated_lines_are_valid() {
+        let cantus_firmi = [
+            (notes("D3 F3 E3 D3 G3 F3 A3 G3 F3 E3 D3"), Key::new(Pitch::D, DiatonicMode::Dorian)),
+            (notes("C4 D4 F4 E4 F4 G4 A4 G4 E4 D4 C4"), Key::major(Pitch::C)),
+            (notes("A3 C4 B3 D4 C4 E4 F4 E4 B3 C4 B3 A3"), Key::minor(Pitch::A)),
+        ];
+
+        for (cantus, key) in &cantus_firmi {
+            for species in Species::iter() {
+                for placement in [Placement::Above, Placement::Below] {
+                    for seed in 0..3 {
+                        let line = Counterpoint::generate(cantus, *key, species, placement, seed)
+                            .unwrap_or_else(|| panic!("no {species:?} species line {placement:?} {cantus:?} with seed {seed}"));
+
+                        assert_eq!(line.validate(cantus, *key), [], "{line:?}");
+                        assert_eq!(line.species, species);
+                        assert_eq!(line.placement, placement);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        let cantus = notes("D3 F3 E3 D3 G3 F3 A3 G3 F3 E3 D3");
+        let key = Key::new(Pitch::D, DiatonicMode::Dorian);
+
+        let generate = |seed| Counterpoint::generate(&cantus, key, Species::Fifth, Placement::Above, seed);
+
+        assert_eq!(generate(1), generate(1));
+
+        let lines = (0..8).map(generate).collect::<Vec<_>>();
+        assert!(lines.iter().any(|line| *line != lines[0]), "different seeds should give different lines");
+    }
+
+    #[test]
+    fn rhythms() {
+        let mut rng = Rng::new(0);
+
+        for species in Species::iter() {
+            for measures in 1..12 {
+                let (rest, slots) = rhythm(species, measures, &mut rng);
+                let length = rest as usize + slots.iter().map(|s| s.duration as usize).sum::<usize>();
+
+                assert_eq!(length, measures * 4, "{species:?} with {measures} measures");
+            }
+        }
+
+        assert_eq!(Counterpoint::generate(&[], Key::major(Pitch::C), Species::First, Placement::Above, 0), None);
+    }
+}
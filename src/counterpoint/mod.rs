@@ -0,0 +1,190 @@
+//! Species counterpoint against a cantus firmus.
+//!
+//! A [`Counterpoint`] is a single line written above or below a cantus firmus, which has one
+//! whole note per measure. The line can be in any of the five [species](Species), and can be
+//! checked against the rules of that species with [`Counterpoint::validate`], or generated
+//! with [`Counterpoint::generate`].
+//!
+//! Durations are measured in quarter notes, so every measure is four long.
+//!
+//! # Examples
+//! ```
+//! # use music_theory::{Note, Pitch};
+//! # use music_theory::harmony::{Key, DiatonicMode};
+//! # use music_theory::counterpoint::{Counterpoint, Placement, Species};
+//! let n = |s: &str| s.parse::<Note>().unwrap();
+//!
+//! let cantus = ["D3", "F3", "E3", "D3", "G3", "F3", "A3", "G3", "F3", "E3", "D3"].map(n);
+//! let key = Key::new(Pitch::D, DiatonicMode::Dorian);
+//!
+//! let line = ["A3", "A3", "G3", "A3", "B3", "C4", "C4", "B3", "D4", "C♯4", "D4"].map(n);
+//! let counterpoint = Counterpoint::first_species(Placement::Above, line.to_vec());
+//!
+//! assert_eq!(counterpoint.validate(&cantus, key), []);
+//!
+//! // generated lines always follow the rules
+//! let generated = Counterpoint::generate(&cantus, key, Species::Third, Placement::Below, 7)
+//!     .expect("this cantus has a third species counterpoint below it");
+//!
+//! assert_eq!(generated.validate(&cantus, key), []);
+//! ```
+
+mod validate;
+pub use validate::*;
+
+mod generate;
+
+use crate::Note;
+
+/// The five species of counterpoint, each with its own rhythm and treatment of dissonance.
+#[repr(u8)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash, strum_macros::EnumIter, strum_macros::FromRepr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Species {
+    /// One whole note per measure, all consonant.
+    First = 1,
+    /// Two half notes per measure. Weak beats can be dissonant passing tones.
+    Second,
+    /// Four quarter notes per measure. Weak beats can be dissonant passing or neighbor tones.
+    Third,
+    /// Half notes tied over the bar line. Downbeats can be dissonant suspensions.
+    Fourth,
+    /// A mix of the other species, with quarter, half, and whole notes.
+    Fifth,
+}
+
+/// Whether a counterpoint is above or below the cantus firmus.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Placement {
+    /// The counterpoint is the upper voice.
+    Above,
+    /// The counterpoint is the lower voice.
+    Below,
+}
+
+/// A note of a [`Counterpoint`] line.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CounterpointNote {
+    /// The note that's played.
+    pub note: Note,
+    /// How long the note is, in quarter notes.
+    pub duration: u8,
+    /// Whether the note is tied to the next one, which must be the same note.
+    pub tied: bool,
+}
+
+impl CounterpointNote {
+    /// Creates a note that isn't tied to the next one.
+    pub fn new(note: Note, duration: u8) -> Self {
+        Self { note, duration, tied: false }
+    }
+
+    /// Returns the same note, tied to the next one.
+    pub fn tied(self) -> Self {
+        Self { tied: true, .. self }
+    }
+}
+
+/// A counterpoint line against a cantus firmus.
+///
+/// The line starts with [`rest`](Self::rest) quarter notes of rest, and is followed by its
+/// [`notes`](Self::notes). Notes can't cross a bar line, so notes that are held across one
+/// are split and [tied](CounterpointNote::tied).
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Counterpoint {
+    /// The species the line is written in.
+    pub species: Species,
+    /// Whether the line is above or below the cantus firmus.
+    pub placement: Placement,
+    /// The length of the rest before the first note, in quarter notes.
+    pub rest: u8,
+    /// The notes of the line, in order.
+    pub notes: Vec<CounterpointNote>,
+}
+
+impl Counterpoint {
+    /// Creates a first species counterpoint, with a whole note for each note given.
+    pub fn first_species(placement: Placement, notes: Vec<Note>) -> Self {
+        Self::uniform(Species::First, placement, notes, 4)
+    }
+
+    /// Creates a second species counterpoint, with a half note for each note given, except for
+    /// the last, which is a whole note.
+    pub fn second_species(placement: Placement, notes: Vec<Note>) -> Self {
+        Self::uniform(Species::Second, placement, notes, 2)
+    }
+
+    /// Creates a third species counterpoint, with a quarter note for each note given, except for
+    /// the last, which is a whole note.
+    pub fn third_species(placement: Placement, notes: Vec<Note>) -> Self {
+        Self::uniform(Species::Third, placement, notes, 1)
+    }
+
+    /// Creates a fourth species counterpoint from the notes that start on each weak beat, and the
+    /// last note.
+    ///
+    /// The line starts with a half rest. Each note given is a half note tied to a half note on
+    /// the next downbeat, except the last two. The second to last note is a half note on the weak
+    /// beat of the second to last measure, usually resolving a suspension, and the last note is a
+    /// whole note.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::counterpoint::{Counterpoint, Placement};
+    /// let n = |s: &str| s.parse::<Note>().unwrap();
+    ///
+    /// let counterpoint = Counterpoint::fourth_species(Placement::Above, vec![n("G4"), n("B4"), n("C5")]);
+    ///
+    /// // G4 is tied over the bar line
+    /// assert_eq!(counterpoint.rest, 2);
+    /// assert_eq!(counterpoint.notes.len(), 4);
+    /// assert!(counterpoint.notes[0].tied);
+    /// assert_eq!(counterpoint.notes[1].note, n("G4"));
+    /// ```
+    pub fn fourth_species(placement: Placement, notes: Vec<Note>) -> Self {
+        let suspended = notes.len().saturating_sub(2);
+
+        let notes = notes.iter()
+            .enumerate()
+            .flat_map(|(i, &note)| {
+                if i < suspended {
+                    vec![CounterpointNote::new(note, 2).tied(), CounterpointNote::new(note, 2)]
+                } else if i + 1 < notes.len() {
+                    vec![CounterpointNote::new(note, 2)]
+                } else {
+                    vec![CounterpointNote::new(note, 4)]
+                }
+            })
+            .collect();
+
+        Self { species: Species::Fourth, placement, rest: 2, notes }
+    }
+
+    fn uniform(species: Species, placement: Placement, notes: Vec<Note>, duration: u8) -> Self {
+        let len = notes.len();
+
+        let notes = notes.into_iter()
+            .enumerate()
+            .map(|(i, note)| CounterpointNote::new(note, if i + 1 == len { 4 } else { duration }))
+            .collect();
+
+        Self { species, placement, rest: 0, notes }
+    }
+
+    // the notes, with when they start in quarter notes
+    fn timed(&self) -> Vec<(usize, CounterpointNote)> {
+        let mut onset = self.rest as usize;
+
+        self.notes.iter()
+            .map(|&note| {
+                let timed = (onset, note);
+                onset += note.duration as usize;
+                timed
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,532 @@
+use crate::counterpoint::{Counterpoint, CounterpointNote, Placement, Species};
+use crate::harmony::{Key, ScaleDegree};
+use crate::interval::{Quality, Stability};
+use crate::{Interval, Note, Pitch};
+
+/// A rule of species counterpoint, broken by a [`Counterpoint`].
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CounterpointRule {
+    /// The durations of the notes don't match the species, a note crosses a bar line,
+    /// a tie is between two different notes, or the line doesn't end with the cantus firmus.
+    #[error("Invalid rhythm")]
+    InvalidRhythm,
+    /// The first note isn't a perfect consonance with the cantus firmus. Below the cantus firmus,
+    /// it has to be a unison or octave.
+    #[error("Improper start")]
+    ImproperStart,
+    /// The last note isn't a unison or octave with the cantus firmus.
+    #[error("Improper ending")]
+    ImproperEnding,
+    /// The last note isn't approached by step, or is approached from below by a whole step.
+    #[error("Improper cadence")]
+    ImproperCadence,
+    /// A note isn't in the key. The raised sixth and seventh scale degrees are allowed in the
+    /// second to last measure.
+    #[error("Note not in key")]
+    NotDiatonic,
+    /// A note starting on a downbeat is dissonant with the cantus firmus.
+    #[error("Dissonant downbeat")]
+    DissonantDownbeat,
+    /// A dissonance on a weak beat isn't a passing tone, or, in third and fifth species, a neighbor tone.
+    #[error("Unresolved dissonance")]
+    UnresolvedDissonance,
+    /// A dissonance tied into a downbeat doesn't resolve down by step to a consonance.
+    #[error("Invalid suspension")]
+    InvalidSuspension,
+    /// Two consecutive downbeats, or a weak beat and the next downbeat, are both perfect fifths.
+    #[error("Parallel fifths")]
+    ParallelFifths,
+    /// Two consecutive downbeats, or a weak beat and the next downbeat, are both unisons or octaves.
+    #[error("Parallel octaves")]
+    ParallelOctaves,
+    /// A downbeat other than the first or last is a unison with the cantus firmus.
+    #[error("Unison on an interior downbeat")]
+    InteriorUnison,
+    /// A leap is augmented, diminished, a seventh, larger than an octave, or a sixth other
+    /// than an ascending minor sixth.
+    #[error("Invalid leap")]
+    InvalidLeap,
+    /// There are more than two leaps in a row.
+    #[error("Too many leaps")]
+    TooManyLeaps,
+    /// A leap larger than a fourth isn't followed by motion in the opposite direction.
+    #[error("Unrecovered leap")]
+    UnrecoveredLeap,
+    /// A note is repeated without a tie. This is allowed in first species.
+    #[error("Repeated note")]
+    RepeatedNote,
+}
+
+/// A rule broken by a [`Counterpoint`], and where it happened.
+///
+/// Measures are numbered from zero, but from one in the error message.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("{rule} in measure {}", .measure + 1)]
+pub struct CounterpointError {
+    /// The measure the rule was broken in.
+    /// For rules about two consecutive downbeats, this is the first measure.
+    pub measure: usize,
+    /// The rule that was broken.
+    pub rule: CounterpointRule,
+}
+
+impl Counterpoint {
+    /// Checks the line against the rules of its [species](Species), returning every rule that's broken.
+    ///
+    /// The cantus firmus has one whole note per measure, and should start and end on the tonic
+    /// of `key`. Each [`CounterpointRule`] describes what's checked. Cambiatas, double neighbors,
+    /// and eighth notes aren't supported, so lines using them are reported as invalid.
+    ///
+    /// If the rhythm of the line is invalid, only [`InvalidRhythm`](CounterpointRule::InvalidRhythm)
+    /// errors are returned, since the other rules can't be checked. Otherwise, errors are sorted
+    /// by measure.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::harmony::Key;
+    /// # use music_theory::counterpoint::{Counterpoint, CounterpointError, CounterpointRule, Placement};
+    /// let n = |s: &str| s.parse::<Note>().unwrap();
+    ///
+    /// let cantus = ["C4", "D4", "E4", "D4", "C4"].map(n);
+    /// let line = ["G4", "A4", "B4", "B4", "C5"].map(n);
+    ///
+    /// let errors = Counterpoint::first_species(Placement::Above, line.to_vec())
+    ///     .validate(&cantus, Key::major(Pitch::C));
+    ///
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         // C and G, then D and A
+    ///         CounterpointError { measure: 0, rule: CounterpointRule::ParallelFifths },
+    ///         // ... then E and B
+    ///         CounterpointError { measure: 1, rule: CounterpointRule::ParallelFifths },
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(errors[0].to_string(), "Parallel fifths in measure 1");
+    /// ```
+    pub fn validate(&self, cantus: &[Note], key: Key) -> Vec<CounterpointError> {
+        self.check(cantus, key, true)
+    }
+
+    // if the line isn't complete, rules about the ending or the note after are skipped
+    pub(super) fn check(&self, cantus: &[Note], key: Key, complete: bool) -> Vec<CounterpointError> {
+        let timed = self.timed();
+
+        let mut errors = self.check_rhythm(&timed, cantus.len(), complete);
+
+        if !errors.is_empty() || timed.is_empty() {
+            return errors;
+        }
+
+        let mut push = |measure, rule| errors.push(CounterpointError { measure, rule });
+
+        let last_measure = cantus.len() - 1;
+
+        let notes = timed.iter()
+            .map(|&(onset, note)| {
+                let cantus = cantus[onset / 4];
+
+                Timed {
+                    onset,
+                    note: note.note,
+                    tied: note.tied,
+                    interval: Interval::between_notes(cantus, note.note).abs(),
+                    cantus,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let first = &notes[0];
+
+        let valid_start = match self.placement {
+            Placement::Above => is_octave(first.interval) || first.interval.as_simple() == Interval::PERFECT_FIFTH,
+            Placement::Below => is_octave(first.interval),
+        };
+
+        if !valid_start {
+            push(first.measure(), CounterpointRule::ImproperStart);
+        }
+
+        if complete {
+            let last = notes.last().expect("not empty");
+
+            if !is_octave(last.interval) {
+                push(last.measure(), CounterpointRule::ImproperEnding);
+            }
+
+            if let [.., before, last] = notes.as_slice() {
+                let approach = Interval::between_notes(before.note, last.note);
+
+                if approach.number().get().abs() != 2 || (approach.is_ascending() && approach.semitones().0 != 1) {
+                    push(before.measure(), CounterpointRule::ImproperCadence);
+                }
+            }
+        }
+
+        let scale = (1..=7)
+            .map(|n| key.relative_pitch(ScaleDegree::from_num(n).expect("in range")))
+            .collect::<Vec<_>>();
+
+        let [raised_sixth, leading_tone] = cadential_pitches(key);
+
+        let mut consecutive_leaps = 0;
+
+        for (i, timed) in notes.iter().enumerate() {
+            let measure = timed.measure();
+            let prev = i.checked_sub(1).map(|i| &notes[i]);
+            let next = notes.get(i + 1);
+
+            let penultimate = measure + 1 == last_measure;
+
+            let diatonic = scale.contains(&timed.note.pitch)
+                || (penultimate && timed.note.pitch == leading_tone)
+                || (penultimate && timed.note.pitch == raised_sixth && match next {
+                    Some(next) => next.note.pitch == leading_tone,
+                    None => !complete,
+                });
+
+            if !diatonic {
+                push(measure, CounterpointRule::NotDiatonic);
+            }
+
+            let tied_into = prev.is_some_and(|p| p.tied);
+
+            if timed.is_downbeat() && !timed.is_consonant() {
+                if !tied_into {
+                    push(measure, CounterpointRule::DissonantDownbeat);
+                } else if let Some(next) = next {
+                    let resolution = Interval::between_notes(timed.note, next.note);
+
+                    if resolution.number().get() != -2 || !next.is_consonant() {
+                        push(measure, CounterpointRule::InvalidSuspension);
+                    }
+                } else if complete {
+                    push(measure, CounterpointRule::InvalidSuspension);
+                }
+            }
+
+            if !timed.is_downbeat() && !timed.is_consonant() {
+                let approach = prev.map(|p| Interval::between_notes(p.note, timed.note));
+                let departure = next.map(|n| Interval::between_notes(timed.note, n.note));
+
+                let stepwise = |ivl: Option<Interval>| ivl.is_none_or(|ivl| ivl.number().get().abs() == 2);
+
+                let valid = match (approach, departure) {
+                    (None, _) => false,
+                    (Some(_), None) if complete => false,
+                    (Some(approach), departure) if stepwise(Some(approach)) && stepwise(departure) => {
+                        let passing = departure.is_none_or(|d| d.is_ascending() == approach.is_ascending());
+                        passing || matches!(self.species, Species::Third | Species::Fifth)
+                    }
+                    _ => false,
+                };
+
+                if !valid {
+                    push(measure, CounterpointRule::UnresolvedDissonance);
+                }
+            }
+
+            if timed.is_downbeat() && measure != 0 && measure != last_measure && timed.interval == Interval::PERFECT_UNISON {
+                push(measure, CounterpointRule::InteriorUnison);
+            }
+
+            // melodic rules, skipping notes that are tied into
+            if tied_into {
+                continue;
+            }
+
+            let Some(prev) = prev else {
+                continue;
+            };
+
+            let motion = Interval::between_notes(prev.note, timed.note);
+            let size = motion.number().get().abs();
+
+            if size == 1 && self.species != Species::First {
+                push(measure, CounterpointRule::RepeatedNote);
+            }
+
+            if size <= 2 {
+                consecutive_leaps = 0;
+            } else {
+                consecutive_leaps += 1;
+
+                let sixth_allowed = size == 6 && motion.is_ascending() && motion.quality() == Quality::Minor;
+
+                if motion.quality().is_augmented() || motion.quality().is_diminished()
+                    || size == 7
+                    || size > 8
+                    || (size == 6 && !sixth_allowed)
+                {
+                    push(measure, CounterpointRule::InvalidLeap);
+                }
+
+                if consecutive_leaps == 3 {
+                    push(measure, CounterpointRule::TooManyLeaps);
+                }
+
+                let following = notes[i + 1..].iter()
+                    .find(|n| n.note != timed.note)
+                    .map(|n| Interval::between_notes(timed.note, n.note));
+
+                if size > 4 && following.is_some_and(|f| f.is_ascending() == motion.is_ascending()) {
+                    push(measure, CounterpointRule::UnrecoveredLeap);
+                }
+            }
+        }
+
+        // since notes can't cross bar lines, every downbeat after the rest starts a note
+        for measure in 1..cantus.len() {
+            let Some(after) = notes.iter().position(|n| n.onset == measure * 4) else {
+                continue;
+            };
+
+            let downbeat = notes.iter().position(|n| n.onset == (measure - 1) * 4);
+            let weak_beat = after.checked_sub(1);
+
+            let mut fifths = false;
+            let mut octaves = false;
+
+            for before in [downbeat, weak_beat].into_iter().flatten() {
+                let (before, after) = (&notes[before], &notes[after]);
+
+                if before.note == after.note || before.cantus == after.cantus {
+                    continue;
+                }
+
+                let both = |f: fn(Interval) -> bool| f(before.interval) && f(after.interval);
+
+                fifths |= both(|ivl| ivl.as_simple() == Interval::PERFECT_FIFTH);
+                octaves |= both(is_octave);
+            }
+
+            if fifths {
+                push(measure - 1, CounterpointRule::ParallelFifths);
+            }
+
+            if octaves {
+                push(measure - 1, CounterpointRule::ParallelOctaves);
+            }
+        }
+
+        errors.sort_by_key(|e| e.measure);
+
+        errors
+    }
+
+    fn check_rhythm(&self, timed: &[(usize, CounterpointNote)], measures: usize, complete: bool) -> Vec<CounterpointError> {
+        let mut errors = Vec::new();
+
+        let length = measures * 4;
+
+        let rest_allowed = match self.species {
+            Species::First => self.rest == 0,
+            Species::Third => self.rest <= 2,
+            Species::Second | Species::Fourth | Species::Fifth => self.rest == 0 || self.rest == 2,
+        };
+
+        if !rest_allowed {
+            errors.push(CounterpointError { measure: 0, rule: CounterpointRule::InvalidRhythm });
+        }
+
+        for (i, &(onset, note)) in timed.iter().enumerate() {
+            let last = complete && i + 1 == timed.len();
+            let duration = note.duration as usize;
+
+            let species_duration = match self.species {
+                _ if last => duration == 4,
+                Species::First => duration == 4,
+                Species::Second | Species::Fourth => duration == 2,
+                Species::Third => duration == 1,
+                Species::Fifth => (1..=4).contains(&duration),
+            };
+
+            let fits = onset % 4 + duration <= 4 && onset + duration <= length;
+
+            let tie = !note.tied || (
+                matches!(self.species, Species::Fourth | Species::Fifth)
+                    && timed.get(i + 1).is_none_or(|(_, next)| next.note == note.note)
+                    && !last
+            );
+
+            if !species_duration || !fits || !tie {
+                errors.push(CounterpointError { measure: onset / 4, rule: CounterpointRule::InvalidRhythm });
+            }
+        }
+
+        let end = timed.last().map_or(self.rest as usize, |&(onset, note)| onset + note.duration as usize);
+
+        if complete && (end != length || measures == 0 || timed.is_empty()) {
+            errors.push(CounterpointError { measure: measures.saturating_sub(1), rule: CounterpointRule::InvalidRhythm });
+        }
+
+        errors
+    }
+}
+
+struct Timed {
+    onset: usize,
+    note: Note,
+    tied: bool,
+    cantus: Note,
+    // the ascending interval between the cantus and the note
+    interval: Interval,
+}
+
+impl Timed {
+    fn measure(&self) -> usize {
+        self.onset / 4
+    }
+
+    fn is_downbeat(&self) -> bool {
+        self.onset.is_multiple_of(4)
+    }
+
+    fn is_consonant(&self) -> bool {
+        self.interval.stability().is_some_and(Stability::is_consonant)
+    }
+}
+
+fn is_octave(interval: Interval) -> bool {
+    matches!(interval.as_simple(), Interval::PERFECT_UNISON | Interval::PERFECT_OCTAVE)
+}
+
+// the pitches a line in the key is allowed to use in the second to last measure
+pub(super) fn cadential_pitches(key: Key) -> [Pitch; 2] {
+    [
+        key.tonic.transpose(-Interval::MINOR_THIRD),
+        key.tonic.transpose(-Interval::MINOR_SECOND),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harmony::DiatonicMode;
+    use CounterpointRule as R;
+
+    fn notes(s: &str) -> Vec<Note> {
+        s.split(' ').map(|n| n.parse().unwrap()).collect()
+    }
+
+    fn dorian() -> (Vec<Note>, Key) {
+        (notes("D3 F3 E3 D3 G3 F3 A3 G3 F3 E3 D3"), Key::new(Pitch::D, DiatonicMode::Dorian))
+    }
+
+    fn rules(counterpoint: &Counterpoint, cantus: &[Note], key: Key) -> Vec<(usize, CounterpointRule)> {
+        counterpoint.validate(cantus, key)
+            .into_iter()
+            .map(|e| (e.measure, e.rule))
+            .collect()
+    }
+
+    #[test]
+    fn first_species() {
+        let (cantus, key) = dorian();
+
+        let valid = Counterpoint::first_species(Placement::Above, notes("A3 A3 G3 A3 B3 C4 C4 B3 D4 C♯4 D4"));
+        assert_eq!(rules(&valid, &cantus, key), []);
+
+        let valid = Counterpoint::first_species(Placement::Below, notes("D2 D2 A2 F2 E2 D2 F2 G2 D3 C♯3 D3"));
+        assert_eq!(rules(&valid, &cantus, key), []);
+
+        // starts on a third, has a dissonant seventh, and ends on a fifth
+        let invalid = Counterpoint::first_species(Placement::Above, notes("F3 A3 G3 A3 B3 C4 G4 B3 D4 C♯4 A3"));
+        assert_eq!(rules(&invalid, &cantus, key), [
+            (0, R::ImproperStart),
+            (6, R::DissonantDownbeat),
+            (7, R::InvalidLeap),
+            (8, R::TooManyLeaps),
+            (9, R::ImproperCadence),
+            (10, R::ImproperEnding),
+        ]);
+
+        // below the cantus, a fifth isn't a valid start
+        let below = Counterpoint::first_species(Placement::Below, notes("G2 D2 A2 F2 E2 D2 F2 G2 D3 C♯3 D3"));
+        assert_eq!(rules(&below, &cantus, key), [(0, R::ImproperStart), (3, R::TooManyLeaps)]);
+    }
+
+    #[test]
+    fn parallels_and_unisons() {
+        let cantus = notes("C4 D4 E4 F4 E4 D4 C4");
+        let key = Key::major(Pitch::C);
+
+        let line = Counterpoint::first_species(Placement::Above, notes("C5 D5 C5 F4 G4 B4 C5"));
+
+        assert_eq!(rules(&line, &cantus, key), [
+            (0, R::ParallelOctaves),
+            (3, R::InteriorUnison),
+        ]);
+
+        // fifths from a weak beat to the next downbeat
+        let line = Counterpoint::second_species(Placement::Above, notes("C5 G4 A4 F4 G4 A4 C5 B4 G4 A4 B4 A4 C5"));
+
+        assert!(rules(&line, &cantus, key).contains(&(0, R::ParallelFifths)));
+    }
+
+    #[test]
+    fn dissonance() {
+        let key = Key::major(Pitch::C);
+
+        // passing tones are fine
+        let cantus = notes("C4 D4 E4 C4");
+        let line = Counterpoint::second_species(Placement::Above, notes("C5 B4 A4 B4 C5 B4 C5"));
+        assert_eq!(rules(&line, &cantus, key), []);
+
+        let line = Counterpoint::second_species(Placement::Above, notes("C5 B4 A4 A4 C5 B4 C5"));
+        assert_eq!(rules(&line, &cantus, key), [(1, R::RepeatedNote)]);
+
+        // neighbor tones are only allowed in third species
+        let cantus = notes("C4 E4 D4 C4");
+        let line = Counterpoint::second_species(Placement::Above, notes("G4 F4 G4 C5 A4 B4 C5"));
+        assert_eq!(rules(&line, &cantus, key), [(0, R::UnresolvedDissonance)]);
+
+        let line = Counterpoint::third_species(Placement::Above, notes("G4 F4 G4 A4 C5 B4 A4 G4 F4 G4 A4 B4 C5"));
+        assert_eq!(rules(&line, &cantus, key), []);
+    }
+
+    #[test]
+    fn fourth_species() {
+        let cantus = notes("C4 D4 E4 D4 C4");
+        let key = Key::major(Pitch::C);
+
+        // 7-6 suspensions, including at the cadence
+        let line = Counterpoint::fourth_species(Placement::Above, notes("C5 B4 C5 B4 C5"));
+        assert_eq!(rules(&line, &cantus, key), []);
+
+        // the suspended C resolves up
+        let line = Counterpoint::fourth_species(Placement::Above, notes("C5 B4 C5 D5 C5"));
+        assert!(rules(&line, &cantus, key).contains(&(3, R::InvalidSuspension)));
+    }
+
+    #[test]
+    fn rhythm() {
+        let (cantus, key) = dorian();
+
+        // too short
+        let line = Counterpoint::first_species(Placement::Above, notes("A3 D4"));
+        assert_eq!(rules(&line, &cantus, key), [(10, R::InvalidRhythm)]);
+
+        let mut line = Counterpoint::second_species(Placement::Above, notes("A3 D4"));
+        line.notes[0].duration = 4;
+        line.notes.insert(0, CounterpointNote::new(notes("D4")[0], 2).tied());
+
+        assert!(rules(&line, &cantus, key).iter().all(|(_, r)| *r == R::InvalidRhythm));
+        assert!(!rules(&line, &cantus, key).is_empty());
+    }
+
+    #[test]
+    fn diatonic() {
+        let (cantus, key) = dorian();
+
+        let line = Counterpoint::first_species(Placement::Above, notes("A3 A3 G3 A3 B♭3 C4 C4 B3 D4 C♯4 D4"));
+        assert_eq!(rules(&line, &cantus, key), [(4, R::NotDiatonic)]);
+
+        assert_eq!(cadential_pitches(key), [Pitch::B, Pitch::C_SHARP]);
+        assert_eq!(cadential_pitches(Key::minor(Pitch::A)), [Pitch::F_SHARP, Pitch::G_SHARP]);
+    }
+}
@@ -0,0 +1,534 @@
+use std::cmp::Ordering;
+use crate::harmony::{DiatonicMode, Key, ScaleDegree};
+use crate::pitch::Spelling;
+use crate::rhythm::{Rational, TimedNote};
+use crate::{Pitch, PitchClass};
+
+/// A key profile, giving how strongly each pitch class is expected in a key, starting from the tonic.
+///
+/// Profiles are compared against a [`PitchClassHistogram`] by a [`KeyFinder`]. They only differ
+/// in how they were measured, and all of them work well for common practice music.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyProfile {
+    /// Krumhansl and Kessler's probe tone ratings (1982), used by the original
+    /// Krumhansl–Schmuckler algorithm.
+    #[default]
+    KrumhanslKessler,
+    /// Temperley's profiles (2007), measured from the Kostka–Payne corpus of tonal excerpts.
+    Temperley,
+    /// Aarden's profiles (2003), measured from folk songs in the Essen collection.
+    AardenEssen,
+}
+
+impl KeyProfile {
+    /// Returns the profile for major keys.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::harmony::KeyProfile;
+    /// let profile = KeyProfile::KrumhanslKessler.major();
+    ///
+    /// // the tonic is the most expected pitch class, and the dominant the next
+    /// assert_eq!(profile[0], 6.35);
+    /// assert_eq!(profile[7], 5.19);
+    /// ```
+    pub fn major(self) -> [f64; 12] {
+        match self {
+            Self::KrumhanslKessler => [6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88],
+            Self::Temperley => [0.748, 0.060, 0.488, 0.082, 0.670, 0.460, 0.096, 0.715, 0.104, 0.366, 0.057, 0.400],
+            Self::AardenEssen => [
+                17.7661, 0.145624, 14.9265, 0.160186, 19.8049, 11.3587,
+                0.291248, 22.062, 0.145624, 8.15494, 0.232998, 4.95122,
+            ],
+        }
+    }
+
+    /// Returns the profile for minor keys.
+    pub fn minor(self) -> [f64; 12] {
+        match self {
+            Self::KrumhanslKessler => [6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17],
+            Self::Temperley => [0.712, 0.084, 0.474, 0.618, 0.049, 0.460, 0.105, 0.747, 0.404, 0.067, 0.133, 0.330],
+            Self::AardenEssen => [
+                18.2648, 0.737619, 14.0499, 16.8599, 0.702494, 14.4362,
+                0.702494, 18.6161, 4.56621, 1.93186, 7.37619, 1.75623,
+            ],
+        }
+    }
+
+    /// Returns the profile for a key in any mode.
+    ///
+    /// Only major and minor keys have measured profiles, so the others are derived from them.
+    /// Modes with a major third take each scale degree's weight from the major profile, and modes
+    /// with a minor third from the minor profile, and the remaining pitch classes take the weights of
+    /// the remaining pitch classes in order. For example, the fourth degree of Lydian has the weight
+    /// of the fourth degree in major, even though it's a semitone higher.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::harmony::{DiatonicMode, KeyProfile};
+    /// let profile = KeyProfile::KrumhanslKessler;
+    ///
+    /// assert_eq!(profile.mode(DiatonicMode::MAJOR), profile.major());
+    ///
+    /// let lydian = profile.mode(DiatonicMode::Lydian);
+    /// assert_eq!(lydian[6], profile.major()[5]);
+    /// ```
+    pub fn mode(self, mode: DiatonicMode) -> [f64; 12] {
+        let degrees = degree_semitones(mode);
+
+        let (source, source_degrees) = if degrees[2] == 4 {
+            (self.major(), degree_semitones(DiatonicMode::MAJOR))
+        } else {
+            (self.minor(), degree_semitones(DiatonicMode::NATURAL_MINOR))
+        };
+
+        let mut profile = [0.0; 12];
+
+        for (&to, &from) in degrees.iter().zip(&source_degrees) {
+            profile[to] = source[from];
+        }
+
+        let chromatic = |degrees: [usize; 7]| (0..12).filter(move |s| !degrees.contains(s));
+
+        for (to, from) in chromatic(degrees).zip(chromatic(source_degrees)) {
+            profile[to] = source[from];
+        }
+
+        profile
+    }
+}
+
+// how many semitones each scale degree of the mode is above the tonic
+fn degree_semitones(mode: DiatonicMode) -> [usize; 7] {
+    let key = Key::new(Pitch::C, mode);
+
+    std::array::from_fn(|i| {
+        let degree = ScaleDegree::from_num(i as u8 + 1).expect("in range");
+        key.relative_pitch(degree).as_pitch_class() as usize
+    })
+}
+
+/// How much each pitch class is used, such as the total duration of its notes.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, PitchClass};
+/// # use music_theory::harmony::PitchClassHistogram;
+/// # use music_theory::rhythm::{Rational, TimedNote};
+/// let n = |s: &str| s.parse::<Note>().unwrap();
+///
+/// let histogram = PitchClassHistogram::from_notes(&[
+///     TimedNote::note(n("C4"), Rational::ZERO, "2".parse().unwrap()),
+///     TimedNote::chord(vec![n("E4"), n("C5")], Rational::new(1, 2), "4".parse().unwrap()),
+///     TimedNote::rest(Rational::new(3, 4), "4".parse().unwrap()),
+/// ]);
+///
+/// assert_eq!(histogram.weight(PitchClass::C), 0.75);
+/// assert_eq!(histogram.weight(PitchClass::E), 0.25);
+/// assert_eq!(histogram.total(), 1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitchClassHistogram([f64; 12]);
+
+impl PitchClassHistogram {
+    /// Creates an empty histogram.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a histogram from the lengths of notes, in whole notes.
+    ///
+    /// Every note of a chord counts for its full length, and rests are ignored.
+    pub fn from_notes(notes: &[TimedNote]) -> Self {
+        let mut histogram = Self::new();
+
+        for timed in notes {
+            for note in &timed.notes {
+                histogram.add(note.pitch.as_pitch_class(), timed.length().to_f64());
+            }
+        }
+
+        histogram
+    }
+
+    // the lengths of the parts of the notes between start and end
+    fn from_notes_between(notes: &[TimedNote], start: Rational, end: Rational) -> Self {
+        let mut histogram = Self::new();
+
+        for timed in notes {
+            let overlap = end.min(timed.end()) - start.max(timed.onset);
+
+            if overlap > Rational::ZERO {
+                for note in &timed.notes {
+                    histogram.add(note.pitch.as_pitch_class(), overlap.to_f64());
+                }
+            }
+        }
+
+        histogram
+    }
+
+    /// Adds `weight` to a pitch class.
+    pub fn add(&mut self, pitch_class: PitchClass, weight: f64) {
+        self.0[pitch_class as usize] += weight;
+    }
+
+    /// Returns the weight of a pitch class.
+    pub fn weight(&self, pitch_class: PitchClass) -> f64 {
+        self.0[pitch_class as usize]
+    }
+
+    /// Returns the weights of every pitch class, starting from C.
+    pub fn weights(&self) -> [f64; 12] {
+        self.0
+    }
+
+    /// Returns the sum of the weights of every pitch class.
+    pub fn total(&self) -> f64 {
+        self.0.iter().sum()
+    }
+
+    /// Returns the Pearson correlation between this histogram and the profile of `key`,
+    /// from `-1.0` to `1.0`.
+    ///
+    /// Returns `None` if every pitch class has the same weight, such as when the histogram is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Pitch, PitchClass};
+    /// # use music_theory::harmony::{Key, KeyProfile, PitchClassHistogram};
+    /// let mut histogram = PitchClassHistogram::new();
+    /// assert_eq!(histogram.correlation(Key::major(Pitch::C), KeyProfile::Temperley), None);
+    ///
+    /// histogram.add(PitchClass::C, 2.0);
+    /// histogram.add(PitchClass::G, 1.0);
+    ///
+    /// let c = histogram.correlation(Key::major(Pitch::C), KeyProfile::Temperley).unwrap();
+    /// let f_sharp = histogram.correlation(Key::major(Pitch::F_SHARP), KeyProfile::Temperley).unwrap();
+    /// assert!(c > f_sharp);
+    /// ```
+    pub fn correlation(&self, key: Key, profile: KeyProfile) -> Option<f64> {
+        let profile = profile.mode(key.mode);
+        let tonic = key.tonic.as_pitch_class() as usize;
+
+        let expected: [f64; 12] = std::array::from_fn(|pc| profile[(pc + 12 - tonic) % 12]);
+
+        pearson(&self.0, &expected)
+    }
+}
+
+fn pearson(x: &[f64; 12], y: &[f64; 12]) -> Option<f64> {
+    let mean = |v: &[f64; 12]| v.iter().sum::<f64>() / 12.0;
+    let (mean_x, mean_y) = (mean(x), mean(y));
+
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+
+    for (x, y) in x.iter().zip(y) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+/// A key, with how well it fits the notes it was found for.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyEstimate {
+    /// The key.
+    pub key: Key,
+    /// The correlation between the notes and the key's profile, from `-1.0` to `1.0`.
+    pub correlation: f64,
+}
+
+/// The most likely key of a stretch of time, found by [`KeyFinder::track`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyWindow {
+    /// When the window starts, in whole notes.
+    pub start: Rational,
+    /// When the window ends, in whole notes.
+    pub end: Rational,
+    /// The most likely key of the notes in the window.
+    pub estimate: KeyEstimate,
+}
+
+/// Finds the key of a piece with the Krumhansl–Schmuckler algorithm.
+///
+/// The notes are summarized in a [`PitchClassHistogram`] of their durations, which is
+/// correlated with the [profile](KeyProfile) of every candidate key. The keys that
+/// correlate best are the most likely.
+///
+/// By default, only major and minor keys are considered, with the
+/// [Krumhansl–Kessler](KeyProfile::KrumhanslKessler) profiles.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// # use music_theory::harmony::{Key, KeyFinder};
+/// # use music_theory::rhythm::{Rational, TimedNote};
+/// let notes = ["A3", "C4", "E4", "G♯4", "A4", "B4", "E4", "A3"]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, n)| TimedNote::note(n.parse().unwrap(), Rational::new(i as i64, 4), "4".parse().unwrap()))
+///     .collect::<Vec<_>>();
+///
+/// let estimate = KeyFinder::default().find(&notes).unwrap();
+/// assert_eq!(estimate.key, Key::minor(Pitch::A));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyFinder {
+    /// The profiles keys are compared with.
+    pub profile: KeyProfile,
+    /// The modes of the candidate keys. Every tonic is tried in each mode.
+    pub modes: Vec<DiatonicMode>,
+}
+
+impl Default for KeyFinder {
+    fn default() -> Self {
+        Self::new(KeyProfile::default())
+    }
+}
+
+impl KeyFinder {
+    /// Creates a key finder for the 24 major and minor keys, using the given profile.
+    pub fn new(profile: KeyProfile) -> Self {
+        Self { profile, modes: vec![DiatonicMode::MAJOR, DiatonicMode::NATURAL_MINOR] }
+    }
+
+    /// Creates a key finder for keys in all seven diatonic modes, using the given profile.
+    ///
+    /// Relative modes share a scale, so they're told apart only by how much
+    /// the tonic and other important degrees are used.
+    pub fn all_modes(profile: KeyProfile) -> Self {
+        let modes = (1..=7)
+            .map(|n| DiatonicMode::from_repr(n).expect("in range"))
+            .collect();
+
+        Self { profile, modes }
+    }
+
+    /// Returns every candidate key, ordered from the best fit to the worst.
+    ///
+    /// Each tonic is spelled with as few accidentals in the key signature as possible, preferring
+    /// flats when there's a tie, such as G♭ major over F♯ major. Returns an empty list if every
+    /// pitch class has the same weight, such as when the histogram is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Pitch, PitchClass};
+    /// # use music_theory::harmony::{Key, KeyFinder, KeyProfile, PitchClassHistogram};
+    /// let mut histogram = PitchClassHistogram::new();
+    ///
+    /// for (pc, weight) in [(PitchClass::G, 4.0), (PitchClass::B, 2.0), (PitchClass::D, 3.0), (PitchClass::Fs, 1.0)] {
+    ///     histogram.add(pc, weight);
+    /// }
+    ///
+    /// let ranking = KeyFinder::new(KeyProfile::AardenEssen).rank(&histogram);
+    ///
+    /// assert_eq!(ranking.len(), 24);
+    /// assert_eq!(ranking[0].key, Key::major(Pitch::G));
+    /// assert!(ranking.windows(2).all(|w| w[0].correlation >= w[1].correlation));
+    /// ```
+    pub fn rank(&self, histogram: &PitchClassHistogram) -> Vec<KeyEstimate> {
+        let mut estimates = self.modes.iter()
+            .flat_map(|&mode| (0..12).map(move |pc| spell_key(PitchClass::from_repr(pc).expect("in range"), mode)))
+            .filter_map(|key| {
+                let correlation = histogram.correlation(key, self.profile)?;
+                Some(KeyEstimate { key, correlation })
+            })
+            .collect::<Vec<_>>();
+
+        estimates.sort_by(|a, b| b.correlation.partial_cmp(&a.correlation).unwrap_or(Ordering::Equal));
+
+        estimates
+    }
+
+    /// Returns the most likely key of the notes, or `None` if there are no notes.
+    pub fn find(&self, notes: &[TimedNote]) -> Option<KeyEstimate> {
+        self.rank(&PitchClassHistogram::from_notes(notes)).first().copied()
+    }
+
+    /// Finds the most likely key in a sliding window, to track modulations.
+    ///
+    /// The first window starts with the first note and is `window` whole notes long, and each
+    /// window starts `hop` after the previous one, until the last note ends. Notes only count for
+    /// the part of their length that's in the window, and windows without any notes are skipped.
+    ///
+    /// # Panics
+    /// Panics if `window` or `hop` isn't positive.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::harmony::{Key, KeyFinder};
+    /// # use music_theory::rhythm::{Rational, TimedNote};
+    /// let melody = |notes: &str, bar: i64| notes.split(' ')
+    ///     .enumerate()
+    ///     .map(move |(i, n)| {
+    ///         let onset = Rational::from_integer(bar) + Rational::new(i as i64, 4);
+    ///         TimedNote::note(n.parse().unwrap(), onset, "4".parse().unwrap())
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// // modulates from C major to G major
+    /// let mut notes = melody("C4 E4 G4 F4 D4 B3 C4 G4", 0);
+    /// notes.extend(melody("G4 B4 D5 C5 A4 F♯4 G4 D4", 2));
+    ///
+    /// let two_bars = Rational::from_integer(2);
+    /// let keys = KeyFinder::default().track(&notes, two_bars, two_bars)
+    ///     .into_iter()
+    ///     .map(|w| w.estimate.key)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(keys, [Key::major(Pitch::C), Key::major(Pitch::G)]);
+    /// ```
+    pub fn track(&self, notes: &[TimedNote], window: Rational, hop: Rational) -> Vec<KeyWindow> {
+        assert!(window > Rational::ZERO && hop > Rational::ZERO, "window and hop must be positive");
+
+        let Some(first) = notes.iter().map(|n| n.onset).min() else {
+            return Vec::new();
+        };
+
+        let last = notes.iter().map(TimedNote::end).max().expect("not empty");
+
+        (0..)
+            .map(|i| first + hop * i)
+            .take_while(|&start| start < last)
+            .filter_map(|start| {
+                let end = start + window;
+                let histogram = PitchClassHistogram::from_notes_between(notes, start, end);
+                let estimate = *self.rank(&histogram).first()?;
+
+                Some(KeyWindow { start, end, estimate })
+            })
+            .collect()
+    }
+}
+
+// spells the tonic with the fewest accidentals in the key signature, preferring flats on a tie
+fn spell_key(tonic: PitchClass, mode: DiatonicMode) -> Key {
+    let flats = Key::new(tonic.spell_with(Spelling::Flats), mode);
+    let sharps = Key::new(tonic.spell_with(Spelling::Sharps), mode);
+
+    if sharps.sharps().abs() < flats.sharps().abs() { sharps } else { flats }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+    use crate::Note;
+    use super::*;
+
+    fn histogram(weights: &[(PitchClass, f64)]) -> PitchClassHistogram {
+        let mut histogram = PitchClassHistogram::new();
+
+        for &(pc, weight) in weights {
+            histogram.add(pc, weight);
+        }
+
+        histogram
+    }
+
+    #[test]
+    fn every_profile_finds_scales() {
+        use PitchClass as P;
+
+        // scales with the tonic triad emphasized
+        let c_major = histogram(&[(P::C, 4.0), (P::D, 1.0), (P::E, 2.0), (P::F, 1.0), (P::G, 3.0), (P::A, 1.0), (P::B, 1.0)]);
+        let a_minor = histogram(&[(P::A, 4.0), (P::B, 1.0), (P::C, 2.0), (P::D, 1.0), (P::E, 3.0), (P::F, 1.0), (P::Gs, 1.0)]);
+        let e_flat_major = histogram(&[(P::Ds, 4.0), (P::F, 1.0), (P::G, 2.0), (P::Gs, 1.0), (P::As, 3.0), (P::C, 1.0), (P::D, 1.0)]);
+
+        for profile in KeyProfile::iter() {
+            let finder = KeyFinder::new(profile);
+
+            assert_eq!(finder.rank(&c_major)[0].key, Key::major(Pitch::C), "{profile:?}");
+            assert_eq!(finder.rank(&a_minor)[0].key, Key::minor(Pitch::A), "{profile:?}");
+            assert_eq!(finder.rank(&e_flat_major)[0].key, Key::major(Pitch::E_FLAT), "{profile:?}");
+        }
+    }
+
+    #[test]
+    fn modes() {
+        use PitchClass as P;
+
+        for profile in KeyProfile::iter() {
+            assert_eq!(profile.mode(DiatonicMode::NATURAL_MINOR), profile.minor());
+
+            for mode in KeyFinder::all_modes(profile).modes {
+                let mut derived = profile.mode(mode);
+                let mut source = if degree_semitones(mode)[2] == 4 { profile.major() } else { profile.minor() };
+
+                derived.sort_by(f64::total_cmp);
+                source.sort_by(f64::total_cmp);
+
+                assert_eq!(derived, source, "{mode:?} should use the same weights");
+            }
+        }
+
+        // D dorian, with the tonic and fifth emphasized
+        let d_dorian = histogram(&[(P::D, 5.0), (P::E, 1.0), (P::F, 2.0), (P::G, 1.0), (P::A, 4.0), (P::B, 1.0), (P::C, 1.0)]);
+
+        let ranking = KeyFinder::all_modes(KeyProfile::Temperley).rank(&d_dorian);
+
+        assert_eq!(ranking.len(), 7 * 12);
+        assert_eq!(ranking[0].key, Key::new(Pitch::D, DiatonicMode::Dorian));
+    }
+
+    #[test]
+    fn spelling() {
+        let finder = KeyFinder::new(KeyProfile::KrumhanslKessler);
+
+        let tonics = finder.rank(&histogram(&[(PitchClass::C, 1.0)]))
+            .into_iter()
+            .map(|e| e.key)
+            .filter(|k| k.mode == DiatonicMode::MAJOR)
+            .map(|k| k.tonic)
+            .collect::<Vec<_>>();
+
+        for pitch in [Pitch::D_FLAT, Pitch::E_FLAT, Pitch::G_FLAT, Pitch::A_FLAT, Pitch::B_FLAT, Pitch::B, Pitch::E] {
+            assert!(tonics.contains(&pitch), "{pitch:?}");
+        }
+
+        assert_eq!(spell_key(PitchClass::Gs, DiatonicMode::NATURAL_MINOR), Key::minor(Pitch::G_SHARP));
+        assert_eq!(spell_key(PitchClass::Ds, DiatonicMode::NATURAL_MINOR), Key::minor(Pitch::E_FLAT));
+    }
+
+    #[test]
+    fn track() {
+        let n = |s: &str| s.parse::<Note>().unwrap();
+
+        let whole = || "1".parse().unwrap();
+        let half = || "2".parse().unwrap();
+
+        let notes = [
+            TimedNote::chord(vec![n("C4"), n("E4"), n("G4")], Rational::ZERO, whole()),
+            TimedNote::rest(Rational::ONE, whole()),
+            // silence from 2 to 5/2
+            TimedNote::chord(vec![n("A3"), n("C4")], Rational::new(5, 2), whole()),
+            TimedNote::note(n("E4"), Rational::new(5, 2), half()),
+            TimedNote::note(n("G♯4"), Rational::new(3, 1), half()),
+        ];
+
+        let windows = KeyFinder::default().track(&notes, Rational::new(1, 2), Rational::new(1, 2));
+
+        let starts = windows.iter().map(|w| w.start).collect::<Vec<_>>();
+        assert_eq!(starts, [Rational::ZERO, Rational::new(1, 2), Rational::new(5, 2), Rational::new(3, 1)]);
+
+        assert_eq!(windows[0].estimate.key, Key::major(Pitch::C));
+        assert_eq!(windows[3].estimate.key, Key::minor(Pitch::A));
+
+        assert_eq!(KeyFinder::default().track(&[], Rational::ONE, Rational::ONE), []);
+        assert_eq!(KeyFinder::default().find(&[]), None);
+    }
+}
@@ -29,4 +29,15 @@ mod mode;
 pub use mode::*;
 
 mod scale_degree;
-pub use scale_degree::*;
\ No newline at end of file
+pub use scale_degree::*;
+
+mod part_writing;
+pub use part_writing::*;
+
+mod key_finding;
+pub use key_finding::*;
+
+#[cfg(feature = "experimental-chords")]
+mod roman;
+#[cfg(feature = "experimental-chords")]
+pub use roman::*;
@@ -0,0 +1,455 @@
+use strum::IntoEnumIterator;
+use crate::harmony::Key;
+use crate::{EnharmonicEq, Interval, Letter, Note, Pitch};
+
+/// One of the four voices of a chorale.
+///
+/// The variants are ordered from highest to lowest, which is also the order of the notes
+/// in each sonority passed to [`check_part_writing`].
+#[repr(u8)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash, strum_macros::EnumIter, strum_macros::FromRepr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Voice {
+    /// The highest voice.
+    Soprano,
+    /// The second highest voice.
+    Alto,
+    /// The second lowest voice.
+    Tenor,
+    /// The lowest voice.
+    Bass,
+}
+
+impl Voice {
+    /// Returns the lowest and highest note this voice is expected to sing, inclusive.
+    ///
+    /// | Voice   | Range    |
+    /// |---------|----------|
+    /// | Soprano | C4 to G5 |
+    /// | Alto    | G3 to D5 |
+    /// | Tenor   | C3 to G4 |
+    /// | Bass    | E2 to C4 |
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::harmony::Voice;
+    /// assert_eq!(Voice::Tenor.range(), (Note::new(Pitch::C, 3), Note::new(Pitch::G, 4)));
+    /// ```
+    pub fn range(self) -> (Note, Note) {
+        match self {
+            Voice::Soprano => (Note::new(Pitch::C, 4), Note::new(Pitch::G, 5)),
+            Voice::Alto => (Note::new(Pitch::G, 3), Note::new(Pitch::D, 5)),
+            Voice::Tenor => (Note::new(Pitch::C, 3), Note::new(Pitch::G, 4)),
+            Voice::Bass => (Note::new(Pitch::E, 2), Note::new(Pitch::C, 4)),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as _
+    }
+}
+
+/// A part-writing rule that was broken, returned by [`check_part_writing`].
+///
+/// Where two voices are involved, `upper` is the voice that's normally higher.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ViolationKind {
+    /// Two voices a perfect fifth apart (or a compound fifth) move to another perfect fifth.
+    ParallelFifths {
+        upper: Voice,
+        lower: Voice,
+    },
+    /// Two voices a perfect octave or unison apart (or a compound octave) move to another.
+    ParallelOctaves {
+        upper: Voice,
+        lower: Voice,
+    },
+    /// The soprano and bass move in similar motion into a perfect fifth, with a leap in the soprano.
+    HiddenFifths,
+    /// The soprano and bass move in similar motion into a perfect octave, with a leap in the soprano.
+    HiddenOctaves,
+    /// A voice is below the voice that's normally under it.
+    VoiceCrossing {
+        upper: Voice,
+        lower: Voice,
+    },
+    /// A voice moves past where the neighboring voice was in the previous sonority.
+    VoiceOverlap {
+        upper: Voice,
+        lower: Voice,
+    },
+    /// The soprano and alto, or the alto and tenor, are more than an octave apart.
+    Spacing {
+        upper: Voice,
+        lower: Voice,
+    },
+    /// The leading tone of a dominant chord doesn't move up to the tonic.
+    UnresolvedLeadingTone {
+        voice: Voice,
+    },
+    /// The seventh of a seventh chord doesn't move down by step.
+    UnresolvedSeventh {
+        voice: Voice,
+    },
+    /// More than one voice has the leading tone.
+    DoubledLeadingTone,
+    /// A voice is outside of its [range](Voice::range).
+    OutOfRange {
+        voice: Voice,
+    },
+}
+
+/// A part-writing rule that was broken, and where.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Violation {
+    /// The index of the sonority the violation is in.
+    /// For violations between two consecutive sonorities, this is the index of the first one.
+    pub position: usize,
+    /// The rule that was broken.
+    pub kind: ViolationKind,
+}
+
+/// Checks a four-part chorale in the given key for common part-writing errors.
+///
+/// Each sonority lists the notes of the soprano, alto, tenor, and bass, in that order.
+///
+/// These rules are checked:
+/// - [Parallel fifths](ViolationKind::ParallelFifths) and [octaves](ViolationKind::ParallelOctaves)
+///   between any two voices, including consecutive ones by contrary motion. Intervals are compared
+///   with [`Interval::as_simple`], so compound intervals count, but a perfect fifth moving to a
+///   diminished fifth doesn't. Both voices have to move.
+/// - [Hidden fifths](ViolationKind::HiddenFifths) and [octaves](ViolationKind::HiddenOctaves)
+///   between the soprano and bass.
+/// - [Voice crossing](ViolationKind::VoiceCrossing) and [overlap](ViolationKind::VoiceOverlap)
+///   between neighboring voices.
+/// - [Spacing](ViolationKind::Spacing) of more than an octave between neighboring upper voices.
+/// - [Unresolved leading tones](ViolationKind::UnresolvedLeadingTone): when a chord rooted on the
+///   fifth or seventh scale degree moves to a chord with the tonic, the leading tone has to move up
+///   to it. In the alto and tenor, the leading tone may instead fall to the fifth scale degree.
+/// - [Unresolved sevenths](ViolationKind::UnresolvedSeventh): the seventh of a chord has to move
+///   down by step or be held into the next sonority.
+/// - [Doubled leading tones](ViolationKind::DoubledLeadingTone).
+/// - [Voices out of range](ViolationKind::OutOfRange).
+///
+/// The leading tone is the pitch a minor second below the tonic, so in minor keys it's the raised
+/// seventh scale degree. The root of each sonority is the note whose letter has every other note's
+/// letter a third, fifth, or seventh above it. If there isn't one, the sonority isn't checked for
+/// unresolved tones.
+///
+/// Violations are sorted by position, then in the order listed above.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// # use music_theory::harmony::{Key, Voice, Violation, ViolationKind, check_part_writing};
+/// let n = |s: &str| s.parse::<Note>().unwrap();
+///
+/// let chorale = [
+///     [n("E5"), n("G4"), n("C4"), n("C3")],
+///     [n("D5"), n("G4"), n("B3"), n("G2")],
+///     [n("C5"), n("G4"), n("C4"), n("C3")],
+/// ];
+///
+/// assert_eq!(check_part_writing(&chorale, Key::major(Pitch::C)), []);
+///
+/// // the bass moves up, making parallel fifths with the soprano
+/// let chorale = [
+///     [n("E5"), n("G4"), n("C4"), n("A2")],
+///     [n("D5"), n("G4"), n("B3"), n("G2")],
+/// ];
+///
+/// assert_eq!(
+///     check_part_writing(&chorale, Key::major(Pitch::C)),
+///     [Violation {
+///         position: 0,
+///         kind: ViolationKind::ParallelFifths { upper: Voice::Soprano, lower: Voice::Bass },
+///     }],
+/// );
+/// ```
+pub fn check_part_writing(sonorities: &[[Note; 4]], key: Key) -> Vec<Violation> {
+    let leading_tone = key.tonic.transpose(-Interval::MINOR_SECOND);
+    let dominant = key.tonic.transpose(Interval::PERFECT_FIFTH);
+
+    let mut violations = Vec::new();
+
+    for (position, sonority) in sonorities.iter().enumerate() {
+        let mut push = |kind| violations.push(Violation { position, kind });
+
+        let next = sonorities.get(position + 1);
+
+        if let Some(next) = next {
+            for (upper, lower) in pairs() {
+                let (u, l) = (upper.index(), lower.index());
+
+                if sonority[u] == next[u] || sonority[l] == next[l] {
+                    continue;
+                }
+
+                let before = simple_interval(sonority[l], sonority[u]);
+                let after = simple_interval(next[l], next[u]);
+
+                if before == Interval::PERFECT_FIFTH && after == Interval::PERFECT_FIFTH {
+                    push(ViolationKind::ParallelFifths { upper, lower });
+                }
+
+                if is_octave(before) && is_octave(after) {
+                    push(ViolationKind::ParallelOctaves { upper, lower });
+                }
+            }
+
+            let (s, b) = (Voice::Soprano.index(), Voice::Bass.index());
+
+            let soprano = sonority[s].semitones_to(next[s]).0;
+            let bass = sonority[b].semitones_to(next[b]).0;
+
+            if soprano.signum() == bass.signum() && soprano != 0 && soprano.abs() > 2 {
+                let before = simple_interval(sonority[b], sonority[s]);
+                let after = simple_interval(next[b], next[s]);
+
+                if after == Interval::PERFECT_FIFTH && before != after {
+                    push(ViolationKind::HiddenFifths);
+                }
+
+                if is_octave(after) && !is_octave(before) {
+                    push(ViolationKind::HiddenOctaves);
+                }
+            }
+        }
+
+        for (upper, lower) in neighbors() {
+            if sonority[upper.index()].semitones_to(sonority[lower.index()]).0 > 0 {
+                push(ViolationKind::VoiceCrossing { upper, lower });
+            }
+        }
+
+        if let Some(next) = next {
+            for (upper, lower) in neighbors() {
+                let (u, l) = (upper.index(), lower.index());
+
+                if sonority[u].semitones_to(next[l]).0 > 0 || sonority[l].semitones_to(next[u]).0 < 0 {
+                    push(ViolationKind::VoiceOverlap { upper, lower });
+                }
+            }
+        }
+
+        for (upper, lower) in neighbors().take(2) {
+            if sonority[lower.index()].semitones_to(sonority[upper.index()]).0 > 12 {
+                push(ViolationKind::Spacing { upper, lower });
+            }
+        }
+
+        let root = root_letter(sonority);
+
+        if let (Some(next), Some(root)) = (next, root) {
+            let steps = key.tonic.letter().offset_between(root);
+            let is_dominant = steps == 4 || steps == 6;
+            let resolves_to_tonic = next.iter().any(|n| n.pitch.eq_enharmonic(&key.tonic));
+
+            if is_dominant && resolves_to_tonic {
+                for voice in Voice::iter() {
+                    let (from, to) = (sonority[voice.index()], next[voice.index()]);
+
+                    if !from.pitch.eq_enharmonic(&leading_tone) {
+                        continue;
+                    }
+
+                    let resolved = to.pitch.eq_enharmonic(&key.tonic) && from.semitones_to(to).0 == 1;
+
+                    let frustrated = matches!(voice, Voice::Alto | Voice::Tenor)
+                        && to.pitch.eq_enharmonic(&dominant)
+                        && from.semitones_to(to).0 < 0;
+
+                    if !resolved && !frustrated {
+                        push(ViolationKind::UnresolvedLeadingTone { voice });
+                    }
+                }
+            }
+        }
+
+        if let (Some(next), Some(root)) = (next, root) {
+            for voice in Voice::iter() {
+                let (from, to) = (sonority[voice.index()], next[voice.index()]);
+
+                if root.offset_between(from.pitch.letter()) != 6 {
+                    continue;
+                }
+
+                if from != to && !(-2..=-1).contains(&from.semitones_to(to).0) {
+                    push(ViolationKind::UnresolvedSeventh { voice });
+                }
+            }
+        }
+
+        let leading_tones = sonority.iter()
+            .filter(|n| n.pitch.eq_enharmonic(&leading_tone))
+            .count();
+
+        if leading_tones > 1 {
+            push(ViolationKind::DoubledLeadingTone);
+        }
+
+        for voice in Voice::iter() {
+            let (low, high) = voice.range();
+
+            let note = sonority[voice.index()];
+
+            if low.semitones_to(note).0 < 0 || note.semitones_to(high).0 < 0 {
+                push(ViolationKind::OutOfRange { voice });
+            }
+        }
+    }
+
+    violations
+}
+
+// every pair of voices, with the higher voice first
+fn pairs() -> impl Iterator<Item = (Voice, Voice)> {
+    Voice::iter().flat_map(|upper| {
+        Voice::iter()
+            .filter(move |&lower| lower > upper)
+            .map(move |lower| (upper, lower))
+    })
+}
+
+// each voice and the voice under it
+fn neighbors() -> impl Iterator<Item = (Voice, Voice)> {
+    Voice::iter().zip(Voice::iter().skip(1))
+}
+
+fn simple_interval(lower: Note, upper: Note) -> Interval {
+    Interval::between_notes(lower, upper).abs().as_simple()
+}
+
+fn is_octave(interval: Interval) -> bool {
+    interval == Interval::PERFECT_OCTAVE || interval == Interval::PERFECT_UNISON
+}
+
+fn root_letter(sonority: &[Note; 4]) -> Option<Letter> {
+    sonority.iter()
+        .rev()
+        .map(|n| n.pitch.letter())
+        .find(|&root| {
+            sonority.iter().all(|n| matches!(root.offset_between(n.pitch.letter()), 0 | 2 | 4 | 6))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ViolationKind as V;
+
+    fn sonority(s: &str) -> [Note; 4] {
+        s.split(' ')
+            .map(|n| n.parse().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    fn check(chorale: &[&str], key: Key) -> Vec<(usize, ViolationKind)> {
+        let sonorities = chorale.iter().map(|s| sonority(s)).collect::<Vec<_>>();
+
+        check_part_writing(&sonorities, key)
+            .into_iter()
+            .map(|v| (v.position, v.kind))
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        let c_major = Key::major(Pitch::C);
+
+        assert_eq!(check(&["E5 G4 C4 C3", "F5 A4 C4 F2", "D5 G4 B3 G2", "C5 G4 C4 C3"], c_major), []);
+        assert_eq!(check(&["B4 D4 G3 G2", "C5 D4 F♯3 D3", "B4 D4 G3 G2"], Key::major(Pitch::G)), []);
+        assert_eq!(check(&[], c_major), []);
+    }
+
+    #[test]
+    fn parallels() {
+        let c_major = Key::major(Pitch::C);
+
+        assert_eq!(check(&["E4 C4 G3 C3", "F4 D4 F3 D3"], c_major), [
+            (0, V::ParallelOctaves { upper: Voice::Alto, lower: Voice::Bass }),
+        ]);
+
+        // consecutive fifths by contrary motion
+        assert_eq!(check(&["G4 E4 C4 C3", "A4 D4 D3 F2"], c_major), [
+            (0, V::ParallelFifths { upper: Voice::Soprano, lower: Voice::Tenor }),
+        ]);
+
+        // a perfect fifth to a diminished fifth is allowed
+        assert_eq!(check(&["G4 E4 C4 C3", "F4 D4 B3 B2"], c_major).iter().filter(|(_, k)| matches!(k, V::ParallelFifths { .. })).count(), 0);
+
+        // a repeated fifth isn't parallel
+        assert_eq!(check(&["G4 E4 C4 C3", "G4 E4 C4 C3"], c_major), []);
+    }
+
+    #[test]
+    fn hidden() {
+        let c_major = Key::major(Pitch::C);
+
+        assert_eq!(check(&["E4 C4 G3 C3", "A4 A3 F3 D3"], c_major), [(0, V::HiddenFifths)]);
+        assert_eq!(check(&["G4 E4 C4 C3", "C5 G4 E4 C4"], c_major), [(0, V::HiddenOctaves)]);
+
+        // the soprano moves by step, so it's fine
+        assert_eq!(check(&["B4 D4 G3 G2", "C5 E4 G3 C3"], c_major), []);
+    }
+
+    #[test]
+    fn crossing_overlap_spacing() {
+        let c_major = Key::major(Pitch::C);
+
+        assert_eq!(check(&["C5 G4 E3 C3"], c_major), [
+            (0, V::Spacing { upper: Voice::Alto, lower: Voice::Tenor }),
+        ]);
+
+        assert_eq!(check(&["C4 E4 G3 C3"], c_major), [
+            (0, V::VoiceCrossing { upper: Voice::Soprano, lower: Voice::Alto }),
+        ]);
+
+        // the tenor moves above where the alto was
+        assert_eq!(check(&["C5 E4 C4 C3", "B4 D4 F4 G2"], c_major).iter()
+            .filter(|(_, k)| matches!(k, V::VoiceOverlap { .. }))
+            .collect::<Vec<_>>(), [
+            &(0, V::VoiceOverlap { upper: Voice::Alto, lower: Voice::Tenor }),
+        ]);
+    }
+
+    #[test]
+    fn tendency_tones() {
+        let c_major = Key::major(Pitch::C);
+
+        // the leading tone in the soprano goes down
+        assert_eq!(check(&["B4 D4 G3 G2", "G4 E4 G3 C3"], c_major), [
+            (0, V::UnresolvedLeadingTone { voice: Voice::Soprano }),
+        ]);
+
+        // but it can go down to the fifth in an inner voice
+        assert_eq!(check(&["D5 G4 B3 G2", "C5 G4 G3 C3"], c_major), []);
+
+        // the seventh goes up
+        assert_eq!(check(&["D5 F4 B3 G2", "C5 G4 C4 C3"], c_major), [
+            (0, V::UnresolvedSeventh { voice: Voice::Alto }),
+        ]);
+
+        // in minor, the leading tone is raised
+        assert_eq!(check(&["B4 E4 G♯3 E3", "A4 E4 A3 A2"], Key::minor(Pitch::A)), []);
+        assert_eq!(check(&["B4 E4 G♯3 E3", "A4 E4 E3 A2"], Key::minor(Pitch::A)), []);
+        assert_eq!(check(&["G♯4 E4 B3 E3", "E4 E4 C4 A3"], Key::minor(Pitch::A)), [
+            (0, V::UnresolvedLeadingTone { voice: Voice::Soprano }),
+        ]);
+
+        assert_eq!(check(&["B4 G4 B3 G2"], c_major), [(0, V::DoubledLeadingTone)]);
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(check(&["A5 C5 C4 C2"], Key::major(Pitch::C)), [
+            (0, V::OutOfRange { voice: Voice::Soprano }),
+            (0, V::OutOfRange { voice: Voice::Bass }),
+        ]);
+    }
+}
@@ -0,0 +1,898 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::chord::{Chord, InvalidInversion};
+use crate::chord::types::ChordType;
+use crate::harmony::{DiatonicMode, Key, ScaleDegree};
+use crate::{AccidentalSign, Interval, Pitch};
+
+/// The quality of the triad a [`RomanNumeral`] is built on.
+///
+/// Major and augmented triads are written with uppercase numerals, minor and diminished
+/// triads with lowercase ones.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriadQuality {
+    /// A major triad, such as `V`.
+    Major,
+    /// A minor triad, such as `ii`.
+    Minor,
+    /// A diminished triad, such as `vii°`.
+    Diminished,
+    /// An augmented triad, such as `III+`.
+    Augmented,
+}
+
+impl TriadQuality {
+    fn intervals(self) -> [Interval; 3] {
+        use Interval as I;
+
+        match self {
+            Self::Major => [I::PERFECT_UNISON, I::MAJOR_THIRD, I::PERFECT_FIFTH],
+            Self::Minor => [I::PERFECT_UNISON, I::MINOR_THIRD, I::PERFECT_FIFTH],
+            Self::Diminished => [I::PERFECT_UNISON, I::MINOR_THIRD, I::DIMINISHED_FIFTH],
+            Self::Augmented => [I::PERFECT_UNISON, I::MAJOR_THIRD, I::AUGMENTED_FIFTH],
+        }
+    }
+
+    fn is_uppercase(self) -> bool {
+        matches!(self, Self::Major | Self::Augmented)
+    }
+}
+
+/// The seventh above the root of a seventh chord [`RomanNumeral`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeventhQuality {
+    /// A diminished seventh, as in `vii°⁷`.
+    Diminished,
+    /// A minor seventh, as in `V⁷`, `ii⁷`, or `viiø⁷`.
+    Minor,
+    /// A major seventh, as in `IM⁷`.
+    Major,
+}
+
+impl SeventhQuality {
+    fn interval(self) -> Interval {
+        match self {
+            Self::Diminished => Interval::DIMINISHED_SEVENTH,
+            Self::Minor => Interval::MINOR_SEVENTH,
+            Self::Major => Interval::MAJOR_SEVENTH,
+        }
+    }
+}
+
+/// The three augmented sixth chords, built on the lowered sixth scale degree.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AugmentedSixth {
+    /// The Italian sixth, with the lowered sixth, tonic, and raised fourth degrees.
+    Italian,
+    /// The French sixth, which adds the second degree to the Italian sixth.
+    French,
+    /// The German sixth, which adds the lowered third degree to the Italian sixth.
+    German,
+}
+
+impl AugmentedSixth {
+    fn intervals(self) -> Vec<Interval> {
+        use Interval as I;
+
+        match self {
+            Self::Italian => vec![I::PERFECT_UNISON, I::MAJOR_THIRD, I::AUGMENTED_SIXTH],
+            Self::French => vec![I::PERFECT_UNISON, I::MAJOR_THIRD, I::AUGMENTED_FOURTH, I::AUGMENTED_SIXTH],
+            Self::German => vec![I::PERFECT_UNISON, I::MAJOR_THIRD, I::PERFECT_FIFTH, I::AUGMENTED_SIXTH],
+        }
+    }
+
+    fn abbreviation(self) -> &'static str {
+        match self {
+            Self::Italian => "It",
+            Self::French => "Fr",
+            Self::German => "Ger",
+        }
+    }
+}
+
+/// The chord a [`RomanNumeral`] stands for, relative to its key.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumeralKind {
+    /// A triad or seventh chord built on a scale degree, such as `ii`, `V⁷`, or `♭VI`.
+    ///
+    /// The `accidental` raises or lowers the root from the scale degree in the key, except that
+    /// in minor keys, diminished chords on an unaltered seventh degree are built on the leading
+    /// tone rather than the subtonic, so `vii°⁷` in A minor is built on G♯.
+    Degree {
+        /// How far the root is raised or lowered from the scale degree.
+        accidental: AccidentalSign,
+        /// The scale degree the chord is built on.
+        degree: ScaleDegree,
+        /// The quality of the triad.
+        triad: TriadQuality,
+        /// The seventh of the chord, if it has one.
+        seventh: Option<SeventhQuality>,
+    },
+    /// The Neapolitan chord (`N`), a major triad on the lowered second scale degree.
+    Neapolitan,
+    /// An augmented sixth chord (`It⁺⁶`, `Fr⁺⁶`, or `Ger⁺⁶`).
+    AugmentedSixth(AugmentedSixth),
+}
+
+/// The key a secondary chord is applied to, such as the `V` in `V⁷/V`.
+///
+/// Uppercase numerals tonicize a major key, and lowercase numerals a minor key.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tonicization {
+    /// How far the tonic is raised or lowered from the scale degree.
+    pub accidental: AccidentalSign,
+    /// The scale degree of the tonicized key's tonic.
+    pub degree: ScaleDegree,
+    /// Whether the tonicized key is minor.
+    pub minor: bool,
+}
+
+impl Tonicization {
+    /// Returns the key this tonicizes, relative to `key`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{AccidentalSign, Pitch};
+    /// # use music_theory::harmony::{Key, ScaleDegree, Tonicization};
+    /// let of_ii = Tonicization { accidental: AccidentalSign::NATURAL, degree: ScaleDegree::II, minor: true };
+    /// assert_eq!(of_ii.key(Key::major(Pitch::C)), Key::minor(Pitch::D));
+    /// ```
+    pub fn key(self, key: Key) -> Key {
+        let tonic = key.relative_pitch(self.degree).transpose_fifths(7 * self.accidental.offset);
+        let mode = if self.minor { DiatonicMode::NATURAL_MINOR } else { DiatonicMode::MAJOR };
+
+        Key::new(tonic, mode)
+    }
+}
+
+/// A roman numeral, naming a chord by its function in a key.
+///
+/// Roman numerals can be parsed from strings such as `"ii⁶₅"`, `"V⁷/V"`, `"♭VI"`, `"viiø⁷"`,
+/// `"N⁶"`, or `"Ger⁺⁶"`, [realized](Self::realize) as a [`Chord`] in any key, and found for a
+/// chord with [`RomanNumeral::analyze`].
+///
+/// Figures can also be written in ASCII, so `"ii65"` is the same as `"ii⁶₅"`, and `b`, `#`,
+/// and `o` can be used for `♭`, `♯`, and `°`. A major seventh is written with an `M`, as in
+/// `"IM7"`. [Display](fmt::Display) always uses the Unicode symbols.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Pitch;
+/// # use music_theory::chord::Chord;
+/// # use music_theory::chord::types::ChordType;
+/// # use music_theory::harmony::{Key, RomanNumeral};
+/// let numeral = "V⁷/V".parse::<RomanNumeral>().unwrap();
+///
+/// // in C major, the dominant of the dominant is D7
+/// let chord = numeral.realize(Key::major(Pitch::C)).unwrap();
+/// assert_eq!(chord.pitches(), [Pitch::D, Pitch::F_SHARP, Pitch::A, Pitch::C]);
+///
+/// // and in E♭ major, it's F7
+/// let chord = numeral.realize(Key::major(Pitch::E_FLAT)).unwrap();
+/// assert_eq!(chord.root, Pitch::F);
+///
+/// // analysis finds the numeral again
+/// assert_eq!(RomanNumeral::analyze(&chord, Key::major(Pitch::E_FLAT)), Some(numeral));
+///
+/// // A♭ major is borrowed from C minor
+/// let chord = Chord::from_type(ChordType::MajorTriad, Pitch::A_FLAT, 0).unwrap();
+/// let numeral = RomanNumeral::analyze(&chord, Key::major(Pitch::C)).unwrap();
+/// assert_eq!(numeral.to_string(), "♭VI");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RomanNumeral {
+    /// The chord, relative to its key.
+    pub kind: NumeralKind,
+    /// Which chord tone is in the bass, with `0` being the root.
+    ///
+    /// Augmented sixth chords are written without figures, so they're always displayed as if
+    /// they're in root position, with the lowered sixth degree in the bass.
+    pub inversion: u8,
+    /// The key the chord is applied to, if it's a secondary chord.
+    pub tonicized: Option<Tonicization>,
+}
+
+/// An error returned when a roman numeral can't be parsed.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseRomanNumeralError {
+    /// The input was empty, or only whitespace.
+    #[error("The roman numeral was empty")]
+    Empty,
+    /// The numeral wasn't one of `I` to `VII`, in either all uppercase or all lowercase.
+    #[error("Invalid roman numeral")]
+    InvalidNumeral,
+    /// A quality symbol that doesn't fit the numeral's case, such as `V°` or `ii+`.
+    #[error("The quality doesn't match the numeral's case")]
+    InvalidQuality,
+    /// The inversion figures weren't one of `6`, `64`, `7`, `65`, `43`, `42`, or `2`,
+    /// or didn't fit the chord, such as `viiø6`.
+    #[error("Invalid inversion figures")]
+    InvalidFigures,
+    /// The part after the `/` wasn't a plain roman numeral.
+    #[error("Invalid tonicized numeral")]
+    InvalidTonicization,
+}
+
+// the chords a degree numeral can stand for, in the order analysis prefers them
+const CHORDS: [(TriadQuality, Option<SeventhQuality>); 12] = {
+    use TriadQuality as T;
+    use SeventhQuality as S;
+
+    [
+        (T::Major, None),
+        (T::Minor, None),
+        (T::Diminished, None),
+        (T::Augmented, None),
+        (T::Major, Some(S::Minor)),
+        (T::Minor, Some(S::Minor)),
+        (T::Diminished, Some(S::Minor)),
+        (T::Diminished, Some(S::Diminished)),
+        (T::Major, Some(S::Major)),
+        (T::Minor, Some(S::Major)),
+        (T::Augmented, Some(S::Minor)),
+        (T::Augmented, Some(S::Major)),
+    ]
+};
+
+// the chords a secondary chord can be: V, V⁷, vii°, vii°⁷, and viiø⁷
+const APPLIED: [(ScaleDegree, TriadQuality, Option<SeventhQuality>); 5] = [
+    (ScaleDegree::V, TriadQuality::Major, None),
+    (ScaleDegree::V, TriadQuality::Major, Some(SeventhQuality::Minor)),
+    (ScaleDegree::VII, TriadQuality::Diminished, None),
+    (ScaleDegree::VII, TriadQuality::Diminished, Some(SeventhQuality::Diminished)),
+    (ScaleDegree::VII, TriadQuality::Diminished, Some(SeventhQuality::Minor)),
+];
+
+impl RomanNumeral {
+    /// Creates a root position triad on an unaltered scale degree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::harmony::{RomanNumeral, ScaleDegree, TriadQuality};
+    /// let numeral = RomanNumeral::new(ScaleDegree::II, TriadQuality::Minor);
+    /// assert_eq!(numeral, "ii".parse().unwrap());
+    /// ```
+    pub fn new(degree: ScaleDegree, triad: TriadQuality) -> Self {
+        Self {
+            kind: NumeralKind::Degree { accidental: AccidentalSign::NATURAL, degree, triad, seventh: None },
+            inversion: 0,
+            tonicized: None,
+        }
+    }
+
+    /// Returns the key the chord is read in: the [tonicized](Self::tonicized) key for a secondary
+    /// chord, otherwise `key` itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::harmony::{Key, RomanNumeral};
+    /// let numeral = "vii°⁷/ii".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.local_key(Key::major(Pitch::C)), Key::minor(Pitch::D));
+    /// ```
+    pub fn local_key(&self, key: Key) -> Key {
+        self.tonicized.map_or(key, |t| t.key(key))
+    }
+
+    /// Builds the chord this numeral stands for in `key`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidInversion`] if [`inversion`](Self::inversion) is too high for the chord,
+    /// which can't happen for parsed numerals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::types::ChordType;
+    /// # use music_theory::harmony::{Key, RomanNumeral};
+    /// let c_minor = Key::minor(Pitch::C);
+    /// let realize = |s: &str| s.parse::<RomanNumeral>().unwrap().realize(c_minor).unwrap();
+    ///
+    /// let chord = realize("iiø⁶₅");
+    /// assert_eq!(chord.chord_type(), Some(ChordType::HalfDiminishedSeventh));
+    /// assert_eq!(chord.pitches(), [Pitch::F, Pitch::A_FLAT, Pitch::C, Pitch::D]);
+    ///
+    /// // the leading tone is raised in minor keys
+    /// assert_eq!(realize("vii°⁷").root, Pitch::B);
+    ///
+    /// assert_eq!(realize("N⁶").pitches(), [Pitch::F, Pitch::A_FLAT, Pitch::D_FLAT]);
+    /// assert_eq!(realize("Ger⁺⁶").pitches(), [Pitch::A_FLAT, Pitch::C, Pitch::E_FLAT, Pitch::F_SHARP]);
+    /// ```
+    pub fn realize(&self, key: Key) -> Result<Chord, InvalidInversion> {
+        let key = self.local_key(key);
+
+        match self.kind {
+            NumeralKind::Degree { accidental, degree, triad, seventh } => {
+                let root = degree_root(key, accidental, degree, triad);
+
+                match chord_type(triad, seventh) {
+                    Some(ty) => Chord::from_type(ty, root, self.inversion),
+                    None => {
+                        let intervals = triad.intervals()
+                            .into_iter()
+                            .chain(seventh.map(SeventhQuality::interval))
+                            .collect();
+
+                        Chord::from_intervals(intervals, root, self.inversion)
+                    }
+                }
+            }
+            NumeralKind::Neapolitan => {
+                Chord::from_type(ChordType::MajorTriad, key.tonic.transpose(Interval::MINOR_SECOND), self.inversion)
+            }
+            NumeralKind::AugmentedSixth(sixth) => {
+                Chord::from_intervals(sixth.intervals(), key.tonic.transpose(Interval::MINOR_SIXTH), self.inversion)
+            }
+        }
+    }
+
+    /// Finds the roman numeral for a chord in `key`.
+    ///
+    /// Chords are matched by their exact spelling, so a German sixth is only found if it's
+    /// spelled with an augmented sixth, and is otherwise a dominant seventh chord. When a chord
+    /// could be written more than one way, the first of these is used:
+    /// 1. A chord built entirely from the key's scale, including the leading tone in minor keys.
+    /// 2. The Neapolitan chord, or an augmented sixth chord.
+    /// 3. A chord borrowed from the parallel major or minor key, such as `iv` or `♭VI` in a major
+    ///    key, or `I` in a minor key. Dominant seventh chords are left for secondary dominants.
+    /// 4. A secondary dominant (`V`, `V⁷`) or leading-tone chord (`vii°`, `vii°⁷`, `viiø⁷`) of a
+    ///    major or minor triad in the key.
+    /// 5. Any other triad or seventh chord, built on a raised, lowered, or unaltered scale degree.
+    ///
+    /// Returns `None` for chords that aren't triads, seventh chords, or augmented sixth chords.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::chord::types::ChordType;
+    /// # use music_theory::harmony::{Key, RomanNumeral};
+    /// let analyze = |ty, root, inversion| {
+    ///     let chord = Chord::from_type(ty, root, inversion).unwrap();
+    ///     RomanNumeral::analyze(&chord, Key::major(Pitch::C)).map(|n| n.to_string())
+    /// };
+    ///
+    /// assert_eq!(analyze(ChordType::MinorSeventh, Pitch::D, 1).as_deref(), Some("ii⁶₅"));
+    /// assert_eq!(analyze(ChordType::DiminishedSeventh, Pitch::F_SHARP, 0).as_deref(), Some("vii°⁷/V"));
+    /// assert_eq!(analyze(ChordType::MinorTriad, Pitch::F, 0).as_deref(), Some("iv"));
+    /// assert_eq!(analyze(ChordType::MajorTriad, Pitch::D_FLAT, 1).as_deref(), Some("N⁶"));
+    /// assert_eq!(analyze(ChordType::DominantNinth, Pitch::G, 0), None);
+    /// ```
+    pub fn analyze(chord: &Chord, key: Key) -> Option<Self> {
+        let pitches = chord.pitches();
+        let bass = *pitches.first()?;
+
+        let in_scale = |key| {
+            let scale = diatonic_pitches(key);
+            pitches.iter().all(|p| scale.contains(p))
+        };
+
+        let diatonic = in_scale(key);
+
+        let borrowed = match key.mode {
+            DiatonicMode::MAJOR => in_scale(key.parallel(DiatonicMode::NATURAL_MINOR)),
+            DiatonicMode::NATURAL_MINOR => in_scale(key.parallel(DiatonicMode::MAJOR)),
+            _ => false,
+        };
+
+        let degrees = || (1..=7).map(|n| ScaleDegree::from_num(n).expect("in range"));
+
+        let on_degrees = |accidentals: &'static [AccidentalSign]| {
+            accidentals.iter().flat_map(move |&accidental| degrees().flat_map(move |degree| {
+                CHORDS.map(|(triad, seventh)| Self {
+                    kind: NumeralKind::Degree { accidental, degree, triad, seventh },
+                    inversion: 0,
+                    tonicized: None,
+                })
+            }))
+        };
+
+        const CHROMATIC: &[AccidentalSign] = &[AccidentalSign::NATURAL, AccidentalSign::FLAT, AccidentalSign::SHARP];
+
+        let special = [NumeralKind::Neapolitan]
+            .into_iter()
+            .chain([AugmentedSixth::Italian, AugmentedSixth::French, AugmentedSixth::German].map(NumeralKind::AugmentedSixth))
+            .map(|kind| Self { kind, inversion: 0, tonicized: None });
+
+        let secondary = degrees()
+            .skip(1)
+            .filter_map(|degree| {
+                let minor = match diatonic_triad(key, degree) {
+                    TriadQuality::Major | TriadQuality::Augmented => false,
+                    TriadQuality::Minor => true,
+                    TriadQuality::Diminished => return None,
+                };
+
+                Some(Tonicization { accidental: AccidentalSign::NATURAL, degree, minor })
+            })
+            .flat_map(|tonicized| APPLIED.map(|(degree, triad, seventh)| Self {
+                kind: NumeralKind::Degree { accidental: AccidentalSign::NATURAL, degree, triad, seventh },
+                inversion: 0,
+                tonicized: Some(tonicized),
+            }));
+
+        // dominant sevenths are read as secondary dominants rather than borrowed chords
+        let dominant_seventh = ChordType::DominantSeventh.intervals();
+
+        on_degrees(&[AccidentalSign::NATURAL])
+            .filter(|_| diatonic)
+            .chain(special)
+            .chain(on_degrees(CHROMATIC).filter(|_| borrowed && chord.intervals() != dominant_seventh))
+            .chain(secondary)
+            .chain(on_degrees(CHROMATIC))
+            .find_map(|numeral| {
+                let realized = numeral.realize(key).expect("root position is always valid").pitches();
+
+                if realized.len() != pitches.len() || !pitches.iter().all(|p| realized.contains(p)) {
+                    return None;
+                }
+
+                let inversion = realized.iter().position(|&p| p == bass).expect("bass is a chord tone");
+
+                Some(Self { inversion: inversion as _, .. numeral })
+            })
+    }
+}
+
+// the root of a degree numeral, raising the seventh degree for leading-tone chords in minor
+fn degree_root(key: Key, accidental: AccidentalSign, degree: ScaleDegree, triad: TriadQuality) -> Pitch {
+    let leading_tone = key.mode == DiatonicMode::NATURAL_MINOR
+        && accidental == AccidentalSign::NATURAL
+        && degree == ScaleDegree::VII
+        && triad == TriadQuality::Diminished;
+
+    if leading_tone {
+        key.tonic.transpose(-Interval::MINOR_SECOND)
+    } else {
+        key.relative_pitch(degree).transpose_fifths(7 * accidental.offset)
+    }
+}
+
+// the pitches of the key's scale, and the leading tone in minor keys
+fn diatonic_pitches(key: Key) -> Vec<Pitch> {
+    let mut pitches = (1..=7)
+        .map(|n| key.relative_pitch(ScaleDegree::from_num(n).expect("in range")))
+        .collect::<Vec<_>>();
+
+    if key.mode == DiatonicMode::NATURAL_MINOR {
+        pitches.push(key.tonic.transpose(-Interval::MINOR_SECOND));
+    }
+
+    pitches
+}
+
+// the triad on a scale degree, with a major dominant in minor keys
+fn diatonic_triad(key: Key, degree: ScaleDegree) -> TriadQuality {
+    if key.mode == DiatonicMode::NATURAL_MINOR && degree == ScaleDegree::V {
+        return TriadQuality::Major;
+    }
+
+    let pitch = |offset| {
+        let n = (degree.as_num() - 1 + offset) % 7 + 1;
+        key.relative_pitch(ScaleDegree::from_num(n).expect("in range"))
+    };
+
+    let root = pitch(0);
+
+    match (root.distance_to(pitch(2)), root.distance_to(pitch(4))) {
+        (Interval::MINOR_THIRD, Interval::DIMINISHED_FIFTH) => TriadQuality::Diminished,
+        (Interval::MAJOR_THIRD, Interval::AUGMENTED_FIFTH) => TriadQuality::Augmented,
+        (Interval::MINOR_THIRD, _) => TriadQuality::Minor,
+        _ => TriadQuality::Major,
+    }
+}
+
+fn chord_type(triad: TriadQuality, seventh: Option<SeventhQuality>) -> Option<ChordType> {
+    use TriadQuality as T;
+    use SeventhQuality as S;
+
+    Some(match (triad, seventh) {
+        (T::Major, None) => ChordType::MajorTriad,
+        (T::Minor, None) => ChordType::MinorTriad,
+        (T::Diminished, None) => ChordType::DiminishedTriad,
+        (T::Augmented, None) => ChordType::AugmentedTriad,
+        (T::Major, Some(S::Minor)) => ChordType::DominantSeventh,
+        (T::Major, Some(S::Major)) => ChordType::MajorSeventh,
+        (T::Minor, Some(S::Minor)) => ChordType::MinorSeventh,
+        (T::Minor, Some(S::Major)) => ChordType::MinorMajorSeventh,
+        (T::Diminished, Some(S::Minor)) => ChordType::HalfDiminishedSeventh,
+        (T::Diminished, Some(S::Diminished)) => ChordType::DiminishedSeventh,
+        (T::Augmented, Some(S::Minor)) => ChordType::AugmentedSeventh,
+        (T::Augmented, Some(S::Major)) => ChordType::AugmentedMajorSeventh,
+        _ => return None,
+    })
+}
+
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+fn write_numeral(f: &mut fmt::Formatter<'_>, accidental: AccidentalSign, degree: ScaleDegree, uppercase: bool) -> fmt::Result {
+    if accidental != AccidentalSign::NATURAL {
+        write!(f, "{accidental}")?;
+    }
+
+    let numeral = NUMERALS[degree.as_num() as usize - 1];
+
+    if uppercase {
+        write!(f, "{numeral}")
+    } else {
+        write!(f, "{}", numeral.to_lowercase())
+    }
+}
+
+fn figures(seventh: bool, inversion: u8) -> &'static str {
+    match (seventh, inversion) {
+        (false, 1) => "⁶",
+        (false, 2) => "⁶₄",
+        (true, 0) => "⁷",
+        (true, 1) => "⁶₅",
+        (true, 2) => "⁴₃",
+        (true, 3) => "⁴₂",
+        _ => "",
+    }
+}
+
+impl fmt::Display for Tonicization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_numeral(f, self.accidental, self.degree, !self.minor)
+    }
+}
+
+impl fmt::Display for RomanNumeral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            NumeralKind::Degree { accidental, degree, triad, seventh } => {
+                write_numeral(f, accidental, degree, triad.is_uppercase())?;
+
+                let quality = match (triad, seventh) {
+                    (TriadQuality::Diminished, Some(SeventhQuality::Minor)) => "ø",
+                    (TriadQuality::Diminished, _) => "°",
+                    (TriadQuality::Augmented, _) => "+",
+                    _ => "",
+                };
+
+                let major_seventh = if seventh == Some(SeventhQuality::Major) { "M" } else { "" };
+
+                write!(f, "{quality}{major_seventh}{}", figures(seventh.is_some(), self.inversion))?;
+            }
+            NumeralKind::Neapolitan => write!(f, "N{}", figures(false, self.inversion))?,
+            NumeralKind::AugmentedSixth(sixth) => write!(f, "{}⁺⁶", sixth.abbreviation())?,
+        }
+
+        if let Some(tonicized) = self.tonicized {
+            write!(f, "/{tonicized}")?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Figures {
+    None,
+    Triad(u8),
+    Seventh(u8),
+}
+
+fn parse_figures(s: &str) -> Result<Figures, ParseRomanNumeralError> {
+    Ok(match s {
+        "" => Figures::None,
+        "53" => Figures::Triad(0),
+        "6" | "63" => Figures::Triad(1),
+        "64" => Figures::Triad(2),
+        "7" => Figures::Seventh(0),
+        "65" => Figures::Seventh(1),
+        "43" => Figures::Seventh(2),
+        "42" | "2" => Figures::Seventh(3),
+        _ => return Err(ParseRomanNumeralError::InvalidFigures),
+    })
+}
+
+// replaces superscript and subscript digits and plus signs with ASCII ones
+fn normalize(c: char) -> char {
+    match c {
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴'..='⁹' => char::from_digit(c as u32 - '⁴' as u32 + 4, 10).expect("is a digit"),
+        '₀'..='₉' => char::from_digit(c as u32 - '₀' as u32, 10).expect("is a digit"),
+        '⁺' => '+',
+        c => c,
+    }
+}
+
+fn parse_accidental(s: &str) -> (AccidentalSign, &str) {
+    let mut offset = 0;
+
+    let rest = s.trim_start_matches(|c| {
+        offset += match c {
+            '♭' | 'b' => -1,
+            '♯' | '#' => 1,
+            '𝄫' => -2,
+            '𝄪' => 2,
+            '♮' => 0,
+            _ => return false,
+        };
+
+        true
+    });
+
+    (AccidentalSign { offset }, rest)
+}
+
+// parses a numeral from I to VII, returning whether it was uppercase
+fn parse_numeral(s: &str) -> Result<(ScaleDegree, bool, &str), ParseRomanNumeralError> {
+    let len = s.find(|c| !matches!(c, 'I' | 'V' | 'i' | 'v')).unwrap_or(s.len());
+    let (numeral, rest) = s.split_at(len);
+
+    let uppercase = numeral.chars().all(|c| c.is_ascii_uppercase());
+
+    if !uppercase && !numeral.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(ParseRomanNumeralError::InvalidNumeral);
+    }
+
+    let position = NUMERALS.iter()
+        .position(|n| n.eq_ignore_ascii_case(numeral))
+        .ok_or(ParseRomanNumeralError::InvalidNumeral)?;
+
+    let degree = ScaleDegree::from_num(position as u8 + 1).expect("in range");
+
+    Ok((degree, uppercase, rest))
+}
+
+fn parse_kind(s: &str) -> Result<(NumeralKind, u8), ParseRomanNumeralError> {
+    use ParseRomanNumeralError as E;
+
+    let (accidental, s) = parse_accidental(s);
+
+    let specials = [
+        ("Ger", Some(AugmentedSixth::German)),
+        ("Gr", Some(AugmentedSixth::German)),
+        ("It", Some(AugmentedSixth::Italian)),
+        ("Fr", Some(AugmentedSixth::French)),
+        ("N", None),
+    ];
+
+    for (name, sixth) in specials {
+        let Some(rest) = s.strip_prefix(name) else {
+            continue;
+        };
+
+        if accidental != AccidentalSign::NATURAL {
+            return Err(E::InvalidNumeral);
+        }
+
+        return match sixth {
+            Some(sixth) if matches!(rest, "" | "6" | "+6") => Ok((NumeralKind::AugmentedSixth(sixth), 0)),
+            Some(_) => Err(E::InvalidFigures),
+            None => match parse_figures(rest)? {
+                Figures::None => Ok((NumeralKind::Neapolitan, 0)),
+                Figures::Triad(inversion) => Ok((NumeralKind::Neapolitan, inversion)),
+                Figures::Seventh(_) => Err(E::InvalidFigures),
+            },
+        };
+    }
+
+    let (degree, uppercase, rest) = parse_numeral(s)?;
+
+    let (mark, rest) = match rest.chars().next() {
+        Some(c @ ('°' | 'o' | 'ø' | 'Ø' | '+')) => (Some(c), &rest[c.len_utf8()..]),
+        _ => (None, rest),
+    };
+
+    let (major_seventh, rest) = match ["maj", "M", "Δ"].into_iter().find_map(|m| rest.strip_prefix(m)) {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+
+    let half_diminished = matches!(mark, Some('ø' | 'Ø'));
+
+    let triad = match (uppercase, mark) {
+        (true, None) => TriadQuality::Major,
+        (true, Some('+')) => TriadQuality::Augmented,
+        (false, None) => TriadQuality::Minor,
+        (false, Some('°' | 'o' | 'ø' | 'Ø')) => TriadQuality::Diminished,
+        _ => return Err(E::InvalidQuality),
+    };
+
+    let figures = match parse_figures(rest)? {
+        Figures::None if half_diminished || major_seventh => Figures::Seventh(0),
+        Figures::Triad(_) if half_diminished || major_seventh => return Err(E::InvalidFigures),
+        figures => figures,
+    };
+
+    let seventh = || if major_seventh {
+        SeventhQuality::Major
+    } else if triad == TriadQuality::Diminished && !half_diminished {
+        SeventhQuality::Diminished
+    } else {
+        SeventhQuality::Minor
+    };
+
+    let (seventh, inversion) = match figures {
+        Figures::None => (None, 0),
+        Figures::Triad(inversion) => (None, inversion),
+        Figures::Seventh(inversion) => (Some(seventh()), inversion),
+    };
+
+    Ok((NumeralKind::Degree { accidental, degree, triad, seventh }, inversion))
+}
+
+impl FromStr for Tonicization {
+    type Err = ParseRomanNumeralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (accidental, s) = parse_accidental(s);
+
+        match parse_numeral(s) {
+            Ok((degree, uppercase, "")) => Ok(Self { accidental, degree, minor: !uppercase }),
+            _ => Err(ParseRomanNumeralError::InvalidTonicization),
+        }
+    }
+}
+
+impl FromStr for RomanNumeral {
+    type Err = ParseRomanNumeralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().chars().map(normalize).collect::<String>();
+
+        if s.is_empty() {
+            return Err(ParseRomanNumeralError::Empty);
+        }
+
+        let (chord, tonicized) = match s.split_once('/') {
+            Some((chord, tonicized)) => (chord, Some(tonicized.parse()?)),
+            None => (s.as_str(), None),
+        };
+
+        let (kind, inversion) = parse_kind(chord)?;
+
+        Ok(Self { kind, inversion, tonicized })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeral(s: &str) -> RomanNumeral {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let cases = [
+            ("I", "I"),
+            ("ii65", "ii⁶₅"),
+            ("ii⁶₅", "ii⁶₅"),
+            ("V7/V", "V⁷/V"),
+            ("bVI", "♭VI"),
+            ("♭VI", "♭VI"),
+            ("viiø7", "viiø⁷"),
+            ("viiø", "viiø⁷"),
+            ("viio7/ii", "vii°⁷/ii"),
+            ("vii°⁴₂", "vii°⁴₂"),
+            ("IV64", "IV⁶₄"),
+            ("V2", "V⁴₂"),
+            ("IM7", "IM⁷"),
+            ("Imaj65", "IM⁶₅"),
+            ("III+", "III+"),
+            ("N6", "N⁶"),
+            ("It+6", "It⁺⁶"),
+            ("Fr⁺⁶", "Fr⁺⁶"),
+            ("Gr+6", "Ger⁺⁶"),
+            ("V/bVI", "V/♭VI"),
+            (" #iv°7 ", "♯iv°⁷"),
+        ];
+
+        for (input, display) in cases {
+            let parsed = numeral(input);
+            assert_eq!(parsed.to_string(), display, "{input}");
+            assert_eq!(numeral(display), parsed, "{display}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        use ParseRomanNumeralError as E;
+
+        let cases = [
+            ("", E::Empty),
+            ("IIII", E::InvalidNumeral),
+            ("Vi", E::InvalidNumeral),
+            ("X", E::InvalidNumeral),
+            ("bN6", E::InvalidNumeral),
+            ("V°", E::InvalidQuality),
+            ("ii+", E::InvalidQuality),
+            ("V8", E::InvalidFigures),
+            ("viiø6", E::InvalidFigures),
+            ("N7", E::InvalidFigures),
+            ("Ger65", E::InvalidFigures),
+            ("V/V7", E::InvalidTonicization),
+            ("V/V/V", E::InvalidTonicization),
+        ];
+
+        for (input, err) in cases {
+            assert_eq!(input.parse::<RomanNumeral>(), Err(err), "{input}");
+        }
+    }
+
+    #[test]
+    fn realize() {
+        use Pitch as P;
+
+        let c_major = Key::major(P::C);
+        let a_minor = Key::minor(P::A);
+
+        let cases = [
+            ("V⁷/V", c_major, vec![P::D, P::F_SHARP, P::A, P::C]),
+            ("vii°⁷/V", c_major, vec![P::F_SHARP, P::A, P::C, P::E_FLAT]),
+            ("viiø⁴₃", c_major, vec![P::F, P::A, P::B, P::D]),
+            ("♭VII", c_major, vec![P::B_FLAT, P::D, P::F]),
+            ("iv⁶", c_major, vec![P::A_FLAT, P::C, P::F]),
+            ("It⁺⁶", c_major, vec![P::A_FLAT, P::C, P::F_SHARP]),
+            ("Fr⁺⁶", c_major, vec![P::A_FLAT, P::C, P::D, P::F_SHARP]),
+            ("V", a_minor, vec![P::E, P::G_SHARP, P::B]),
+            ("v", a_minor, vec![P::E, P::G, P::B]),
+            ("VII", a_minor, vec![P::G, P::B, P::D]),
+            ("vii°", a_minor, vec![P::G_SHARP, P::B, P::D]),
+            ("III+", a_minor, vec![P::C, P::E, P::G_SHARP]),
+            ("N⁶", a_minor, vec![P::D, P::F, P::B_FLAT]),
+            ("V⁷/III", a_minor, vec![P::G, P::B, P::D, P::F]),
+        ];
+
+        for (input, key, pitches) in cases {
+            assert_eq!(numeral(input).realize(key).unwrap().pitches(), pitches, "{input} in {key:?}");
+        }
+    }
+
+    #[test]
+    fn analyze() {
+        let keys = [Key::major(Pitch::C), Key::minor(Pitch::C), Key::major(Pitch::F_SHARP), Key::minor(Pitch::B_FLAT)];
+
+        let major = [
+            "I", "ii⁶", "iii", "IV⁶₄", "V⁷", "vi", "vii°⁶", "IM⁷", "ii⁴₃", "viiø⁴₂",
+            "V⁷/V", "vii°⁷/V", "V⁶₅/ii", "V/vi", "viiø⁷/V",
+            "iv", "♭VI", "♭III", "♭VII", "ii°", "N⁶", "It⁺⁶", "Fr⁺⁶", "Ger⁺⁶",
+        ];
+
+        let minor = [
+            "i", "ii°⁶", "III", "iv", "V", "V⁷", "VI", "VII", "vii°⁷", "III+",
+            "V⁷/iv", "V/V", "vii°⁷/iv", "V⁷/VI",
+            "I", "IV", "N⁶", "It⁺⁶", "Fr⁺⁶", "Ger⁺⁶",
+        ];
+
+        for key in keys {
+            let numerals = if key.mode == DiatonicMode::MAJOR { &major[..] } else { &minor[..] };
+
+            for &s in numerals {
+                let chord = numeral(s).realize(key).unwrap();
+                let analyzed = RomanNumeral::analyze(&chord, key).map(|n| n.to_string());
+
+                assert_eq!(analyzed.as_deref(), Some(s), "{chord:?} in {key:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn analyze_by_spelling() {
+        let c_major = Key::major(Pitch::C);
+
+        // A♭7 rather than a German sixth
+        let chord = Chord::from_type(ChordType::DominantSeventh, Pitch::A_FLAT, 0).unwrap();
+        assert_eq!(RomanNumeral::analyze(&chord, c_major), Some(numeral("♭VI7")));
+
+        // the French sixth is a D7♭5 in second inversion
+        let chord = Chord::from_type(ChordType::DominantSeventhFlatFive, Pitch::D, 2).unwrap();
+        assert_eq!(RomanNumeral::analyze(&chord, c_major), Some(numeral("Fr+6")));
+
+        let chord = Chord::from_type(ChordType::Suspended4Triad, Pitch::G, 0).unwrap();
+        assert_eq!(RomanNumeral::analyze(&chord, c_major), None);
+    }
+}
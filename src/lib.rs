@@ -5,6 +5,10 @@ mod semitone;
 pub mod pitch;
 pub mod set;
 pub mod harmony;
+pub mod counterpoint;
+pub mod tuning;
+pub mod rhythm;
+pub mod midi;
 
 
 // experimental features:
@@ -30,6 +34,6 @@ mod generator;
 // -- REEXPORTS --
 pub use crate::interval::Interval;
 pub use crate::pitch::{Pitch, PitchClass, AccidentalSign, Letter};
-pub use crate::note::Note;
+pub use crate::note::{Note, ParseNoteError};
 pub use crate::enharmonic::{EnharmonicEq, EnharmonicOrd};
 pub use crate::semitone::Semitones;
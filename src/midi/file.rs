@@ -0,0 +1,224 @@
+use crate::Note;
+use crate::harmony::Key;
+use crate::rhythm::{Duration, Rational, Tempo, TimeSignature, TimedNote};
+#[cfg(feature = "experimental-chords")]
+use std::ops::{Range, RangeInclusive};
+#[cfg(feature = "experimental-chords")]
+use crate::chord::{Chord, Voicing};
+
+/// How the tracks of a [`MidiFile`] relate to each other.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, strum_macros::FromRepr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u16)]
+pub enum MidiFormat {
+    /// Format 0, a single track with every channel.
+    SingleTrack = 0,
+    /// Format 1, tracks played at the same time, usually with tempo and other meta events in the first.
+    MultiTrack = 1,
+    /// Format 2, independent tracks, each with their own tempo and meta events.
+    MultiSequence = 2,
+}
+
+/// A note from a [`MidiTrack`], with its velocity and channel.
+///
+/// Onsets and lengths are measured in whole notes, so a quarter note is `1/4`
+/// no matter the resolution of the file.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidiNote {
+    /// The note, spelled in the key signature in effect, or with sharps if there isn't one.
+    pub note: Note,
+    /// When the note starts, in whole notes.
+    pub onset: Rational,
+    /// How long the note lasts, in whole notes.
+    pub length: Rational,
+    /// How hard the note is played, from 1 to 127.
+    pub velocity: u8,
+    /// The channel the note is played on, from 0 to 15.
+    pub channel: u8,
+}
+
+impl MidiNote {
+    /// Returns when the note ends, in whole notes.
+    pub fn end(&self) -> Rational {
+        self.onset + self.length
+    }
+
+    /// Converts the note to a [`TimedNote`], or returns `None` if its length isn't
+    /// a single note value with at most two dots, see [`Duration::from_length`].
+    pub fn timed(&self) -> Option<TimedNote> {
+        Duration::from_length(self.length).map(|duration| TimedNote::note(self.note, self.onset, duration))
+    }
+}
+
+/// A program change, which switches the instrument a channel plays.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramChange {
+    /// When the program changes, in whole notes.
+    pub onset: Rational,
+    /// The channel whose program changes, from 0 to 15.
+    pub channel: u8,
+    /// The new program, from 0 to 127, which is a General MIDI instrument if the file uses it.
+    pub program: u8,
+}
+
+/// A track of a [`MidiFile`], with its notes and the meta events that affect them.
+///
+/// Every list is in order of onset.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidiTrack {
+    /// The name of the track, from a track name meta event.
+    pub name: Option<String>,
+    /// The notes in the track, in the order they start.
+    pub notes: Vec<MidiNote>,
+    /// Tempo changes, as when they happen and the microseconds per quarter note.
+    pub tempos: Vec<(Rational, u32)>,
+    /// Time signature changes.
+    pub time_signatures: Vec<(Rational, TimeSignature)>,
+    /// Key signature changes.
+    pub key_signatures: Vec<(Rational, Key)>,
+    /// Program changes.
+    pub programs: Vec<ProgramChange>,
+    /// When the track ends, in whole notes. When it's written, the track ends
+    /// after its last event if that's later.
+    pub end: Rational,
+}
+
+impl MidiTrack {
+    /// Creates an empty track with a name.
+    pub fn named(name: impl Into<String>) -> Self {
+        Self { name: Some(name.into()), ..Self::default() }
+    }
+
+    /// Adds notes sounding together, all with the same velocity and channel,
+    /// and moves the [end](Self::end) of the track to when they end if that's later.
+    pub fn push_notes(&mut self, notes: &[Note], onset: Rational, length: Rational, velocity: u8, channel: u8) {
+        self.notes.extend(notes.iter().map(|&note| MidiNote { note, onset, length, velocity, channel }));
+
+        if !notes.is_empty() {
+            self.end = self.end.max(onset + length);
+        }
+    }
+
+    /// Adds the notes of a [`TimedNote`], which adds nothing if it's a rest.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::midi::MidiTrack;
+    /// # use music_theory::rhythm::{Rational, TimedNote};
+    /// let chord = TimedNote::chord(
+    ///     vec!["C4".parse().unwrap(), "E4".parse().unwrap()],
+    ///     Rational::new(1, 4),
+    ///     "2.".parse().unwrap(),
+    /// );
+    ///
+    /// let mut track = MidiTrack::default();
+    /// track.push_timed(&chord, 80, 0);
+    ///
+    /// assert_eq!(track.notes.len(), 2);
+    /// assert_eq!(track.notes[1].end(), Rational::ONE);
+    /// ```
+    pub fn push_timed(&mut self, note: &TimedNote, velocity: u8, channel: u8) {
+        self.push_notes(&note.notes, note.onset, note.length(), velocity, channel);
+    }
+
+    /// Voices a chord within a range, see [`Chord::voice`], and adds its notes from `span.start`
+    /// until `span.end`.
+    ///
+    /// Returns `false`, adding nothing, if the chord can't be voiced in the range.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::chord::{Chord, VoicingStyle};
+    /// # use music_theory::midi::MidiTrack;
+    /// # use music_theory::rhythm::Rational;
+    /// let chord = "Cmaj7".parse::<Chord>().unwrap();
+    /// let range = Note::new(Pitch::C, 3)..=Note::new(Pitch::C, 6);
+    ///
+    /// let mut track = MidiTrack::default();
+    /// assert!(track.push_chord(&chord, VoicingStyle::Drop2.into(), range, Rational::ZERO..Rational::ONE, 64, 0));
+    ///
+    /// assert_eq!(track.notes[0].note, Note::new(Pitch::G, 3));
+    /// ```
+    #[cfg(feature = "experimental-chords")]
+    pub fn push_chord(&mut self, chord: &Chord, voicing: Voicing, range: RangeInclusive<Note>, span: Range<Rational>, velocity: u8, channel: u8) -> bool {
+        let Some(notes) = chord.voice(voicing, *range.start(), *range.end()) else {
+            return false;
+        };
+
+        self.push_notes(&notes, span.start, span.end - span.start, velocity, channel);
+        true
+    }
+
+    /// Adds a tempo change, rounded to the nearest microsecond per quarter note.
+    pub fn push_tempo(&mut self, onset: Rational, tempo: Tempo) {
+        let microseconds = (60_000_000.0 / tempo.quarter_notes_per_minute()).round() as u32;
+
+        self.tempos.push((onset, microseconds));
+    }
+}
+
+/// A Standard MIDI File, made of tracks of notes.
+///
+/// Files are read with [`Self::from_bytes`], and written with [`Self::to_bytes`]. Only files that
+/// measure time in ticks per quarter note are supported, not SMPTE time codes.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidiFile {
+    /// How the tracks relate to each other.
+    pub format: MidiFormat,
+    /// How many ticks make up a quarter note, the resolution of the file.
+    pub ticks_per_quarter: u16,
+    /// The tracks of the file.
+    pub tracks: Vec<MidiTrack>,
+}
+
+/// The default tempo of a MIDI file, a quarter note at 120 beats per minute, in microseconds per quarter note.
+pub(crate) const DEFAULT_MICROSECONDS_PER_QUARTER: u32 = 500_000;
+
+impl MidiFile {
+    /// Creates a file with no tracks.
+    pub fn new(format: MidiFormat, ticks_per_quarter: u16) -> Self {
+        Self { format, ticks_per_quarter, tracks: Vec::new() }
+    }
+
+    /// Returns the tempo changes of every track, in order.
+    ///
+    /// This is the tempo map of the whole file for formats 0 and 1, but not for format 2,
+    /// where each track has its own tempo.
+    pub fn tempo_map(&self) -> Vec<(Rational, Tempo)> {
+        let mut tempos = self.tracks.iter()
+            .flat_map(|track| &track.tempos)
+            .map(|&(onset, microseconds)| (onset, tempo_from_microseconds(microseconds)))
+            .collect::<Vec<_>>();
+
+        tempos.sort_by_key(|&(onset, _)| onset);
+        tempos
+    }
+
+    /// Returns when a position happens, in seconds, following the [tempo map](Self::tempo_map).
+    ///
+    /// Before the first tempo change, the tempo is a quarter note at 120 beats per minute.
+    pub fn seconds(&self, position: Rational) -> f64 {
+        let mut seconds = 0.0;
+        let mut last = (Rational::ZERO, tempo_from_microseconds(DEFAULT_MICROSECONDS_PER_QUARTER));
+
+        for (onset, tempo) in self.tempo_map() {
+            if onset >= position {
+                break;
+            }
+
+            seconds += last.1.seconds(onset - last.0);
+            last = (onset, tempo);
+        }
+
+        seconds + last.1.seconds(position - last.0)
+    }
+}
+
+pub(crate) fn tempo_from_microseconds(microseconds: u32) -> Tempo {
+    Tempo::quarter_notes(60_000_000.0 / microseconds as f64)
+}
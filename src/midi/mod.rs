@@ -0,0 +1,35 @@
+//! Reading and writing Standard MIDI Files.
+//!
+//! A [`MidiFile`] is made of [`MidiTrack`]s, each with its [`MidiNote`]s and the tempo, time
+//! signature, key signature, and program changes that affect them. Time is measured in whole
+//! notes with [`Rational`](crate::rhythm::Rational)s, like the rest of the [`rhythm`](crate::rhythm)
+//! module, and the file's [tempo map](MidiFile::tempo_map) converts it to seconds.
+//!
+//! Files are read with [`MidiFile::from_bytes`] and written with [`MidiFile::to_bytes`].
+//!
+//! # Examples
+//! ```
+//! # use music_theory::midi::MidiFile;
+//! # use music_theory::rhythm::Rational;
+//! let bytes = [
+//!     b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 120,
+//!     b'M', b'T', b'r', b'k', 0, 0, 0, 9,
+//!     0x00, 0x90, 60, 64, // middle C
+//!     0x81, 0x70, 0x80, 60, 64, // two quarter notes later
+//! ];
+//!
+//! let file = MidiFile::from_bytes(&bytes).unwrap();
+//! let note = file.tracks[0].notes[0];
+//!
+//! assert_eq!(note.length, Rational::new(1, 2));
+//! assert_eq!(file.seconds(note.end()), 1.0);
+//! ```
+
+mod file;
+pub use file::*;
+
+mod read;
+pub use read::*;
+
+mod write;
+pub use write::*;
@@ -0,0 +1,481 @@
+use std::collections::{HashMap, VecDeque};
+use crate::Note;
+use crate::harmony::{DiatonicMode, Key};
+use crate::midi::{MidiFile, MidiFormat, MidiNote, MidiTrack, ProgramChange};
+use crate::rhythm::{Rational, TimeSignature};
+
+/// An error returned when a Standard MIDI File can't be parsed.
+///
+/// The [`offset`](Self::offset) is the position in bytes responsible for the error,
+/// or the length of the file if it ended too early.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("{kind} (at byte {offset})")]
+pub struct ParseMidiError {
+    pub kind: ParseMidiErrorKind,
+    pub offset: usize,
+}
+
+/// The reason a Standard MIDI File couldn't be parsed.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseMidiErrorKind {
+    /// The file or a chunk ended in the middle of something.
+    #[error("The file ended too early")]
+    UnexpectedEnd,
+    /// The file didn't start with a six byte `MThd` header chunk.
+    #[error("Expected an 'MThd' header chunk")]
+    InvalidHeader,
+    /// The format wasn't 0, 1, or 2.
+    #[error("The format must be 0, 1, or 2")]
+    InvalidFormat,
+    /// A format 0 file didn't have exactly one track.
+    #[error("A format 0 file must have exactly one track")]
+    InvalidTrackCount,
+    /// The division was an SMPTE time code, or zero ticks per quarter note.
+    #[error("Only a nonzero number of ticks per quarter note is supported")]
+    UnsupportedDivision,
+    /// A variable length quantity was longer than four bytes.
+    #[error("A variable length quantity was longer than four bytes")]
+    InvalidVariableLength,
+    /// An event started with a data byte, but there was no running status to use.
+    #[error("There was no running status for the data byte")]
+    MissingStatus,
+    /// A status byte was a system common or real time message, which can't be in a file.
+    #[error("Unexpected status byte")]
+    InvalidStatus,
+    /// A data byte of a channel message was more than 127.
+    #[error("Data bytes must be at most 127")]
+    InvalidData,
+    /// A tempo, time signature, or key signature meta event had the wrong length or values.
+    #[error("Invalid tempo, time signature, or key signature")]
+    InvalidMetaEvent,
+}
+
+// reads bytes from the file, keeping track of where it is for errors
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    // the end of the current chunk
+    end: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self, kind: ParseMidiErrorKind) -> ParseMidiError {
+        ParseMidiError { kind, offset: self.position }
+    }
+
+    fn is_done(&self) -> bool {
+        self.position >= self.end
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], ParseMidiError> {
+        if self.end - self.position < count {
+            return Err(ParseMidiError { kind: ParseMidiErrorKind::UnexpectedEnd, offset: self.end });
+        }
+
+        let bytes = &self.bytes[self.position..self.position + count];
+        self.position += count;
+
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, ParseMidiError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ParseMidiError> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().expect("took two bytes")))
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseMidiError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().expect("took four bytes")))
+    }
+
+    fn data(&mut self) -> Result<u8, ParseMidiError> {
+        let byte = self.byte()?;
+
+        if byte > 0x7F {
+            self.position -= 1;
+            return Err(self.error(ParseMidiErrorKind::InvalidData));
+        }
+
+        Ok(byte)
+    }
+
+    // a variable length quantity, seven bits per byte, with the high bit set on all but the last byte
+    fn variable_length(&mut self) -> Result<u32, ParseMidiError> {
+        let start = self.position;
+        let mut value = 0;
+
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ParseMidiError { kind: ParseMidiErrorKind::InvalidVariableLength, offset: start })
+    }
+
+    // a chunk's type and the reader for its contents, skipping the contents in this reader
+    fn chunk(&mut self) -> Result<([u8; 4], Reader<'a>), ParseMidiError> {
+        let id = self.take(4)?.try_into().expect("took four bytes");
+        let length = self.u32()? as usize;
+
+        let start = self.position;
+        self.take(length)?;
+
+        Ok((id, Reader { bytes: self.bytes, position: start, end: self.position }))
+    }
+}
+
+// a track as it's read, before its notes are spelled
+struct RawTrack {
+    track: MidiTrack,
+    // the MIDI note numbers of the track's notes
+    keys: Vec<u8>,
+}
+
+impl MidiFile {
+    /// Parses a Standard MIDI File of format 0, 1, or 2.
+    ///
+    /// Notes are paired from note on and note off events, where a note on with a velocity of zero
+    /// is a note off. Overlapping notes with the same key and channel end first in, first out,
+    /// and notes that never end last until the end of their track.
+    ///
+    /// Notes are spelled in the key signature in effect when they start, which comes from every
+    /// track in formats 0 and 1, or just from their own track in format 2. Control changes,
+    /// system exclusive messages, and other meta events are skipped, as are unknown chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file isn't a valid Standard MIDI File, see [`ParseMidiErrorKind`],
+    /// or if it measures time in SMPTE time codes instead of ticks per quarter note.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::midi::{MidiFile, MidiFormat};
+    /// # use music_theory::rhythm::Rational;
+    /// let bytes = [
+    ///     b'M', b'T', b'h', b'd', 0, 0, 0, 6, // header chunk
+    ///     0, 0, 0, 1, 0, 96, // format 0, one track, 96 ticks per quarter note
+    ///     b'M', b'T', b'r', b'k', 0, 0, 0, 17, // track chunk
+    ///     0x00, 0xFF, 0x59, 0x02, 0xFD, 0x01, // C minor
+    ///     0x00, 0x90, 63, 100, // note on, E♭4
+    ///     0x60, 63, 0, // a quarter note later, note off with running status
+    ///     0x00, 0xFF, 0x2F, 0x00, // end of track
+    /// ];
+    ///
+    /// let file = MidiFile::from_bytes(&bytes).unwrap();
+    /// assert_eq!(file.format, MidiFormat::SingleTrack);
+    ///
+    /// let note = file.tracks[0].notes[0];
+    /// assert_eq!(note.note, Note::new(Pitch::E_FLAT, 4));
+    /// assert_eq!(note.length, Rational::new(1, 4));
+    /// assert_eq!(note.velocity, 100);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseMidiError> {
+        use ParseMidiErrorKind as E;
+
+        let mut reader = Reader { bytes, position: 0, end: bytes.len() };
+
+        let (id, mut header) = reader.chunk().map_err(|_| ParseMidiError { kind: E::InvalidHeader, offset: 0 })?;
+
+        if &id != b"MThd" || header.end - header.position < 6 {
+            return Err(ParseMidiError { kind: E::InvalidHeader, offset: 0 });
+        }
+
+        let format = MidiFormat::from_repr(header.u16()?).ok_or(ParseMidiError { kind: E::InvalidFormat, offset: 8 })?;
+        let track_count = header.u16()?;
+
+        let ticks_per_quarter = header.u16()?;
+
+        if ticks_per_quarter == 0 || ticks_per_quarter & 0x8000 != 0 {
+            return Err(ParseMidiError { kind: E::UnsupportedDivision, offset: 12 });
+        }
+
+        if format == MidiFormat::SingleTrack && track_count != 1 {
+            return Err(ParseMidiError { kind: E::InvalidTrackCount, offset: 10 });
+        }
+
+        let mut tracks = Vec::new();
+
+        while !reader.is_done() && tracks.len() < track_count as usize {
+            let (id, mut chunk) = reader.chunk()?;
+
+            if &id == b"MTrk" {
+                tracks.push(read_track(&mut chunk, ticks_per_quarter)?);
+            }
+        }
+
+        if tracks.len() < track_count as usize {
+            return Err(reader.error(E::UnexpectedEnd));
+        }
+
+        // spell notes in the key signatures that apply to them
+        let shared_keys = match format {
+            MidiFormat::MultiSequence => Vec::new(),
+            _ => {
+                let mut keys = tracks.iter()
+                    .flat_map(|raw| raw.track.key_signatures.iter().copied())
+                    .collect::<Vec<_>>();
+
+                keys.sort_by_key(|&(onset, _)| onset);
+                keys
+            }
+        };
+
+        let tracks = tracks.into_iter()
+            .map(|RawTrack { mut track, keys }| {
+                let key_signatures = match format {
+                    MidiFormat::MultiSequence => &track.key_signatures,
+                    _ => &shared_keys,
+                };
+
+                for (note, &key) in track.notes.iter_mut().zip(&keys) {
+                    note.note = spell(key, note.onset, key_signatures);
+                }
+
+                track
+            })
+            .collect();
+
+        Ok(Self { format, ticks_per_quarter, tracks })
+    }
+}
+
+fn spell(key: u8, onset: Rational, key_signatures: &[(Rational, Key)]) -> Note {
+    let note = Note::from_midi(key);
+
+    match key_signatures.iter().rev().find(|(change, _)| *change <= onset) {
+        Some(&(_, key)) => note.respell_with(key.spelling().unwrap_or_default()).respell_in_key(key),
+        None => note,
+    }
+}
+
+fn read_track(reader: &mut Reader, ticks_per_quarter: u16) -> Result<RawTrack, ParseMidiError> {
+    use ParseMidiErrorKind as E;
+
+    let whole = 4 * ticks_per_quarter as i64;
+    let time = |ticks: u64| Rational::new(ticks as _, whole);
+
+    let mut track = MidiTrack::default();
+    let mut keys = Vec::new();
+
+    // notes that haven't ended yet, by channel and key, as their index and the tick they started
+    let mut sounding = HashMap::<(u8, u8), VecDeque<(usize, u64)>>::new();
+
+    let mut ticks = 0u64;
+    let mut running_status = None;
+
+    while !reader.is_done() {
+        ticks += reader.variable_length()? as u64;
+
+        let start = reader.position;
+        let mut status = reader.byte()?;
+
+        if status < 0x80 {
+            // running status, so this was the first data byte
+            status = running_status.ok_or(ParseMidiError { kind: E::MissingStatus, offset: start })?;
+            reader.position -= 1;
+        }
+
+        match status {
+            0x80..=0xEF => {
+                running_status = Some(status);
+
+                let channel = status & 0x0F;
+                let first = reader.data()?;
+
+                let second = match status & 0xF0 {
+                    0xC0 | 0xD0 => 0,
+                    _ => reader.data()?,
+                };
+
+                match (status & 0xF0, second) {
+                    (0x80, _) | (0x90, 0) => {
+                        if let Some((index, onset)) = sounding.get_mut(&(channel, first)).and_then(VecDeque::pop_front) {
+                            track.notes[index].length = time(ticks - onset);
+                        }
+                    }
+                    (0x90, velocity) => {
+                        sounding.entry((channel, first)).or_default().push_back((track.notes.len(), ticks));
+
+                        track.notes.push(MidiNote {
+                            note: Note::from_midi(first),
+                            onset: time(ticks),
+                            length: Rational::ZERO,
+                            velocity,
+                            channel,
+                        });
+
+                        keys.push(first);
+                    }
+                    (0xC0, _) => track.programs.push(ProgramChange { onset: time(ticks), channel, program: first }),
+                    _ => {}
+                }
+            }
+            0xF0 | 0xF7 => {
+                running_status = None;
+
+                let length = reader.variable_length()?;
+                reader.take(length as _)?;
+            }
+            0xFF => {
+                running_status = None;
+
+                let ty = reader.byte()?;
+                let length = reader.variable_length()?;
+
+                let data_start = reader.position;
+                let data = reader.take(length as _)?;
+                let invalid = ParseMidiError { kind: E::InvalidMetaEvent, offset: data_start };
+
+                match ty {
+                    0x03 => track.name = Some(String::from_utf8_lossy(data).into_owned()),
+                    0x2F => break,
+                    0x51 => {
+                        let &[a, b, c] = data else { return Err(invalid) };
+
+                        track.tempos.push((time(ticks), u32::from_be_bytes([0, a, b, c])));
+                    }
+                    0x58 => {
+                        let &[numerator, exponent, _, _] = data else { return Err(invalid) };
+
+                        let time_signature = 1u16.checked_shl(exponent as _)
+                            .and_then(|denominator| TimeSignature::new(numerator, denominator))
+                            .ok_or(invalid)?;
+
+                        track.time_signatures.push((time(ticks), time_signature));
+                    }
+                    0x59 => {
+                        let &[sharps, minor] = data else { return Err(invalid) };
+
+                        let sharps = sharps as i8;
+
+                        let mode = match minor {
+                            0 => DiatonicMode::MAJOR,
+                            1 => DiatonicMode::NATURAL_MINOR,
+                            _ => return Err(invalid),
+                        };
+
+                        if !(-7..=7).contains(&sharps) {
+                            return Err(invalid);
+                        }
+
+                        track.key_signatures.push((time(ticks), Key::from_sharps(sharps as _, mode)));
+                    }
+                    _ => {}
+                }
+            }
+            _ => return Err(ParseMidiError { kind: E::InvalidStatus, offset: start }),
+        }
+    }
+
+    // notes that never end last until the end of the track
+    for (index, onset) in sounding.into_values().flatten() {
+        track.notes[index].length = time(ticks - onset);
+    }
+
+    track.end = time(ticks);
+
+    Ok(RawTrack { track, keys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pitch;
+
+    fn file(format: u16, tracks: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = b"MThd\0\0\0\x06".to_vec();
+        bytes.extend(format.to_be_bytes());
+        bytes.extend((tracks.len() as u16).to_be_bytes());
+        bytes.extend(480u16.to_be_bytes());
+
+        for track in tracks {
+            bytes.extend(b"MTrk");
+            bytes.extend((track.len() as u32).to_be_bytes());
+            bytes.extend(*track);
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn read_tracks() {
+        let conductor: &[u8] = &[
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // 120 bpm
+            0x00, 0xFF, 0x58, 0x04, 0x06, 0x03, 0x24, 0x08, // 6/8
+            0x00, 0xFF, 0x59, 0x02, 0xFE, 0x00, // B♭ major
+            0x87, 0x40, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, // a half note later, 60 bpm
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+
+        let melody: &[u8] = &[
+            0x00, 0xFF, 0x03, 0x05, b'V', b'o', b'i', b'c', b'e',
+            0x00, 0xC1, 0x28, // violin on channel 2
+            0x00, 0x91, 70, 80, // B♭4
+            0x00, 75, 90, // E♭5, with running status
+            0x87, 0x40, 70, 0, // both end after a half note
+            0x00, 0x81, 75, 0x40,
+            0x00, 0xF0, 0x02, 0x01, 0xF7, // system exclusive
+            0x85, 0x50, 0x91, 66, 100, // a dotted quarter later, G♭4, which never ends
+            0x87, 0x40, 0xFF, 0x2F, 0x00,
+        ];
+
+        let file = MidiFile::from_bytes(&file(1, &[conductor, melody])).unwrap();
+
+        assert_eq!(file.format, MidiFormat::MultiTrack);
+        assert_eq!(file.ticks_per_quarter, 480);
+
+        let [conductor, melody] = file.tracks.as_slice() else { panic!("expected two tracks") };
+
+        assert_eq!(conductor.time_signatures, [(Rational::ZERO, "6/8".parse().unwrap())]);
+        assert_eq!(conductor.key_signatures, [(Rational::ZERO, Key::major(Pitch::B_FLAT))]);
+        assert_eq!(conductor.end, Rational::new(1, 2));
+        assert_eq!(file.seconds(Rational::ONE), 1.0 + 2.0);
+
+        assert_eq!(melody.name.as_deref(), Some("Voice"));
+        assert_eq!(melody.programs, [ProgramChange { onset: Rational::ZERO, channel: 1, program: 40 }]);
+
+        let notes = melody.notes.iter()
+            .map(|note| (note.note.to_string(), note.onset, note.length, note.velocity))
+            .collect::<Vec<_>>();
+
+        assert_eq!(notes, [
+            ("B♭4".to_owned(), Rational::ZERO, Rational::new(1, 2), 80),
+            ("E♭5".to_owned(), Rational::ZERO, Rational::new(1, 2), 90),
+            ("G♭4".to_owned(), Rational::new(7, 8), Rational::new(1, 2), 100),
+        ]);
+
+        assert_eq!(melody.notes[1].timed().unwrap().duration.to_string(), "2");
+    }
+
+    #[test]
+    fn invalid_files() {
+        use ParseMidiErrorKind as E;
+
+        let error = |bytes: &[u8]| MidiFile::from_bytes(bytes).unwrap_err().kind;
+
+        assert_eq!(error(b"RIFF"), E::InvalidHeader);
+        assert_eq!(error(&file(3, &[])), E::InvalidFormat);
+        assert_eq!(error(&file(0, &[&[], &[]])), E::InvalidTrackCount);
+        assert_eq!(error(&file(1, &[&[0x00, 0x40, 0x7F]])), E::MissingStatus);
+        assert_eq!(error(&file(1, &[&[0x00, 0xF2, 0x00, 0x00]])), E::InvalidStatus);
+        assert_eq!(error(&file(1, &[&[0x00, 0x90, 0x80, 0x40]])), E::InvalidData);
+        assert_eq!(error(&file(1, &[&[0xFF, 0xFF, 0xFF, 0xFF, 0x00]])), E::InvalidVariableLength);
+        assert_eq!(error(&file(1, &[&[0x00, 0xFF, 0x59, 0x02, 0x08, 0x00]])), E::InvalidMetaEvent);
+        assert_eq!(error(&file(1, &[&[0x00, 0x90, 0x40]])), E::UnexpectedEnd);
+
+        let mut smpte = file(1, &[]);
+        smpte[12] = 0xE7;
+        assert_eq!(error(&smpte), E::UnsupportedDivision);
+
+        let mut missing = file(1, &[]);
+        missing[11] = 1;
+        assert_eq!(error(&missing), E::UnexpectedEnd);
+    }
+}
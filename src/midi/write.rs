@@ -0,0 +1,366 @@
+use crate::Note;
+use crate::harmony::{DiatonicMode, Key};
+use crate::midi::{MidiFile, MidiFormat, MidiTrack};
+use crate::rhythm::Rational;
+
+/// An error returned when a [`MidiFile`] can't be written, because something in it can't be
+/// represented in a Standard MIDI File.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum WriteMidiError {
+    /// A format 0 file didn't have exactly one track.
+    #[error("A format 0 file must have exactly one track")]
+    InvalidTrackCount,
+    /// The ticks per quarter note was zero, or too large to fit in 15 bits.
+    #[error("The ticks per quarter note must be from 1 to 32767")]
+    InvalidTicksPerQuarter,
+    /// A time wasn't a whole number of ticks, was negative, or was too late to be written.
+    #[error("The time {0} isn't a whole number of ticks in range")]
+    InvalidTime(Rational),
+    /// A note was outside of the MIDI range, from C-1 to G9.
+    #[error("The note {0} is outside of the MIDI range")]
+    InvalidNote(Note),
+    /// A velocity wasn't from 1 to 127.
+    #[error("The velocity {0} isn't from 1 to 127")]
+    InvalidVelocity(u8),
+    /// A channel was more than 15.
+    #[error("The channel {0} isn't from 0 to 15")]
+    InvalidChannel(u8),
+    /// A program was more than 127.
+    #[error("The program {0} isn't from 0 to 127")]
+    InvalidProgram(u8),
+    /// A tempo was zero, or too slow to fit in 24 bits.
+    #[error("The tempo of {0} microseconds per quarter note isn't from 1 to 16777215")]
+    InvalidTempo(u32),
+    /// A time signature's denominator wasn't a power of two.
+    #[error("Irrational time signatures can't be written")]
+    IrrationalTimeSignature,
+    /// A key wasn't major or minor, or had more than seven sharps or flats.
+    #[error("The key {0:?} must be major or minor with at most seven sharps or flats")]
+    InvalidKey(Key),
+}
+
+// events at the same tick are written in this order, so notes ending make room for notes starting
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum Priority {
+    Meta,
+    Program,
+    NoteOff,
+    NoteOn,
+    // a note that ends as soon as it starts has to end after it starts
+    EmptyNoteOff,
+}
+
+// builds the bytes of a track, using running status where it can
+#[derive(Default)]
+struct TrackWriter {
+    bytes: Vec<u8>,
+    running_status: Option<u8>,
+    ticks: u32,
+}
+
+impl TrackWriter {
+    fn variable_length(&mut self, mut value: u32) {
+        let mut bytes = vec![(value & 0x7F) as u8];
+
+        while value > 0x7F {
+            value >>= 7;
+            bytes.push((value & 0x7F) as u8 | 0x80);
+        }
+
+        self.bytes.extend(bytes.iter().rev());
+    }
+
+    fn delta(&mut self, ticks: u32) {
+        self.variable_length(ticks - self.ticks);
+        self.ticks = ticks;
+    }
+
+    fn channel(&mut self, ticks: u32, status: u8, data: &[u8]) {
+        self.delta(ticks);
+
+        if self.running_status != Some(status) {
+            self.bytes.push(status);
+            self.running_status = Some(status);
+        }
+
+        self.bytes.extend(data);
+    }
+
+    fn meta(&mut self, ticks: u32, ty: u8, data: &[u8]) {
+        self.delta(ticks);
+        self.running_status = None;
+
+        self.bytes.extend([0xFF, ty]);
+        self.variable_length(data.len() as _);
+        self.bytes.extend(data);
+    }
+}
+
+// the largest delta time a variable length quantity can hold
+const MAX_TICKS: u32 = 0x0FFF_FFFF;
+
+impl MidiFile {
+    /// Writes the file as a Standard MIDI File.
+    ///
+    /// Within a track, events at the same time are written as meta events first, in the order
+    /// track name, tempo, time signature, and key signature, then program changes, note offs,
+    /// and note ons. Notes end with note off events with a velocity of 64, and running status
+    /// is used whenever it can be. Time signatures are written with 24 MIDI clocks per
+    /// metronome click and 8 thirty-second notes per quarter note.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if something in the file can't be represented in a Standard MIDI File,
+    /// see [`WriteMidiError`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::midi::{MidiFile, MidiFormat, MidiTrack};
+    /// # use music_theory::rhythm::Rational;
+    /// let mut track = MidiTrack::default();
+    /// track.push_notes(&[Note::MIDDLE_C], Rational::ZERO, Rational::new(1, 4), 64, 0);
+    ///
+    /// let mut file = MidiFile::new(MidiFormat::SingleTrack, 96);
+    /// file.tracks.push(track);
+    ///
+    /// let bytes = file.to_bytes().unwrap();
+    ///
+    /// assert_eq!(bytes, [
+    ///     b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 96,
+    ///     b'M', b'T', b'r', b'k', 0, 0, 0, 12,
+    ///     0x00, 0x90, 60, 64,
+    ///     0x60, 0x80, 60, 64,
+    ///     0x00, 0xFF, 0x2F, 0x00,
+    /// ]);
+    ///
+    /// assert_eq!(MidiFile::from_bytes(&bytes), Ok(file));
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WriteMidiError> {
+        if self.format == MidiFormat::SingleTrack && self.tracks.len() != 1 {
+            return Err(WriteMidiError::InvalidTrackCount);
+        }
+
+        if self.ticks_per_quarter == 0 || self.ticks_per_quarter > 0x7FFF {
+            return Err(WriteMidiError::InvalidTicksPerQuarter);
+        }
+
+        let track_count = u16::try_from(self.tracks.len()).map_err(|_| WriteMidiError::InvalidTrackCount)?;
+
+        let mut bytes = b"MThd\0\0\0\x06".to_vec();
+        bytes.extend((self.format as u16).to_be_bytes());
+        bytes.extend(track_count.to_be_bytes());
+        bytes.extend(self.ticks_per_quarter.to_be_bytes());
+
+        for track in &self.tracks {
+            let data = self.write_track(track)?;
+
+            bytes.extend(b"MTrk");
+            bytes.extend((data.len() as u32).to_be_bytes());
+            bytes.extend(data);
+        }
+
+        Ok(bytes)
+    }
+
+    fn ticks(&self, time: Rational) -> Result<u32, WriteMidiError> {
+        let ticks = time * (4 * self.ticks_per_quarter as i64);
+
+        if !ticks.is_integer() || ticks.is_negative() || ticks.numerator() > MAX_TICKS as i64 {
+            return Err(WriteMidiError::InvalidTime(time));
+        }
+
+        Ok(ticks.numerator() as u32)
+    }
+
+    fn write_track(&self, track: &MidiTrack) -> Result<Vec<u8>, WriteMidiError> {
+        use WriteMidiError as E;
+
+        // the tick, the order to write it in, and the event, which is a meta event's type and data,
+        // or a channel message's status and data
+        let mut events = Vec::<(u32, Priority, u8, Vec<u8>)>::new();
+
+        if let Some(name) = &track.name {
+            events.push((0, Priority::Meta, 0x03, name.as_bytes().to_vec()));
+        }
+
+        for &(onset, microseconds) in &track.tempos {
+            if !(1..=0xFF_FFFF).contains(&microseconds) {
+                return Err(E::InvalidTempo(microseconds));
+            }
+
+            events.push((self.ticks(onset)?, Priority::Meta, 0x51, microseconds.to_be_bytes()[1..].to_vec()));
+        }
+
+        for (onset, time_signature) in &track.time_signatures {
+            if time_signature.is_irrational() {
+                return Err(E::IrrationalTimeSignature);
+            }
+
+            let exponent = time_signature.denominator().trailing_zeros() as u8;
+
+            events.push((self.ticks(*onset)?, Priority::Meta, 0x58, vec![time_signature.numerator(), exponent, 24, 8]));
+        }
+
+        for &(onset, key) in &track.key_signatures {
+            let minor = match key.mode {
+                DiatonicMode::MAJOR => 0,
+                DiatonicMode::NATURAL_MINOR => 1,
+                _ => return Err(E::InvalidKey(key)),
+            };
+
+            let sharps = i8::try_from(key.sharps())
+                .ok()
+                .filter(|sharps| (-7..=7).contains(sharps))
+                .ok_or(E::InvalidKey(key))?;
+
+            events.push((self.ticks(onset)?, Priority::Meta, 0x59, vec![sharps as u8, minor]));
+        }
+
+        for program in &track.programs {
+            if program.channel > 15 {
+                return Err(E::InvalidChannel(program.channel));
+            } else if program.program > 127 {
+                return Err(E::InvalidProgram(program.program));
+            }
+
+            events.push((self.ticks(program.onset)?, Priority::Program, 0xC0 | program.channel, vec![program.program]));
+        }
+
+        for note in &track.notes {
+            let key = note.note.as_midi_strict().ok_or(E::InvalidNote(note.note))?;
+
+            if !(1..=127).contains(&note.velocity) {
+                return Err(E::InvalidVelocity(note.velocity));
+            } else if note.channel > 15 {
+                return Err(E::InvalidChannel(note.channel));
+            }
+
+            let (on, off) = (self.ticks(note.onset)?, self.ticks(note.end())?);
+            let priority = if on == off { Priority::EmptyNoteOff } else { Priority::NoteOff };
+
+            events.push((on, Priority::NoteOn, 0x90 | note.channel, vec![key, note.velocity]));
+            events.push((off, priority, 0x80 | note.channel, vec![key, 64]));
+        }
+
+        // stable, so events of the same kind at the same time stay in order
+        events.sort_by_key(|&(ticks, priority, ..)| (ticks, priority));
+
+        let mut writer = TrackWriter::default();
+
+        for (ticks, priority, ty, data) in events {
+            match priority {
+                Priority::Meta => writer.meta(ticks, ty, &data),
+                _ => writer.channel(ticks, ty, &data),
+            }
+        }
+
+        let end = self.ticks(track.end)?.max(writer.ticks);
+        writer.meta(end, 0x2F, &[]);
+
+        Ok(writer.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pitch;
+    use crate::midi::{MidiNote, ProgramChange};
+    use crate::rhythm::Tempo;
+
+    fn sample() -> MidiFile {
+        let r = Rational::new;
+
+        let mut conductor = MidiTrack::named("Conductor");
+        conductor.push_tempo(Rational::ZERO, Tempo::quarter_notes(90.0));
+        conductor.time_signatures.push((Rational::ZERO, "6/8".parse().unwrap()));
+        conductor.key_signatures.push((Rational::ZERO, Key::minor(Pitch::E)));
+        conductor.key_signatures.push((r(3, 4), Key::major(Pitch::E_FLAT)));
+        conductor.end = r(3, 2);
+
+        let mut melody = MidiTrack::named("Melody");
+        melody.programs.push(ProgramChange { onset: Rational::ZERO, channel: 3, program: 73 });
+
+        let notes = [
+            (Pitch::F_SHARP, Rational::ZERO, r(3, 8)),
+            (Pitch::G, r(3, 8), r(3, 8)),
+            (Pitch::A_FLAT, r(3, 4), r(1, 8)),
+            (Pitch::A_FLAT, r(7, 8), r(1, 16)),
+            // a note with no length
+            (Pitch::B_FLAT, r(15, 16), Rational::ZERO),
+        ];
+
+        for (pitch, onset, length) in notes {
+            melody.push_notes(&[Note::new(pitch, 4)], onset, length, 100, 3);
+        }
+
+        // a chord with a tied note
+        melody.push_notes(&[Note::new(Pitch::E_FLAT, 4), Note::new(Pitch::G, 4)], r(1, 1), r(1, 4), 70, 3);
+        melody.push_notes(&[Note::new(Pitch::C, 5)], r(1, 1), r(1, 2), 70, 4);
+        melody.end = r(3, 2);
+
+        let mut file = MidiFile::new(MidiFormat::MultiTrack, 480);
+        file.tracks = vec![conductor, melody];
+        file
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = sample();
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(MidiFile::from_bytes(&bytes), Ok(file.clone()));
+        assert_eq!(MidiFile::from_bytes(&bytes).unwrap().to_bytes(), Ok(bytes.clone()));
+
+        // the conductor track, byte for byte
+        let conductor = [
+            b"MTrk".as_slice(), &[0, 0, 0, 46],
+            &[0x00, 0xFF, 0x03, 9], b"Conductor",
+            &[0x00, 0xFF, 0x51, 0x03, 0x0A, 0x2C, 0x2B],
+            &[0x00, 0xFF, 0x58, 0x04, 6, 3, 24, 8],
+            &[0x00, 0xFF, 0x59, 0x02, 1, 1],
+            &[0x8B, 0x20, 0xFF, 0x59, 0x02, 0xFD, 0],
+            &[0x8B, 0x20, 0xFF, 0x2F, 0x00],
+        ].concat();
+
+        assert_eq!(&bytes[14..14 + conductor.len()], conductor);
+
+        // format 0 and 2 files too
+        for format in [MidiFormat::SingleTrack, MidiFormat::MultiSequence] {
+            let mut file = file.clone();
+            file.format = format;
+            file.tracks.truncate(1);
+
+            assert_eq!(MidiFile::from_bytes(&file.to_bytes().unwrap()), Ok(file));
+        }
+    }
+
+    #[test]
+    fn invalid_files() {
+        use WriteMidiError as E;
+
+        let error = |edit: fn(&mut MidiFile)| {
+            let mut file = sample();
+            edit(&mut file);
+            file.to_bytes().unwrap_err()
+        };
+
+        fn note(file: &mut MidiFile) -> &mut MidiNote {
+            &mut file.tracks[1].notes[0]
+        }
+
+        assert_eq!(error(|file| file.format = MidiFormat::SingleTrack), E::InvalidTrackCount);
+        assert_eq!(error(|file| file.ticks_per_quarter = 0x8000), E::InvalidTicksPerQuarter);
+        assert_eq!(error(|file| file.ticks_per_quarter = 1), E::InvalidTime(Rational::new(3, 8)));
+        assert_eq!(error(|file| note(file).onset = Rational::new(-1, 4)), E::InvalidTime(Rational::new(-1, 4)));
+        assert_eq!(error(|file| note(file).note = Note::new(Pitch::A, 9)), E::InvalidNote(Note::new(Pitch::A, 9)));
+        assert_eq!(error(|file| note(file).velocity = 0), E::InvalidVelocity(0));
+        assert_eq!(error(|file| note(file).channel = 16), E::InvalidChannel(16));
+        assert_eq!(error(|file| file.tracks[1].programs[0].program = 128), E::InvalidProgram(128));
+        assert_eq!(error(|file| file.tracks[0].tempos[0].1 = 1 << 24), E::InvalidTempo(1 << 24));
+        assert_eq!(error(|file| file.tracks[0].time_signatures[0].1 = "4/3".parse().unwrap()), E::IrrationalTimeSignature);
+        assert_eq!(error(|file| file.tracks[0].key_signatures[0].1 = Key::major(Pitch::G_SHARP)), E::InvalidKey(Key::major(Pitch::G_SHARP)));
+        assert_eq!(error(|file| file.tracks[0].key_signatures[0].1.mode = DiatonicMode::Dorian), E::InvalidKey(Key::new(Pitch::E, DiatonicMode::Dorian)));
+    }
+}
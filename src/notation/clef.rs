@@ -46,6 +46,10 @@ impl PitchClef {
     pub fn anchor(self) -> OctaveLetter {
         self.anchor
     }
+
+    pub fn staff_line(self) -> u8 {
+        self.staff_line.get()
+    }
     
     pub fn get_note(self, position: StaffPosition) -> OctaveLetter {
         match position {
@@ -0,0 +1,633 @@
+//! Writing notes, keys, clefs, and chords in LilyPond's input syntax.
+//!
+//! A [`LilyPond`] writer names pitches in one of LilyPond's note name [`Language`]s, and writes
+//! notes either in absolute pitch, where `c'` is always middle C, or with [`Relative`], where
+//! each note's octave marks are relative to the note before it. Since notes keep their spelling,
+//! `F♯4` is written `fis'` and `G♭4` is written `ges'`.
+//!
+//! # Examples
+//! ```
+//! # use music_theory::{Note, Pitch};
+//! # use music_theory::harmony::Key;
+//! # use music_theory::notation::PitchClef;
+//! # use music_theory::notation::lilypond::{Language, LilyPond};
+//! let lilypond = LilyPond::new(Language::English);
+//!
+//! let notes = ["D4", "F#4", "A4", "D5"].map(|note| note.parse::<Note>().unwrap());
+//!
+//! assert_eq!(lilypond.key(Key::major(Pitch::D)).unwrap(), "\\key d \\major");
+//! assert_eq!(lilypond.clef(PitchClef::TREBLE).unwrap(), "\\clef treble");
+//! assert_eq!(lilypond.relative(notes[0], &notes).unwrap(), "\\relative d' { d fs a d }");
+//! ```
+
+use crate::{Interval, Letter, Note, Pitch};
+use crate::harmony::{DiatonicMode, Key};
+use crate::notation::PitchClef;
+#[cfg(feature = "experimental-chords")]
+use crate::chord::Chord;
+#[cfg(feature = "experimental-chords")]
+use crate::interval::Quality;
+
+/// An error returned when something can't be written in LilyPond's syntax.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum LilyPondError {
+    /// A pitch had more than two sharps or flats, which LilyPond has no name for.
+    #[error("LilyPond can't name {0}, which has more than two sharps or flats")]
+    UnsupportedAccidental(Pitch),
+    /// A clef had a sign on a line LilyPond has no clef for, like a G clef on the third line.
+    #[error("LilyPond has no clef like {0:?}")]
+    UnsupportedClef(PitchClef),
+    /// A chord had an interval that chord mode can't write, like a descending interval or
+    /// one more than a semitone away from its major, perfect, or minor seventh step.
+    #[error("LilyPond's chord mode can't write the interval {0}")]
+    UnsupportedInterval(Interval),
+}
+
+/// A language for LilyPond note names, chosen in a score with `\language`.
+///
+/// | Pitch | `Nederlands` | `English` | `Deutsch` | `Italiano` |
+/// |-------|--------------|-----------|-----------|------------|
+/// | C♯ | `cis` | `cs` | `cis` | `dod` |
+/// | E♭ | `ees` | `ef` | `es` | `mib` |
+/// | B | `b` | `b` | `h` | `si` |
+/// | B♭ | `bes` | `bf` | `b` | `sib` |
+/// | G𝄪 | `gisis` | `gss` | `gisis` | `soldd` |
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    /// Dutch names, LilyPond's default, with `is` for sharps and `es` for flats.
+    #[default]
+    Nederlands,
+    /// English names, with `s` for sharps and `f` for flats.
+    English,
+    /// German names, with `is` and `es` like Dutch, but with `h` for B and `b` for B♭.
+    Deutsch,
+    /// Italian solfège names, with `d` for sharps and `b` for flats.
+    Italiano,
+}
+
+impl Language {
+    /// Returns the name of the language, as given to `\language`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::notation::lilypond::Language;
+    /// assert_eq!(Language::Deutsch.name(), "deutsch");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Nederlands => "nederlands",
+            Self::English => "english",
+            Self::Deutsch => "deutsch",
+            Self::Italiano => "italiano",
+        }
+    }
+}
+
+/// Writes library types in LilyPond's input syntax, naming pitches in a [`Language`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LilyPond {
+    /// The language pitches are named in.
+    pub language: Language,
+}
+
+impl LilyPond {
+    /// Creates a writer that names pitches in a language.
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// Returns the `\language` command that sets the language of a score, which must come
+    /// before any notes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::notation::lilypond::{Language, LilyPond};
+    /// assert_eq!(LilyPond::new(Language::English).language_command(), "\\language \"english\"");
+    /// ```
+    pub fn language_command(&self) -> String {
+        format!("\\language \"{}\"", self.language.name())
+    }
+
+    /// Names a pitch, with no octave.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if the pitch has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::notation::lilypond::{Language, LilyPond};
+    /// assert_eq!(LilyPond::default().pitch(Pitch::F_SHARP).unwrap(), "fis");
+    /// assert_eq!(LilyPond::default().pitch(Pitch::A_FLAT).unwrap(), "aes");
+    /// assert_eq!(LilyPond::new(Language::Deutsch).pitch(Pitch::A_FLAT).unwrap(), "as");
+    /// assert_eq!(LilyPond::new(Language::Italiano).pitch(Pitch::B_DOUBLE_FLAT).unwrap(), "sibb");
+    /// ```
+    pub fn pitch(&self, pitch: Pitch) -> Result<String, LilyPondError> {
+        let letter = pitch.letter();
+        let offset = pitch.accidental().offset_semitones().0;
+
+        if offset.abs() > 2 {
+            return Err(LilyPondError::UnsupportedAccidental(pitch));
+        }
+
+        let count = offset.unsigned_abs() as usize;
+
+        let name = match self.language {
+            Language::Nederlands | Language::Deutsch => {
+                let base = if self.language == Language::Deutsch && letter == Letter::B { "h" } else { dutch_letter(letter) };
+
+                match (self.language, letter, offset) {
+                    // German contracts the flats of vowels, and calls B♭ "b"
+                    (Language::Deutsch, Letter::E | Letter::A, ..0) => format!("{}s{}", dutch_letter(letter), "es".repeat(count - 1)),
+                    (Language::Deutsch, Letter::B, -1) => "b".to_owned(),
+                    (_, _, ..0) => format!("{base}{}", "es".repeat(count)),
+                    _ => format!("{base}{}", "is".repeat(count)),
+                }
+            }
+            Language::English => format!("{}{}", dutch_letter(letter), (if offset < 0 { "f" } else { "s" }).repeat(count)),
+            Language::Italiano => {
+                let base = match letter {
+                    Letter::C => "do",
+                    Letter::D => "re",
+                    Letter::E => "mi",
+                    Letter::F => "fa",
+                    Letter::G => "sol",
+                    Letter::A => "la",
+                    Letter::B => "si",
+                };
+
+                format!("{base}{}", (if offset < 0 { "b" } else { "d" }).repeat(count))
+            }
+        };
+
+        Ok(name)
+    }
+
+    /// Writes a note in absolute pitch, where `c` is C3, and each `'` raises it by an octave
+    /// and each `,` lowers it by one.
+    ///
+    /// The octave marks come from [`Note::octave`], so B♯3 is written `bis`, not `bis'`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if the note has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let lilypond = LilyPond::default();
+    ///
+    /// assert_eq!(lilypond.note(Note::new(Pitch::C, 4)).unwrap(), "c'");
+    /// assert_eq!(lilypond.note(Note::new(Pitch::E_FLAT, 6)).unwrap(), "ees'''");
+    /// assert_eq!(lilypond.note(Note::new(Pitch::B_SHARP, 3)).unwrap(), "bis");
+    /// assert_eq!(lilypond.note(Note::new(Pitch::G, 1)).unwrap(), "g,,");
+    /// ```
+    pub fn note(&self, note: Note) -> Result<String, LilyPondError> {
+        Ok(self.pitch(note.pitch)? + &octave_marks(note.octave - 3))
+    }
+
+    /// Writes notes sounding together in absolute pitch, as `<...>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if a note has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let notes = ["E3", "G#3", "D4"].map(|note| note.parse::<Note>().unwrap());
+    ///
+    /// assert_eq!(LilyPond::default().notes(&notes).unwrap(), "<e gis d'>");
+    /// ```
+    pub fn notes(&self, notes: &[Note]) -> Result<String, LilyPondError> {
+        let notes = notes.iter()
+            .map(|&note| self.note(note))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("<{}>", notes.join(" ")))
+    }
+
+    /// Writes notes in relative pitch, as a `\relative` block starting from a reference note.
+    ///
+    /// See [`Relative`] for how octave marks are chosen, and to write chords in relative pitch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if a note has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let notes = ["C4", "G4", "C5", "B3", "F2"].map(|note| note.parse::<Note>().unwrap());
+    ///
+    /// assert_eq!(LilyPond::default().relative(notes[0], &notes).unwrap(), "\\relative c' { c g' c b, f, }");
+    /// ```
+    pub fn relative(&self, reference: Note, notes: &[Note]) -> Result<String, LilyPondError> {
+        let mut relative = Relative::new(*self, reference);
+
+        let notes = notes.iter()
+            .map(|&note| relative.note(note))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("\\relative {} {{ {} }}", self.note(reference)?, notes.join(" ")))
+    }
+
+    /// Writes a key signature as `\key` with its tonic and mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if the tonic has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::harmony::{DiatonicMode, Key};
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let lilypond = LilyPond::default();
+    ///
+    /// assert_eq!(lilypond.key(Key::minor(Pitch::C_SHARP)).unwrap(), "\\key cis \\minor");
+    /// assert_eq!(lilypond.key(Key::new(Pitch::E_FLAT, DiatonicMode::Lydian)).unwrap(), "\\key ees \\lydian");
+    /// ```
+    pub fn key(&self, key: Key) -> Result<String, LilyPondError> {
+        let mode = match key.mode {
+            DiatonicMode::Ionian => "major",
+            DiatonicMode::Dorian => "dorian",
+            DiatonicMode::Phrygian => "phrygian",
+            DiatonicMode::Lydian => "lydian",
+            DiatonicMode::Mixolydian => "mixolydian",
+            DiatonicMode::Aeolian => "minor",
+            DiatonicMode::Locrian => "locrian",
+        };
+
+        Ok(format!("\\key {} \\{mode}", self.pitch(key.tonic)?))
+    }
+
+    /// Writes a clef as `\clef`, with `_8` or `^8` if it's transposed by an octave.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedClef`] for G clefs on the third line or above,
+    /// and F clefs on the first or second line, which LilyPond has no names for.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::notation::PitchClef;
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let lilypond = LilyPond::default();
+    ///
+    /// assert_eq!(lilypond.clef(PitchClef::ALTO).unwrap(), "\\clef alto");
+    /// assert_eq!(lilypond.clef(PitchClef::TREBLE_8VB).unwrap(), "\\clef \"treble_8\"");
+    /// assert_eq!(lilypond.clef(PitchClef::BASS_8VA).unwrap(), "\\clef \"bass^8\"");
+    /// ```
+    pub fn clef(&self, clef: PitchClef) -> Result<String, LilyPondError> {
+        let anchor = clef.anchor();
+
+        let (name, octave) = match (anchor.letter, clef.staff_line()) {
+            (Letter::G, 1) => ("french", 4),
+            (Letter::G, 2) => ("treble", 4),
+            (Letter::F, 3) => ("varbaritone", 3),
+            (Letter::F, 4) => ("bass", 3),
+            (Letter::F, 5) => ("subbass", 3),
+            (Letter::C, 1) => ("soprano", 4),
+            (Letter::C, 2) => ("mezzosoprano", 4),
+            (Letter::C, 3) => ("alto", 4),
+            (Letter::C, 4) => ("tenor", 4),
+            (Letter::C, 5) => ("baritone", 4),
+            _ => return Err(LilyPondError::UnsupportedClef(clef)),
+        };
+
+        // transpositions are counted in steps, so an octave is 8
+        let clef = match anchor.octave - octave {
+            0 => return Ok(format!("\\clef {name}")),
+            octaves @ 1.. => format!("{name}^{}", octaves * 7 + 1),
+            octaves => format!("{name}_{}", -octaves * 7 + 1),
+        };
+
+        Ok(format!("\\clef \"{clef}\""))
+    }
+
+    /// Writes a chord in chord mode, like `c:m7` or `bes:7/d`.
+    ///
+    /// Common chords use their names, like `m`, `dim`, `aug`, `sus4`, `7`, or `maj7`, and other
+    /// chords list their steps, like `c:1.3.5+.7.9+`, where `+` and `-` raise or lower a step
+    /// from major or perfect, or from a minor seventh. Inverted chords are written with their
+    /// bass note after a `/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if the root or bass has more than two sharps
+    /// or flats, or [`LilyPondError::UnsupportedInterval`] if an interval is descending or more than
+    /// a semitone from its step.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::notation::lilypond::{Language, LilyPond};
+    /// let lilypond = LilyPond::default();
+    ///
+    /// assert_eq!(lilypond.chord(&"F#m7b5".parse::<Chord>().unwrap()).unwrap(), "fis:m7.5-");
+    /// assert_eq!(lilypond.chord(&"Bb7/D".parse::<Chord>().unwrap()).unwrap(), "bes:7/d");
+    /// assert_eq!(lilypond.chord(&"C7#9".parse::<Chord>().unwrap()).unwrap(), "c:1.3.5.7.9+");
+    /// assert_eq!(LilyPond::new(Language::English).chord(&"Eb".parse::<Chord>().unwrap()).unwrap(), "ef");
+    /// ```
+    #[cfg(feature = "experimental-chords")]
+    pub fn chord(&self, chord: &Chord) -> Result<String, LilyPondError> {
+        let mut has_root = false;
+        let mut steps = Vec::new();
+
+        for &interval in chord.intervals() {
+            let number = interval.number();
+
+            if !number.is_ascending() {
+                return Err(LilyPondError::UnsupportedInterval(interval));
+            }
+
+            // in chord mode, sevenths are minor unless they're raised
+            let default = if (number.get() - 1) % 7 == 6 {
+                Interval::new(Quality::Minor, number).expect("sevenths can be minor")
+            } else {
+                Interval::new_maj_or_perfect(number)
+            };
+
+            let alteration = match interval.semitones().0 - default.semitones().0 {
+                -1 => "-",
+                0 => "",
+                1 => "+",
+                _ => return Err(LilyPondError::UnsupportedInterval(interval)),
+            };
+
+            if number.get() == 1 && alteration.is_empty() {
+                has_root = true;
+            } else {
+                steps.push(format!("{}{alteration}", number.get()));
+            }
+        }
+
+        let steps = steps.join(".");
+
+        let modifiers = match steps.as_str() {
+            _ if !has_root => format!(":1.{steps}^1"),
+            "3.5" => String::new(),
+            "3-.5" => ":m".to_owned(),
+            "3-.5-" => ":dim".to_owned(),
+            "3.5+" => ":aug".to_owned(),
+            "2.5" => ":sus2".to_owned(),
+            "4.5" => ":sus4".to_owned(),
+            "3.5.6" => ":6".to_owned(),
+            "3-.5.6" => ":m6".to_owned(),
+            "3.5.7" => ":7".to_owned(),
+            "3.5.7+" => ":maj7".to_owned(),
+            "3-.5.7" => ":m7".to_owned(),
+            "3-.5-.7" => ":m7.5-".to_owned(),
+            "3-.5-.7-" => ":dim7".to_owned(),
+            "3.5.7.9" => ":9".to_owned(),
+            "3.5.7+.9" => ":maj9".to_owned(),
+            "3-.5.7.9" => ":m9".to_owned(),
+            "" => ":1".to_owned(),
+            steps => format!(":1.{steps}"),
+        };
+
+        let mut symbol = self.pitch(chord.root)? + &modifiers;
+
+        if chord.inversion() != 0 {
+            symbol.push('/');
+            symbol += &self.pitch(chord.pitches()[0])?;
+        }
+
+        Ok(symbol)
+    }
+
+    /// Writes a chord as notes sounding together, as `<...>`, in close position upward from
+    /// its bass note in an octave.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if a note has more than two sharps or flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::chord::Chord;
+    /// # use music_theory::notation::lilypond::LilyPond;
+    /// let chord = "Cmaj7/E".parse::<Chord>().unwrap();
+    ///
+    /// assert_eq!(LilyPond::default().chord_notes(&chord, 3).unwrap(), "<e g b c'>");
+    /// ```
+    #[cfg(feature = "experimental-chords")]
+    pub fn chord_notes(&self, chord: &Chord, octave: i16) -> Result<String, LilyPondError> {
+        let mut notes = Vec::<Note>::new();
+
+        for pitch in chord.pitches() {
+            let mut note = Note::new(pitch, octave);
+
+            if let Some(&last) = notes.last() {
+                while staff_position(note) <= staff_position(last) {
+                    note.octave += 1;
+                }
+            }
+
+            notes.push(note);
+        }
+
+        self.notes(&notes)
+    }
+}
+
+/// Writes notes in relative pitch, keeping track of the note each one is relative to.
+///
+/// Without octave marks, a note is placed as close as possible to the previous note, counting by
+/// letter and ignoring accidentals, so it's at most a fourth away. Each `'` or `,` moves it up or
+/// down by an octave from there. In a chord, each note is relative to the note before it, and the
+/// next note or chord after it is relative to its first note.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Note;
+/// # use music_theory::notation::lilypond::{LilyPond, Relative};
+/// let note = |s: &str| s.parse::<Note>().unwrap();
+///
+/// let mut relative = Relative::new(LilyPond::default(), note("C4"));
+///
+/// assert_eq!(relative.chord(&[note("E4"), note("G4"), note("C5")]).unwrap(), "<e g c>");
+/// assert_eq!(relative.note(note("F#3")).unwrap(), "fis,");
+/// assert_eq!(relative.note(note("Bb3")).unwrap(), "bes");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Relative {
+    lilypond: LilyPond,
+    previous: Note,
+}
+
+impl Relative {
+    /// Starts writing notes relative to a reference note, the note given to `\relative`.
+    pub fn new(lilypond: LilyPond, reference: Note) -> Self {
+        Self { lilypond, previous: reference }
+    }
+
+    /// Returns the note the next note will be relative to.
+    pub fn previous(&self) -> Note {
+        self.previous
+    }
+
+    /// Writes a note relative to the previous note.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if the note has more than two sharps or flats.
+    pub fn note(&mut self, note: Note) -> Result<String, LilyPondError> {
+        let written = self.lilypond.pitch(note.pitch)?;
+
+        let previous = staff_position(self.previous);
+        let position = staff_position(note);
+
+        // the closest position with the same letter, at most three steps away
+        let closest = previous + (position - previous + 3).rem_euclid(7) - 3;
+
+        self.previous = note;
+
+        Ok(written + &octave_marks((position - closest) / 7))
+    }
+
+    /// Writes notes sounding together as `<...>`, each relative to the note before it,
+    /// and makes the first note the one the next note is relative to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LilyPondError::UnsupportedAccidental`] if a note has more than two sharps or flats.
+    pub fn chord(&mut self, notes: &[Note]) -> Result<String, LilyPondError> {
+        let previous = self.previous;
+
+        let written = notes.iter()
+            .map(|&note| self.note(note))
+            .collect::<Result<Vec<_>, _>>()
+            .inspect_err(|_| self.previous = previous)?;
+
+        self.previous = notes.first().copied().unwrap_or(previous);
+
+        Ok(format!("<{}>", written.join(" ")))
+    }
+}
+
+// the lowercase letter, which is the natural note's name in Dutch and English
+fn dutch_letter(letter: Letter) -> &'static str {
+    match letter {
+        Letter::C => "c",
+        Letter::D => "d",
+        Letter::E => "e",
+        Letter::F => "f",
+        Letter::G => "g",
+        Letter::A => "a",
+        Letter::B => "b",
+    }
+}
+
+fn octave_marks(octaves: i16) -> String {
+    if octaves < 0 {
+        ",".repeat(octaves.unsigned_abs() as usize)
+    } else {
+        "'".repeat(octaves as usize)
+    }
+}
+
+// how many steps a note is above C0, counting by letter
+fn staff_position(note: Note) -> i16 {
+    note.octave * 7 + note.pitch.letter().step() as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn pitch_names() {
+        let pitches = [Pitch::C, Pitch::C_SHARP, Pitch::E_FLAT, Pitch::A_DOUBLE_FLAT, Pitch::B, Pitch::B_FLAT, Pitch::B_DOUBLE_FLAT, Pitch::F_DOUBLE_SHARP];
+
+        let names = |language| pitches.map(|pitch| LilyPond::new(language).pitch(pitch).unwrap());
+
+        assert_eq!(names(Language::Nederlands), ["c", "cis", "ees", "aeses", "b", "bes", "beses", "fisis"]);
+        assert_eq!(names(Language::English), ["c", "cs", "ef", "aff", "b", "bf", "bff", "fss"]);
+        assert_eq!(names(Language::Deutsch), ["c", "cis", "es", "ases", "h", "b", "heses", "fisis"]);
+        assert_eq!(names(Language::Italiano), ["do", "dod", "mib", "labb", "si", "sib", "sibb", "fadd"]);
+
+        let triple_sharp = note("G###4");
+
+        assert_eq!(LilyPond::default().note(triple_sharp), Err(LilyPondError::UnsupportedAccidental(triple_sharp.pitch)));
+        assert_eq!(LilyPond::default().notes(&[]), Ok("<>".to_owned()));
+    }
+
+    #[test]
+    fn relative_octaves() {
+        let mut relative = Relative::new(LilyPond::default(), note("F3"));
+
+        // a fourth away by letter is the closest, even when it's augmented or diminished, and a fifth isn't
+        let written = ["B3", "Bb3", "F4", "Cb4", "E#3", "B#3", "C3", "C6", "Gb2"]
+            .map(|s| relative.note(note(s)).unwrap());
+
+        assert_eq!(written, ["b", "bes", "f'", "ces", "eis,", "bis'", "c,", "c'''", "ges,,,"]);
+
+        relative = Relative::new(LilyPond::default(), note("C4"));
+
+        assert_eq!(relative.chord(&[note("G3"), note("E4"), note("C5")]).unwrap(), "<g e' c'>");
+        assert_eq!(relative.previous(), note("G3"));
+        assert_eq!(relative.chord(&[]).unwrap(), "<>");
+        assert_eq!(relative.note(note("D4")).unwrap(), "d'");
+
+        let invalid = note("Dbbb4");
+
+        assert_eq!(relative.chord(&[note("A4"), invalid]), Err(LilyPondError::UnsupportedAccidental(invalid.pitch)));
+        assert_eq!(relative.previous(), note("D4"));
+    }
+
+    #[test]
+    fn clefs() {
+        let lilypond = LilyPond::default();
+
+        assert_eq!(lilypond.clef(PitchClef::FRENCH_VIOLIN).unwrap(), "\\clef french");
+        assert_eq!(lilypond.clef(PitchClef::SUB_BASS).unwrap(), "\\clef subbass");
+        assert_eq!(lilypond.clef(PitchClef::C_BARITONE).unwrap(), "\\clef baritone");
+        assert_eq!(lilypond.clef(PitchClef::TREBLE_8VA).unwrap(), "\\clef \"treble^8\"");
+
+        let double_octave = PitchClef::new(crate::notation::OctaveLetter::new(Letter::F, 1), 4).unwrap();
+        assert_eq!(lilypond.clef(double_octave).unwrap(), "\\clef \"bass_15\"");
+
+        let g_on_third = PitchClef::new(crate::notation::OctaveLetter::new(Letter::G, 4), 3).unwrap();
+        assert_eq!(lilypond.clef(g_on_third), Err(LilyPondError::UnsupportedClef(g_on_third)));
+    }
+
+    #[cfg(feature = "experimental-chords")]
+    #[test]
+    fn chord_mode() {
+        let lilypond = LilyPond::default();
+        let chord = |s: &str| lilypond.chord(&s.parse().unwrap()).unwrap();
+
+        assert_eq!(chord("C"), "c");
+        assert_eq!(chord("Dbm"), "des:m");
+        assert_eq!(chord("Bdim"), "b:dim");
+        assert_eq!(chord("F#aug"), "fis:aug");
+        assert_eq!(chord("Gsus4"), "g:sus4");
+        assert_eq!(chord("Ebmaj7"), "ees:maj7");
+        assert_eq!(chord("Bbdim7"), "bes:dim7");
+        assert_eq!(chord("Am9"), "a:m9");
+        assert_eq!(chord("C5"), "c:1.5");
+        assert_eq!(chord("Cm(maj7)"), "c:1.3-.5.7+");
+        assert_eq!(chord("C7/G"), "c:7/g");
+        assert_eq!(chord("Am/G"), "a:m7/g");
+        assert_eq!(chord("Am/B"), "a:1.2.3-.5/b");
+
+        let deutsch = LilyPond::new(Language::Deutsch);
+        assert_eq!(deutsch.chord(&"Bbmaj7/A".parse().unwrap()).unwrap(), "b:maj7/a");
+
+        let c = "C".parse::<Chord>().unwrap();
+        assert_eq!(lilypond.chord_notes(&c, 4).unwrap(), "<c' e' g'>");
+        assert_eq!(lilypond.chord_notes(&"G7/F".parse().unwrap(), 2).unwrap(), "<f, g, b, d>");
+    }
+}
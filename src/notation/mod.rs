@@ -5,4 +5,6 @@ mod stem_direction;
 pub use stem_direction::*;
 
 mod octave_letter;
-pub use octave_letter::*;
\ No newline at end of file
+pub use octave_letter::*;
+pub mod lilypond;
+pub mod musicxml;
@@ -0,0 +1,42 @@
+//! Reading and writing scores as MusicXML.
+//!
+//! A [`Score`] is made of [`Part`]s, each a list of [`Measure`](crate::rhythm::Measure)s with
+//! key signatures and [clefs](crate::notation::PitchClef). Since notes keep their spelling,
+//! they're written exactly as spelled, and keys are written with their mode.
+//!
+//! Scores are read from partwise or timewise documents with [`Score::from_musicxml`], and from
+//! compressed `.mxl` files with [`Score::from_mxl`].
+//!
+//! # Examples
+//! ```
+//! # use music_theory::Pitch;
+//! # use music_theory::harmony::Key;
+//! # use music_theory::notation::PitchClef;
+//! # use music_theory::notation::musicxml::{Part, Score};
+//! # use music_theory::rhythm::{Measure, Rational, TimeSignature, TimedNote};
+//! let notes = ["C4", "E4", "G4", "C5"]
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, note)| TimedNote::note(note.parse().unwrap(), Rational::new(i as _, 4), "4".parse().unwrap()))
+//!     .collect::<Vec<_>>();
+//!
+//! let measures = Measure::from_notes(&notes, "3/4".parse().unwrap(), None).unwrap();
+//! let part = Part::new("Piano", Key::major(Pitch::C), PitchClef::TREBLE, measures);
+//!
+//! let score = Score { title: Some("Arpeggio".to_owned()), parts: vec![part] };
+//! let xml = score.to_musicxml().unwrap();
+//!
+//! assert!(xml.contains("<measure number=\"2\">"));
+//! ```
+
+mod xml;
+mod mxl;
+
+mod score;
+pub use score::*;
+
+mod read;
+pub use read::*;
+
+mod write;
+pub use write::*;
@@ -0,0 +1,375 @@
+use crate::notation::musicxml::xml;
+
+// reads the score out of a compressed MusicXML file, a ZIP archive whose
+// META-INF/container.xml names the score file
+pub(crate) fn extract_score(bytes: &[u8]) -> Result<String, &'static str> {
+    let files = central_directory(bytes)?;
+
+    let path = match files.iter().find(|file| file.name == "META-INF/container.xml") {
+        Some(container) => {
+            let container = String::from_utf8(read_file(bytes, container)?)
+                .map_err(|_| "the container isn't UTF-8")?;
+
+            let root = xml::parse(&container).map_err(|_| "the container isn't valid XML")?;
+
+            root.child("rootfiles")
+                .and_then(|rootfiles| rootfiles.child("rootfile"))
+                .and_then(|rootfile| rootfile.attribute("full-path"))
+                .ok_or("the container doesn't name a score")?
+                .to_owned()
+        }
+        // older files may not have a container, so the score is the only file at the top level
+        None => files.iter()
+            .find(|file| !file.name.contains('/') && (file.name.ends_with(".xml") || file.name.ends_with(".musicxml")))
+            .ok_or("the archive has no container")?
+            .name
+            .clone(),
+    };
+
+    let file = files.iter()
+        .find(|file| file.name == path)
+        .ok_or("the score named in the container isn't in the archive")?;
+
+    String::from_utf8(read_file(bytes, file)?).map_err(|_| "the score isn't UTF-8")
+}
+
+struct ZipFile {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    header_offset: usize,
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, &'static str> {
+    bytes.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or("the archive is truncated")
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, &'static str> {
+    bytes.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or("the archive is truncated")
+}
+
+// lists the files of the archive from its central directory
+fn central_directory(bytes: &[u8]) -> Result<Vec<ZipFile>, &'static str> {
+    const END_SIGNATURE: u32 = 0x0605_4b50;
+    const ENTRY_SIGNATURE: u32 = 0x0201_4b50;
+
+    // the end of central directory record is at least 22 bytes, followed by a comment of up to 65535
+    let end = (0..=bytes.len().saturating_sub(22))
+        .rev()
+        .take(22 + u16::MAX as usize)
+        .find(|&offset| u32_at(bytes, offset) == Ok(END_SIGNATURE))
+        .ok_or("not a ZIP archive")?;
+
+    let count = u16_at(bytes, end + 10)?;
+    let mut offset = u32_at(bytes, end + 16)? as usize;
+    let mut files = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        if u32_at(bytes, offset)? != ENTRY_SIGNATURE {
+            return Err("invalid central directory");
+        }
+
+        let name_length = u16_at(bytes, offset + 28)? as usize;
+        let extra_length = u16_at(bytes, offset + 30)? as usize;
+        let comment_length = u16_at(bytes, offset + 32)? as usize;
+
+        let name = bytes.get(offset + 46..offset + 46 + name_length).ok_or("the archive is truncated")?;
+
+        files.push(ZipFile {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method: u16_at(bytes, offset + 10)?,
+            compressed_size: u32_at(bytes, offset + 20)? as usize,
+            size: u32_at(bytes, offset + 24)? as usize,
+            header_offset: u32_at(bytes, offset + 42)? as usize,
+        });
+
+        offset += 46 + name_length + extra_length + comment_length;
+    }
+
+    Ok(files)
+}
+
+fn read_file(bytes: &[u8], file: &ZipFile) -> Result<Vec<u8>, &'static str> {
+    const HEADER_SIGNATURE: u32 = 0x0403_4b50;
+
+    if u32_at(bytes, file.header_offset)? != HEADER_SIGNATURE {
+        return Err("invalid local file header");
+    }
+
+    let name_length = u16_at(bytes, file.header_offset + 26)? as usize;
+    let extra_length = u16_at(bytes, file.header_offset + 28)? as usize;
+    let start = file.header_offset + 30 + name_length + extra_length;
+
+    let data = bytes.get(start..start + file.compressed_size).ok_or("the archive is truncated")?;
+
+    let contents = match file.method {
+        0 => data.to_vec(),
+        8 => inflate(data, file.size)?,
+        _ => return Err("unsupported compression method"),
+    };
+
+    if contents.len() != file.size {
+        return Err("a file has the wrong size");
+    }
+
+    Ok(contents)
+}
+
+// reads bits from the least significant end of each byte, as DEFLATE stores them
+struct Bits<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Bits<'_> {
+    fn bit(&mut self) -> Result<u32, &'static str> {
+        let byte = self.bytes.get(self.position / 8).ok_or("the compressed data is truncated")?;
+        let bit = (byte >> (self.position % 8)) & 1;
+
+        self.position += 1;
+        Ok(bit as u32)
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32, &'static str> {
+        (0..count).try_fold(0, |bits, i| Ok(bits | self.bit()? << i))
+    }
+
+    // skips to the next byte boundary
+    fn align(&mut self) {
+        self.position = self.position.next_multiple_of(8);
+    }
+}
+
+// a canonical Huffman code, as how many codes have each length and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0; 16];
+
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+
+        counts[0] = 0;
+
+        let mut offsets = [0; 16];
+
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols = vec![0; counts.iter().sum::<u16>() as usize];
+
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16, &'static str> {
+        // the first code of the current length, and the index of its symbol
+        let (mut code, mut first, mut index) = (0, 0, 0);
+
+        for &count in &self.counts[1..] {
+            code |= bits.bit()? as i32;
+
+            if code - (count as i32) < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count as i32;
+            first = (first + count as i32) << 1;
+            code <<= 1;
+        }
+
+        Err("invalid Huffman code")
+    }
+}
+
+const LENGTH_BASES: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA_BITS: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASES: [u16; 30] = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+// the order code length code lengths are stored in, in dynamic blocks
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+// decompresses raw DEFLATE data, as stored in ZIP archives, failing as soon as it's longer than `size`
+pub(crate) fn inflate(bytes: &[u8], size: usize) -> Result<Vec<u8>, &'static str> {
+    let mut bits = Bits { bytes, position: 0 };
+    let mut out = Vec::new();
+
+    loop {
+        let last = bits.bit()? == 1;
+
+        match bits.bits(2)? {
+            0 => {
+                bits.align();
+
+                let start = bits.position / 8;
+                let header = bytes.get(start..start + 4).ok_or("the compressed data is truncated")?;
+                let length = u16::from_le_bytes([header[0], header[1]]);
+
+                if length != !u16::from_le_bytes([header[2], header[3]]) {
+                    return Err("invalid stored block length");
+                }
+
+                let data = bytes.get(start + 4..start + 4 + length as usize).ok_or("the compressed data is truncated")?;
+
+                if out.len() + data.len() > size {
+                    return Err("a file has the wrong size");
+                }
+
+                out.extend_from_slice(data);
+                bits.position = (start + 4 + length as usize) * 8;
+            }
+            1 => {
+                let mut lengths = [0; 288 + 30];
+
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..288].fill(8);
+                lengths[288..].fill(5);
+
+                inflate_block(&mut bits, &mut out, size, &Huffman::new(&lengths[..288]), &Huffman::new(&lengths[288..]))?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut bits)?;
+                inflate_block(&mut bits, &mut out, size, &literals, &distances)?;
+            }
+            _ => return Err("invalid block type"),
+        }
+
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+// reads the literal/length and distance codes at the start of a dynamic block
+fn dynamic_codes(bits: &mut Bits) -> Result<(Huffman, Huffman), &'static str> {
+    let literal_count = bits.bits(5)? as usize + 257;
+    let distance_count = bits.bits(5)? as usize + 1;
+    let code_length_count = bits.bits(4)? as usize + 4;
+
+    if literal_count > 286 || distance_count > 30 {
+        return Err("too many codes in a dynamic block");
+    }
+
+    let mut code_lengths = [0; 19];
+
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[index] = bits.bits(3)? as u8;
+    }
+
+    let code_lengths = Huffman::new(&code_lengths);
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_lengths.decode(bits)?;
+
+        let (length, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or("a repeated code length has nothing to repeat")?, 3 + bits.bits(2)?),
+            17 => (0, 3 + bits.bits(3)?),
+            _ => (0, 11 + bits.bits(7)?),
+        };
+
+        if lengths.len() + repeat as usize > literal_count + distance_count {
+            return Err("too many code lengths in a dynamic block");
+        }
+
+        lengths.extend(std::iter::repeat_n(length, repeat as usize));
+    }
+
+    if lengths[256] == 0 {
+        return Err("a dynamic block has no end code");
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+// decodes the symbols of a compressed block until its end code, failing as soon as the output
+// would be longer than `size`
+fn inflate_block(bits: &mut Bits, out: &mut Vec<u8>, size: usize, literals: &Huffman, distances: &Huffman) -> Result<(), &'static str> {
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+
+        if symbol != 256 && out.len() == size {
+            return Err("a file has the wrong size");
+        }
+
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        } else if symbol == 256 {
+            return Ok(());
+        }
+
+        let symbol = symbol - 257;
+
+        if symbol >= LENGTH_BASES.len() {
+            return Err("invalid length code");
+        }
+
+        let length = LENGTH_BASES[symbol] as usize + bits.bits(LENGTH_EXTRA_BITS[symbol] as u32)? as usize;
+
+        let symbol = distances.decode(bits)? as usize;
+
+        if symbol >= DISTANCE_BASES.len() {
+            return Err("invalid distance code");
+        }
+
+        let distance = DISTANCE_BASES[symbol] as usize + bits.bits(DISTANCE_EXTRA_BITS[symbol] as u32)? as usize;
+
+        if distance > out.len() {
+            return Err("a distance is too far back");
+        }
+
+        if out.len() + length > size {
+            return Err("a file has the wrong size");
+        }
+
+        // copied a byte at a time, since the copy can overlap what it writes
+        for _ in 0..length {
+            out.push(out[out.len() - distance]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflate_blocks() {
+        // a stored block, then a fixed block with a back reference
+        let stored = [0x00, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+        assert_eq!(inflate(&[&stored[..], &[0x03, 0x00]].concat(), 3), Ok(b"abc".to_vec()));
+        assert_eq!(inflate(&stored, 2), Err("a file has the wrong size"));
+
+        let fixed = [0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00];
+        assert_eq!(inflate(&fixed, 12), Ok(b"abcabcabcabc".to_vec()));
+
+        // stops at the literals, and at the back reference
+        assert_eq!(inflate(&fixed, 2), Err("a file has the wrong size"));
+        assert_eq!(inflate(&fixed, 11), Err("a file has the wrong size"));
+
+        assert_eq!(inflate(&[0x07], 0), Err("invalid block type"));
+        assert_eq!(inflate(&fixed[..3], 12), Err("the compressed data is truncated"));
+    }
+}
@@ -0,0 +1,846 @@
+use crate::{AccidentalSign, Letter, Note, Pitch, Semitones};
+use crate::harmony::{DiatonicMode, Key};
+use crate::notation::{OctaveLetter, PitchClef};
+use crate::notation::musicxml::{Part, Score, mxl, note_type};
+use crate::notation::musicxml::xml::{self, Element};
+use crate::rhythm::{Duration, Measure, NoteValue, Rational, TimeSignature, TimedNote, Tuplet};
+#[cfg(feature = "experimental-chords")]
+use crate::chord::Chord;
+#[cfg(feature = "experimental-chords")]
+use crate::notation::musicxml::{DegreeType, HARMONY_KINDS, harmony_symbol};
+
+/// An error returned when a [`Score`] can't be read from MusicXML.
+#[derive(Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseMusicXmlError {
+    /// The document wasn't well-formed XML.
+    #[error("Invalid XML at byte {offset}: {reason}")]
+    Xml {
+        /// The byte offset of the error in the document.
+        offset: usize,
+        /// What was wrong.
+        reason: &'static str,
+    },
+    /// A compressed file couldn't be unpacked.
+    #[error("Invalid compressed MusicXML file: {0}")]
+    Archive(&'static str),
+    /// The root element wasn't `<score-partwise>` or `<score-timewise>`.
+    #[error("Expected a partwise or timewise score, found <{0}>")]
+    InvalidRoot(String),
+    /// A required element was missing.
+    #[error("Missing <{0}> element")]
+    MissingElement(&'static str),
+    /// An element or attribute had a value that couldn't be read.
+    #[error("Invalid value {value:?} in <{element}>")]
+    InvalidValue {
+        /// The name of the element.
+        element: &'static str,
+        /// The value that couldn't be read.
+        value: String,
+    },
+    /// The score used something that can't be represented, like unpitched notes or tablature.
+    #[error("Unsupported MusicXML: {0}")]
+    Unsupported(String),
+}
+
+impl Score {
+    /// Reads a score from a MusicXML document, either partwise or timewise.
+    ///
+    /// Each `<pitch>` is read as a spelled [`Note`], and notes of a `<chord>` are joined into one
+    /// [`TimedNote`]. Onsets and lengths come from `<duration>`, `<backup>`, and `<forward>`, and
+    /// each note's [`Duration`] from its `<type>`, dots, and time modification, or from its length
+    /// if it has no type. Notes from every voice are merged in order of onset without their voice
+    /// numbers, and grace notes, which take no time, and cue notes, which aren't played, are skipped.
+    ///
+    /// Keys are read with their mode, treating a mode of `none` as major, and clefs with their octave
+    /// change. Only the first key and clef of parts with several staves are kept. An implicit measure
+    /// that's shorter than its time signature is read as a pickup. With the `experimental-chords`
+    /// feature, `<harmony>` chord symbols are read into each part's `harmonies`.
+    ///
+    /// Everything else, like dynamics, lyrics, and layout, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document isn't well-formed, a required element is missing or has an
+    /// invalid value, or the score uses something that can't be represented, like unpitched
+    /// notes, percussion or tablature clefs, or microtones, see [`ParseMusicXmlError`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::notation::musicxml::Score;
+    /// # use music_theory::rhythm::Rational;
+    /// let xml = r#"
+    ///     <score-partwise version="4.0">
+    ///       <part-list>
+    ///         <score-part id="P1"><part-name>Oboe</part-name></score-part>
+    ///       </part-list>
+    ///       <part id="P1">
+    ///         <measure number="1">
+    ///           <attributes>
+    ///             <divisions>2</divisions>
+    ///             <key><fifths>-3</fifths><mode>minor</mode></key>
+    ///             <time><beats>2</beats><beat-type>4</beat-type></time>
+    ///           </attributes>
+    ///           <note>
+    ///             <pitch><step>E</step><alter>-1</alter><octave>5</octave></pitch>
+    ///             <duration>3</duration>
+    ///             <type>quarter</type>
+    ///             <dot/>
+    ///           </note>
+    ///           <note>
+    ///             <rest/>
+    ///             <duration>1</duration>
+    ///           </note>
+    ///         </measure>
+    ///       </part>
+    ///     </score-partwise>
+    /// "#;
+    ///
+    /// let score = Score::from_musicxml(xml).unwrap();
+    /// let part = &score.parts[0];
+    ///
+    /// assert_eq!(part.name, "Oboe");
+    /// assert_eq!(part.keys[0].1.tonic, Pitch::C);
+    ///
+    /// let notes = &part.measures[0].notes;
+    ///
+    /// assert_eq!(notes[0].notes, [Note::new(Pitch::E_FLAT, 5)]);
+    /// assert_eq!(notes[0].duration, "4.".parse().unwrap());
+    /// assert!(notes[1].is_rest());
+    /// assert_eq!(notes[1].onset, Rational::new(3, 8));
+    /// ```
+    pub fn from_musicxml(xml: &str) -> Result<Self, ParseMusicXmlError> {
+        let root = xml::parse(xml).map_err(|error| ParseMusicXmlError::Xml { offset: error.offset, reason: error.reason })?;
+
+        let title = root.child("work")
+            .and_then(|work| work.child("work-title"))
+            .or_else(|| root.child("movement-title"))
+            .map(Element::text);
+
+        let part_list = root.child("part-list").ok_or(ParseMusicXmlError::MissingElement("part-list"))?;
+
+        // each part's id and name, and its measures as the <measure> element and the element with its
+        // content, which is the same element in partwise scores and a <part> in timewise scores
+        let mut parts = part_list.children("score-part")
+            .map(|part| {
+                let id = part.attribute("id").ok_or(ParseMusicXmlError::InvalidValue { element: "score-part", value: String::new() })?;
+                let name = part.child("part-name").map(Element::text).unwrap_or_default();
+
+                Ok((id, name, Vec::new()))
+            })
+            .collect::<Result<Vec<PartMeasures>, ParseMusicXmlError>>()?;
+
+        match root.name.as_str() {
+            "score-partwise" => {
+                for part in root.children("part") {
+                    measures_of(&mut parts, part.attribute("id"))?.extend(part.children("measure").map(|measure| (measure, measure)));
+                }
+            }
+            "score-timewise" => {
+                for measure in root.children("measure") {
+                    for part in measure.children("part") {
+                        measures_of(&mut parts, part.attribute("id"))?.push((measure, part));
+                    }
+                }
+            }
+            name => return Err(ParseMusicXmlError::InvalidRoot(name.to_owned())),
+        }
+
+        let parts = parts.into_iter()
+            .map(|(_, name, measures)| read_part(name, &measures))
+            .collect::<Result<_, ParseMusicXmlError>>()?;
+
+        Ok(Self { title, parts })
+    }
+
+    /// Reads a score from a compressed MusicXML file, usually with the extension `.mxl`.
+    ///
+    /// The file is a ZIP archive, whose `META-INF/container.xml` names the score inside it,
+    /// which is read with [`Self::from_musicxml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseMusicXmlError::Archive`] if the archive can't be unpacked or the score isn't UTF-8,
+    /// or any error from reading the score.
+    pub fn from_mxl(bytes: &[u8]) -> Result<Self, ParseMusicXmlError> {
+        let xml = mxl::extract_score(bytes).map_err(ParseMusicXmlError::Archive)?;
+
+        Self::from_musicxml(&xml)
+    }
+}
+
+// a part's id, name, and measures, while they're gathered from the document
+type PartMeasures<'a> = (&'a str, String, Vec<(&'a Element, &'a Element)>);
+
+fn measures_of<'a, 'b>(parts: &'b mut [PartMeasures<'a>], id: Option<&str>) -> Result<&'b mut Vec<(&'a Element, &'a Element)>, ParseMusicXmlError> {
+    parts.iter_mut()
+        .find(|(part, _, _)| Some(*part) == id)
+        .map(|(_, _, measures)| measures)
+        .ok_or_else(|| ParseMusicXmlError::InvalidValue { element: "part", value: id.unwrap_or_default().to_owned() })
+}
+
+// the text of a child element parsed as a number
+fn number<T: std::str::FromStr>(parent: &Element, name: &'static str) -> Result<Option<T>, ParseMusicXmlError> {
+    parent.child(name)
+        .map(|element| {
+            let text = element.text();
+            text.parse().map_err(|_| ParseMusicXmlError::InvalidValue { element: name, value: text })
+        })
+        .transpose()
+}
+
+// limits on values that are valid MusicXML, but would overflow the numbers they're stored in,
+// or split a note into an unreasonable number of tied notes
+const MAX_DIVISIONS: i64 = i64::MAX / 4;
+const MAX_ALTER: f64 = 12.0;
+const MAX_TIED_LENGTH: Rational = Rational::from_integer(1024);
+
+// converts a number of divisions to whole notes
+fn length(divisions: Option<i64>, duration: i64) -> Result<Rational, ParseMusicXmlError> {
+    let divisions = divisions.ok_or(ParseMusicXmlError::MissingElement("divisions"))?;
+
+    Ok(Rational::new(duration, 4 * divisions))
+}
+
+// the <duration> of a note, backup, or forward, in divisions
+fn read_duration_divisions(parent: &Element) -> Result<i64, ParseMusicXmlError> {
+    let duration = number::<i64>(parent, "duration")?.ok_or(ParseMusicXmlError::MissingElement("duration"))?;
+
+    if duration < 0 {
+        return Err(ParseMusicXmlError::InvalidValue { element: "duration", value: duration.to_string() });
+    }
+
+    Ok(duration)
+}
+
+// moves a position in a measure, or fails if it can't be stored
+fn advance(position: Rational, length: Rational, element: &'static str, value: i64) -> Result<Rational, ParseMusicXmlError> {
+    position.checked_add(length).ok_or_else(|| ParseMusicXmlError::InvalidValue { element, value: value.to_string() })
+}
+
+fn read_part(name: String, measures: &[(&Element, &Element)]) -> Result<Part, ParseMusicXmlError> {
+    let mut part = Part {
+        name,
+        measures: Vec::new(),
+        keys: Vec::new(),
+        clefs: Vec::new(),
+        #[cfg(feature = "experimental-chords")]
+        harmonies: Vec::new(),
+    };
+
+    let mut divisions = None;
+    let mut time_signature = TimeSignature::COMMON;
+
+    for (i, &(measure, content)) in measures.iter().enumerate() {
+        let mut notes = Vec::<TimedNote>::new();
+        // the notes added for the last note, which later notes of its chord are added to
+        let mut last = 0..0;
+        let mut position = Rational::ZERO;
+        let mut end = Rational::ZERO;
+
+        for element in content.elements() {
+            match element.name.as_str() {
+                "attributes" => {
+                    if let Some(value) = number::<i64>(element, "divisions")? {
+                        if !(1..=MAX_DIVISIONS).contains(&value) {
+                            return Err(ParseMusicXmlError::InvalidValue { element: "divisions", value: value.to_string() });
+                        }
+
+                        divisions = Some(value);
+                    }
+
+                    if let Some(key) = element.children("key").find(|key| is_first_staff(key)) {
+                        set_change(&mut part.keys, i, read_key(key)?);
+                    }
+
+                    if let Some(time) = element.child("time") {
+                        time_signature = read_time(time)?;
+                    }
+
+                    if let Some(clef) = element.children("clef").find(|clef| is_first_staff(clef)) {
+                        set_change(&mut part.clefs, i, read_clef(clef)?);
+                    }
+                }
+                "note" => {
+                    if element.child("grace").is_some() || element.child("cue").is_some() {
+                        continue;
+                    }
+
+                    let pitch = read_pitch(element)?;
+                    let duration = read_duration_divisions(element)?;
+                    let tied = element.children("tie").any(|tie| tie.attribute("type") == Some("start"));
+
+                    if element.child("chord").is_some() && !last.is_empty() {
+                        for note in &mut notes[last.clone()] {
+                            note.notes.extend(pitch);
+                            note.tied |= tied && pitch.is_some();
+                        }
+
+                        continue;
+                    }
+
+                    let length = length(divisions, duration)?;
+                    let start = notes.len();
+
+                    notes.extend(read_timed(element, pitch.into_iter().collect(), position, duration, length)?);
+
+                    if tied && pitch.is_some() {
+                        // a note split into tied notes is already tied within itself
+                        notes.last_mut().expect("a note was just added").tied = true;
+                    }
+
+                    last = start..notes.len();
+                    position = advance(position, length, "duration", duration)?;
+                }
+                "backup" | "forward" => {
+                    let duration = read_duration_divisions(element)?;
+                    let length = length(divisions, duration)?;
+
+                    position = if element.name == "backup" {
+                        advance(position, -length, "duration", duration)?.max(Rational::ZERO)
+                    } else {
+                        advance(position, length, "duration", duration)?
+                    };
+                    last = 0..0;
+                }
+                #[cfg(feature = "experimental-chords")]
+                "harmony" => {
+                    let offset = number(element, "offset")?.unwrap_or(0);
+                    let onset = advance(position, length(divisions, offset)?, "offset", offset)?;
+
+                    if let Some(chord) = read_harmony(element)? {
+                        part.harmonies.push((i, onset, chord));
+                    }
+                }
+                _ => {}
+            }
+
+            end = end.max(position);
+        }
+
+        notes.sort_by_key(|note| note.onset);
+
+        let implicit = measure.attribute("implicit") == Some("yes");
+
+        let mut measure = match Measure::pickup(time_signature.clone(), end) {
+            Some(pickup) if implicit => pickup,
+            _ => Measure::new(time_signature.clone()),
+        };
+
+        measure.notes = notes;
+        part.measures.push(measure);
+    }
+
+    Ok(part)
+}
+
+// keys and clefs can be given for each staff, and only the first staff's are kept
+fn is_first_staff(element: &Element) -> bool {
+    element.attribute("number").is_none_or(|number| number == "1")
+}
+
+// adds a key or clef change, replacing an earlier change in the same measure
+fn set_change<T>(changes: &mut Vec<(usize, T)>, measure: usize, value: T) {
+    if let Some(last) = changes.last_mut().filter(|(last, _)| *last == measure) {
+        last.1 = value;
+    } else {
+        changes.push((measure, value));
+    }
+}
+
+fn read_key(key: &Element) -> Result<Key, ParseMusicXmlError> {
+    let Some(fifths) = number(key, "fifths")? else {
+        return Err(ParseMusicXmlError::Unsupported("non-traditional key signatures".to_owned()));
+    };
+
+    let mode = match key.child("mode").map(Element::text).as_deref() {
+        None | Some("major" | "ionian" | "none") => DiatonicMode::MAJOR,
+        Some("minor" | "aeolian") => DiatonicMode::NATURAL_MINOR,
+        Some("dorian") => DiatonicMode::Dorian,
+        Some("phrygian") => DiatonicMode::Phrygian,
+        Some("lydian") => DiatonicMode::Lydian,
+        Some("mixolydian") => DiatonicMode::Mixolydian,
+        Some("locrian") => DiatonicMode::Locrian,
+        Some(mode) => return Err(ParseMusicXmlError::Unsupported(format!("the {mode} mode"))),
+    };
+
+    Ok(Key::from_sharps(fifths, mode))
+}
+
+fn read_time(time: &Element) -> Result<TimeSignature, ParseMusicXmlError> {
+    if time.child("senza-misura").is_some() {
+        return Err(ParseMusicXmlError::Unsupported("unmeasured time".to_owned()));
+    } else if time.children("beats").count() > 1 {
+        return Err(ParseMusicXmlError::Unsupported("composite time signatures".to_owned()));
+    }
+
+    let beats = time.child("beats").ok_or(ParseMusicXmlError::MissingElement("beats"))?.text();
+    let beat_type = time.child("beat-type").ok_or(ParseMusicXmlError::MissingElement("beat-type"))?.text();
+
+    let value = format!("{beats}/{beat_type}");
+
+    value.parse().map_err(|_| ParseMusicXmlError::InvalidValue { element: "time", value })
+}
+
+fn read_clef(clef: &Element) -> Result<PitchClef, ParseMusicXmlError> {
+    let sign = clef.child("sign").ok_or(ParseMusicXmlError::MissingElement("sign"))?.text();
+
+    // the note each sign marks, and the line it's usually on
+    let (anchor, line) = match sign.as_str() {
+        "G" => (OctaveLetter::new(Letter::G, 4), 2),
+        "F" => (OctaveLetter::new(Letter::F, 3), 4),
+        "C" => (OctaveLetter::C4, 3),
+        _ => return Err(ParseMusicXmlError::Unsupported(format!("{sign} clefs"))),
+    };
+
+    let line = number(clef, "line")?.unwrap_or(line);
+    let octave_change = number::<i16>(clef, "clef-octave-change")?.unwrap_or(0);
+
+    let octave = anchor.octave.checked_add(octave_change)
+        .ok_or_else(|| ParseMusicXmlError::InvalidValue { element: "clef-octave-change", value: octave_change.to_string() })?;
+
+    let anchor = OctaveLetter::new(anchor.letter, octave);
+
+    PitchClef::new(anchor, line).ok_or_else(|| ParseMusicXmlError::InvalidValue { element: "line", value: line.to_string() })
+}
+
+// reads an accidental from an <alter>, which can't be a microtone
+fn read_alter(parent: &Element, name: &'static str) -> Result<AccidentalSign, ParseMusicXmlError> {
+    let alter = number::<f64>(parent, name)?.unwrap_or(0.0);
+
+    if !alter.is_finite() || alter.abs() > MAX_ALTER {
+        return Err(ParseMusicXmlError::InvalidValue { element: name, value: alter.to_string() });
+    } else if alter.fract() != 0.0 {
+        return Err(ParseMusicXmlError::Unsupported("microtonal alterations".to_owned()));
+    }
+
+    Ok(AccidentalSign::from_offset_semitones(Semitones(alter as i16)))
+}
+
+fn read_letter(parent: &Element, name: &'static str) -> Result<Letter, ParseMusicXmlError> {
+    let step = parent.child(name).ok_or(ParseMusicXmlError::MissingElement(name))?.text();
+
+    step.parse().map_err(|_| ParseMusicXmlError::InvalidValue { element: name, value: step })
+}
+
+// reads the pitch of a note, or `None` if it's a rest
+fn read_pitch(note: &Element) -> Result<Option<Note>, ParseMusicXmlError> {
+    if note.child("unpitched").is_some() {
+        return Err(ParseMusicXmlError::Unsupported("unpitched notes".to_owned()));
+    }
+
+    let Some(pitch) = note.child("pitch") else {
+        return if note.child("rest").is_some() { Ok(None) } else { Err(ParseMusicXmlError::MissingElement("pitch")) };
+    };
+
+    let letter = read_letter(pitch, "step")?;
+    let accidental = read_alter(pitch, "alter")?;
+    let octave = number(pitch, "octave")?.ok_or(ParseMusicXmlError::MissingElement("octave"))?;
+
+    Ok(Some(Note::new(Pitch::from_letter_and_accidental(letter, accidental), octave)))
+}
+
+// reads the notated duration of a note, which may be split into tied notes
+// if it doesn't match its length or it has no type
+fn read_timed(note: &Element, notes: Vec<Note>, onset: Rational, raw_duration: i64, length: Rational) -> Result<Vec<TimedNote>, ParseMusicXmlError> {
+    if let Some(duration) = read_duration(note)?.filter(|duration| duration.length() == length) {
+        return Ok(vec![TimedNote { notes, onset, duration, tied: false }]);
+    }
+
+    if let Some(duration) = Duration::from_length(length) {
+        return Ok(vec![TimedNote { notes, onset, duration, tied: false }]);
+    }
+
+    let invalid = || ParseMusicXmlError::InvalidValue { element: "duration", value: raw_duration.to_string() };
+
+    // tied notes are multiples of the shortest note value, which is checked first so that
+    // dividing by it can't overflow
+    if length > MAX_TIED_LENGTH || NoteValue::ThousandTwentyFourth.length().denominator() % length.denominator() != 0 {
+        return Err(invalid());
+    }
+
+    // split from zero, so that adding the onset is the only step that can overflow
+    TimedNote::tied_notes(notes, Rational::ZERO, length)
+        .ok_or_else(invalid)?
+        .into_iter()
+        .map(|note| Ok(TimedNote { onset: advance(onset, note.onset, "duration", raw_duration)?, ..note }))
+        .collect()
+}
+
+fn read_duration(note: &Element) -> Result<Option<Duration>, ParseMusicXmlError> {
+    use strum::IntoEnumIterator;
+
+    let Some(value) = note.child("type").map(Element::text) else {
+        return Ok(None);
+    };
+
+    let Some(value) = NoteValue::iter().find(|&note_value| note_type(note_value) == value) else {
+        return Err(ParseMusicXmlError::Unsupported(format!("{value} notes")));
+    };
+
+    let dots = note.children("dot").count();
+
+    if dots > Duration::MAX_DOTS as usize {
+        return Err(ParseMusicXmlError::Unsupported(format!("notes with more than {} dots", Duration::MAX_DOTS)));
+    }
+
+    let mut duration = Duration::new(value).with_dots(dots as u8);
+
+    if let Some(modification) = note.child("time-modification") {
+        let actual = number(modification, "actual-notes")?.ok_or(ParseMusicXmlError::MissingElement("actual-notes"))?;
+        let normal = number(modification, "normal-notes")?.ok_or(ParseMusicXmlError::MissingElement("normal-notes"))?;
+
+        let tuplet = Tuplet::new(actual, normal)
+            .ok_or_else(|| ParseMusicXmlError::InvalidValue { element: "time-modification", value: format!("{actual}:{normal}") })?;
+
+        duration = duration.with_tuplet(tuplet);
+    }
+
+    Ok(Some(duration))
+}
+
+// reads a chord symbol by writing it as a lead sheet symbol, or `None` if it's "no chord"
+#[cfg(feature = "experimental-chords")]
+fn read_harmony(harmony: &Element) -> Result<Option<Chord>, ParseMusicXmlError> {
+    fn pitch(parent: &Element, step: &'static str, alter: &'static str) -> Result<Pitch, ParseMusicXmlError> {
+        Ok(Pitch::from_letter_and_accidental(read_letter(parent, step)?, read_alter(parent, alter)?))
+    }
+
+    if harmony.child("numeral").is_some() || harmony.child("function").is_some() {
+        return Err(ParseMusicXmlError::Unsupported("functional harmony".to_owned()));
+    }
+
+    let kind = harmony.child("kind").ok_or(ParseMusicXmlError::MissingElement("kind"))?.text();
+
+    if kind == "none" {
+        return Ok(None);
+    }
+
+    let Some(&(_, quality)) = HARMONY_KINDS.iter().find(|&&(name, _)| name == kind) else {
+        return Err(ParseMusicXmlError::Unsupported(format!("{kind} chords")));
+    };
+
+    let root = pitch(harmony.child("root").ok_or(ParseMusicXmlError::MissingElement("root"))?, "root-step", "root-alter")?;
+    let mut degrees = Vec::new();
+
+    for degree in harmony.children("degree") {
+        let value = number::<u8>(degree, "degree-value")?.ok_or(ParseMusicXmlError::MissingElement("degree-value"))?;
+        let alter = read_alter(degree, "degree-alter")?.offset_semitones().0;
+
+        let ty = match degree.child("degree-type").map(Element::text).as_deref() {
+            Some("add") => DegreeType::Add,
+            Some("alter") => DegreeType::Alter,
+            Some("subtract") => DegreeType::Subtract,
+            kind => return Err(ParseMusicXmlError::InvalidValue { element: "degree-type", value: kind.unwrap_or_default().to_owned() }),
+        };
+
+        degrees.push((ty, value, alter));
+    }
+
+    let bass = harmony.child("bass").map(|bass| pitch(bass, "bass-step", "bass-alter")).transpose()?;
+    let symbol = harmony_symbol(root, quality, &degrees, bass);
+
+    symbol.parse().map(Some).map_err(|_| ParseMusicXmlError::InvalidValue { element: "harmony", value: symbol })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let notes = [
+            TimedNote::note(note("Bb3"), Rational::ZERO, "4".parse().unwrap()),
+            TimedNote::chord(vec![note("C4"), note("Eb4")], Rational::new(1, 4), "2.".parse().unwrap()).tied(),
+            TimedNote::note(note("C4"), Rational::ONE, "8 3:2".parse().unwrap()),
+            TimedNote::note(note("D4"), Rational::new(13, 12), "8 3:2".parse().unwrap()),
+            TimedNote::note(note("Bb3"), Rational::new(7, 6), "8 3:2".parse().unwrap()),
+        ];
+
+        let measures = Measure::from_notes(&notes, "3+2/8".parse().unwrap(), Some(Rational::new(1, 4))).unwrap();
+
+        let mut part = Part::new("Clarinet & Voice", Key::new(Pitch::C, DiatonicMode::Dorian), PitchClef::TREBLE_8VB, measures);
+        part.clefs.push((1, PitchClef::ALTO));
+        part.keys.push((2, Key::from_sharps(4, DiatonicMode::NATURAL_MINOR)));
+
+        #[cfg(feature = "experimental-chords")]
+        {
+            let symbols = ["Bb7#9b5", "Ebmaj7/G", "F#m7b5", "Am/B", "C7alt", "Db9sus4", "C13no11", "Cm(maj7)"];
+
+            part.harmonies = symbols.iter()
+                .enumerate()
+                .map(|(i, symbol)| (i / 3, Rational::new(i as i64 % 3, 6), symbol.parse().unwrap()))
+                .collect();
+        }
+
+        let bass = Part::new("Bass", Key::minor(Pitch::E), PitchClef::BASS, part.measures.clone());
+        let score = Score { title: Some("Étude".to_owned()), parts: vec![part, bass] };
+        let xml = score.to_musicxml().unwrap();
+
+        assert_eq!(Score::from_musicxml(&xml), Ok(score));
+    }
+
+    #[test]
+    fn read_voices_and_timewise() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE score-timewise PUBLIC "-//Recordare//DTD MusicXML 4.0 Timewise//EN" "http://www.musicxml.org/dtds/timewise.dtd" [
+  <!ENTITY unused "<part>">
+]>
+<!-- two voices, a chord, and a pickup -->
+<score-timewise version="4.0">
+  <movement-title>Chorale &#x2014; &lt;draft&gt;</movement-title>
+  <part-list>
+    <score-part id="P1"><part-name><![CDATA[Soprano & Alto]]></part-name></score-part>
+  </part-list>
+  <measure number="0" implicit="yes">
+    <part id="P1">
+      <attributes>
+        <divisions>4</divisions>
+        <key><fifths>1</fifths></key>
+        <time symbol="common"><beats>4</beats><beat-type>4</beat-type></time>
+        <clef number="1"><sign>G</sign><line>2</line></clef>
+        <clef number="2"><sign>F</sign><line>4</line></clef>
+      </attributes>
+      <note><grace/><pitch><step>A</step><octave>4</octave></pitch><type>eighth</type></note>
+      <note><pitch><step>G</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>quarter</type></note>
+      <backup><duration>4</duration></backup>
+      <note><pitch><step>D</step><octave>4</octave></pitch><duration>4</duration><voice>2</voice><type>quarter</type></note>
+    </part>
+  </measure>
+  <measure number="1">
+    <part id="P1">
+      <note>
+        <pitch><step>F</step><alter>1</alter><octave>4</octave></pitch>
+        <duration>10</duration>
+        <tie type="start"/>
+      </note>
+      <note><chord/><pitch><step>A</step><octave>4</octave></pitch><duration>10</duration></note>
+      <forward><duration>2</duration></forward>
+      <note><rest measure="no"/><duration>4</duration><type>quarter</type></note>
+    </part>
+  </measure>
+</score-timewise>"#;
+
+        let score = Score::from_musicxml(xml).unwrap();
+
+        assert_eq!(score.title.as_deref(), Some("Chorale \u{2014} <draft>"));
+
+        let part = &score.parts[0];
+
+        assert_eq!(part.name, "Soprano & Alto");
+        assert_eq!(part.keys, [(0, Key::major(Pitch::G))]);
+        assert_eq!(part.clefs, [(0, PitchClef::TREBLE)]);
+
+        let pickup = &part.measures[0];
+
+        assert_eq!(pickup.pickup, Some(Rational::new(1, 4)));
+        assert_eq!(pickup.time_signature, TimeSignature::COMMON);
+        assert_eq!(pickup.notes.iter().map(|note| (note.onset, note.notes.clone())).collect::<Vec<_>>(), [
+            (Rational::ZERO, vec![note("G4")]),
+            (Rational::ZERO, vec![note("D4")]),
+        ]);
+
+        // a length of 5/8 with no type is split into tied notes, the last tied to the next measure
+        let notes = &part.measures[1].notes;
+        let chord = vec![note("F#4"), note("A4")];
+
+        assert!(!part.measures[1].is_pickup());
+        assert_eq!(notes, &[
+            TimedNote::chord(chord.clone(), Rational::ZERO, "2".parse().unwrap()).tied(),
+            TimedNote::chord(chord, Rational::new(1, 2), "8".parse().unwrap()).tied(),
+            TimedNote::rest(Rational::new(3, 4), "4".parse().unwrap()),
+        ]);
+
+        // the voices are split again when writing
+        assert_eq!(Score::from_musicxml(&score.to_musicxml().unwrap()).as_ref(), Ok(&score));
+    }
+
+    #[cfg(feature = "experimental-chords")]
+    #[test]
+    fn read_harmonies() {
+        let xml = r#"
+<score-partwise>
+  <part-list><score-part id="P1"/></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes><divisions>2</divisions></attributes>
+      <harmony>
+        <root><root-step>B</root-step><root-alter>-1</root-alter></root>
+        <kind text="7">dominant</kind>
+        <degree><degree-value>9</degree-value><degree-alter>1</degree-alter><degree-type>add</degree-type></degree>
+        <degree><degree-value>5</degree-value><degree-alter>-1</degree-alter><degree-type>alter</degree-type></degree>
+      </harmony>
+      <note><rest/><duration>4</duration></note>
+      <harmony>
+        <root><root-step>D</root-step></root>
+        <kind>minor-seventh</kind>
+        <bass><bass-step>C</bass-step></bass>
+        <offset>1</offset>
+      </harmony>
+      <harmony><kind>none</kind></harmony>
+      <note><rest/><duration>4</duration></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+        let score = Score::from_musicxml(xml).unwrap();
+
+        assert_eq!(score.parts[0].name, "");
+        assert_eq!(score.parts[0].harmonies, [
+            (0, Rational::ZERO, "Bb7#9b5".parse().unwrap()),
+            (0, Rational::new(5, 8), "Dm7/C".parse().unwrap()),
+        ]);
+
+        let numeral = xml.replace("<kind>none</kind>", "<numeral><numeral-root>2</numeral-root></numeral><kind>minor</kind>");
+        assert_eq!(Score::from_musicxml(&numeral), Err(ParseMusicXmlError::Unsupported("functional harmony".to_owned())));
+    }
+
+    #[test]
+    fn invalid_scores() {
+        use ParseMusicXmlError as E;
+
+        let score = |measure: &str| format!(r#"
+<score-partwise>
+  <part-list><score-part id="P1"/></part-list>
+  <part id="P1"><measure number="1">{measure}</measure></part>
+</score-partwise>"#);
+
+        let divisions = "<attributes><divisions>1</divisions></attributes>";
+
+        assert_eq!(Score::from_musicxml("<score-partwise>"), Err(E::Xml { offset: 16, reason: "unterminated element" }));
+        assert_eq!(Score::from_musicxml("<a></b>"), Err(E::Xml { offset: 6, reason: "mismatched end tag" }));
+        assert_eq!(Score::from_musicxml("<a>&nbsp;</a>"), Err(E::Xml { offset: 3, reason: "unknown entity" }));
+        assert_eq!(Score::from_musicxml("<a/><b/>"), Err(E::Xml { offset: 4, reason: "expected the end of the document" }));
+
+        let nested = |depth: usize| "<a>".repeat(depth) + &"</a>".repeat(depth);
+        assert_eq!(Score::from_musicxml(&nested(256)), Err(E::MissingElement("part-list")));
+        assert_eq!(Score::from_musicxml(&nested(257)), Err(E::Xml { offset: 768, reason: "too deeply nested" }));
+        assert_eq!(Score::from_musicxml(&"<a>".repeat(1_000_000)), Err(E::Xml { offset: 768, reason: "too deeply nested" }));
+        assert_eq!(Score::from_musicxml("<opus/>"), Err(E::MissingElement("part-list")));
+        assert_eq!(Score::from_musicxml("<opus><part-list/></opus>"), Err(E::InvalidRoot("opus".to_owned())));
+
+        assert_eq!(
+            Score::from_musicxml(&score("").replace("<part id=\"P1\">", "<part id=\"P2\">")),
+            Err(E::InvalidValue { element: "part", value: "P2".to_owned() }),
+        );
+
+        assert_eq!(Score::from_musicxml(&score("<note><rest/><duration>1</duration></note>")), Err(E::MissingElement("divisions")));
+        assert_eq!(Score::from_musicxml(&score("<attributes><divisions>0</divisions></attributes>")), Err(E::InvalidValue { element: "divisions", value: "0".to_owned() }));
+        assert_eq!(
+            Score::from_musicxml(&score("<attributes><divisions>9223372036854775807</divisions></attributes>")),
+            Err(E::InvalidValue { element: "divisions", value: i64::MAX.to_string() }),
+        );
+        assert_eq!(Score::from_musicxml(&score("<note><rest/></note>")), Err(E::MissingElement("duration")));
+        assert_eq!(Score::from_musicxml(&score("<note><duration>1</duration></note>")), Err(E::MissingElement("pitch")));
+
+        let invalid = |measure: &str| Score::from_musicxml(&score(&format!("{divisions}{measure}")));
+        let max = i64::MAX.to_string();
+
+        assert_eq!(invalid("<note><rest/><duration>-1</duration></note>"), Err(E::InvalidValue { element: "duration", value: "-1".to_owned() }));
+        assert_eq!(invalid(&format!("<note><rest/><duration>{max}</duration></note>")), Err(E::InvalidValue { element: "duration", value: max.clone() }));
+        assert_eq!(
+            invalid(&format!("<forward><duration>{max}</duration></forward><forward><duration>{max}</duration></forward>")),
+            Err(E::InvalidValue { element: "duration", value: max.clone() }),
+        );
+        assert_eq!(
+            invalid("<note><pitch><step>C</step><alter>30000</alter><octave>4</octave></pitch><duration>1</duration></note>"),
+            Err(E::InvalidValue { element: "alter", value: "30000".to_owned() }),
+        );
+        assert_eq!(
+            invalid("<note><pitch><step>C</step><alter>inf</alter><octave>4</octave></pitch><duration>1</duration></note>"),
+            Err(E::InvalidValue { element: "alter", value: "inf".to_owned() }),
+        );
+        assert_eq!(
+            invalid("<attributes><clef><sign>G</sign><clef-octave-change>32767</clef-octave-change></clef></attributes>"),
+            Err(E::InvalidValue { element: "clef-octave-change", value: "32767".to_owned() }),
+        );
+
+        let unsupported = |measure: &str| match Score::from_musicxml(&score(&format!("{divisions}{measure}"))) {
+            Err(E::Unsupported(what)) => what,
+            result => panic!("expected an unsupported element, got {result:?}"),
+        };
+
+        assert_eq!(unsupported("<note><unpitched/><duration>1</duration></note>"), "unpitched notes");
+        assert_eq!(unsupported("<note><pitch><step>E</step><alter>-0.5</alter><octave>4</octave></pitch><duration>1</duration></note>"), "microtonal alterations");
+        assert_eq!(unsupported("<note><rest/><duration>8</duration><type>breve</type></note>"), "breve notes");
+        assert_eq!(unsupported(&format!("<note><rest/><duration>1</duration><type>quarter</type>{}</note>", "<dot/>".repeat(70))), "notes with more than 8 dots");
+        assert_eq!(unsupported("<attributes><clef><sign>percussion</sign></clef></attributes>"), "percussion clefs");
+        assert_eq!(unsupported("<attributes><clef><sign>TAB</sign></clef></attributes>"), "TAB clefs");
+        assert_eq!(unsupported("<attributes><time><senza-misura/></time></attributes>"), "unmeasured time");
+        assert_eq!(unsupported("<attributes><key><key-step>C</key-step><key-alter>1</key-alter></key></attributes>"), "non-traditional key signatures");
+
+        assert_eq!(
+            Score::from_musicxml(&score("<attributes><clef><sign>G</sign><line>6</line></clef></attributes>")),
+            Err(E::InvalidValue { element: "line", value: "6".to_owned() }),
+        );
+
+        assert_eq!(
+            Score::from_musicxml(&score(&format!("{divisions}<note><rest/><duration>1</duration><type>quarter</type><time-modification><actual-notes>0</actual-notes><normal-notes>2</normal-notes></time-modification></note>"))),
+            Err(E::InvalidValue { element: "time-modification", value: "0:2".to_owned() }),
+        );
+    }
+
+    #[test]
+    fn read_mxl() {
+        // a ZIP archive with a stored container and a compressed score
+        let mxl = [
+            0x50, 0x4b, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x96, 0x05, 0x51, 0x5d, 0xd3, 0x62,
+            0x35, 0x49, 0x53, 0x00, 0x00, 0x00, 0x53, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x4d, 0x45,
+            0x54, 0x41, 0x2d, 0x49, 0x4e, 0x46, 0x2f, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x72,
+            0x2e, 0x78, 0x6d, 0x6c, 0x3c, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x3e, 0x3c,
+            0x72, 0x6f, 0x6f, 0x74, 0x66, 0x69, 0x6c, 0x65, 0x73, 0x3e, 0x3c, 0x72, 0x6f, 0x6f, 0x74, 0x66,
+            0x69, 0x6c, 0x65, 0x20, 0x66, 0x75, 0x6c, 0x6c, 0x2d, 0x70, 0x61, 0x74, 0x68, 0x3d, 0x22, 0x62,
+            0x61, 0x73, 0x73, 0x2e, 0x6d, 0x75, 0x73, 0x69, 0x63, 0x78, 0x6d, 0x6c, 0x22, 0x2f, 0x3e, 0x3c,
+            0x2f, 0x72, 0x6f, 0x6f, 0x74, 0x66, 0x69, 0x6c, 0x65, 0x73, 0x3e, 0x3c, 0x2f, 0x63, 0x6f, 0x6e,
+            0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x3e, 0x50, 0x4b, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x08,
+            0x00, 0x96, 0x05, 0x51, 0x5d, 0x82, 0x9e, 0xfb, 0x19, 0xcc, 0x00, 0x00, 0x00, 0x8b, 0x02, 0x00,
+            0x00, 0x0d, 0x00, 0x00, 0x00, 0x62, 0x61, 0x73, 0x73, 0x2e, 0x6d, 0x75, 0x73, 0x69, 0x63, 0x78,
+            0x6d, 0x6c, 0xbd, 0x92, 0xcd, 0x6e, 0xc2, 0x30, 0x0c, 0xc7, 0x5f, 0x05, 0xf5, 0x8e, 0x2c, 0xd8,
+            0xd5, 0x58, 0x02, 0x24, 0x76, 0xe5, 0x15, 0x42, 0xeb, 0x81, 0x25, 0x9a, 0x96, 0xd8, 0x29, 0xe2,
+            0xed, 0x97, 0xb4, 0x99, 0x3a, 0xd8, 0x75, 0xe2, 0xe4, 0xaf, 0x7f, 0xfc, 0xb3, 0x15, 0xa3, 0xd6,
+            0x5d, 0xe0, 0x65, 0xef, 0x82, 0xdd, 0x45, 0x99, 0x30, 0x7b, 0xcb, 0xab, 0xa8, 0x11, 0xce, 0xa5,
+            0x85, 0x34, 0x9b, 0xea, 0xb8, 0xaa, 0x4a, 0xd9, 0xbb, 0x96, 0x69, 0xe7, 0x54, 0x11, 0xe6, 0x18,
+            0x61, 0xd6, 0x53, 0x29, 0x4c, 0x7d, 0x9e, 0x3b, 0xb4, 0xec, 0x34, 0x06, 0x5e, 0xf8, 0xd8, 0x9e,
+            0x38, 0x6c, 0xaa, 0x9c, 0x73, 0x66, 0x41, 0x4e, 0xd1, 0x58, 0x09, 0x1b, 0x19, 0x44, 0xa5, 0xf3,
+            0x4a, 0x2b, 0x84, 0x39, 0xc0, 0xfa, 0xca, 0x5f, 0x69, 0x26, 0x39, 0x7b, 0x3a, 0x24, 0x56, 0xb6,
+            0x08, 0x53, 0x12, 0x7e, 0xbf, 0xf7, 0x9d, 0xe5, 0x35, 0xc4, 0xea, 0x4b, 0x92, 0x1b, 0xf7, 0xf4,
+            0x99, 0xe4, 0xd9, 0x62, 0x57, 0x9b, 0x1b, 0x98, 0xd6, 0x08, 0xc5, 0x4b, 0x63, 0x4e, 0xba, 0x26,
+            0x06, 0x67, 0x89, 0x33, 0x32, 0x7f, 0x7c, 0xb4, 0x47, 0xcf, 0x74, 0x8b, 0x69, 0x7c, 0x0e, 0x08,
+            0x63, 0x84, 0x30, 0xf5, 0xff, 0x4b, 0xd9, 0xbe, 0x85, 0xb2, 0x7b, 0x0b, 0x65, 0xff, 0x42, 0xf9,
+            0xf8, 0x07, 0x0a, 0x94, 0x8f, 0x2f, 0xb7, 0xf1, 0x74, 0x2f, 0xe3, 0xe9, 0x7d, 0x03, 0x50, 0x4b,
+            0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x96, 0x05, 0x51, 0x5d, 0xd3, 0x62,
+            0x35, 0x49, 0x53, 0x00, 0x00, 0x00, 0x53, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00, 0x00, 0x4d, 0x45, 0x54, 0x41,
+            0x2d, 0x49, 0x4e, 0x46, 0x2f, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x2e, 0x78,
+            0x6d, 0x6c, 0x50, 0x4b, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x96, 0x05,
+            0x51, 0x5d, 0x82, 0x9e, 0xfb, 0x19, 0xcc, 0x00, 0x00, 0x00, 0x8b, 0x02, 0x00, 0x00, 0x0d, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x87, 0x00, 0x00, 0x00,
+            0x62, 0x61, 0x73, 0x73, 0x2e, 0x6d, 0x75, 0x73, 0x69, 0x63, 0x78, 0x6d, 0x6c, 0x50, 0x4b, 0x05,
+            0x06, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x02, 0x00, 0x7f, 0x00, 0x00, 0x00, 0x7e, 0x01, 0x00,
+            0x00, 0x00, 0x00,
+        ];
+
+        let score = Score::from_mxl(&mxl).unwrap();
+        let part = &score.parts[0];
+
+        assert_eq!(part.name, "Bass");
+        assert_eq!(part.clefs, [(0, PitchClef::BASS)]);
+        assert_eq!(part.measures[0].notes.iter().flat_map(|note| note.notes.clone()).collect::<Vec<_>>(), ["G2", "A2", "B2", "C3"].map(note));
+
+        assert_eq!(Score::from_mxl(&mxl[..mxl.len() - 1]), Err(ParseMusicXmlError::Archive("not a ZIP archive")));
+
+        // the signature of the score's local header
+        let mut corrupted = mxl;
+        corrupted[135] = 0;
+        assert_eq!(Score::from_mxl(&corrupted), Err(ParseMusicXmlError::Archive("invalid local file header")));
+
+        // the compressed score
+        let mut corrupted = mxl;
+        corrupted[250] ^= 0xff;
+        assert!(matches!(Score::from_mxl(&corrupted), Err(ParseMusicXmlError::Archive(_))));
+    }
+}
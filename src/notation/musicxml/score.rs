@@ -0,0 +1,151 @@
+use crate::harmony::{DiatonicMode, Key};
+use crate::notation::PitchClef;
+use crate::rhythm::{Measure, NoteValue};
+#[cfg(feature = "experimental-chords")]
+use crate::{Pitch, chord::Chord, rhythm::Rational};
+
+/// A part of a [`Score`], like a single instrument, made of measures.
+///
+/// Key signatures and clefs change at the start of a measure, and the time signature is
+/// taken from each [`Measure`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part {
+    /// The name of the part.
+    pub name: String,
+    /// The measures of the part, which can start with a pickup.
+    pub measures: Vec<Measure>,
+    /// Key signature changes, as the index of the measure they start in and the key.
+    pub keys: Vec<(usize, Key)>,
+    /// Clef changes, as the index of the measure they start in and the clef.
+    pub clefs: Vec<(usize, PitchClef)>,
+    /// Chord symbols, as the index of their measure, their offset from its start in whole notes,
+    /// and the chord. They're read from and written as `<harmony>` elements.
+    #[cfg(feature = "experimental-chords")]
+    pub harmonies: Vec<(usize, Rational, Chord)>,
+}
+
+impl Part {
+    /// Creates a part with a key and clef from its first measure.
+    pub fn new(name: impl Into<String>, key: Key, clef: PitchClef, measures: Vec<Measure>) -> Self {
+        Self {
+            name: name.into(),
+            measures,
+            keys: vec![(0, key)],
+            clefs: vec![(0, clef)],
+            #[cfg(feature = "experimental-chords")]
+            harmonies: Vec::new(),
+        }
+    }
+}
+
+/// A score, made of parts played together, which can be read from and written as MusicXML.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Score {
+    /// The title of the work.
+    pub title: Option<String>,
+    /// The parts of the score, from top to bottom.
+    pub parts: Vec<Part>,
+}
+
+// the MusicXML name of a note value, for <type>
+pub(crate) fn note_type(value: NoteValue) -> &'static str {
+    match value {
+        NoteValue::Whole => "whole",
+        NoteValue::Half => "half",
+        NoteValue::Quarter => "quarter",
+        NoteValue::Eighth => "eighth",
+        NoteValue::Sixteenth => "16th",
+        NoteValue::ThirtySecond => "32nd",
+        NoteValue::SixtyFourth => "64th",
+        NoteValue::HundredTwentyEighth => "128th",
+        NoteValue::TwoHundredFiftySixth => "256th",
+        NoteValue::FiveHundredTwelfth => "512th",
+        NoteValue::ThousandTwentyFourth => "1024th",
+    }
+}
+
+// the MusicXML name of a mode, for <mode>
+pub(crate) fn mode_name(mode: DiatonicMode) -> &'static str {
+    match mode {
+        DiatonicMode::Ionian => "major",
+        DiatonicMode::Dorian => "dorian",
+        DiatonicMode::Phrygian => "phrygian",
+        DiatonicMode::Lydian => "lydian",
+        DiatonicMode::Mixolydian => "mixolydian",
+        DiatonicMode::Aeolian => "minor",
+        DiatonicMode::Locrian => "locrian",
+    }
+}
+
+// MusicXML chord kinds, for <kind>, and the quality of the chord symbol each is read as
+#[cfg(feature = "experimental-chords")]
+pub(crate) const HARMONY_KINDS: [(&str, &str); 25] = [
+    ("major", ""),
+    ("minor", "m"),
+    ("augmented", "aug"),
+    ("diminished", "dim"),
+    ("dominant", "7"),
+    ("major-seventh", "maj7"),
+    ("minor-seventh", "m7"),
+    ("diminished-seventh", "dim7"),
+    ("augmented-seventh", "aug7"),
+    ("half-diminished", "m7b5"),
+    ("major-minor", "m(maj7)"),
+    ("major-sixth", "6"),
+    ("minor-sixth", "m6"),
+    ("dominant-ninth", "9"),
+    ("major-ninth", "maj9"),
+    ("minor-ninth", "m9"),
+    ("dominant-11th", "11"),
+    ("major-11th", "maj11"),
+    ("minor-11th", "m11"),
+    ("dominant-13th", "13"),
+    ("major-13th", "maj13"),
+    ("minor-13th", "m13"),
+    ("suspended-second", "sus2"),
+    ("suspended-fourth", "sus4"),
+    ("power", "5"),
+];
+
+// how a <degree> changes the chord of its kind, for <degree-type>
+#[cfg(feature = "experimental-chords")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum DegreeType {
+    Add,
+    Alter,
+    Subtract,
+}
+
+// a <degree>, with its value, like 9 for a ninth, and its alteration in semitones
+// from the major or perfect interval
+#[cfg(feature = "experimental-chords")]
+pub(crate) type Degree = (DegreeType, u8, i16);
+
+// writes a <harmony> as a chord symbol, which is how it's read
+#[cfg(feature = "experimental-chords")]
+pub(crate) fn harmony_symbol(root: Pitch, quality: &str, degrees: &[Degree], bass: Option<Pitch>) -> String {
+    fn accidental(alter: i16) -> String {
+        (if alter < 0 { "b" } else { "#" }).repeat(alter.unsigned_abs() as usize)
+    }
+
+    let pitch = |pitch: Pitch| format!("{}{}", pitch.letter(), accidental(pitch.accidental().offset_semitones().0));
+
+    let mut symbol = pitch(root) + quality;
+
+    for &(ty, value, alter) in degrees {
+        symbol += &match ty {
+            DegreeType::Add => format!("add{}{value}", accidental(alter)),
+            DegreeType::Alter => format!("{}{value}", accidental(alter)),
+            DegreeType::Subtract => format!("no{value}"),
+        };
+    }
+
+    if let Some(bass) = bass {
+        symbol += "/";
+        symbol += &pitch(bass);
+    }
+
+    symbol
+}
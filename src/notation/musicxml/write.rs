@@ -0,0 +1,855 @@
+use crate::{Letter, Note};
+use crate::notation::PitchClef;
+use crate::notation::musicxml::{Part, Score, mode_name, note_type};
+use crate::notation::musicxml::xml::XmlWriter;
+use crate::rhythm::{Rational, TimedNote, gcd};
+#[cfg(feature = "experimental-chords")]
+use crate::{Interval, Pitch, chord::Chord};
+#[cfg(feature = "experimental-chords")]
+use crate::notation::musicxml::{Degree, DegreeType, HARMONY_KINDS, harmony_symbol};
+
+const PROLOG: &str = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>
+<!DOCTYPE score-partwise PUBLIC \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" \"http://www.musicxml.org/dtds/partwise.dtd\">
+";
+
+/// An error returned when a [`Score`] can't be written as MusicXML.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum WriteMusicXmlError {
+    /// The score had no parts.
+    #[error("A score must have at least one part")]
+    NoParts,
+    /// The part at this index had no measures.
+    #[error("Part {0} has no measures")]
+    EmptyPart(usize),
+    /// A key or clef change, or a chord symbol, was in a measure past the end of its part.
+    #[error("Part {part} has a key or clef change or a chord symbol in measure {measure}, which doesn't exist")]
+    InvalidMeasureIndex {
+        /// The index of the part.
+        part: usize,
+        /// The index of the missing measure.
+        measure: usize,
+    },
+    /// The notes of the part at this index needed more divisions per quarter note than can be
+    /// stored.
+    #[error("Part {0} has durations too fine to be stored as divisions of a quarter note")]
+    TooManyDivisions(usize),
+    /// A note was outside of the octaves MusicXML allows, 0 to 9.
+    #[error("The note {0} isn't in octaves 0 to 9")]
+    InvalidOctave(Note),
+    /// A chord symbol couldn't be written as a MusicXML chord kind with added, altered, and
+    /// removed degrees.
+    #[cfg(feature = "experimental-chords")]
+    #[error("Chord symbol {harmony} of part {part} can't be written as a harmony")]
+    UnsupportedChord {
+        /// The index of the part.
+        part: usize,
+        /// The index of the chord symbol in the part's harmonies.
+        harmony: usize,
+    },
+}
+
+impl Score {
+    /// Writes the score as a MusicXML 4.0 partwise document.
+    ///
+    /// Each part's first measure sets the number of divisions per quarter note, which is the
+    /// smallest that makes every duration in the part a whole number. Time signatures are written
+    /// whenever they change, and pickup measures are numbered zero and marked as implicit.
+    /// Notes in a measure are written in order of onset, and gaps between them are skipped with
+    /// `<forward>`. Notes that overlap are split into voices, each going in the lowest voice that's
+    /// free when it starts, and each voice after the first goes back to the start of the measure with
+    /// `<backup>`. Voices aren't stored in a [`Measure`](crate::rhythm::Measure), so the voices of a
+    /// score read from MusicXML aren't kept, and may be numbered differently when it's written.
+    ///
+    /// Notes are written with their spelling, as a step, alteration, and octave, and ties are
+    /// written as both `<tie>` and `<tied>`. Tuplets are written as time modifications.
+    ///
+    /// With the `experimental-chords` feature, each part's `harmonies` are written as `<harmony>`
+    /// elements at the start of their measure, with an `<offset>` to their onset. Each chord is
+    /// written as the kind that needs the fewest added, altered, or removed `<degree>`s to spell it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the score has no parts, a part has no measures, a key or clef changes or a
+    /// chord symbol is in a measure that doesn't exist, a note isn't in octaves 0 to 9, or a chord
+    /// symbol can't be written, see [`WriteMusicXmlError`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::harmony::Key;
+    /// # use music_theory::notation::PitchClef;
+    /// # use music_theory::notation::musicxml::{Part, Score};
+    /// # use music_theory::rhythm::{Measure, TimeSignature};
+    /// let mut measure = Measure::new(TimeSignature::COMMON);
+    /// measure.push(vec!["F#4".parse().unwrap()], "1".parse().unwrap());
+    ///
+    /// let part = Part::new("Flute", Key::major(Pitch::D), PitchClef::TREBLE, vec![measure]);
+    /// let score = Score { title: None, parts: vec![part] };
+    ///
+    /// let xml = score.to_musicxml().unwrap();
+    ///
+    /// assert!(xml.contains("<fifths>2</fifths>"));
+    /// assert!(xml.contains("<step>F</step>"));
+    /// assert!(xml.contains("<alter>1</alter>"));
+    /// assert!(xml.contains("<type>whole</type>"));
+    /// ```
+    pub fn to_musicxml(&self) -> Result<String, WriteMusicXmlError> {
+        if self.parts.is_empty() {
+            return Err(WriteMusicXmlError::NoParts);
+        }
+
+        let mut xml = XmlWriter::new(PROLOG);
+
+        xml.open("score-partwise", &[("version", "4.0")]);
+
+        if let Some(title) = &self.title {
+            xml.open("work", &[]);
+            xml.text("work-title", title);
+            xml.close("work");
+        }
+
+        xml.open("part-list", &[]);
+
+        for (i, part) in self.parts.iter().enumerate() {
+            xml.open("score-part", &[("id", &part_id(i))]);
+            xml.text("part-name", &part.name);
+            xml.close("score-part");
+        }
+
+        xml.close("part-list");
+
+        for (i, part) in self.parts.iter().enumerate() {
+            write_part(&mut xml, i, part)?;
+        }
+
+        xml.close("score-partwise");
+
+        Ok(xml.finish())
+    }
+}
+
+fn part_id(index: usize) -> String {
+    format!("P{}", index + 1)
+}
+
+// the smallest number of divisions per quarter note that makes every time in the part whole,
+// or `None` if a time wouldn't fit in divisions
+fn divisions(part: &Part) -> Option<i64> {
+    let times = part.measures.iter()
+        .flat_map(|measure| &measure.notes)
+        .flat_map(|note| [note.onset, note.length(), note.end()]);
+
+    #[cfg(feature = "experimental-chords")]
+    let times = times.chain(part.harmonies.iter().map(|&(_, onset, _)| onset));
+
+    let times = times.collect::<Vec<_>>();
+    let quarter = Rational::from_integer(4);
+
+    let divisions = times.iter().try_fold(1_i64, |lcm, time| {
+        let denominator = time.checked_mul(quarter)?.denominator();
+        (lcm / gcd(lcm, denominator)).checked_mul(denominator)
+    })?;
+
+    // every other duration written is at most the end of a note
+    let scale = Rational::from_integer(divisions.checked_mul(4)?);
+    times.iter().all(|time| time.checked_mul(scale).is_some()).then_some(divisions)
+}
+
+fn write_part(xml: &mut XmlWriter, index: usize, part: &Part) -> Result<(), WriteMusicXmlError> {
+    use WriteMusicXmlError as E;
+
+    if part.measures.is_empty() {
+        return Err(E::EmptyPart(index));
+    }
+
+    let changes = part.keys.iter().map(|&(measure, _)| measure).chain(part.clefs.iter().map(|&(measure, _)| measure));
+
+    #[cfg(feature = "experimental-chords")]
+    let changes = changes.chain(part.harmonies.iter().map(|&(measure, _, _)| measure));
+
+    let mut changes = changes;
+
+    if let Some(measure) = changes.find(|&measure| measure >= part.measures.len()) {
+        return Err(E::InvalidMeasureIndex { part: index, measure });
+    }
+
+    let divisions = divisions(part).ok_or(E::TooManyDivisions(index))?;
+    // converts a length in whole notes to divisions
+    let duration = |length: Rational| (length * 4 * divisions).numerator();
+
+    let first_number = if part.measures[0].is_pickup() { 0 } else { 1 };
+
+    // the end and pitch of each tied note in the measure, and of those reaching the end of the
+    // last measure, which end at zero
+    let mut tied = Vec::<(Rational, Note)>::new();
+
+    xml.open("part", &[("id", &part_id(index))]);
+
+    for (i, measure) in part.measures.iter().enumerate() {
+        let number = (first_number + i).to_string();
+
+        if measure.is_pickup() {
+            xml.open("measure", &[("number", &number), ("implicit", "yes")]);
+        } else {
+            xml.open("measure", &[("number", &number)]);
+        }
+
+        let key = part.keys.iter().rev().find(|&&(measure, _)| measure == i).map(|&(_, key)| key);
+        let clef = part.clefs.iter().rev().find(|&&(measure, _)| measure == i).map(|&(_, clef)| clef);
+
+        let time_signature = (i == 0 || part.measures[i - 1].time_signature != measure.time_signature)
+            .then_some(&measure.time_signature);
+
+        if i == 0 || key.is_some() || clef.is_some() || time_signature.is_some() {
+            xml.open("attributes", &[]);
+
+            if i == 0 {
+                xml.text("divisions", divisions);
+            }
+
+            if let Some(key) = key {
+                xml.open("key", &[]);
+                xml.text("fifths", key.sharps());
+                xml.text("mode", mode_name(key.mode));
+                xml.close("key");
+            }
+
+            if let Some(time_signature) = time_signature {
+                let beats = time_signature.groups().iter().map(u8::to_string).collect::<Vec<_>>().join("+");
+
+                xml.open("time", &[]);
+                xml.text("beats", beats);
+                xml.text("beat-type", time_signature.denominator());
+                xml.close("time");
+            }
+
+            if let Some(clef) = clef {
+                write_clef(xml, clef);
+            }
+
+            xml.close("attributes");
+        }
+
+        #[cfg(feature = "experimental-chords")]
+        for (j, (_, onset, chord)) in part.harmonies.iter().enumerate().filter(|(_, (measure, _, _))| *measure == i) {
+            write_harmony(xml, chord, duration(*onset)).ok_or(E::UnsupportedChord { part: index, harmony: j })?;
+        }
+
+        tied.extend(measure.notes.iter()
+            .filter(|note| note.tied)
+            .flat_map(|note| note.notes.iter().map(|&pitch| (note.end(), pitch))));
+
+        let voices = voices(&measure.notes);
+
+        for (voice, notes) in voices.iter().enumerate() {
+            let mut position = Rational::ZERO;
+
+            for &note in notes {
+                if note.onset > position {
+                    xml.open("forward", &[]);
+                    xml.text("duration", duration(note.onset - position));
+                    xml.close("forward");
+                }
+
+                let tied_from = tied.iter()
+                    .filter(|&&(end, _)| end == note.onset)
+                    .map(|&(_, pitch)| pitch)
+                    .collect::<Vec<_>>();
+
+                write_note(xml, note, voice + 1, duration(note.length()), &tied_from)?;
+
+                position = note.end();
+            }
+
+            if voice + 1 != voices.len() {
+                xml.open("backup", &[]);
+                xml.text("duration", duration(position));
+                xml.close("backup");
+            }
+        }
+
+        let end = measure.notes.iter().map(TimedNote::end).max().unwrap_or(Rational::ZERO);
+
+        tied.retain(|&(tied_end, _)| tied_end == end);
+        tied.iter_mut().for_each(|(tied_end, _)| *tied_end = Rational::ZERO);
+
+        xml.close("measure");
+    }
+
+    xml.close("part");
+
+    Ok(())
+}
+
+// splits notes into voices where no notes overlap, putting each note in the lowest voice
+// that's free when it starts
+fn voices(notes: &[TimedNote]) -> Vec<Vec<&TimedNote>> {
+    let mut sorted = notes.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|note| note.onset);
+
+    let mut voices = Vec::<Vec<&TimedNote>>::new();
+
+    for note in sorted {
+        match voices.iter_mut().find(|voice| voice.last().is_none_or(|last| last.end() <= note.onset)) {
+            Some(voice) => voice.push(note),
+            None => voices.push(vec![note]),
+        }
+    }
+
+    voices
+}
+
+// writes a chord symbol, at an offset in divisions from the start of the measure
+#[cfg(feature = "experimental-chords")]
+fn write_harmony(xml: &mut XmlWriter, chord: &Chord, offset: i64) -> Option<()> {
+    fn write_pitch(xml: &mut XmlWriter, name: &str, pitch: Pitch) {
+        let alter = pitch.accidental().offset_semitones().0;
+
+        xml.open(name, &[]);
+        xml.text(&format!("{name}-step"), pitch.letter());
+
+        if alter != 0 {
+            xml.text(&format!("{name}-alter"), alter);
+        }
+
+        xml.close(name);
+    }
+
+    let bass = (chord.inversion() != 0).then(|| chord.pitches()[0]);
+    let (kind, degrees) = harmony_kind(chord, bass)?;
+
+    xml.open("harmony", &[]);
+    write_pitch(xml, "root", chord.root);
+    xml.text("kind", kind);
+
+    if let Some(bass) = bass {
+        write_pitch(xml, "bass", bass);
+    }
+
+    for (ty, value, alter) in degrees {
+        let ty = match ty {
+            DegreeType::Add => "add",
+            DegreeType::Alter => "alter",
+            DegreeType::Subtract => "subtract",
+        };
+
+        xml.open("degree", &[]);
+        xml.text("degree-value", value);
+        xml.text("degree-alter", alter);
+        xml.text("degree-type", ty);
+        xml.close("degree");
+    }
+
+    if offset != 0 {
+        xml.text("offset", offset);
+    }
+
+    xml.close("harmony");
+
+    Some(())
+}
+
+// finds the chord kind that spells a chord with the fewest degrees, checking that
+// it's read back as the same chord
+#[cfg(feature = "experimental-chords")]
+fn harmony_kind(chord: &Chord, bass: Option<Pitch>) -> Option<(&'static str, Vec<Degree>)> {
+    HARMONY_KINDS.iter()
+        .filter_map(|&(kind, quality)| {
+            let reference = format!("C{quality}").parse::<Chord>().expect("every kind is a valid chord symbol");
+            let degrees = degrees(reference.intervals(), chord.intervals())?;
+
+            let read = harmony_symbol(chord.root, quality, &degrees, bass).parse::<Chord>();
+            read.is_ok_and(|read| read == *chord).then_some((kind, degrees))
+        })
+        .min_by_key(|(_, degrees)| degrees.len())
+}
+
+// the degrees that turn the intervals of a chord kind into the intervals of a chord, where a
+// tone that replaces one with the same number is an alteration
+#[cfg(feature = "experimental-chords")]
+fn degrees(kind: &[Interval], chord: &[Interval]) -> Option<Vec<Degree>> {
+    let mut removed = kind.iter().filter(|interval| !chord.contains(interval)).copied().collect::<Vec<_>>();
+    let mut degrees = Vec::new();
+
+    for &interval in chord.iter().filter(|interval| !kind.contains(interval)) {
+        let number = interval.number();
+        let alter = interval.semitones().0 - Interval::new_maj_or_perfect(number).semitones().0;
+
+        let ty = match removed.iter().position(|removed| removed.number() == number) {
+            Some(i) => {
+                removed.remove(i);
+                DegreeType::Alter
+            }
+            None => DegreeType::Add,
+        };
+
+        degrees.push((ty, u8::try_from(number.get()).ok()?, alter));
+    }
+
+    for interval in removed {
+        degrees.push((DegreeType::Subtract, u8::try_from(interval.number().get()).ok()?, 0));
+    }
+
+    Some(degrees)
+}
+
+fn write_clef(xml: &mut XmlWriter, clef: PitchClef) {
+    let anchor = clef.anchor();
+
+    let (sign, octave) = match anchor.letter {
+        Letter::G => ("G", 4),
+        Letter::F => ("F", 3),
+        _ => ("C", 4),
+    };
+
+    xml.open("clef", &[]);
+    xml.text("sign", sign);
+    xml.text("line", clef.staff_line());
+
+    if anchor.octave != octave {
+        xml.text("clef-octave-change", anchor.octave - octave);
+    }
+
+    xml.close("clef");
+}
+
+// writes a note, or each note of a chord, or a rest
+fn write_note(xml: &mut XmlWriter, note: &TimedNote, voice: usize, duration: i64, tied_from: &[Note]) -> Result<(), WriteMusicXmlError> {
+    // a rest is written like a chord with no pitches
+    let pitches = if note.is_rest() { vec![None] } else { note.notes.iter().map(Some).collect() };
+
+    for (i, pitch) in pitches.into_iter().enumerate() {
+        let stop = pitch.is_some_and(|pitch| tied_from.contains(pitch));
+        let start = pitch.is_some() && note.tied;
+
+        xml.open("note", &[]);
+
+        if i != 0 {
+            xml.empty("chord", &[]);
+        }
+
+        match pitch {
+            Some(pitch) => {
+                if !(0..=9).contains(&pitch.octave) {
+                    return Err(WriteMusicXmlError::InvalidOctave(*pitch));
+                }
+
+                xml.open("pitch", &[]);
+                xml.text("step", pitch.pitch.letter());
+
+                let alter = pitch.pitch.accidental().offset_semitones().0;
+
+                if alter != 0 {
+                    xml.text("alter", alter);
+                }
+
+                xml.text("octave", pitch.octave);
+                xml.close("pitch");
+            }
+            None => xml.empty("rest", &[]),
+        }
+
+        xml.text("duration", duration);
+
+        for (tie, kind) in [(stop, "stop"), (start, "start")] {
+            if tie {
+                xml.empty("tie", &[("type", kind)]);
+            }
+        }
+
+        xml.text("voice", voice);
+        xml.text("type", note_type(note.duration.value));
+
+        for _ in 0..note.duration.dots {
+            xml.empty("dot", &[]);
+        }
+
+        if !note.duration.tuplets.is_empty() {
+            let (actual, normal) = note.duration.tuplets.iter()
+                .fold((1, 1), |(actual, normal), tuplet| (actual * tuplet.actual() as u32, normal * tuplet.normal() as u32));
+
+            xml.open("time-modification", &[]);
+            xml.text("actual-notes", actual);
+            xml.text("normal-notes", normal);
+            xml.close("time-modification");
+        }
+
+        if stop || start {
+            xml.open("notations", &[]);
+
+            for (tie, kind) in [(stop, "stop"), (start, "start")] {
+                if tie {
+                    xml.empty("tied", &[("type", kind)]);
+                }
+            }
+
+            xml.close("notations");
+        }
+
+        xml.close("note");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pitch;
+    use crate::harmony::{DiatonicMode, Key};
+    use crate::notation::musicxml::xml::{self, Element};
+    use crate::rhythm::{Measure, TimeSignature};
+
+    #[test]
+    fn write_score() {
+        let c4 = Note::new(Pitch::C, 4);
+        let b_flat3 = Note::new(Pitch::B_FLAT, 3);
+
+        let notes = [
+            TimedNote::note(b_flat3, Rational::ZERO, "4".parse().unwrap()),
+            TimedNote::chord(vec![c4, Note::new(Pitch::E_FLAT, 4)], Rational::new(1, 4), "2.".parse().unwrap()).tied(),
+            TimedNote::note(c4, Rational::ONE, "8 3:2".parse().unwrap()),
+            TimedNote::note(Note::new(Pitch::D, 4), Rational::new(13, 12), "8 3:2".parse().unwrap()),
+            TimedNote::note(b_flat3, Rational::new(7, 6), "8 3:2".parse().unwrap()),
+        ];
+
+        let measures = Measure::from_notes(&notes, TimeSignature::COMMON, Some(Rational::new(1, 4))).unwrap();
+
+        let mut part = Part::new("Clarinet & Voice", Key::new(Pitch::C, DiatonicMode::Dorian), PitchClef::TREBLE_8VB, measures);
+        part.clefs.push((1, PitchClef::ALTO));
+
+        let score = Score { title: Some("Étude".to_owned()), parts: vec![part] };
+
+        let expected = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>
+<!DOCTYPE score-partwise PUBLIC \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" \"http://www.musicxml.org/dtds/partwise.dtd\">
+<score-partwise version=\"4.0\">
+  <work>
+    <work-title>Étude</work-title>
+  </work>
+  <part-list>
+    <score-part id=\"P1\">
+      <part-name>Clarinet &amp; Voice</part-name>
+    </score-part>
+  </part-list>
+  <part id=\"P1\">
+    <measure number=\"0\" implicit=\"yes\">
+      <attributes>
+        <divisions>3</divisions>
+        <key>
+          <fifths>-2</fifths>
+          <mode>dorian</mode>
+        </key>
+        <time>
+          <beats>4</beats>
+          <beat-type>4</beat-type>
+        </time>
+        <clef>
+          <sign>G</sign>
+          <line>2</line>
+          <clef-octave-change>-1</clef-octave-change>
+        </clef>
+      </attributes>
+      <note>
+        <pitch>
+          <step>B</step>
+          <alter>-1</alter>
+          <octave>3</octave>
+        </pitch>
+        <duration>3</duration>
+        <voice>1</voice>
+        <type>quarter</type>
+      </note>
+    </measure>
+    <measure number=\"1\">
+      <attributes>
+        <clef>
+          <sign>C</sign>
+          <line>3</line>
+        </clef>
+      </attributes>
+      <note>
+        <pitch>
+          <step>C</step>
+          <octave>4</octave>
+        </pitch>
+        <duration>9</duration>
+        <tie type=\"start\"/>
+        <voice>1</voice>
+        <type>half</type>
+        <dot/>
+        <notations>
+          <tied type=\"start\"/>
+        </notations>
+      </note>
+      <note>
+        <chord/>
+        <pitch>
+          <step>E</step>
+          <alter>-1</alter>
+          <octave>4</octave>
+        </pitch>
+        <duration>9</duration>
+        <tie type=\"start\"/>
+        <voice>1</voice>
+        <type>half</type>
+        <dot/>
+        <notations>
+          <tied type=\"start\"/>
+        </notations>
+      </note>
+      <note>
+        <pitch>
+          <step>C</step>
+          <octave>4</octave>
+        </pitch>
+        <duration>1</duration>
+        <tie type=\"stop\"/>
+        <voice>1</voice>
+        <type>eighth</type>
+        <time-modification>
+          <actual-notes>3</actual-notes>
+          <normal-notes>2</normal-notes>
+        </time-modification>
+        <notations>
+          <tied type=\"stop\"/>
+        </notations>
+      </note>
+      <note>
+        <pitch>
+          <step>D</step>
+          <octave>4</octave>
+        </pitch>
+        <duration>1</duration>
+        <voice>1</voice>
+        <type>eighth</type>
+        <time-modification>
+          <actual-notes>3</actual-notes>
+          <normal-notes>2</normal-notes>
+        </time-modification>
+      </note>
+      <note>
+        <pitch>
+          <step>B</step>
+          <alter>-1</alter>
+          <octave>3</octave>
+        </pitch>
+        <duration>1</duration>
+        <voice>1</voice>
+        <type>eighth</type>
+        <time-modification>
+          <actual-notes>3</actual-notes>
+          <normal-notes>2</normal-notes>
+        </time-modification>
+      </note>
+    </measure>
+  </part>
+</score-partwise>
+";
+
+        assert_eq!(score.to_musicxml().unwrap(), expected);
+    }
+
+    #[test]
+    fn write_voices() {
+        let note = |s: &str| s.parse::<Note>().unwrap();
+        let quarter = || "4".parse().unwrap();
+
+        // a held note under a moving line, tied into a second measure
+        let mut first = Measure::new(TimeSignature::COMMON);
+        first.notes = vec![
+            TimedNote::note(note("E5"), Rational::ZERO, quarter()),
+            TimedNote::note(note("C4"), Rational::ZERO, "1".parse().unwrap()).tied(),
+            TimedNote::note(note("D5"), Rational::new(1, 4), quarter()),
+            TimedNote::note(note("C5"), Rational::new(3, 4), quarter()),
+        ];
+
+        let mut second = Measure::new(TimeSignature::COMMON);
+        second.notes = vec![
+            TimedNote::note(note("C4"), Rational::ZERO, quarter()),
+            TimedNote::note(note("G4"), Rational::ZERO, "2".parse().unwrap()),
+        ];
+
+        let part = Part::new("Piano", Key::major(Pitch::C), PitchClef::TREBLE, vec![first, second]);
+        let score = Score { title: None, parts: vec![part] };
+
+        let xml = score.to_musicxml().unwrap();
+        let compact = xml.lines().map(str::trim).collect::<String>();
+
+        // the first voice skips the gap before its last note, and the second goes back to the start
+        assert!(compact.contains("<forward><duration>1</duration></forward><note><pitch><step>C</step><octave>5</octave>"));
+        assert!(compact.contains("<backup><duration>4</duration></backup><note><pitch><step>C</step><octave>4</octave></pitch><duration>4</duration><tie type=\"start\"/><voice>2</voice>"));
+        assert!(compact.contains("<octave>4</octave></pitch><duration>1</duration><tie type=\"stop\"/><voice>1</voice>"));
+        assert!(compact.contains("<backup><duration>1</duration></backup><note><pitch><step>G</step><octave>4</octave></pitch><duration>2</duration><voice>2</voice>"));
+        assert_eq!(compact.matches("<backup>").count(), 2);
+
+        assert_eq!(Score::from_musicxml(&xml), Ok(score));
+    }
+
+    // alternative children, and the fewest and most times they can appear in a row
+    type Slot = (&'static [&'static str], usize, usize);
+
+    // the content models of the elements that are written, transcribed by hand from the MusicXML 4.0
+    // schema, as slots of children in order. This is only a partial structural check, since the XSD
+    // isn't bundled to validate against: it checks which children each element has and their order,
+    // but not attributes, text values, or the content of elements that are never written.
+    const SCHEMA: &[(&str, &[Slot])] = &[
+        ("score-partwise", &[
+            (&["work"], 0, 1), (&["movement-number"], 0, 1), (&["movement-title"], 0, 1), (&["identification"], 0, 1),
+            (&["defaults"], 0, 1), (&["credit"], 0, usize::MAX), (&["part-list"], 1, 1), (&["part"], 1, usize::MAX),
+        ]),
+        ("work", &[(&["work-number"], 0, 1), (&["work-title"], 0, 1), (&["opus"], 0, 1)]),
+        ("part-list", &[(&["part-group", "score-part"], 1, usize::MAX)]),
+        ("score-part", &[(&["identification"], 0, 1), (&["part-link"], 0, usize::MAX), (&["part-name"], 1, 1)]),
+        ("part", &[(&["measure"], 1, usize::MAX)]),
+        ("measure", &[(&["note", "backup", "forward", "direction", "attributes", "harmony", "barline"], 0, usize::MAX)]),
+        ("attributes", &[
+            (&["footnote"], 0, 1), (&["level"], 0, 1), (&["divisions"], 0, 1), (&["key"], 0, usize::MAX),
+            (&["time"], 0, usize::MAX), (&["staves"], 0, 1), (&["part-symbol"], 0, 1), (&["instruments"], 0, 1),
+            (&["clef"], 0, usize::MAX), (&["staff-details"], 0, usize::MAX), (&["transpose", "for-part"], 0, usize::MAX),
+            (&["directive"], 0, usize::MAX), (&["measure-style"], 0, usize::MAX),
+        ]),
+        ("key", &[(&["cancel"], 0, 1), (&["fifths"], 1, 1), (&["mode"], 0, 1), (&["key-octave"], 0, usize::MAX)]),
+        ("time", &[(&["beats"], 1, 1), (&["beat-type"], 1, 1), (&["interchangeable"], 0, 1)]),
+        ("clef", &[(&["sign"], 1, 1), (&["line"], 0, 1), (&["clef-octave-change"], 0, 1)]),
+        ("note", &[
+            (&["chord"], 0, 1), (&["pitch", "unpitched", "rest"], 1, 1), (&["duration"], 1, 1), (&["tie"], 0, 2),
+            (&["instrument"], 0, usize::MAX), (&["footnote"], 0, 1), (&["level"], 0, 1), (&["voice"], 0, 1),
+            (&["type"], 0, 1), (&["dot"], 0, usize::MAX), (&["accidental"], 0, 1), (&["time-modification"], 0, 1),
+            (&["stem"], 0, 1), (&["notehead"], 0, 1), (&["notehead-text"], 0, 1), (&["staff"], 0, 1),
+            (&["beam"], 0, 8), (&["notations"], 0, usize::MAX), (&["lyric"], 0, usize::MAX), (&["play"], 0, 1), (&["listen"], 0, 1),
+        ]),
+        ("pitch", &[(&["step"], 1, 1), (&["alter"], 0, 1), (&["octave"], 1, 1)]),
+        ("time-modification", &[(&["actual-notes"], 1, 1), (&["normal-notes"], 1, 1), (&["normal-type"], 0, 1), (&["normal-dot"], 0, usize::MAX)]),
+        ("notations", &[(&["footnote"], 0, 1), (&["level"], 0, 1), (&["tied", "slur", "tuplet", "ornaments", "articulations"], 0, usize::MAX)]),
+        ("backup", &[(&["duration"], 1, 1), (&["footnote"], 0, 1), (&["level"], 0, 1)]),
+        ("forward", &[(&["duration"], 1, 1), (&["footnote"], 0, 1), (&["level"], 0, 1), (&["voice"], 0, 1), (&["staff"], 0, 1)]),
+        ("harmony", &[
+            (&["root", "numeral", "function"], 1, 1), (&["kind"], 1, 1), (&["inversion"], 0, 1), (&["bass"], 0, 1),
+            (&["degree"], 0, usize::MAX), (&["frame"], 0, 1), (&["offset"], 0, 1), (&["footnote"], 0, 1),
+            (&["level"], 0, 1), (&["staff"], 0, 1),
+        ]),
+        ("root", &[(&["root-step"], 1, 1), (&["root-alter"], 0, 1)]),
+        ("bass", &[(&["bass-separator"], 0, 1), (&["bass-step"], 1, 1), (&["bass-alter"], 0, 1)]),
+        ("degree", &[(&["degree-value"], 1, 1), (&["degree-alter"], 1, 1), (&["degree-type"], 1, 1)]),
+    ];
+
+    // checks that the children of an element, and all of theirs, match the schema
+    fn check_schema(element: &Element) {
+        let Some(&(_, slots)) = SCHEMA.iter().find(|&&(name, _)| name == element.name) else {
+            assert!(element.elements().next().is_none(), "<{}> should only have text", element.name);
+            return;
+        };
+
+        let mut counts = vec![0; slots.len()];
+        let mut slot = 0;
+
+        for child in element.elements() {
+            let Some(next) = (slot..slots.len()).find(|&i| slots[i].0.contains(&child.name.as_str())) else {
+                panic!("<{}> can't be in <{}> after {:?}", child.name, element.name, slots[slot].0);
+            };
+
+            slot = next;
+            counts[slot] += 1;
+
+            assert!(counts[slot] <= slots[slot].2, "too many {:?} in <{}>", slots[slot].0, element.name);
+            check_schema(child);
+        }
+
+        for (&(names, min, _), &count) in slots.iter().zip(&counts) {
+            assert!(count >= min, "<{}> is missing {names:?}", element.name);
+        }
+    }
+
+    // checks the order of a written score's elements against the transcribed content models,
+    // which is only a partial check of the schema, see `SCHEMA`
+    #[test]
+    fn schema_order() {
+        let note = |s: &str| s.parse::<Note>().unwrap();
+
+        let notes = [
+            TimedNote::note(note("Bb3"), Rational::ZERO, "4".parse().unwrap()),
+            TimedNote::chord(vec![note("C4"), note("Eb4")], Rational::new(1, 4), "2.".parse().unwrap()).tied(),
+            TimedNote::note(note("C4"), Rational::ONE, "8 3:2".parse().unwrap()),
+            TimedNote::rest(Rational::new(13, 12), "8 3:2".parse().unwrap()),
+            TimedNote::note(note("F#4"), Rational::new(7, 6), "8 3:2".parse().unwrap()),
+        ];
+
+        let mut measures = Measure::from_notes(&notes, "3+2/8".parse().unwrap(), Some(Rational::new(1, 4))).unwrap();
+
+        // a second voice under the chord
+        measures[1].notes.push(TimedNote::note(note("E4"), Rational::new(1, 8), "8.".parse().unwrap()));
+
+        let mut part = Part::new("Clarinet", Key::new(Pitch::C, DiatonicMode::Dorian), PitchClef::TREBLE_8VB, measures);
+        part.clefs.push((1, PitchClef::ALTO));
+        part.keys.push((2, Key::from_sharps(4, DiatonicMode::NATURAL_MINOR)));
+
+        #[cfg(feature = "experimental-chords")]
+        part.harmonies.extend([
+            (0, Rational::ZERO, "Bb7#9b5".parse().unwrap()),
+            (1, Rational::new(1, 8), "Dm7/C".parse().unwrap()),
+            (1, Rational::new(1, 4), "C7alt".parse().unwrap()),
+        ]);
+
+        let score = Score { title: Some("Étude".to_owned()), parts: vec![part] };
+        let xml = score.to_musicxml().unwrap();
+
+        // make sure the score has what's being checked
+        for element in ["chord", "tie", "tied", "time-modification", "backup", "forward", "dot"] {
+            assert!(xml.contains(&format!("<{element}")), "{element} isn't written");
+        }
+
+        #[cfg(feature = "experimental-chords")]
+        for element in ["harmony", "degree", "bass", "offset"] {
+            assert!(xml.contains(&format!("<{element}")), "{element} isn't written");
+        }
+
+        let root = xml::parse(&xml).unwrap();
+
+        assert_eq!(root.name, "score-partwise");
+        check_schema(&root);
+
+        let swapped = xml::parse("<note><duration>1</duration><rest/></note>").unwrap();
+        assert!(std::panic::catch_unwind(|| check_schema(&swapped)).is_err());
+    }
+
+    #[test]
+    fn invalid_scores() {
+        use WriteMusicXmlError as E;
+
+        let part = Part::new("Piano", Key::major(Pitch::C), PitchClef::TREBLE, vec![Measure::new(TimeSignature::COMMON)]);
+        let score = |part: Part| Score { title: None, parts: vec![part] };
+
+        assert_eq!(Score::default().to_musicxml(), Err(E::NoParts));
+        assert_eq!(score(Part { measures: Vec::new(), ..part.clone() }).to_musicxml(), Err(E::EmptyPart(0)));
+
+        let mut late_clef = part.clone();
+        late_clef.clefs.push((1, PitchClef::BASS));
+        assert_eq!(score(late_clef).to_musicxml(), Err(E::InvalidMeasureIndex { part: 0, measure: 1 }));
+
+        let mut low = part.clone();
+        low.measures[0].push(vec![Note::new(Pitch::B, -1)], "1".parse().unwrap());
+        assert_eq!(score(low).to_musicxml(), Err(E::InvalidOctave(Note::new(Pitch::B, -1))));
+
+        let mut too_fine = part.clone();
+        too_fine.measures[0].notes = [2_147_483_647, 2_147_483_629, 1_000_000_007].into_iter()
+            .map(|prime| TimedNote::rest(Rational::new(1, prime), "1024".parse().unwrap()))
+            .collect();
+        assert_eq!(score(too_fine).to_musicxml(), Err(E::TooManyDivisions(0)));
+
+        #[cfg(feature = "experimental-chords")]
+        {
+            use crate::{Interval, chord::Chord};
+
+            let mut octave = part.clone();
+            let chord = Chord::from_intervals(vec![Interval::PERFECT_UNISON, Interval::PERFECT_OCTAVE], Pitch::C, 0).unwrap();
+            octave.harmonies = vec![(0, Rational::ZERO, "C".parse().unwrap()), (0, Rational::ZERO, chord)];
+            assert_eq!(score(octave).to_musicxml(), Err(E::UnsupportedChord { part: 0, harmony: 1 }));
+
+            let mut late_chord = part;
+            late_chord.harmonies.push((1, Rational::ZERO, "C".parse().unwrap()));
+            assert_eq!(score(late_chord).to_musicxml(), Err(E::InvalidMeasureIndex { part: 0, measure: 1 }));
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+// escapes text and attribute values
+pub(crate) fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+// writes indented XML, one element per line
+pub(crate) struct XmlWriter {
+    out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    pub(crate) fn new(prolog: &str) -> Self {
+        Self { out: prolog.to_owned(), depth: 0 }
+    }
+
+    fn start(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.out.push_str(&"  ".repeat(self.depth));
+        self.out.push('<');
+        self.out.push_str(tag);
+
+        for (name, value) in attributes {
+            write!(self.out, " {name}=\"{}\"", escape(value)).expect("writing to a string can't fail");
+        }
+    }
+
+    pub(crate) fn open(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.start(tag, attributes);
+        self.out.push_str(">\n");
+        self.depth += 1;
+    }
+
+    pub(crate) fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.out.push_str(&"  ".repeat(self.depth));
+        writeln!(self.out, "</{tag}>").expect("writing to a string can't fail");
+    }
+
+    pub(crate) fn empty(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.start(tag, attributes);
+        self.out.push_str("/>\n");
+    }
+
+    pub(crate) fn text(&mut self, tag: &str, text: impl fmt::Display) {
+        self.start(tag, &[]);
+        writeln!(self.out, ">{}</{tag}>", escape(&text.to_string())).expect("writing to a string can't fail");
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.out
+    }
+}
+
+// an element of a parsed document, with its attributes and content in order
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // the child elements, skipping text
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub(crate) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements().filter(move |element| element.name == name)
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    // the text directly inside the element, without surrounding whitespace
+    pub(crate) fn text(&self) -> String {
+        let text = self.children.iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect::<String>();
+
+        text.trim().to_owned()
+    }
+}
+
+// why a document couldn't be parsed, and the byte offset where it happened
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct XmlError {
+    pub(crate) offset: usize,
+    pub(crate) reason: &'static str,
+}
+
+// parses the root element of a document, skipping the prolog, comments, processing
+// instructions, and the document type declaration, which isn't used to validate it
+pub(crate) fn parse(document: &str) -> Result<Element, XmlError> {
+    let mut parser = Parser { document, position: 0 };
+
+    parser.eat("\u{feff}");
+    parser.misc()?;
+
+    if !parser.rest().starts_with('<') {
+        return Err(parser.error("expected the root element"));
+    }
+
+    let root = parser.element(1)?;
+
+    parser.misc()?;
+
+    if !parser.rest().is_empty() {
+        return Err(parser.error("expected the end of the document"));
+    }
+
+    Ok(root)
+}
+
+// how deeply elements can be nested, which is far more than any score needs, so that
+// parsing a malicious document can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    document: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.document[self.position..]
+    }
+
+    fn error(&self, reason: &'static str) -> XmlError {
+        XmlError { offset: self.position, reason }
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+
+        if found {
+            self.position += prefix.len();
+        }
+
+        found
+    }
+
+    fn expect(&mut self, prefix: &str, reason: &'static str) -> Result<(), XmlError> {
+        if self.eat(prefix) { Ok(()) } else { Err(self.error(reason)) }
+    }
+
+    // returns everything before `end`, and moves past it
+    fn until(&mut self, end: &str, reason: &'static str) -> Result<&'a str, XmlError> {
+        let Some(length) = self.rest().find(end) else {
+            return Err(self.error(reason));
+        };
+
+        let skipped = &self.rest()[..length];
+        self.position += length + end.len();
+
+        Ok(skipped)
+    }
+
+    fn whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn name(&mut self) -> Result<String, XmlError> {
+        let rest = self.rest();
+        let length = rest.find(|c: char| c.is_whitespace() || "/>=<'\"".contains(c)).unwrap_or(rest.len());
+
+        if length == 0 {
+            return Err(self.error("expected a name"));
+        }
+
+        self.position += length;
+        Ok(rest[..length].to_owned())
+    }
+
+    // skips whitespace, comments, processing instructions, and document type declarations
+    fn misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.whitespace();
+
+            if self.eat("<!--") {
+                self.until("-->", "unterminated comment")?;
+            } else if self.eat("<?") {
+                self.until("?>", "unterminated processing instruction")?;
+            } else if self.eat("<!DOCTYPE") {
+                self.doctype()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn doctype(&mut self) -> Result<(), XmlError> {
+        loop {
+            let Some(c) = self.rest().chars().next() else {
+                return Err(self.error("unterminated document type declaration"));
+            };
+
+            self.position += c.len_utf8();
+
+            match c {
+                '>' => return Ok(()),
+                '"' | '\'' => {
+                    self.until(if c == '"' { "\"" } else { "'" }, "unterminated literal")?;
+                }
+                '[' => {
+                    self.until("]", "unterminated internal subset")?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, depth: usize) -> Result<Element, XmlError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+
+        self.expect("<", "expected an element")?;
+
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+
+        loop {
+            self.whitespace();
+
+            if self.eat("/>") {
+                return Ok(Element { name, attributes, children: Vec::new() });
+            } else if self.eat(">") {
+                break;
+            }
+
+            let attribute = self.name()?;
+
+            self.whitespace();
+            self.expect("=", "expected '=' after an attribute name")?;
+            self.whitespace();
+
+            let value = if self.eat("\"") {
+                self.until("\"", "unterminated attribute value")?
+            } else if self.eat("'") {
+                self.until("'", "unterminated attribute value")?
+            } else {
+                return Err(self.error("expected a quoted attribute value"));
+            };
+
+            let offset = self.position - value.len() - 1;
+            attributes.push((attribute, decode(value, offset)?));
+        }
+
+        let mut children = Vec::new();
+
+        loop {
+            if self.eat("</") {
+                if self.name()? != name {
+                    return Err(self.error("mismatched end tag"));
+                }
+
+                self.whitespace();
+                self.expect(">", "expected '>' after an end tag")?;
+
+                return Ok(Element { name, attributes, children });
+            } else if self.eat("<!--") {
+                self.until("-->", "unterminated comment")?;
+            } else if self.eat("<![CDATA[") {
+                let text = self.until("]]>", "unterminated CDATA section")?;
+                children.push(Node::Text(text.to_owned()));
+            } else if self.eat("<?") {
+                self.until("?>", "unterminated processing instruction")?;
+            } else if self.rest().starts_with('<') {
+                children.push(Node::Element(self.element(depth + 1)?));
+            } else if self.rest().is_empty() {
+                return Err(self.error("unterminated element"));
+            } else {
+                let offset = self.position;
+                let length = self.rest().find('<').unwrap_or(self.rest().len());
+                let text = &self.rest()[..length];
+
+                self.position += length;
+                children.push(Node::Text(decode(text, offset)?));
+            }
+        }
+    }
+}
+
+// replaces entity and character references
+fn decode(text: &str, offset: usize) -> Result<String, XmlError> {
+    if !text.contains('&') {
+        return Ok(text.to_owned());
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let error = |reason| XmlError { offset: offset + text.len() - rest.len() - 1, reason };
+
+        let Some(end) = rest.find(';') else {
+            return Err(error("unterminated reference"));
+        };
+
+        let c = match &rest[..end] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            reference => {
+                let code = if let Some(hex) = reference.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(decimal) = reference.strip_prefix('#') {
+                    decimal.parse().ok()
+                } else {
+                    return Err(error("unknown entity"));
+                };
+
+                code.and_then(char::from_u32).ok_or_else(|| error("invalid character reference"))?
+            }
+        };
+
+        decoded.push(c);
+        rest = &rest[end + 1..];
+    }
+
+    decoded.push_str(rest);
+    Ok(decoded)
+}
@@ -13,10 +13,12 @@
 use std::cmp::Ordering;
 use std::fmt;
 use std::ops::{Add, Sub};
+use std::str::FromStr;
 use crate::enharmonic::{EnharmonicEq, EnharmonicOrd};
 use crate::harmony::Key;
-use crate::{Pitch, PitchClass, Interval, Semitones};
+use crate::{Pitch, PitchClass, Letter, AccidentalSign, Interval, Semitones};
 use crate::pitch::Spelling;
+use crate::tuning::Tuning;
 
 /// A musical note with pitch and octave.
 ///
@@ -50,6 +52,13 @@ use crate::pitch::Spelling;
 /// assert_eq!(Note::A4.as_frequency_hz(), 440.0);
 /// ```
 ///
+/// And can be [parsed][FromStr] from scientific pitch notation:
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// let bb3 = "Bb3".parse::<Note>().expect("should be a valid note");
+/// assert_eq!(bb3, Note::new(Pitch::B_FLAT, 3));
+/// ```
+///
 /// [`Letter`]: crate::pitch::Letter
 /// [`AccidentalSign`]: crate::pitch::AccidentalSign
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -134,6 +143,9 @@ impl Note {
     /// Returns `None` if the frequency is non-positive or not finite. Some [subnormal](f32::is_subnormal) floats
     /// may also return `None`.
     ///
+    /// To use a different tuning, see [`from_frequency_hz_with`](Self::from_frequency_hz_with), and to
+    /// also get how many cents the frequency is from the note, see [`Tuning::deviation`].
+    ///
     /// # Examples
     /// ```
     /// # use music_theory::Note;
@@ -149,33 +161,30 @@ impl Note {
     /// ```
     // TODO: this includes spelling, when it probably shouldn't
     pub fn from_frequency_hz(hz: f32) -> Option<Self> {
-        if hz <= 0.0 || !hz.is_finite() {
-            return None;
-        }
-
-        let semitones_from_a4 = 12.0 * (hz / 440.0).log2();
-
-        if !semitones_from_a4.is_finite() {
-            return None;
-        }
-
-        let semitones_from_c0 = semitones_from_a4.round() as i16 + 9 + 4 * 12;
-
-        let octave = semitones_from_c0.div_euclid(12);
-
-        let pitch = semitones_from_c0.rem_euclid(12)
-            .try_into()
-            .expect("i32::rem_euclid(12) must be within [0,12)");
-
-        let pitch = PitchClass::from_chroma(pitch)
-            .expect("i32::rem_euclid(12) must be within [0,12)");
+        Self::from_frequency_hz_with(hz as f64, &Tuning::A440)
+    }
 
-        Some( Self { pitch: Pitch::from(pitch), octave } )
+    /// Returns the closest `Note` from a frequency in Hz in the given [`Tuning`], spelled with sharps.
+    ///
+    /// Returns `None` if the frequency is non-positive or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::tuning::Tuning;
+    /// let a415 = Tuning::equal(415.0);
+    ///
+    /// // 440 Hz is a semitone higher than A4 at A415
+    /// assert_eq!(Note::from_frequency_hz_with(440.0, &a415), Some(Note::new(Pitch::A_SHARP, 4)));
+    /// ```
+    pub fn from_frequency_hz_with(hz: f64, tuning: &Tuning) -> Option<Self> {
+        tuning.nearest_note(hz)
     }
 
     /// Converts the note to its frequency in Hz, using 12-tone equal temperament.
     ///
-    /// Uses A4 = 440 Hz as the reference pitch.
+    /// Uses A4 = 440 Hz as the reference pitch. To use a different tuning, see
+    /// [`as_frequency_hz_with`](Self::as_frequency_hz_with).
     ///
     /// # Examples
     /// ```
@@ -190,9 +199,25 @@ impl Note {
     /// assert!((d2_freq - 73.4162).abs() < 1e-4);
     /// ```
     pub fn as_frequency_hz(self) -> f32 {
-        let semitones_from_a4 = Self::A4.semitones_to(self);
+        self.as_frequency_hz_with(&Tuning::A440) as f32
+    }
 
-        440.0 * f32::exp2(semitones_from_a4.0 as f32 / 12.0)
+    /// Converts the note to its frequency in Hz in the given [`Tuning`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::tuning::{Temperament, Tuning};
+    /// let werckmeister = Tuning::new(Temperament::WerckmeisterIII, 415.0);
+    ///
+    /// assert_eq!(Note::A4.as_frequency_hz_with(&werckmeister), 415.0);
+    ///
+    /// // C5 is higher than in equal temperament, since A is tuned low
+    /// let c5 = Note::new(Pitch::C, 5);
+    /// assert!(c5.as_frequency_hz_with(&werckmeister) > c5.as_frequency_hz_with(&Tuning::equal(415.0)));
+    /// ```
+    pub fn as_frequency_hz_with(self, tuning: &Tuning) -> f64 {
+        tuning.frequency(self)
     }
 
     /// Transposes the note by the given interval. Has the same behavior as the [`+` operator](Add::add).
@@ -523,6 +548,64 @@ impl Note {
             .. unchecked
         }
     }
+
+    /// Parses a note written in Helmholtz pitch notation.
+    ///
+    /// In Helmholtz notation, the case of the letter and the octave marks that follow it
+    /// determine the octave:
+    /// - Uppercase letters are in octave 2 (the "great" octave), and each comma (`,`) lowers the note an octave.
+    /// - Lowercase letters are in octave 3 (the "small" octave), and each prime (`'` or `′`) raises the note an octave.
+    ///
+    /// Accidentals are written between the letter and the octave marks, and accept the same
+    /// symbols as [`Note::from_str`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseNoteError`] if the string isn't a valid note. Primes after an uppercase
+    /// letter or commas after a lowercase letter are reported as [`ParseNoteError::InvalidOctave`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::ParseNoteError;
+    /// assert_eq!(Note::from_helmholtz("c'"), Ok(Note::MIDDLE_C));
+    /// assert_eq!(Note::from_helmholtz("a'"), Ok(Note::A4));
+    /// assert_eq!(Note::from_helmholtz("f#''"), Ok(Note::new(Pitch::F_SHARP, 5)));
+    ///
+    /// // Uppercase letters are in the great octave, and go down with commas
+    /// assert_eq!(Note::from_helmholtz("C"), Ok(Note::new(Pitch::C, 2)));
+    /// assert_eq!(Note::from_helmholtz("Bb,,"), Ok(Note::new(Pitch::B_FLAT, 0)));
+    ///
+    /// // Octave marks must match the case of the letter
+    /// assert_eq!(Note::from_helmholtz("C'"), Err(ParseNoteError::InvalidOctave));
+    /// assert_eq!(Note::from_helmholtz("c,"), Err(ParseNoteError::InvalidOctave));
+    /// ```
+    pub fn from_helmholtz(s: &str) -> Result<Self, ParseNoteError> {
+        let (pitch, marks) = parse_letter_and_accidental(s)?;
+
+        let uppercase = s.starts_with(|c: char| c.is_ascii_uppercase());
+
+        let (base, mark, direction) = if uppercase {
+            (2, ",", -1)
+        } else {
+            (3, "'", 1)
+        };
+
+        let marks = marks.replace('′', "'");
+
+        let count = marks.matches(mark).count();
+
+        if count * mark.len() != marks.len() {
+            return Err(ParseNoteError::InvalidOctave);
+        }
+
+        let octave = i16::try_from(count)
+            .ok()
+            .and_then(|count| (base as i16).checked_add(direction * count))
+            .ok_or(ParseNoteError::InvalidOctave)?;
+
+        Ok(Self { pitch, octave })
+    }
 }
 
 impl PartialOrd for Note {
@@ -566,6 +649,160 @@ impl fmt::Display for Note {
     }
 }
 
+/// Error returned when parsing a [`Note`] from a [`&str`](prim@str) fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Note;
+/// # use music_theory::ParseNoteError;
+/// assert_eq!("".parse::<Note>(), Err(ParseNoteError::Empty));
+/// assert_eq!("H4".parse::<Note>(), Err(ParseNoteError::InvalidLetter));
+/// assert_eq!("C#b4".parse::<Note>(), Err(ParseNoteError::InvalidAccidental));
+/// assert_eq!("Eb".parse::<Note>(), Err(ParseNoteError::MissingOctave));
+/// assert_eq!("G99999".parse::<Note>(), Err(ParseNoteError::InvalidOctave));
+/// ```
+#[derive(Debug, thiserror::Error, Eq, PartialEq, Copy, Clone)]
+pub enum ParseNoteError {
+    /// The input string was empty.
+    #[error("The input was empty")]
+    Empty,
+    /// The note didn't start with a valid letter.
+    #[error("Letter must be A, B, C, D, E, F, or G")]
+    InvalidLetter,
+    /// The accidental was invalid, such as mixing sharps and flats.
+    #[error("The accidental was invalid")]
+    InvalidAccidental,
+    /// The note had no octave.
+    #[error("The note didn't have an octave")]
+    MissingOctave,
+    /// The octave was invalid, or out of the range a [`Note`] can hold.
+    #[error("The octave was invalid or out of range")]
+    InvalidOctave,
+}
+
+/// Parses a letter and its accidentals from the start of `s`, returning the pitch and
+/// the remainder of the string.
+pub(crate) fn parse_letter_and_accidental(s: &str) -> Result<(Pitch, &str), ParseNoteError> {
+    let mut chars = s.chars();
+
+    let letter = match chars.next().ok_or(ParseNoteError::Empty)? {
+        'C' | 'c' => Letter::C,
+        'D' | 'd' => Letter::D,
+        'E' | 'e' => Letter::E,
+        'F' | 'f' => Letter::F,
+        'G' | 'g' => Letter::G,
+        'A' | 'a' => Letter::A,
+        'B' | 'b' => Letter::B,
+        _ => return Err(ParseNoteError::InvalidLetter),
+    };
+
+    let mut offset = 0i16;
+    let mut symbols = 0;
+    let mut natural = false;
+
+    let mut rest = chars.as_str();
+
+    loop {
+        let mut chars = rest.chars();
+
+        let step = match chars.next() {
+            Some('#' | '♯') => 1,
+            Some('x' | '𝄪') => 2,
+            Some('b' | '♭') => -1,
+            Some('𝄫') => -2,
+            Some('♮') => {
+                natural = true;
+                0
+            }
+            _ => break,
+        };
+
+        // can't mix sharps and flats, e.g. "C#b"
+        if offset.signum() * step < 0 {
+            return Err(ParseNoteError::InvalidAccidental);
+        }
+
+        offset = offset.checked_add(step).ok_or(ParseNoteError::InvalidAccidental)?;
+        symbols += 1;
+        rest = chars.as_str();
+    }
+
+    // a natural sign can only be written on its own
+    if natural && symbols > 1 {
+        return Err(ParseNoteError::InvalidAccidental);
+    }
+
+    // the internal representation of pitch must be able to hold this many accidentals
+    let fits = offset.checked_mul(7)
+        .and_then(|fifths| fifths.checked_add(letter.fifths_from_c()))
+        .is_some();
+
+    if !fits {
+        return Err(ParseNoteError::InvalidAccidental);
+    }
+
+    let pitch = Pitch::from_letter_and_accidental(letter, AccidentalSign { offset });
+
+    Ok((pitch, rest))
+}
+
+impl FromStr for Note {
+    type Err = ParseNoteError;
+
+    /// Parses a note from scientific pitch notation, such as `C#4` or `Bbb-1`.
+    ///
+    /// A note is a letter, followed by any number of accidentals, followed by the octave.
+    /// Accidentals can be written with ASCII or Unicode symbols:
+    /// - Sharp: `#`, `♯`
+    /// - Flat: `b`, `♭`
+    /// - Double sharp: `x`, `𝄪`
+    /// - Double flat: `𝄫`
+    /// - Natural: `♮`
+    ///
+    /// Accidentals can be repeated (e.g. `F♯𝄪` is a triple sharp), but sharps and flats can't be mixed.
+    /// Letters are case-insensitive. The octave can be negative, and must fit in an [`i16`](prim@i16).
+    ///
+    /// Any note formatted with [`Display`](fmt::Display) can be parsed back into the same note.
+    ///
+    /// To parse Helmholtz pitch notation (like `c'`), see [`Note::from_helmholtz`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseNoteError`] describing why the string couldn't be parsed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::ParseNoteError;
+    /// assert_eq!("C4".parse(), Ok(Note::MIDDLE_C));
+    /// assert_eq!("C#4".parse(), Ok(Note::new(Pitch::C_SHARP, 4)));
+    /// assert_eq!("Bbb-1".parse(), Ok(Note::new(Pitch::B_DOUBLE_FLAT, -1)));
+    /// assert_eq!("F𝄪10".parse(), Ok(Note::new(Pitch::F_DOUBLE_SHARP, 10)));
+    /// assert_eq!("e♭2".parse(), Ok(Note::new(Pitch::E_FLAT, 2)));
+    ///
+    /// // Round trips with Display
+    /// let note = Note::new(Pitch::G_DOUBLE_SHARP, -3);
+    /// assert_eq!(note.to_string().parse(), Ok(note));
+    ///
+    /// // Invalid inputs return errors
+    /// assert_eq!("C".parse::<Note>(), Err(ParseNoteError::MissingOctave));
+    /// assert_eq!("C+4".parse::<Note>(), Err(ParseNoteError::InvalidAccidental));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pitch, octave) = parse_letter_and_accidental(s)?;
+
+        let octave = match octave.chars().next() {
+            None => return Err(ParseNoteError::MissingOctave),
+            Some(c) if c.is_ascii_digit() || c == '-' => octave
+                .parse()
+                .map_err(|_| ParseNoteError::InvalidOctave)?,
+            Some(_) => return Err(ParseNoteError::InvalidAccidental),
+        };
+
+        Ok(Self { pitch, octave })
+    }
+}
+
 impl From<Note> for Pitch {
     fn from(note: Note) -> Self {
         note.pitch
@@ -600,6 +837,91 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn from_str_round_trip() {
+        // every octave a note can hold, cycling through pitches
+        for (i, octave) in (i16::MIN..=i16::MAX).enumerate() {
+            let pitch = Pitch::ALL_CONSTS[i % Pitch::ALL_CONSTS.len()];
+
+            let note = Note::new(pitch, octave);
+
+            assert_eq!(note.to_string().parse(), Ok(note), "{note} should round trip");
+        }
+
+        // many accidentals, including ones without constants
+        for offset in -12..=12 {
+            for letter in [Letter::C, Letter::F, Letter::B] {
+                let pitch = Pitch::from_letter_and_accidental(letter, AccidentalSign { offset });
+
+                for octave in [i16::MIN, -1, 0, 4, i16::MAX] {
+                    let note = Note::new(pitch, octave);
+
+                    assert_eq!(note.to_string().parse(), Ok(note), "{note} should round trip");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        let cases = [
+            ("C4", Note::MIDDLE_C),
+            ("a4", Note::A4),
+            ("C#4", Note::new(Pitch::C_SHARP, 4)),
+            ("Db4", Note::new(Pitch::D_FLAT, 4)),
+            ("Cx4", Note::new(Pitch::C_DOUBLE_SHARP, 4)),
+            ("C##4", Note::new(Pitch::C_DOUBLE_SHARP, 4)),
+            ("Bbb-1", Note::new(Pitch::B_DOUBLE_FLAT, -1)),
+            ("B-1", Note::new(Pitch::B, -1)),
+            ("F𝄪10", Note::new(Pitch::F_DOUBLE_SHARP, 10)),
+            ("G♮3", Note::new(Pitch::G, 3)),
+            ("E𝄫0", Note::new(Pitch::E_DOUBLE_FLAT, 0)),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(input.parse(), Ok(expected), "{input} should parse to {expected}");
+        }
+
+        let errors = [
+            ("", ParseNoteError::Empty),
+            ("4", ParseNoteError::InvalidLetter),
+            ("#4", ParseNoteError::InvalidLetter),
+            ("C♮#4", ParseNoteError::InvalidAccidental),
+            ("Cb#4", ParseNoteError::InvalidAccidental),
+            ("C 4", ParseNoteError::InvalidAccidental),
+            ("C#", ParseNoteError::MissingOctave),
+            ("C-", ParseNoteError::InvalidOctave),
+            ("C4.5", ParseNoteError::InvalidOctave),
+            ("C32768", ParseNoteError::InvalidOctave),
+        ];
+
+        for (input, expected) in errors {
+            assert_eq!(input.parse::<Note>(), Err(expected), "{input:?} should fail to parse");
+        }
+    }
+
+    #[test]
+    fn from_helmholtz() {
+        let cases = [
+            ("C,,", Note::new(Pitch::C, 0)),
+            ("C,", Note::new(Pitch::C, 1)),
+            ("C", Note::new(Pitch::C, 2)),
+            ("c", Note::new(Pitch::C, 3)),
+            ("c'", Note::new(Pitch::C, 4)),
+            ("c′′", Note::new(Pitch::C, 5)),
+            ("bb'", Note::new(Pitch::B_FLAT, 4)),
+            ("G#,", Note::new(Pitch::G_SHARP, 1)),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(Note::from_helmholtz(input), Ok(expected), "{input} should parse to {expected}");
+        }
+
+        assert_eq!(Note::from_helmholtz("c4"), Err(ParseNoteError::InvalidOctave));
+        assert_eq!(Note::from_helmholtz("c',"), Err(ParseNoteError::InvalidOctave));
+        assert_eq!(Note::from_helmholtz("h'"), Err(ParseNoteError::InvalidLetter));
+    }
+
     #[test]
     fn test_respell_with() {
         let cases = [
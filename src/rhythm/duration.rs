@@ -0,0 +1,451 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::rhythm::{Rational, Tempo};
+
+/// The basic note values, from a whole note to a 1024th note.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{NoteValue, Rational};
+/// assert_eq!(NoteValue::Quarter.length(), Rational::new(1, 4));
+/// assert_eq!(NoteValue::from_denominator(16), Some(NoteValue::Sixteenth));
+/// assert_eq!(NoteValue::from_denominator(12), None);
+/// ```
+#[repr(u8)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, strum_macros::EnumIter, strum_macros::FromRepr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoteValue {
+    /// A whole note, or semibreve.
+    Whole = 0,
+    /// A half note, or minim.
+    Half,
+    /// A quarter note, or crotchet.
+    Quarter,
+    /// An eighth note, or quaver.
+    Eighth,
+    /// A sixteenth note, or semiquaver.
+    Sixteenth,
+    /// A 32nd note, or demisemiquaver.
+    ThirtySecond,
+    /// A 64th note, or hemidemisemiquaver.
+    SixtyFourth,
+    /// A 128th note.
+    HundredTwentyEighth,
+    /// A 256th note.
+    TwoHundredFiftySixth,
+    /// A 512th note.
+    FiveHundredTwelfth,
+    /// A 1024th note.
+    ThousandTwentyFourth,
+}
+
+impl NoteValue {
+    /// Returns the note value that is `1/denominator` of a whole note, if there is one.
+    pub fn from_denominator(denominator: u16) -> Option<Self> {
+        if !denominator.is_power_of_two() {
+            return None;
+        }
+
+        Self::from_repr(denominator.trailing_zeros() as u8)
+    }
+
+    /// Returns how many of the note value are in a whole note, like 4 for a quarter note.
+    pub fn denominator(self) -> u16 {
+        1 << self as u8
+    }
+
+    /// Returns the length of the note value, in whole notes.
+    pub fn length(self) -> Rational {
+        Rational::new(1, self.denominator() as _)
+    }
+
+    /// Returns the note value half as long, or `None` for a 1024th note.
+    pub fn halved(self) -> Option<Self> {
+        Self::from_repr(self as u8 + 1)
+    }
+
+    /// Returns the note value twice as long, or `None` for a whole note.
+    pub fn doubled(self) -> Option<Self> {
+        Self::from_repr((self as u8).checked_sub(1)?)
+    }
+}
+
+/// A tuplet ratio, where `actual` notes are played in the time of `normal` notes.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Rational, Tuplet};
+/// assert_eq!(Tuplet::TRIPLET.ratio(), Rational::new(2, 3));
+/// assert_eq!(Tuplet::new(5, 4).unwrap().to_string(), "5:4");
+/// assert_eq!(Tuplet::new(0, 4), None);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedTuplet"))]
+pub struct Tuplet {
+    actual: u8,
+    normal: u8,
+}
+
+// a tuplet as it's deserialized, before checking that neither number is zero
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedTuplet {
+    actual: u8,
+    normal: u8,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedTuplet> for Tuplet {
+    type Error = ParseDurationError;
+
+    fn try_from(unchecked: UncheckedTuplet) -> Result<Self, Self::Error> {
+        Self::new(unchecked.actual, unchecked.normal).ok_or(ParseDurationError::InvalidTuplet)
+    }
+}
+
+impl Tuplet {
+    /// Three notes in the time of two.
+    pub const TRIPLET: Self = Self { actual: 3, normal: 2 };
+
+    /// Two notes in the time of three, as in compound meters.
+    pub const DUPLET: Self = Self { actual: 2, normal: 3 };
+
+    /// Creates a tuplet of `actual` notes in the time of `normal` notes,
+    /// or `None` if either is zero.
+    pub fn new(actual: u8, normal: u8) -> Option<Self> {
+        (actual != 0 && normal != 0).then_some(Self { actual, normal })
+    }
+
+    /// Returns how many notes are played.
+    pub fn actual(self) -> u8 {
+        self.actual
+    }
+
+    /// Returns how many notes' time they're played in.
+    pub fn normal(self) -> u8 {
+        self.normal
+    }
+
+    /// Returns how much the tuplet scales the length of each note, `normal / actual`.
+    pub fn ratio(self) -> Rational {
+        Rational::new(self.normal as _, self.actual as _)
+    }
+}
+
+impl fmt::Display for Tuplet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.actual, self.normal)
+    }
+}
+
+/// A notated duration: a [note value](NoteValue), with dots, inside any number of nested [tuplets](Tuplet).
+///
+/// The same length can be notated in different ways, so durations compare by notation. Use
+/// [`length`](Self::length) to compare how long they are.
+///
+/// Durations display like LilyPond: the note value's denominator, a period for each dot, then
+/// each tuplet from the outermost, like `8. 3:2`.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Duration, NoteValue, Rational, Tuplet};
+/// let dotted_quarter = Duration::new(NoteValue::Quarter).with_dots(1);
+/// assert_eq!(dotted_quarter.length(), Rational::new(3, 8));
+///
+/// let triplet_eighth = Duration::new(NoteValue::Eighth).with_tuplet(Tuplet::TRIPLET);
+/// assert_eq!(triplet_eighth.length(), Rational::new(1, 12));
+///
+/// // a quintuplet inside a triplet
+/// let nested = triplet_eighth.with_tuplet(Tuplet::new(5, 4).unwrap());
+/// assert_eq!(nested.length(), Rational::new(1, 15));
+///
+/// assert_eq!(nested.to_string(), "8 3:2 5:4");
+/// assert_eq!("8 3:2 5:4".parse(), Ok(nested));
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duration {
+    /// The undotted note value.
+    pub value: NoteValue,
+    /// How many dots the note has. Each dot adds half the previous dot's length.
+    pub dots: u8,
+    /// The tuplets the note is in, from the outermost to the innermost.
+    pub tuplets: Vec<Tuplet>,
+}
+
+impl Duration {
+    /// The most dots a parsed duration can have.
+    pub const MAX_DOTS: u8 = 8;
+
+    /// The most tuplets a parsed duration can be nested in.
+    pub const MAX_TUPLETS: usize = 4;
+
+    /// Creates a duration of a note value, with no dots or tuplets.
+    pub fn new(value: NoteValue) -> Self {
+        Self { value, dots: 0, tuplets: Vec::new() }
+    }
+
+    /// Returns the duration with the given number of dots.
+    #[must_use = "This method returns a new Duration instead of mutating the original"]
+    pub fn with_dots(self, dots: u8) -> Self {
+        Self { dots, ..self }
+    }
+
+    /// Returns the duration inside another tuplet, nested inside any it's already in.
+    #[must_use = "This method returns a new Duration instead of mutating the original"]
+    pub fn with_tuplet(mut self, tuplet: Tuplet) -> Self {
+        self.tuplets.push(tuplet);
+        self
+    }
+
+    /// Returns the length of the duration, in whole notes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length doesn't fit in a [`Rational`], which can only happen with more than
+    /// [`MAX_DOTS`](Self::MAX_DOTS) dots or [`MAX_TUPLETS`](Self::MAX_TUPLETS) tuplets.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Duration, NoteValue, Rational};
+    /// let double_dotted_half = Duration::new(NoteValue::Half).with_dots(2);
+    /// assert_eq!(double_dotted_half.length(), Rational::new(7, 8));
+    /// ```
+    pub fn length(&self) -> Rational {
+        // n dots make the note (2 - 1/2^n) times as long
+        let dots = Rational::from_integer(2) - Rational::new(1, 1 << self.dots.min(62));
+
+        self.tuplets.iter()
+            .fold(self.value.length() * dots, |length, tuplet| length * tuplet.ratio())
+    }
+
+    /// Returns how many seconds the duration lasts at a tempo.
+    pub fn seconds(&self, tempo: Tempo) -> f64 {
+        tempo.seconds(self.length())
+    }
+
+    /// Returns the duration with no tuplets and at most two dots that has the given length,
+    /// if there is one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Duration, NoteValue, Rational};
+    /// assert_eq!(Duration::from_length(Rational::new(3, 16)), Some(Duration::new(NoteValue::Eighth).with_dots(1)));
+    /// assert_eq!(Duration::from_length(Rational::new(5, 16)), None);
+    /// ```
+    pub fn from_length(length: Rational) -> Option<Self> {
+        use strum::IntoEnumIterator;
+
+        NoteValue::iter()
+            .flat_map(|value| (0..=2).map(move |dots| Self::new(value).with_dots(dots)))
+            .find(|duration| duration.length() == length)
+    }
+
+    /// Splits a length into durations with no tuplets and at most two dots, to be tied together.
+    ///
+    /// The longest duration that fits is always chosen first, so longer notes come first.
+    /// Returns `None` if the length isn't positive or isn't a multiple of a 1024th note.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Duration, Rational};
+    /// let durations = Duration::tied(Rational::new(5, 8)).unwrap();
+    /// assert_eq!(durations, ["2".parse().unwrap(), "8".parse().unwrap()]);
+    ///
+    /// // longer than a whole note
+    /// let durations = Duration::tied(Rational::new(9, 4)).unwrap();
+    /// assert_eq!(durations, ["1..".parse().unwrap(), "2".parse().unwrap()]);
+    ///
+    /// assert_eq!(Duration::tied(Rational::new(1, 3)), None);
+    /// ```
+    pub fn tied(length: Rational) -> Option<Vec<Self>> {
+        use strum::IntoEnumIterator;
+
+        let smallest = NoteValue::ThousandTwentyFourth.length();
+
+        if length <= Rational::ZERO || !(length / smallest).is_integer() {
+            return None;
+        }
+
+        let mut candidates = NoteValue::iter()
+            .flat_map(|value| (0..=2).map(move |dots| Self::new(value).with_dots(dots)))
+            .filter(|duration| (duration.length() / smallest).is_integer())
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|duration| std::cmp::Reverse(duration.length()));
+
+        let mut remaining = length;
+        let mut durations = Vec::new();
+
+        while !remaining.is_zero() {
+            let next = candidates.iter()
+                .find(|duration| duration.length() <= remaining)
+                .expect("the smallest note value divides the length")
+                .clone();
+
+            remaining -= next.length();
+            durations.push(next);
+        }
+
+        Some(durations)
+    }
+}
+
+impl From<NoteValue> for Duration {
+    fn from(value: NoteValue) -> Self {
+        Self::new(value)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value.denominator())?;
+
+        for _ in 0..self.dots {
+            f.write_str(".")?;
+        }
+
+        for tuplet in &self.tuplets {
+            write!(f, " {tuplet}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`Duration`] fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Duration, ParseDurationError};
+/// assert_eq!("3".parse::<Duration>(), Err(ParseDurationError::InvalidNoteValue));
+/// assert_eq!("4 3-2".parse::<Duration>(), Err(ParseDurationError::InvalidTuplet));
+/// assert_eq!("4 3:2 3:2 3:2 3:2 3:2".parse::<Duration>(), Err(ParseDurationError::TooManyTuplets));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseDurationError {
+    /// The note value wasn't a power of two from 1 to 1024, optionally followed by up to
+    /// [`Duration::MAX_DOTS`] dots.
+    #[error("Expected a note value from 1 to 1024, like '4' or '8.'")]
+    InvalidNoteValue,
+    /// A tuplet wasn't two nonzero numbers separated by a colon.
+    #[error("Expected a tuplet like '3:2'")]
+    InvalidTuplet,
+    /// There were more than [`Duration::MAX_TUPLETS`] tuplets.
+    #[error("Expected at most {} tuplets", Duration::MAX_TUPLETS)]
+    TooManyTuplets,
+}
+
+impl FromStr for Duration {
+    type Err = ParseDurationError;
+
+    /// Parses a duration from a note value's denominator, followed by up to
+    /// [`MAX_DOTS`](Duration::MAX_DOTS) dots, and then up to [`MAX_TUPLETS`](Duration::MAX_TUPLETS)
+    /// tuplets separated by whitespace, like `16.. 3:2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+
+        let value = parts.next().ok_or(ParseDurationError::InvalidNoteValue)?;
+        let undotted = value.trim_end_matches('.');
+
+        let dots = u8::try_from(value.len() - undotted.len()).ok()
+            .filter(|&dots| dots <= Self::MAX_DOTS)
+            .ok_or(ParseDurationError::InvalidNoteValue)?;
+
+        let value = undotted.parse::<u16>().ok()
+            .filter(|_| undotted.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(NoteValue::from_denominator)
+            .ok_or(ParseDurationError::InvalidNoteValue)?;
+
+        let tuplets = parts
+            .map(|tuplet| {
+                let (actual, normal) = tuplet.split_once(':').ok_or(ParseDurationError::InvalidTuplet)?;
+
+                actual.parse().ok()
+                    .zip(normal.parse().ok())
+                    .and_then(|(actual, normal)| Tuplet::new(actual, normal))
+                    .ok_or(ParseDurationError::InvalidTuplet)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if tuplets.len() > Self::MAX_TUPLETS {
+            return Err(ParseDurationError::TooManyTuplets);
+        }
+
+        Ok(Self { value, dots, tuplets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+    use super::*;
+
+    #[test]
+    fn lengths() {
+        for value in NoteValue::iter() {
+            let duration = Duration::new(value);
+
+            assert_eq!(duration.length() * value.denominator() as i64, Rational::ONE);
+            assert_eq!(duration.to_string().parse(), Ok(duration.clone()));
+
+            if let Some(halved) = value.halved() {
+                assert_eq!(duration.with_dots(1).length(), value.length() + halved.length());
+                assert_eq!(halved.doubled(), Some(value));
+            }
+        }
+
+        let triplets = std::iter::repeat_n(Duration::new(NoteValue::Eighth).with_tuplet(Tuplet::TRIPLET), 3);
+        assert_eq!(triplets.map(|d| d.length()).sum::<Rational>(), Rational::new(1, 4));
+
+        let duplet = Duration::new(NoteValue::Eighth).with_tuplet(Tuplet::DUPLET);
+        assert_eq!(duplet.length(), Rational::new(3, 16));
+    }
+
+    #[test]
+    fn longest_parsed() {
+        let dots = ".".repeat(Duration::MAX_DOTS as usize);
+        let tuplets = |tuplet: &str| [tuplet; Duration::MAX_TUPLETS].join(" ");
+
+        let longest = format!("1{dots} {}", tuplets("1:255")).parse::<Duration>().unwrap();
+        let shortest = format!("1024{dots} {}", tuplets("255:1")).parse::<Duration>().unwrap();
+        assert!(longest.length() > shortest.length());
+
+        assert_eq!(format!("1024{dots}.").parse::<Duration>(), Err(ParseDurationError::InvalidNoteValue));
+        assert_eq!(format!("4 {} 3:2", tuplets("3:2")).parse::<Duration>(), Err(ParseDurationError::TooManyTuplets));
+    }
+
+    #[test]
+    fn tied() {
+        for numerator in 1..=64 {
+            let length = Rational::new(numerator, 32);
+            let durations = Duration::tied(length).unwrap();
+
+            assert_eq!(durations.iter().map(Duration::length).sum::<Rational>(), length);
+            assert!(durations.windows(2).all(|pair| pair[0].length() >= pair[1].length()));
+
+            if let Some(single) = Duration::from_length(length) {
+                assert_eq!(durations, [single]);
+            }
+        }
+
+        assert_eq!(Duration::tied(Rational::ZERO), None);
+        assert_eq!(Duration::tied(Rational::new(-1, 4)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, MapDeserializer};
+
+        let deserialize = |actual: u8, normal: u8| Tuplet::deserialize(
+            MapDeserializer::<_, Error>::new([("actual", actual), ("normal", normal)].into_iter())
+        ).map_err(|e| e.to_string());
+
+        assert_eq!(deserialize(3, 2), Ok(Tuplet::TRIPLET));
+        assert_eq!(deserialize(0, 2), Err(ParseDurationError::InvalidTuplet.to_string()));
+        assert_eq!(deserialize(3, 0), Err(ParseDurationError::InvalidTuplet.to_string()));
+    }
+}
@@ -0,0 +1,343 @@
+use crate::Note;
+use crate::rhythm::{BeatStrength, Duration, Rational, TimeSignature, TimedNote};
+
+/// A measure of notes in a time signature, which can be a shorter pickup measure.
+///
+/// Onsets of the notes are measured in whole notes from the start of the measure.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Measure, MeasureError, Rational, TimeSignature};
+/// let mut measure = Measure::new("3/4".parse().unwrap());
+///
+/// measure.push(vec!["C4".parse().unwrap()], "2".parse().unwrap());
+/// assert_eq!(measure.validate(), Err(MeasureError::Underfull(Rational::new(1, 4))));
+///
+/// measure.push_rest("4".parse().unwrap());
+/// assert_eq!(measure.validate(), Ok(()));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measure {
+    /// The time signature of the measure.
+    pub time_signature: TimeSignature,
+    /// The length of a pickup measure, or `None` for a full measure.
+    pub pickup: Option<Rational>,
+    /// The notes and rests in the measure, in order.
+    pub notes: Vec<TimedNote>,
+}
+
+/// Error returned when the notes of a [`Measure`] don't fit it,
+/// or when notes can't be split into measures.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum MeasureError {
+    /// The notes are shorter than the measure, by this much.
+    #[error("The measure is {0} of a whole note short")]
+    Underfull(Rational),
+    /// The notes are longer than the measure, by this much.
+    #[error("The measure is {0} of a whole note too long")]
+    Overfull(Rational),
+    /// The note at this index starts after the previous note ends.
+    #[error("There's a gap before note {0}")]
+    Gap(usize),
+    /// The note at this index starts before the previous note ends.
+    #[error("Note {0} overlaps the previous note")]
+    Overlap(usize),
+    /// The note at this index crosses a barline, and the parts in each measure can't be notated.
+    #[error("Note {0} can't be split at the barline")]
+    Unsplittable(usize),
+    /// The pickup isn't longer than zero and shorter than a full measure.
+    #[error("A pickup must be shorter than a full measure")]
+    InvalidPickup,
+}
+
+impl Measure {
+    /// Creates an empty full measure.
+    pub fn new(time_signature: TimeSignature) -> Self {
+        Self { time_signature, pickup: None, notes: Vec::new() }
+    }
+
+    /// Creates an empty pickup measure, or anacrusis, of a length shorter than a full measure.
+    ///
+    /// Returns `None` if the length isn't longer than zero and shorter than a full measure.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{BeatStrength, Measure, Rational, TimeSignature};
+    /// let pickup = Measure::pickup(TimeSignature::COMMON, Rational::new(1, 4)).unwrap();
+    ///
+    /// // the pickup is the last beat of a measure
+    /// assert_eq!(pickup.strength(Rational::ZERO), BeatStrength::Beat);
+    /// assert_eq!(pickup.strength(Rational::new(1, 4)), BeatStrength::Downbeat);
+    ///
+    /// assert_eq!(Measure::pickup(TimeSignature::COMMON, Rational::ONE), None);
+    /// ```
+    pub fn pickup(time_signature: TimeSignature, length: Rational) -> Option<Self> {
+        if length <= Rational::ZERO || length >= time_signature.bar_length() {
+            return None;
+        }
+
+        Some(Self { time_signature, pickup: Some(length), notes: Vec::new() })
+    }
+
+    /// Returns `true` if this is a pickup measure.
+    pub fn is_pickup(&self) -> bool {
+        self.pickup.is_some()
+    }
+
+    /// Returns the length the notes should fill, in whole notes.
+    pub fn length(&self) -> Rational {
+        self.pickup.unwrap_or_else(|| self.time_signature.bar_length())
+    }
+
+    /// Returns when the last note ends, in whole notes.
+    pub fn filled(&self) -> Rational {
+        self.notes.last().map_or(Rational::ZERO, TimedNote::end)
+    }
+
+    /// Returns how much of the measure is left after the last note, in whole notes,
+    /// which is negative if the measure is overfull.
+    pub fn remaining(&self) -> Rational {
+        self.length() - self.filled()
+    }
+
+    /// Adds notes sounding together after the last note.
+    pub fn push(&mut self, notes: Vec<Note>, duration: Duration) {
+        let onset = self.filled();
+
+        self.notes.push(TimedNote::chord(notes, onset, duration));
+    }
+
+    /// Adds a rest after the last note.
+    pub fn push_rest(&mut self, duration: Duration) {
+        self.push(Vec::new(), duration);
+    }
+
+    /// Checks that the notes follow each other without gaps or overlaps, and exactly fill the measure.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Measure, MeasureError, Rational, TimeSignature, TimedNote};
+    /// let mut measure = Measure::new("2/4".parse().unwrap());
+    ///
+    /// measure.push_rest("4".parse().unwrap());
+    /// measure.notes.push(TimedNote::rest(Rational::new(1, 8), "4".parse().unwrap()));
+    /// assert_eq!(measure.validate(), Err(MeasureError::Overlap(1)));
+    ///
+    /// measure.notes[1].onset = Rational::new(1, 4);
+    /// measure.push_rest("4".parse().unwrap());
+    /// assert_eq!(measure.validate(), Err(MeasureError::Overfull(Rational::new(1, 4))));
+    /// ```
+    pub fn validate(&self) -> Result<(), MeasureError> {
+        let mut end = Rational::ZERO;
+
+        for (i, note) in self.notes.iter().enumerate() {
+            if note.onset > end {
+                return Err(MeasureError::Gap(i));
+            } else if note.onset < end {
+                return Err(MeasureError::Overlap(i));
+            }
+
+            end = note.end();
+        }
+
+        let remaining = self.length() - end;
+
+        if remaining.is_negative() {
+            Err(MeasureError::Overfull(-remaining))
+        } else if !remaining.is_zero() {
+            Err(MeasureError::Underfull(remaining))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns how strongly accented a position in the measure is, see [`TimeSignature::strength`].
+    ///
+    /// A pickup measure is the end of a full measure, so its positions are offset by the missing part.
+    pub fn strength(&self, position: Rational) -> BeatStrength {
+        let offset = self.time_signature.bar_length() - self.length();
+
+        self.time_signature.strength(position + offset)
+    }
+
+    /// Splits notes into measures, starting with a pickup if it's given.
+    ///
+    /// Notes are measured from the start of the music, which is the start of the pickup,
+    /// and must not overlap each other. Notes that cross a barline are split into tied notes,
+    /// see [`TimedNote::tied_notes`], and gaps between notes are filled with rests.
+    /// The last measure is filled with rests too, unless it would complete the pickup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MeasureError::Overlap`] if a note starts before the previous note ends,
+    /// [`MeasureError::Unsplittable`] if a note crosses a barline and one of its parts can't
+    /// be notated, or if the rest after the last note can't be notated, or
+    /// [`MeasureError::InvalidPickup`] if the pickup doesn't fit the time signature.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Measure, Rational, TimeSignature, TimedNote};
+    /// let c4 = "C4".parse().unwrap();
+    /// let d4 = "D4".parse().unwrap();
+    ///
+    /// let notes = [
+    ///     TimedNote::note(c4, Rational::ZERO, "8".parse().unwrap()),
+    ///     TimedNote::note(d4, Rational::new(1, 8), "1".parse().unwrap()),
+    /// ];
+    ///
+    /// let time = TimeSignature::COMMON;
+    /// let measures = Measure::from_notes(&notes, time, Some(Rational::new(1, 4))).unwrap();
+    ///
+    /// assert_eq!(measures.len(), 2);
+    /// assert!(measures[0].is_pickup());
+    ///
+    /// // the whole note is tied over the barline, and the last measure is filled with a rest
+    /// assert!(measures[0].notes[1].tied);
+    ///
+    /// let last = &measures[1].notes;
+    /// assert_eq!(last[0].duration.to_string(), "2..");
+    /// assert!(!last[0].tied);
+    /// assert!(last[1].is_rest());
+    /// ```
+    pub fn from_notes(notes: &[TimedNote], time_signature: TimeSignature, pickup: Option<Rational>) -> Result<Vec<Self>, MeasureError> {
+        let mut current = match pickup {
+            Some(length) => Self::pickup(time_signature.clone(), length).ok_or(MeasureError::InvalidPickup)?,
+            None => Self::new(time_signature.clone()),
+        };
+
+        let mut measures = Vec::new();
+        // where the current measure starts, from the start of the music
+        let mut start = Rational::ZERO;
+
+        for (i, note) in notes.iter().enumerate() {
+            let end = start + current.filled();
+
+            if note.onset < end {
+                return Err(MeasureError::Overlap(i));
+            }
+
+            // rests before the note, then the note itself
+            let parts = [
+                (Vec::new(), end, note.onset - end, false),
+                (note.notes.clone(), note.onset, note.length(), note.tied),
+            ];
+
+            for (index, (sounding, mut onset, mut length, tied)) in parts.into_iter().enumerate() {
+                while !length.is_zero() {
+                    if current.remaining().is_zero() {
+                        start += current.length();
+                        measures.push(current);
+                        current = Self::new(time_signature.clone());
+                    }
+
+                    let part = length.min(current.remaining());
+                    let whole = index == 1 && part == note.length();
+
+                    if whole {
+                        // keep the duration as written, which may have tuplets
+                        current.notes.push(TimedNote { onset: onset - start, ..note.clone() });
+                    } else {
+                        let mut split = TimedNote::tied_notes(sounding.clone(), onset - start, part)
+                            .ok_or(MeasureError::Unsplittable(i))?;
+
+                        // every part but the last is tied into the next, and the last is tied only if the note was
+                        if let Some(last) = split.last_mut() {
+                            last.tied = !sounding.is_empty() && (part != length || tied);
+                        }
+
+                        current.notes.append(&mut split);
+                    }
+
+                    onset += part;
+                    length -= part;
+                }
+            }
+        }
+
+        let completes_pickup = !current.is_pickup()
+            && pickup.is_some_and(|length| current.filled() + length == time_signature.bar_length());
+
+        if !current.remaining().is_zero() && !current.notes.is_empty() && !completes_pickup {
+            let onset = current.filled();
+            let mut rests = TimedNote::tied_notes(Vec::new(), onset, current.remaining())
+                .ok_or(MeasureError::Unsplittable(notes.len() - 1))?;
+
+            current.notes.append(&mut rests);
+        }
+
+        if !current.notes.is_empty() || measures.is_empty() {
+            measures.push(current);
+        }
+
+        Ok(measures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(measures: &[Measure]) -> Vec<String> {
+        measures.iter()
+            .map(|measure| {
+                measure.notes.iter()
+                    .map(|note| {
+                        let rest = if note.is_rest() { "r" } else { "" };
+                        let tie = if note.tied { "~" } else { "" };
+                        format!("{rest}{}{tie}", note.duration)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_at_barlines() {
+        let c4 = "C4".parse::<Note>().unwrap();
+        let r = Rational::new;
+        let triplet = "8 3:2".parse::<Duration>().unwrap();
+
+        // a dotted half on the last beat of 3/4, three triplet eighths, a rest, and a half note
+        let notes = [
+            TimedNote::note(c4, r(1, 2), "2.".parse().unwrap()),
+            TimedNote::note(c4, r(5, 4), triplet.clone()),
+            TimedNote::note(c4, r(5, 4) + r(1, 12), triplet.clone()),
+            TimedNote::note(c4, r(5, 4) + r(1, 6), triplet),
+            TimedNote::note(c4, r(7, 4), "2".parse().unwrap()),
+        ];
+
+        let measures = Measure::from_notes(&notes, "3/4".parse().unwrap(), None).unwrap();
+
+        assert_eq!(summary(&measures), ["r2 4~", "2 8 3:2 8 3:2 8 3:2", "r4 2"]);
+
+        for measure in &measures {
+            assert_eq!(measure.validate(), Ok(()));
+        }
+
+        // a tied note keeps its tie at the end, and the last measure completes the pickup
+        let notes = [TimedNote::note(c4, r(3, 8), "4".parse().unwrap()).tied()];
+        let measures = Measure::from_notes(&notes, "3+2/8".parse().unwrap(), Some(r(1, 2))).unwrap();
+
+        assert_eq!(summary(&measures), ["r4. 8~", "8~"]);
+
+        // otherwise it's filled with rests
+        let notes = [TimedNote::note(c4, r(3, 8), "2".parse().unwrap())];
+        let measures = Measure::from_notes(&notes, "3+2/8".parse().unwrap(), None).unwrap();
+
+        assert_eq!(summary(&measures), ["r4. 4~", "4 r4."]);
+
+        let overlapping = [
+            TimedNote::note(c4, r(0, 1), "2".parse().unwrap()),
+            TimedNote::note(c4, r(1, 4), "2".parse().unwrap()),
+        ];
+
+        assert_eq!(Measure::from_notes(&overlapping, TimeSignature::COMMON, None), Err(MeasureError::Overlap(1)));
+
+        let across = [TimedNote::note(c4, r(0, 1), "2 3:2".parse().unwrap())];
+
+        assert_eq!(Measure::from_notes(&across, "1/4".parse().unwrap(), None), Err(MeasureError::Unsplittable(0)));
+        assert_eq!(Measure::from_notes(&[], TimeSignature::COMMON, Some(r(1, 1))), Err(MeasureError::InvalidPickup));
+    }
+}
@@ -0,0 +1,432 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::rhythm::Rational;
+
+/// A time signature, with a numerator that can be split into groups of beats.
+///
+/// Besides simple and compound meters, time signatures can be additive, like `3+2+2/8`, or
+/// irrational, with a denominator that isn't a power of two, like `4/3`.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Rational, TimeSignature};
+/// let waltz = "3/4".parse::<TimeSignature>().unwrap();
+/// assert!(waltz.is_simple());
+/// assert_eq!(waltz.bar_length(), Rational::new(3, 4));
+///
+/// let jig = "6/8".parse::<TimeSignature>().unwrap();
+/// assert!(jig.is_compound());
+/// assert_eq!(jig.beats(), [Rational::new(3, 8); 2]);
+///
+/// let additive = "3+2+2/8".parse::<TimeSignature>().unwrap();
+/// assert!(additive.is_additive());
+/// assert_eq!(additive.beats(), [Rational::new(3, 8), Rational::new(1, 4), Rational::new(1, 4)]);
+///
+/// let irrational = "4/3".parse::<TimeSignature>().unwrap();
+/// assert!(irrational.is_irrational());
+/// assert_eq!(irrational.bar_length(), Rational::new(4, 3));
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedTimeSignature", into = "UncheckedTimeSignature"))]
+pub struct TimeSignature {
+    numerator: Numerator,
+    denominator: u16,
+}
+
+// the groups of a time signature's numerator, where common and cut time don't need a `Vec`,
+// so they can be constants
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+enum Numerator {
+    // 4/4
+    Common,
+    // 2/2
+    Cut,
+    // any other time signature, with at least one group
+    Groups(Vec<u8>),
+}
+
+// a time signature as it's serialized, with its groups written out, and as it's deserialized,
+// before checking them
+#[cfg(feature = "serde")]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct UncheckedTimeSignature {
+    groups: Vec<u8>,
+    denominator: u16,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedTimeSignature> for TimeSignature {
+    type Error = ParseTimeSignatureError;
+
+    fn try_from(unchecked: UncheckedTimeSignature) -> Result<Self, Self::Error> {
+        Self::additive(unchecked.groups, unchecked.denominator).ok_or(ParseTimeSignatureError)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TimeSignature> for UncheckedTimeSignature {
+    fn from(time: TimeSignature) -> Self {
+        Self { groups: time.groups().to_vec(), denominator: time.denominator }
+    }
+}
+
+/// How strongly accented a position in a measure is, from [`Offbeat`](Self::Offbeat)
+/// to [`Downbeat`](Self::Downbeat).
+///
+/// Stronger positions compare greater.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{BeatStrength, Rational, TimeSignature};
+/// let common = TimeSignature::COMMON;
+///
+/// assert_eq!(common.strength(Rational::ZERO), BeatStrength::Downbeat);
+/// assert_eq!(common.strength(Rational::new(1, 2)), BeatStrength::Strong);
+/// assert_eq!(common.strength(Rational::new(1, 4)), BeatStrength::Beat);
+/// assert_eq!(common.strength(Rational::new(1, 8)), BeatStrength::Subdivision);
+/// assert_eq!(common.strength(Rational::new(1, 16)), BeatStrength::Offbeat);
+///
+/// assert!(BeatStrength::Strong > BeatStrength::Beat);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BeatStrength {
+    /// Anywhere that isn't on a beat or a subdivision of it.
+    Offbeat,
+    /// The first subdivision of a beat, halves in simple meters, and thirds in compound meters.
+    Subdivision,
+    /// The start of a beat.
+    Beat,
+    /// The start of the middle beat of a measure with an even number of at least four beats,
+    /// like the third beat of `4/4`.
+    Strong,
+    /// The start of the measure.
+    Downbeat,
+}
+
+impl TimeSignature {
+    /// Four quarter notes per measure, common time.
+    pub const COMMON: Self = Self { numerator: Numerator::Common, denominator: 4 };
+
+    /// Two half notes per measure, cut time.
+    pub const CUT: Self = Self { numerator: Numerator::Cut, denominator: 2 };
+
+    /// Creates a time signature, or returns `None` if either number is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::TimeSignature;
+    /// assert_eq!(TimeSignature::new(4, 4), Some(TimeSignature::COMMON));
+    /// assert_eq!(TimeSignature::new(0, 4), None);
+    /// ```
+    pub fn new(numerator: u8, denominator: u16) -> Option<Self> {
+        Self::additive(vec![numerator], denominator)
+    }
+
+    /// Creates an additive time signature, whose numerator is the sum of the groups, like `3+2+2/8`.
+    ///
+    /// Returns `None` if there are no groups, or if any group or the denominator is zero.
+    pub fn additive(groups: Vec<u8>, denominator: u16) -> Option<Self> {
+        if groups.is_empty() || groups.contains(&0) || denominator == 0 {
+            return None;
+        }
+
+        groups.iter().try_fold(0u8, |sum, &group| sum.checked_add(group))?;
+
+        let numerator = match (groups.as_slice(), denominator) {
+            ([4], 4) => Numerator::Common,
+            ([2], 2) => Numerator::Cut,
+            _ => Numerator::Groups(groups),
+        };
+
+        Some(Self { numerator, denominator })
+    }
+
+    /// Returns the groups the numerator is made of, which is just the numerator unless the
+    /// time signature is additive.
+    pub fn groups(&self) -> &[u8] {
+        match &self.numerator {
+            Numerator::Common => &[4],
+            Numerator::Cut => &[2],
+            Numerator::Groups(groups) => groups,
+        }
+    }
+
+    /// Returns the top number of the time signature, the sum of its groups.
+    pub fn numerator(&self) -> u8 {
+        self.groups().iter().sum()
+    }
+
+    /// Returns the bottom number of the time signature.
+    pub fn denominator(&self) -> u16 {
+        self.denominator
+    }
+
+    /// Returns the length of a full measure, in whole notes.
+    pub fn bar_length(&self) -> Rational {
+        Rational::new(self.numerator() as _, self.denominator as _)
+    }
+
+    /// Returns `true` if the numerator is split into more than one group.
+    pub fn is_additive(&self) -> bool {
+        self.groups().len() > 1
+    }
+
+    /// Returns `true` if the denominator isn't a power of two, so the beat isn't a
+    /// [note value](crate::rhythm::NoteValue).
+    pub fn is_irrational(&self) -> bool {
+        !self.denominator.is_power_of_two()
+    }
+
+    /// Returns `true` if the meter is compound: not additive, with a numerator of
+    /// 6, 9, 12, or any larger multiple of three, so each beat is divided into three.
+    pub fn is_compound(&self) -> bool {
+        let numerator = self.numerator();
+
+        !self.is_additive() && numerator > 3 && numerator.is_multiple_of(3)
+    }
+
+    /// Returns `true` if the meter is neither compound nor additive, so each beat is
+    /// divided into two.
+    pub fn is_simple(&self) -> bool {
+        !self.is_compound() && !self.is_additive()
+    }
+
+    /// Returns the length of each beat in a measure, in whole notes.
+    ///
+    /// Simple meters have a beat for each count, compound meters have a beat for every three,
+    /// and additive meters have a beat for each group.
+    pub fn beats(&self) -> Vec<Rational> {
+        let unit = Rational::new(1, self.denominator as _);
+
+        if self.is_compound() {
+            vec![unit * 3; self.numerator() as usize / 3]
+        } else if self.is_additive() {
+            self.groups().iter().map(|&group| unit * group as i64).collect()
+        } else {
+            vec![unit; self.numerator() as usize]
+        }
+    }
+
+    /// Returns when each beat starts, from the start of the measure, in whole notes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{Rational, TimeSignature};
+    /// let additive = "2+3/8".parse::<TimeSignature>().unwrap();
+    /// assert_eq!(additive.beat_onsets(), [Rational::ZERO, Rational::new(1, 4)]);
+    /// ```
+    pub fn beat_onsets(&self) -> Vec<Rational> {
+        self.beats()
+            .into_iter()
+            .scan(Rational::ZERO, |onset, beat| {
+                let current = *onset;
+                *onset += beat;
+                Some(current)
+            })
+            .collect()
+    }
+
+    /// Returns how strongly accented a position is, measured from the start of a measure
+    /// in whole notes. Positions past the end of the measure wrap around into the next measure.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::{BeatStrength, Rational, TimeSignature};
+    /// let jig = "6/8".parse::<TimeSignature>().unwrap();
+    ///
+    /// assert_eq!(jig.strength(Rational::new(3, 8)), BeatStrength::Beat);
+    /// assert_eq!(jig.strength(Rational::new(1, 8)), BeatStrength::Subdivision);
+    /// assert_eq!(jig.strength(Rational::new(1, 16)), BeatStrength::Offbeat);
+    ///
+    /// // the next measure's downbeat
+    /// assert_eq!(jig.strength(Rational::new(3, 4)), BeatStrength::Downbeat);
+    /// ```
+    pub fn strength(&self, position: Rational) -> BeatStrength {
+        let position = position.rem_euclid(self.bar_length());
+
+        if position.is_zero() {
+            return BeatStrength::Downbeat;
+        }
+
+        let beats = self.beats();
+        let onsets = self.beat_onsets();
+
+        if let Some(index) = onsets.iter().position(|&onset| onset == position) {
+            let strong = beats.len() >= 4 && beats.len().is_multiple_of(2) && index == beats.len() / 2;
+
+            return if strong { BeatStrength::Strong } else { BeatStrength::Beat };
+        }
+
+        // the beat the position is in, and how far into it
+        let (beat, offset) = beats.iter()
+            .zip(&onsets)
+            .rev()
+            .find(|&(_, &onset)| onset < position)
+            .map(|(&beat, &onset)| (beat, position - onset))
+            .expect("the first beat starts at zero");
+
+        let subdivision = if self.is_simple() {
+            beat / 2
+        } else {
+            Rational::new(1, self.denominator as _)
+        };
+
+        if (offset / subdivision).is_integer() {
+            BeatStrength::Subdivision
+        } else {
+            BeatStrength::Offbeat
+        }
+    }
+
+    /// Returns `true` if the position is a downbeat or a strong beat, see [`Self::strength`].
+    pub fn is_strong(&self, position: Rational) -> bool {
+        self.strength(position) >= BeatStrength::Strong
+    }
+}
+
+impl Default for TimeSignature {
+    fn default() -> Self {
+        Self::COMMON
+    }
+}
+
+impl fmt::Display for TimeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.groups().iter().enumerate() {
+            if i != 0 {
+                f.write_str("+")?;
+            }
+
+            write!(f, "{group}")?;
+        }
+
+        write!(f, "/{}", self.denominator)
+    }
+}
+
+/// Error returned when parsing a [`TimeSignature`] fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{ParseTimeSignatureError, TimeSignature};
+/// assert_eq!("4".parse::<TimeSignature>(), Err(ParseTimeSignatureError));
+/// assert_eq!("3+/8".parse::<TimeSignature>(), Err(ParseTimeSignatureError));
+/// assert_eq!("4/0".parse::<TimeSignature>(), Err(ParseTimeSignatureError));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("Expected a time signature like '4/4' or '3+2+2/8'")]
+pub struct ParseTimeSignatureError;
+
+impl FromStr for TimeSignature {
+    type Err = ParseTimeSignatureError;
+
+    /// Parses a time signature like `4/4`, or an additive one like `3+2+2/8`.
+    ///
+    /// `C` and `¢` are also accepted for common and cut time.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "C" => return Ok(Self::COMMON),
+            "¢" => return Ok(Self::CUT),
+            _ => {}
+        }
+
+        let (numerator, denominator) = s.split_once('/').ok_or(ParseTimeSignatureError)?;
+
+        let groups = numerator.split('+')
+            .map(|group| group.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseTimeSignatureError)?;
+
+        let denominator = denominator.trim().parse::<u16>().map_err(|_| ParseTimeSignatureError)?;
+
+        Self::additive(groups, denominator).ok_or(ParseTimeSignatureError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds() {
+        let cases = [
+            ("2/4", true, false, false, 2),
+            ("4/4", true, false, false, 4),
+            ("3/8", true, false, false, 3),
+            ("6/8", false, true, false, 2),
+            ("12/8", false, true, false, 4),
+            ("9/16", false, true, false, 3),
+            ("5/4", true, false, false, 5),
+            ("3+3+2/8", false, false, true, 3),
+            ("4/3", true, false, false, 4),
+        ];
+
+        for (s, simple, compound, additive, beats) in cases {
+            let time = s.parse::<TimeSignature>().unwrap();
+
+            assert_eq!(time.to_string(), s);
+            assert_eq!(time.is_simple(), simple, "{s}");
+            assert_eq!(time.is_compound(), compound, "{s}");
+            assert_eq!(time.is_additive(), additive, "{s}");
+            assert_eq!(time.beats().len(), beats, "{s}");
+            assert_eq!(time.beats().into_iter().sum::<Rational>(), time.bar_length(), "{s}");
+        }
+
+        assert_eq!("C".parse(), Ok(TimeSignature::COMMON));
+        assert_eq!(TimeSignature::CUT.to_string(), "2/2");
+        assert_eq!(TimeSignature::new(2, 2), Some(TimeSignature::CUT));
+        assert_eq!(TimeSignature::additive(vec![200, 100], 4), None);
+    }
+
+    #[test]
+    fn strengths() {
+        use BeatStrength as S;
+
+        let time = "12/8".parse::<TimeSignature>().unwrap();
+
+        let strengths = (0..12)
+            .map(|eighth| time.strength(Rational::new(eighth, 8)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(strengths, [
+            S::Downbeat, S::Subdivision, S::Subdivision,
+            S::Beat, S::Subdivision, S::Subdivision,
+            S::Strong, S::Subdivision, S::Subdivision,
+            S::Beat, S::Subdivision, S::Subdivision,
+        ]);
+
+        let time = "3+2+2/8".parse::<TimeSignature>().unwrap();
+
+        let strengths = (0..7)
+            .map(|eighth| time.strength(Rational::new(eighth, 8)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(strengths, [S::Downbeat, S::Subdivision, S::Subdivision, S::Beat, S::Subdivision, S::Beat, S::Subdivision]);
+
+        assert!(TimeSignature::COMMON.is_strong(Rational::new(1, 2)));
+        assert!(!TimeSignature::COMMON.is_strong(Rational::new(3, 4)));
+        assert_eq!(TimeSignature::COMMON.strength(Rational::new(-1, 4)), S::Beat);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        let deserialize = |groups: Vec<u8>, denominator: u16| {
+            TimeSignature::try_from(UncheckedTimeSignature { groups, denominator })
+        };
+
+        assert_eq!(deserialize(vec![4], 4), Ok(TimeSignature::COMMON));
+        assert_eq!(deserialize(vec![3, 2], 8), "3+2/8".parse());
+        assert_eq!(deserialize(vec![3], 0), Err(ParseTimeSignatureError));
+        assert_eq!(deserialize(vec![], 8), Err(ParseTimeSignatureError));
+        assert_eq!(deserialize(vec![0, 2], 8), Err(ParseTimeSignatureError));
+
+        for time in [TimeSignature::COMMON, TimeSignature::CUT, "7/8".parse().unwrap()] {
+            let serialized = UncheckedTimeSignature::from(time.clone());
+            assert_eq!(serialized.groups, time.groups());
+            assert_eq!(TimeSignature::try_from(serialized), Ok(time));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Durations, rhythm, and time.
+//!
+//! Time is measured exactly with [`Rational`]s, in whole notes. A notated [`Duration`] is a
+//! [`NoteValue`] with dots and [tuplets](Tuplet), and a [`TimedNote`] pairs notes, or a rest,
+//! with an onset and a duration. A [`Tempo`] converts lengths to seconds.
+//!
+//! A [`TimeSignature`] gives the metric structure, and notes are grouped into [`Measure`]s,
+//! split and tied across barlines.
+//!
+//! # Examples
+//! ```
+//! # use music_theory::Note;
+//! # use music_theory::rhythm::{Duration, NoteValue, Rational, Tempo, TimedNote, Tuplet};
+//! let triplet = Duration::new(NoteValue::Eighth).with_tuplet(Tuplet::TRIPLET);
+//!
+//! let mut onset = Rational::ZERO;
+//! let mut notes = Vec::new();
+//!
+//! for note in ["C4", "D4", "E4"] {
+//!     notes.push(TimedNote::note(note.parse().unwrap(), onset, triplet.clone()));
+//!     onset += triplet.length();
+//! }
+//!
+//! // three triplet eighths fill a quarter note
+//! assert_eq!(notes[2].end(), Rational::new(1, 4));
+//! assert_eq!(Tempo::quarter_notes(60.0).seconds(notes[2].end()), 1.0);
+//! ```
+
+mod rational;
+pub use rational::*;
+
+mod duration;
+pub use duration::*;
+
+mod tempo;
+pub use tempo::*;
+
+mod timed;
+pub use timed::*;
+
+mod meter;
+pub use meter::*;
+
+mod measure;
+pub use measure::*;
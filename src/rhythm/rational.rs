@@ -0,0 +1,417 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// An exact fraction, used for lengths of time and positions in time, measured in whole notes.
+///
+/// Rationals are always stored in lowest terms with a positive denominator,
+/// so equal values have equal fields.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::Rational;
+/// let quarter = Rational::new(1, 4);
+/// let triplet_eighth = Rational::new(1, 12);
+///
+/// assert_eq!(quarter + triplet_eighth, Rational::new(1, 3));
+/// assert_eq!(triplet_eighth * 3, Rational::new(1, 4));
+/// assert_eq!(Rational::new(6, 8).to_string(), "3/4");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedRational"))]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+// a rational as it's deserialized, before checking the denominator and reducing it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedRational {
+    numerator: i64,
+    denominator: i64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedRational> for Rational {
+    type Error = ParseRationalError;
+
+    fn try_from(unchecked: UncheckedRational) -> Result<Self, Self::Error> {
+        Self::checked_new(unchecked.numerator, unchecked.denominator).ok_or(ParseRationalError)
+    }
+}
+
+pub(crate) const fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.abs()
+}
+
+impl Rational {
+    /// Zero, the length of nothing.
+    pub const ZERO: Self = Self::new(0, 1);
+
+    /// One, the length of a whole note.
+    pub const ONE: Self = Self::new(1, 1);
+
+    /// Creates a rational from a numerator and denominator, reducing it to lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero, or if the reduced rational doesn't fit.
+    pub const fn new(numerator: i64, denominator: i64) -> Self {
+        assert!(denominator != 0, "denominator must be nonzero");
+
+        match Self::checked_new(numerator, denominator) {
+            Some(rational) => rational,
+            None => panic!("attempt to negate with overflow"),
+        }
+    }
+
+    /// Creates a rational from a numerator and denominator, reducing it to lowest terms,
+    /// or returns `None` if `denominator` is zero or the reduced rational doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Rational;
+    /// assert_eq!(Rational::checked_new(2, -4), Some(Rational::new(-1, 2)));
+    /// assert_eq!(Rational::checked_new(1, 0), None);
+    /// assert_eq!(Rational::checked_new(1, i64::MIN), None);
+    /// ```
+    pub const fn checked_new(numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+
+        // make the denominator positive first, so the divisor is too
+        let (numerator, denominator) = if denominator < 0 {
+            match (numerator.checked_neg(), denominator.checked_neg()) {
+                (Some(numerator), Some(denominator)) => (numerator, denominator),
+                _ => return None,
+            }
+        } else {
+            (numerator, denominator)
+        };
+
+        let divisor = gcd(numerator, denominator);
+
+        Some(Self { numerator: numerator / divisor, denominator: denominator / divisor })
+    }
+
+    /// Creates a rational equal to an integer.
+    pub const fn from_integer(n: i64) -> Self {
+        Self::new(n, 1)
+    }
+
+    /// Returns the numerator, in lowest terms.
+    pub const fn numerator(self) -> i64 {
+        self.numerator
+    }
+
+    /// Returns the denominator, in lowest terms. It's always positive.
+    pub const fn denominator(self) -> i64 {
+        self.denominator
+    }
+
+    /// Returns `true` if the rational is zero.
+    pub const fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// Returns `true` if the rational is less than zero.
+    pub const fn is_negative(self) -> bool {
+        self.numerator < 0
+    }
+
+    /// Returns `true` if the rational is a whole number.
+    pub const fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Returns the largest integer less than or equal to the rational.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Rational;
+    /// assert_eq!(Rational::new(7, 4).floor(), 1);
+    /// assert_eq!(Rational::new(-1, 4).floor(), -1);
+    /// ```
+    pub const fn floor(self) -> i64 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    /// Returns the remainder of dividing by `rhs`, which has the same sign as `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Rational;
+    /// let bar = Rational::new(3, 4);
+    ///
+    /// assert_eq!(Rational::new(7, 4).rem_euclid(bar), Rational::new(1, 4));
+    /// assert_eq!(Rational::new(-1, 4).rem_euclid(bar), Rational::new(1, 2));
+    /// ```
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        self - rhs * (self / rhs).floor()
+    }
+
+    /// Returns the reciprocal, or `None` if the rational is zero.
+    pub const fn recip(self) -> Option<Self> {
+        if self.numerator == 0 {
+            None
+        } else {
+            Some(Self::new(self.denominator, self.numerator))
+        }
+    }
+
+    /// Returns the absolute value.
+    pub const fn abs(self) -> Self {
+        Self { numerator: self.numerator.abs(), denominator: self.denominator }
+    }
+
+    /// Returns the closest floating point number.
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Adds two rationals, or returns `None` if the result doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Rational;
+    /// assert_eq!(Rational::new(1, 4).checked_add(Rational::new(1, 6)), Some(Rational::new(5, 12)));
+    /// assert_eq!(Rational::from_integer(i64::MAX).checked_add(Rational::ONE), None);
+    /// assert_eq!(Rational::new(1, i64::MAX).checked_add(Rational::new(1, 2)), None);
+    /// ```
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let lcm = (self.denominator / divisor).checked_mul(rhs.denominator)?;
+
+        let lhs = self.numerator.checked_mul(lcm / self.denominator)?;
+        let rhs = rhs.numerator.checked_mul(lcm / rhs.denominator)?;
+
+        Some(Self::new(lhs.checked_add(rhs)?, lcm))
+    }
+
+    /// Multiplies two rationals, or returns `None` if the result doesn't fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Rational;
+    /// assert_eq!(Rational::new(2, 3).checked_mul(Rational::new(9, 4)), Some(Rational::new(3, 2)));
+    /// assert_eq!(Rational::new(1, 1 << 40).checked_mul(Rational::new(1, 1 << 40)), None);
+    /// ```
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // reduce across first, to keep the products small
+        let a = gcd(self.numerator, rhs.denominator).max(1);
+        let b = gcd(rhs.numerator, self.denominator).max(1);
+
+        Self::checked_new(
+            (self.numerator / a).checked_mul(rhs.numerator / b)?,
+            (self.denominator / b).checked_mul(rhs.denominator / a)?,
+        )
+    }
+
+    /// Subtracts two rationals, or returns `None` if the result doesn't fit.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(Self { numerator: rhs.numerator.checked_neg()?, denominator: rhs.denominator })
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so cross multiplying keeps the order
+        (self.numerator as i128 * other.denominator as i128)
+            .cmp(&(other.numerator as i128 * self.denominator as i128))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self { numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("attempt to multiply with overflow")
+    }
+}
+
+impl Mul<i64> for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self * Self::from_integer(rhs)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// Divides two rationals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        Mul::mul(self, rhs.recip().expect("can't divide by zero"))
+    }
+}
+
+impl Div<i64> for Rational {
+    type Output = Self;
+
+    /// Divides a rational by an integer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: i64) -> Self::Output {
+        self / Self::from_integer(rhs)
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl fmt::Display for Rational {
+    /// Formats the rational as a fraction, or as an integer if its denominator is one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Error returned when parsing a [`Rational`] fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{ParseRationalError, Rational};
+/// assert_eq!("3/8".parse(), Ok(Rational::new(3, 8)));
+/// assert_eq!("2".parse(), Ok(Rational::from_integer(2)));
+/// assert_eq!("3/0".parse::<Rational>(), Err(ParseRationalError));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("Expected an integer or a fraction like '3/8'")]
+pub struct ParseRationalError;
+
+impl FromStr for Rational {
+    type Err = ParseRationalError;
+
+    /// Parses a rational from an integer or a fraction, like `-3/8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator, denominator) = s.trim().split_once('/').unwrap_or((s.trim(), "1"));
+
+        let numerator = numerator.trim().parse::<i64>().map_err(|_| ParseRationalError)?;
+        let denominator = denominator.trim().parse::<i64>().map_err(|_| ParseRationalError)?;
+
+        Self::checked_new(numerator, denominator).ok_or(ParseRationalError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let r = Rational::new;
+
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(0, -5), Rational::ZERO);
+        assert_eq!(r(1, 3) + r(1, 6), r(1, 2));
+        assert_eq!(r(1, 3) - r(1, 2), r(-1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert_eq!(r(-3, 4).abs(), r(3, 4));
+        assert_eq!(Rational::ZERO.recip(), None);
+
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+
+        assert_eq!([r(1, 2), r(1, 4), r(1, 8)].into_iter().sum::<Rational>(), r(7, 8));
+        assert_eq!("-3 / 6".parse(), Ok(r(-1, 2)));
+        assert_eq!("a/2".parse::<Rational>(), Err(ParseRationalError));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, MapDeserializer};
+
+        let deserialize = |numerator: i64, denominator: i64| Rational::deserialize(
+            MapDeserializer::<_, Error>::new([("numerator", numerator), ("denominator", denominator)].into_iter())
+        ).map_err(|e| e.to_string());
+
+        assert_eq!(deserialize(3, 8), Ok(Rational::new(3, 8)));
+        assert_eq!(deserialize(2, -4), Ok(Rational::new(-1, 2)));
+        assert_eq!(deserialize(1, 0), Err(ParseRationalError.to_string()));
+        assert_eq!(deserialize(i64::MIN, -1), Err(ParseRationalError.to_string()));
+    }
+}
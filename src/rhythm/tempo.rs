@@ -0,0 +1,58 @@
+use crate::rhythm::{Duration, NoteValue, Rational};
+
+/// A tempo, as a number of beats per minute, where each beat is some length.
+///
+/// # Examples
+/// ```
+/// # use music_theory::rhythm::{Duration, NoteValue, Rational, Tempo};
+/// let tempo = Tempo::new(120.0, &Duration::new(NoteValue::Quarter));
+/// assert_eq!(tempo.seconds(Rational::ONE), 2.0);
+///
+/// // compound meters often count dotted quarters
+/// let tempo = Tempo::new(60.0, &"4.".parse().unwrap());
+/// assert_eq!(tempo.seconds(Rational::new(3, 4)), 2.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tempo {
+    /// How many beats there are per minute.
+    pub beats_per_minute: f64,
+    /// The length of each beat, in whole notes.
+    pub beat: Rational,
+}
+
+impl Tempo {
+    /// Creates a tempo of `beats_per_minute` beats of the given duration.
+    pub fn new(beats_per_minute: f64, beat: &Duration) -> Self {
+        Self { beats_per_minute, beat: beat.length() }
+    }
+
+    /// Creates a tempo of `beats_per_minute` quarter notes per minute.
+    pub fn quarter_notes(beats_per_minute: f64) -> Self {
+        Self::new(beats_per_minute, &Duration::new(NoteValue::Quarter))
+    }
+
+    /// Returns how many seconds a length of time lasts at the tempo.
+    pub fn seconds(self, length: Rational) -> f64 {
+        (length / self.beat).to_f64() * 60.0 / self.beats_per_minute
+    }
+
+    /// Returns how many quarter notes are played per minute at the tempo.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::rhythm::Tempo;
+    /// let tempo = Tempo::new(60.0, &"2".parse().unwrap());
+    /// assert_eq!(tempo.quarter_notes_per_minute(), 120.0);
+    /// ```
+    pub fn quarter_notes_per_minute(self) -> f64 {
+        self.beats_per_minute * (self.beat / NoteValue::Quarter.length()).to_f64()
+    }
+}
+
+impl Default for Tempo {
+    /// The default tempo is 120 quarter notes per minute.
+    fn default() -> Self {
+        Self::quarter_notes(120.0)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::Note;
+use crate::rhythm::{Duration, Rational, Tempo};
+
+/// A note, chord, or rest, with the time it starts and how long it lasts.
+///
+/// Onsets are measured in whole notes from the start of the music.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Note;
+/// # use music_theory::rhythm::{Rational, Tempo, TimedNote};
+/// let c4 = "C4".parse::<Note>().unwrap();
+///
+/// let note = TimedNote::note(c4, Rational::new(1, 4), "2".parse().unwrap());
+/// assert_eq!(note.end(), Rational::new(3, 4));
+///
+/// let tempo = Tempo::quarter_notes(60.0);
+/// assert_eq!(note.onset_seconds(tempo), 1.0);
+/// assert_eq!(note.seconds(tempo), 2.0);
+///
+/// let rest = TimedNote::rest(note.end(), "4".parse().unwrap());
+/// assert!(rest.is_rest());
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedNote {
+    /// The notes sounding together. A single note has one, and a rest has none.
+    pub notes: Vec<Note>,
+    /// When the notes start, in whole notes.
+    pub onset: Rational,
+    /// How long the notes last.
+    pub duration: Duration,
+    /// Whether the notes are tied to the next notes with the same pitches.
+    pub tied: bool,
+}
+
+impl TimedNote {
+    /// Creates a single note.
+    pub fn note(note: Note, onset: Rational, duration: Duration) -> Self {
+        Self::chord(vec![note], onset, duration)
+    }
+
+    /// Creates notes sounding together.
+    pub fn chord(notes: Vec<Note>, onset: Rational, duration: Duration) -> Self {
+        Self { notes, onset, duration, tied: false }
+    }
+
+    /// Creates a rest.
+    pub fn rest(onset: Rational, duration: Duration) -> Self {
+        Self::chord(Vec::new(), onset, duration)
+    }
+
+    /// Returns the same notes, tied to the next notes.
+    #[must_use = "This method returns a new TimedNote instead of mutating the original"]
+    pub fn tied(self) -> Self {
+        Self { tied: true, ..self }
+    }
+
+    /// Returns `true` if there are no notes.
+    pub fn is_rest(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Returns how long the notes last, in whole notes.
+    pub fn length(&self) -> Rational {
+        self.duration.length()
+    }
+
+    /// Returns when the notes end, in whole notes.
+    pub fn end(&self) -> Rational {
+        self.onset + self.length()
+    }
+
+    /// Returns when the notes start at a tempo, in seconds.
+    pub fn onset_seconds(&self, tempo: Tempo) -> f64 {
+        tempo.seconds(self.onset)
+    }
+
+    /// Returns how long the notes last at a tempo, in seconds.
+    pub fn seconds(&self, tempo: Tempo) -> f64 {
+        self.duration.seconds(tempo)
+    }
+
+    /// Splits a note into tied notes that can each be notated without tuplets,
+    /// see [`Duration::tied`].
+    ///
+    /// Each note is tied to the next, except the last, which isn't tied. Rests, with no notes,
+    /// are never tied. Returns `None` if the length can't be split, see [`Duration::tied`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::rhythm::{Rational, TimedNote};
+    /// let c4 = "C4".parse::<Note>().unwrap();
+    ///
+    /// let notes = TimedNote::tied_notes(vec![c4], Rational::ZERO, Rational::new(5, 8)).unwrap();
+    ///
+    /// assert_eq!(notes.len(), 2);
+    /// assert!(notes[0].tied && !notes[1].tied);
+    /// assert_eq!(notes[1].onset, Rational::new(1, 2));
+    /// ```
+    pub fn tied_notes(notes: Vec<Note>, onset: Rational, length: Rational) -> Option<Vec<Self>> {
+        let durations = Duration::tied(length)?;
+        let count = durations.len();
+
+        let mut onset = onset;
+
+        Some(durations.into_iter()
+            .enumerate()
+            .map(|(i, duration)| {
+                let note = Self {
+                    notes: notes.clone(),
+                    onset,
+                    tied: i + 1 != count && !notes.is_empty(),
+                    duration,
+                };
+
+                onset += note.length();
+                note
+            })
+            .collect())
+    }
+}
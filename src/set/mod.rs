@@ -1,5 +1,16 @@
 pub mod pcset;
-pub use pcset::PitchClassSet;
+pub use pcset::{PitchClassSet, PrimeFormConvention, Subsets};
 
 mod icv;
-pub use icv::*;
\ No newline at end of file
+pub use icv::*;
+
+mod set_class;
+pub use set_class::*;
+
+mod similarity;
+
+mod segment;
+pub use segment::*;
+
+mod row;
+pub use row::*;
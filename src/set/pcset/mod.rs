@@ -44,6 +44,12 @@ pub use ops::*;
 mod into_iter;
 pub use into_iter::*;
 
+mod prime_form;
+pub use prime_form::*;
+
+mod subsets;
+pub use subsets::*;
+
 /// A collection of pitch classes, stored as a 12-bit bitset.
 ///
 /// Pitch class sets support standard set operations (union, intersection, complement),
@@ -373,6 +379,66 @@ impl PitchClassSet {
         Self(result).transpose(Semitones(2 * axis.chroma() as i16))
     }
 
+    /// Multiplies every pitch class by the given factor, modulo 12.
+    ///
+    /// Multiplication maps each pitch class `p` to `(factor × p) mod 12`. Only factors coprime
+    /// to 12 (1, 5, 7, and 11) keep the set's cardinality, see [`Self::m5`] and [`Self::m7`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let set = PitchClassSet::from_chromas([0, 1, 2]);
+    ///
+    /// assert_eq!(set.multiply(5), PitchClassSet::from_chromas([0, 5, 10]));
+    ///
+    /// // multiplying by 11 is inversion around C
+    /// assert_eq!(set.multiply(11), PitchClassSet::from_chromas([0, 11, 10]));
+    /// ```
+    #[must_use = "This method returns a new PitchClassSet instead of mutating the original"]
+    pub fn multiply(self, factor: u8) -> Self {
+        Self::from_chromas(self.into_iter().map(|pc| (pc.chroma() as u16 * factor as u16 % 12) as u8))
+    }
+
+    /// The M5 transformation, which multiplies every pitch class by 5.
+    ///
+    /// M5 maps the chromatic scale to the circle of fourths, so it exchanges interval classes
+    /// 1 and 5, and keeps the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let chromatic_tetrachord = PitchClassSet::from_chromas([0, 1, 2, 3]);
+    ///
+    /// assert_eq!(chromatic_tetrachord.m5(), PitchClassSet::from_chromas([0, 5, 10, 3]));
+    /// assert_eq!(*chromatic_tetrachord.interval_class_vector(), [3, 2, 1, 0, 0, 0]);
+    /// assert_eq!(*chromatic_tetrachord.m5().interval_class_vector(), [0, 2, 1, 0, 3, 0]);
+    /// ```
+    #[must_use = "This method returns a new PitchClassSet instead of mutating the original"]
+    pub fn m5(self) -> Self {
+        self.multiply(5)
+    }
+
+    /// The M7 transformation, which multiplies every pitch class by 7.
+    ///
+    /// M7 maps the chromatic scale to the circle of fifths, and is the inversion of [M5](Self::m5).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::PitchClass;
+    /// # use music_theory::set::PitchClassSet;
+    /// let set = PitchClassSet::from_chromas([0, 1, 2, 3]);
+    ///
+    /// assert_eq!(set.m7(), PitchClassSet::from_chromas([0, 7, 2, 9]));
+    /// assert_eq!(set.m7(), set.m5().invert_around(PitchClass::C));
+    /// ```
+    #[must_use = "This method returns a new PitchClassSet instead of mutating the original"]
+    pub fn m7(self) -> Self {
+        self.multiply(7)
+    }
+
     /// Returns `true` if this set is a superset of the other set.
     ///
     /// A set is a superset of another if it contains all pitch classes in the other set.
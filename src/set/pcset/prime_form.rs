@@ -0,0 +1,178 @@
+use crate::PitchClass;
+use crate::set::PitchClassSet;
+
+/// The convention used to break ties when finding the [prime form](PitchClassSet::prime_form) of a set.
+///
+/// The two conventions only disagree for six set classes: 5-20, 6-Z29, 6-31, 7-20, 7-Z18, and 8-26.
+///
+/// # Examples
+/// ```
+/// # use music_theory::set::{PitchClassSet, PrimeFormConvention};
+/// let set = PitchClassSet::from_chromas([0, 1, 5, 6, 8]);
+///
+/// assert_eq!(set.prime_form(PrimeFormConvention::Forte), PitchClassSet::from_chromas([0, 1, 3, 7, 8]));
+/// assert_eq!(set.prime_form(PrimeFormConvention::Rahn), PitchClassSet::from_chromas([0, 1, 5, 6, 8]));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrimeFormConvention {
+    /// Allen Forte's convention, which prefers the form with the smallest intervals from the first
+    /// pitch class. This is the convention used for [Forte numbers](crate::set::SetClass).
+    #[default]
+    Forte,
+    /// John Rahn's convention, which prefers the form with the smallest intervals to the last
+    /// pitch classes, as used by most textbooks and software.
+    Rahn,
+}
+
+// the chromas of a set starting from `first`, as intervals above it
+fn intervals_from(chromas: &[u8], first: usize) -> Vec<u8> {
+    let root = chromas[first];
+
+    chromas[first..].iter()
+        .chain(&chromas[..first])
+        .map(|c| (c + 12 - root) % 12)
+        .collect()
+}
+
+// how tightly packed a rotation is: the span first, then the intervals
+// from the first pitch class to the others, from the left or right
+fn packing(intervals: &[u8], convention: PrimeFormConvention) -> Vec<u8> {
+    let (span, rest) = intervals.split_last().expect("rotations are non-empty");
+
+    let mut packing = vec![*span];
+
+    match convention {
+        PrimeFormConvention::Forte => packing.extend(rest.iter().skip(1)),
+        PrimeFormConvention::Rahn => packing.extend(rest.iter().skip(1).rev()),
+    }
+
+    packing
+}
+
+impl PitchClassSet {
+    /// Creates a pitch class set from chroma values, where C is 0, ignoring chromas above 11.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::PitchClass;
+    /// # use music_theory::set::PitchClassSet;
+    /// assert_eq!(
+    ///     PitchClassSet::from_chromas([0, 4, 7]),
+    ///     PitchClassSet::from_iter([PitchClass::C, PitchClass::E, PitchClass::G]),
+    /// );
+    /// ```
+    pub fn from_chromas(chromas: impl IntoIterator<Item = u8>) -> Self {
+        chromas.into_iter()
+            .filter_map(PitchClass::from_chroma)
+            .collect()
+    }
+
+    /// Returns the pitch classes of the set in normal order, the rotation of the set that is most
+    /// tightly packed.
+    ///
+    /// The normal order has the smallest span from its first to last pitch class. Ties are broken
+    /// by the smallest interval from the first to the second to last pitch class, then the third
+    /// to last and so on, and then by the lowest first pitch class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::PitchClass;
+    /// # use music_theory::set::PitchClassSet;
+    /// use PitchClass as PC;
+    ///
+    /// let set = PitchClassSet::from_iter([PC::C, PC::E, PC::A]);
+    /// assert_eq!(set.normal_order(), [PC::A, PC::C, PC::E]);
+    ///
+    /// let set = PitchClassSet::from_iter([PC::C, PC::D, PC::Fs, PC::A, PC::B]);
+    /// assert_eq!(set.normal_order(), [PC::Fs, PC::A, PC::B, PC::C, PC::D]);
+    /// ```
+    pub fn normal_order(self) -> Vec<PitchClass> {
+        let chromas = self.into_iter().map(PitchClass::chroma).collect::<Vec<_>>();
+
+        let Some(first) = (0..chromas.len()).min_by_key(|&i| packing(&intervals_from(&chromas, i), PrimeFormConvention::Rahn)) else {
+            return Vec::new();
+        };
+
+        chromas[first..].iter()
+            .chain(&chromas[..first])
+            .filter_map(|&c| PitchClass::from_chroma(c))
+            .collect()
+    }
+
+    /// Returns the prime form of the set, the most tightly packed form of the set or its inversion,
+    /// transposed to start on C.
+    ///
+    /// Unlike [`normalized`](Self::normalized), sets that are inversions of each other have the same
+    /// prime form, so the prime form names the set's set class. The `convention` decides how ties are
+    /// broken, see [`PrimeFormConvention`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{PitchClassSet, PrimeFormConvention};
+    /// let major = PitchClassSet::from_chromas([0, 4, 7]);
+    /// let minor = PitchClassSet::from_chromas([2, 5, 9]);
+    ///
+    /// let prime = PitchClassSet::from_chromas([0, 3, 7]);
+    ///
+    /// assert_eq!(major.prime_form(PrimeFormConvention::Forte), prime);
+    /// assert_eq!(minor.prime_form(PrimeFormConvention::Forte), prime);
+    ///
+    /// // normalizing only considers transpositions
+    /// assert_ne!(major.normalized(), minor.normalized());
+    /// ```
+    #[must_use = "This method returns a new PitchClassSet instead of mutating the original"]
+    pub fn prime_form(self, convention: PrimeFormConvention) -> Self {
+        [self, self.invert_around(PitchClass::C)]
+            .into_iter()
+            .flat_map(|set| {
+                let chromas = set.into_iter().map(PitchClass::chroma).collect::<Vec<_>>();
+
+                (0..chromas.len()).map(move |i| intervals_from(&chromas, i))
+            })
+            .min_by_key(|intervals| packing(intervals, convention))
+            .map(Self::from_chromas)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_forms() {
+        use PrimeFormConvention as C;
+
+        let cases: [(&[u8], &[u8], &[u8]); 6] = [
+            (&[0, 1, 5, 6, 8], &[0, 1, 3, 7, 8], &[0, 1, 5, 6, 8]),
+            (&[0, 1, 3, 6, 8, 9], &[0, 1, 3, 6, 8, 9], &[0, 2, 3, 6, 7, 9]),
+            (&[0, 1, 3, 5, 8, 9], &[0, 1, 3, 5, 8, 9], &[0, 1, 4, 5, 7, 9]),
+            (&[2, 6, 9, 0], &[0, 2, 5, 8], &[0, 2, 5, 8]),
+            (&[11, 3, 6], &[0, 3, 7], &[0, 3, 7]),
+            (&[], &[], &[]),
+        ];
+
+        for (set, forte, rahn) in cases {
+            let set = PitchClassSet::from_chromas(set.iter().copied());
+
+            assert_eq!(set.prime_form(C::Forte), PitchClassSet::from_chromas(forte.iter().copied()), "{set}");
+            assert_eq!(set.prime_form(C::Rahn), PitchClassSet::from_chromas(rahn.iter().copied()), "{set}");
+        }
+    }
+
+    #[test]
+    fn normal_order() {
+        let chromas = |set: &[u8]| PitchClassSet::from_chromas(set.iter().copied())
+            .normal_order()
+            .into_iter()
+            .map(PitchClass::chroma)
+            .collect::<Vec<_>>();
+
+        assert_eq!(chromas(&[]), []);
+        assert_eq!(chromas(&[5]), [5]);
+        assert_eq!(chromas(&[0, 4, 8]), [0, 4, 8]);
+        assert_eq!(chromas(&[1, 2, 7, 8]), [1, 2, 7, 8]);
+        assert_eq!(chromas(&[0, 1, 4, 6, 10]), [10, 0, 1, 4, 6]);
+    }
+}
@@ -0,0 +1,66 @@
+use std::iter::FusedIterator;
+use crate::set::pcset::PitchClassSet;
+
+/// An iterator over the subsets of a [`PitchClassSet`].
+///
+/// Obtained via [`PitchClassSet::subsets()`] or [`PitchClassSet::supersets()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subsets {
+    set: PitchClassSet,
+    // added to every subset, for supersets
+    base: PitchClassSet,
+    next: Option<u16>,
+}
+
+impl Iterator for Subsets {
+    type Item = PitchClassSet;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        // the next smaller subset of the set's bits
+        self.next = (current != 0).then(|| (current - 1) & self.set.0);
+
+        Some(PitchClassSet(current) | self.base)
+    }
+}
+
+impl FusedIterator for Subsets {}
+
+impl PitchClassSet {
+    /// Returns an iterator over every subset of the set, including the set itself and the empty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let triad = PitchClassSet::from_chromas([0, 4, 7]);
+    ///
+    /// assert_eq!(triad.subsets().count(), 8);
+    ///
+    /// // the dyads of the triad
+    /// let dyads = triad.subsets().filter(|s| s.len() == 2).collect::<Vec<_>>();
+    /// assert_eq!(dyads.len(), 3);
+    /// assert!(dyads.contains(&PitchClassSet::from_chromas([4, 7])));
+    /// ```
+    pub fn subsets(self) -> Subsets {
+        Subsets { set: self, base: Self::EMPTY, next: Some(self.0) }
+    }
+
+    /// Returns an iterator over every superset of the set, including the set itself and the
+    /// chromatic aggregate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let triad = PitchClassSet::from_chromas([0, 4, 7]);
+    ///
+    /// // there are 2^9 ways to add to the triad
+    /// assert_eq!(triad.supersets().count(), 512);
+    /// assert!(triad.supersets().all(|s| s.is_superset_of(triad)));
+    /// ```
+    pub fn supersets(self) -> Subsets {
+        Subsets { set: self.complement(), base: self, next: Some(self.complement().0) }
+    }
+}
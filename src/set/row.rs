@@ -0,0 +1,657 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::{PitchClass, Semitones};
+use crate::set::{PitchClassSegment, PitchClassSet};
+
+/// A twelve-tone row, an ordering of all twelve pitch classes.
+///
+/// The forms of a row are labeled by the pitch class they start on: `P3` is the prime form
+/// starting on E♭, and `I3` is the inversion starting on E♭. Retrogrades are labeled like the
+/// form they are the retrograde of, so `R3` and `RI3` end on E♭. The row itself is `Pn`,
+/// where `n` is its first pitch class.
+///
+/// # Examples
+/// ```
+/// # use music_theory::PitchClass;
+/// # use music_theory::set::{RowForm, ToneRow};
+/// // Webern's Concerto, Op. 24
+/// let row = ToneRow::from_chromas([11, 10, 2, 3, 7, 6, 8, 4, 5, 0, 1, 9]).unwrap();
+///
+/// let inversion = row.form("I0".parse().unwrap());
+/// assert_eq!(inversion.pitch_classes()[..3], [PitchClass::C, PitchClass::Cs, PitchClass::A]);
+///
+/// assert_eq!(row.find_form(&inversion), Some("I0".parse().unwrap()));
+/// assert_eq!(row.find_form(&row), Some("P11".parse().unwrap()));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "[PitchClass; 12]"))]
+pub struct ToneRow([PitchClass; 12]);
+
+/// Error returned when a [`ToneRow`] doesn't have every pitch class exactly once.
+///
+/// # Examples
+/// ```
+/// # use music_theory::PitchClass;
+/// # use music_theory::set::{ToneRow, ToneRowError};
+/// assert_eq!(ToneRow::from_chromas(0..11), Err(ToneRowError::WrongLength(11)));
+/// assert_eq!(
+///     ToneRow::from_chromas([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0]),
+///     Err(ToneRowError::RepeatedPitchClass(PitchClass::C)),
+/// );
+/// ```
+#[derive(Debug, thiserror::Error, Eq, PartialEq, Copy, Clone)]
+pub enum ToneRowError {
+    /// The row didn't have exactly twelve pitch classes.
+    #[error("A tone row must have 12 pitch classes, not {0}")]
+    WrongLength(usize),
+    /// The pitch class appeared in the row more than once.
+    #[error("{0} appears in the tone row more than once")]
+    RepeatedPitchClass(PitchClass),
+}
+
+/// The four ways a [`ToneRow`] can be transformed, apart from transposition.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, strum_macros::EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RowFormKind {
+    /// The row in its original order, `P`.
+    Prime,
+    /// The row with every interval inverted, `I`.
+    Inversion,
+    /// The row backwards, `R`.
+    Retrograde,
+    /// The inversion backwards, `RI`.
+    RetrogradeInversion,
+}
+
+/// A label for one of the 48 forms of a [`ToneRow`], such as `P0` or `RI11`.
+///
+/// # Examples
+/// ```
+/// # use music_theory::set::{RowForm, RowFormKind};
+/// let form = RowForm::new(RowFormKind::RetrogradeInversion, 11);
+///
+/// assert_eq!(form.to_string(), "RI11");
+/// assert_eq!("RI11".parse(), Ok(form));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RowForm {
+    kind: RowFormKind,
+    pitch_class: PitchClass,
+}
+
+impl RowForm {
+    /// Creates a row form label from its kind and the chroma of the pitch class it's labeled by,
+    /// modulo 12.
+    pub fn new(kind: RowFormKind, n: u8) -> Self {
+        let pitch_class = PitchClass::from_chroma(n % 12).expect("must be between [0,11] since did % 12");
+
+        Self { kind, pitch_class }
+    }
+
+    /// Returns every row form label, the primes first, then the inversions, retrogrades and
+    /// retrograde inversions.
+    pub fn all() -> impl Iterator<Item = Self> {
+        use strum::IntoEnumIterator;
+
+        RowFormKind::iter().flat_map(|kind| (0..12).map(move |n| Self::new(kind, n)))
+    }
+
+    /// Returns the kind of the form.
+    pub fn kind(self) -> RowFormKind {
+        self.kind
+    }
+
+    /// Returns the pitch class the form starts on, or ends on for retrogrades.
+    pub fn pitch_class(self) -> PitchClass {
+        self.pitch_class
+    }
+}
+
+impl fmt::Display for RowForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            RowFormKind::Prime => "P",
+            RowFormKind::Inversion => "I",
+            RowFormKind::Retrograde => "R",
+            RowFormKind::RetrogradeInversion => "RI",
+        };
+
+        write!(f, "{kind}{}", self.pitch_class.chroma())
+    }
+}
+
+/// Error returned when parsing a [`RowForm`] fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::set::{ParseRowFormError, RowForm};
+/// assert_eq!("X0".parse::<RowForm>(), Err(ParseRowFormError));
+/// assert_eq!("P12".parse::<RowForm>(), Err(ParseRowFormError));
+/// ```
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("Expected a row form like 'P0', 'I4', 'R11' or 'RI7'")]
+pub struct ParseRowFormError;
+
+impl FromStr for RowForm {
+    type Err = ParseRowFormError;
+
+    /// Parses a row form from its label, `P`, `I`, `R` or `RI` followed by a number from 0 to 11.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (kind, n) = if let Some(n) = s.strip_prefix("RI") {
+            (RowFormKind::RetrogradeInversion, n)
+        } else if let Some(n) = s.strip_prefix('R') {
+            (RowFormKind::Retrograde, n)
+        } else if let Some(n) = s.strip_prefix('I') {
+            (RowFormKind::Inversion, n)
+        } else if let Some(n) = s.strip_prefix('P') {
+            (RowFormKind::Prime, n)
+        } else {
+            return Err(ParseRowFormError);
+        };
+
+        if n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseRowFormError);
+        }
+
+        match n.parse::<u8>() {
+            Ok(n) if n < 12 => Ok(Self::new(kind, n)),
+            _ => Err(ParseRowFormError),
+        }
+    }
+}
+
+impl ToneRow {
+    /// Creates a tone row from pitch classes, which must be all twelve pitch classes in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToneRowError`] if there aren't twelve pitch classes, or a pitch class is repeated.
+    pub fn new(pitch_classes: impl IntoIterator<Item = PitchClass>) -> Result<Self, ToneRowError> {
+        let pitch_classes = pitch_classes.into_iter().collect::<Vec<_>>();
+
+        let mut seen = PitchClassSet::EMPTY;
+
+        for &pc in &pitch_classes {
+            if seen.is_set(pc) {
+                return Err(ToneRowError::RepeatedPitchClass(pc));
+            }
+
+            seen = seen | PitchClassSet::from_iter([pc]);
+        }
+
+        pitch_classes.try_into()
+            .map(Self)
+            .map_err(|pitch_classes: Vec<_>| ToneRowError::WrongLength(pitch_classes.len()))
+    }
+
+    /// Creates a tone row from chroma values, where C is 0, ignoring chromas above 11.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToneRowError`] if there aren't twelve pitch classes, or a pitch class is repeated.
+    pub fn from_chromas(chromas: impl IntoIterator<Item = u8>) -> Result<Self, ToneRowError> {
+        Self::new(chromas.into_iter().filter_map(PitchClass::from_chroma))
+    }
+
+    /// Returns the pitch classes of the row, in order.
+    pub fn pitch_classes(&self) -> &[PitchClass; 12] {
+        &self.0
+    }
+
+    /// Returns the row as a [`PitchClassSegment`].
+    pub fn segment(&self) -> PitchClassSegment {
+        PitchClassSegment::new(self.0)
+    }
+
+    // a segment that's known to be a row, such as a form of a row
+    fn from_segment(segment: &PitchClassSegment) -> Self {
+        Self::new(segment.pitch_classes().iter().copied()).expect("transformations of rows are rows")
+    }
+
+    /// Returns a form of the row, see [`ToneRow`] for how forms are labeled.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{RowForm, RowFormKind, ToneRow};
+    /// let row = ToneRow::from_chromas([0, 11, 7, 8, 3, 1, 2, 10, 6, 5, 4, 9]).unwrap();
+    ///
+    /// let r0 = row.form(RowForm::new(RowFormKind::Retrograde, 0));
+    /// assert_eq!(r0, ToneRow::from_chromas([9, 4, 5, 6, 10, 2, 1, 3, 8, 7, 11, 0]).unwrap());
+    ///
+    /// let i3 = row.form(RowForm::new(RowFormKind::Inversion, 3));
+    /// assert_eq!(i3, ToneRow::from_chromas([3, 4, 8, 7, 0, 2, 1, 5, 9, 10, 11, 6]).unwrap());
+    /// ```
+    pub fn form(&self, form: RowForm) -> Self {
+        let first = self.0[0];
+        let transposition = Semitones(first.semitones_to(form.pitch_class).0);
+
+        let segment = self.segment();
+
+        let segment = match form.kind {
+            RowFormKind::Prime => segment.transpose(transposition),
+            RowFormKind::Inversion => segment.invert_around(first).transpose(transposition),
+            RowFormKind::Retrograde => segment.transpose(transposition).retrograde(),
+            RowFormKind::RetrogradeInversion => segment.invert_around(first).transpose(transposition).retrograde(),
+        };
+
+        Self::from_segment(&segment)
+    }
+
+    /// Returns all 48 forms of the row along with their labels, in the order of [`RowForm::all`].
+    ///
+    /// Rows with symmetries have forms that are the same, with different labels.
+    pub fn forms(&self) -> impl Iterator<Item = (RowForm, Self)> {
+        RowForm::all().map(|form| (form, self.form(form)))
+    }
+
+    /// Returns which form of this row the other row is, if it is one.
+    ///
+    /// If the row has symmetries, the first matching label in the order of [`RowForm::all`] is returned.
+    pub fn find_form(&self, other: &Self) -> Option<RowForm> {
+        self.forms()
+            .find(|(_, form)| form == other)
+            .map(|(label, _)| label)
+    }
+
+    /// Rotates the row `n` places to the left, see [`PitchClassSegment::rotate`].
+    #[must_use = "This method returns a new ToneRow instead of mutating the original"]
+    pub fn rotate(&self, n: usize) -> Self {
+        let mut pitch_classes = self.0;
+        pitch_classes.rotate_left(n % 12);
+
+        Self(pitch_classes)
+    }
+
+    /// Returns the 11 ordered pitch class intervals between adjacent pitch classes.
+    pub fn intervals(&self) -> [Semitones; 11] {
+        std::array::from_fn(|i| self.0[i].semitones_to(self.0[i + 1]))
+    }
+
+    /// Returns `true` if every ordered interval from 1 to 11 semitones appears between adjacent
+    /// pitch classes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::ToneRow;
+    /// // Berg's Lyric Suite
+    /// let row = ToneRow::from_chromas([5, 4, 0, 9, 7, 2, 8, 1, 3, 6, 10, 11]).unwrap();
+    /// assert!(row.is_all_interval());
+    ///
+    /// let chromatic = ToneRow::from_chromas(0..12).unwrap();
+    /// assert!(!chromatic.is_all_interval());
+    /// ```
+    pub fn is_all_interval(&self) -> bool {
+        let intervals = self.intervals()
+            .into_iter()
+            .fold(0u16, |seen, interval| seen | 1 << interval.0);
+
+        intervals == 0xffe
+    }
+
+    /// Returns every all-interval row starting on C, see [`Self::is_all_interval`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::ToneRow;
+    /// let rows = ToneRow::all_interval_rows();
+    ///
+    /// assert_eq!(rows.len(), 3856);
+    /// assert!(rows.iter().all(ToneRow::is_all_interval));
+    /// ```
+    pub fn all_interval_rows() -> Vec<Self> {
+        fn extend(row: &mut Vec<u8>, used_intervals: u16, rows: &mut Vec<ToneRow>) {
+            if row.len() == 12 {
+                rows.push(ToneRow::from_chromas(row.iter().copied()).expect("every pitch class is used once"));
+                return;
+            }
+
+            let last = *row.last().expect("rows start on C");
+
+            for interval in 1..12 {
+                let next = (last + interval) % 12;
+
+                if used_intervals & 1 << interval != 0 || row.contains(&next) {
+                    continue;
+                }
+
+                row.push(next);
+                extend(row, used_intervals | 1 << interval, rows);
+                row.pop();
+            }
+        }
+
+        let mut rows = Vec::new();
+        extend(&mut vec![0], 0, &mut rows);
+        rows
+    }
+
+    /// Returns the unordered sets of the first and last six pitch classes of the row.
+    pub fn hexachords(&self) -> (PitchClassSet, PitchClassSet) {
+        (self.0[..6].iter().copied().collect(), self.0[6..].iter().copied().collect())
+    }
+
+    /// Returns the forms of the row whose first hexachord is the row's second hexachord,
+    /// so that combining the row with them forms aggregates at each hexachord.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{RowForm, ToneRow};
+    /// // Schoenberg's Piano Piece, Op. 33a
+    /// let row = ToneRow::from_chromas([10, 5, 0, 11, 9, 6, 1, 3, 7, 8, 2, 4]).unwrap();
+    ///
+    /// let forms = row.combinatorial_forms().iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(forms, ["I3", "R10"]);
+    /// ```
+    pub fn combinatorial_forms(&self) -> Vec<RowForm> {
+        let (_, second) = self.hexachords();
+
+        self.forms()
+            .filter(|(_, form)| form.hexachords().0 == second)
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    /// Returns `true` if the row is hexachordally combinatorial with one of its forms of the kind.
+    ///
+    /// Every row is combinatorial with its own retrograde. A row is semi-combinatorial if it's
+    /// combinatorial with a transposition, an inversion or a retrograde inversion.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{RowFormKind, ToneRow};
+    /// // Schoenberg's Piano Piece, Op. 33a
+    /// let row = ToneRow::from_chromas([10, 5, 0, 11, 9, 6, 1, 3, 7, 8, 2, 4]).unwrap();
+    ///
+    /// assert!(row.is_combinatorial(RowFormKind::Inversion));
+    /// assert!(!row.is_combinatorial(RowFormKind::Prime));
+    /// ```
+    pub fn is_combinatorial(&self, kind: RowFormKind) -> bool {
+        self.combinatorial_forms().iter().any(|form| form.kind == kind)
+    }
+
+    /// Returns `true` if the row is hexachordally combinatorial with a transposition, an inversion
+    /// and a retrograde inversion of itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::ToneRow;
+    /// let chromatic = ToneRow::from_chromas(0..12).unwrap();
+    /// assert!(chromatic.is_all_combinatorial());
+    ///
+    /// // Schoenberg's Piano Piece, Op. 33a
+    /// let row = ToneRow::from_chromas([10, 5, 0, 11, 9, 6, 1, 3, 7, 8, 2, 4]).unwrap();
+    /// assert!(!row.is_all_combinatorial());
+    /// ```
+    pub fn is_all_combinatorial(&self) -> bool {
+        [RowFormKind::Prime, RowFormKind::Inversion, RowFormKind::RetrogradeInversion]
+            .into_iter()
+            .all(|kind| self.is_combinatorial(kind))
+    }
+
+    /// Returns `true` if the row can be split into segments of `segment_len` pitch classes that
+    /// are all [forms](PitchClassSegment::is_form_of) of the first segment.
+    ///
+    /// Returns `false` if `segment_len` doesn't divide 12.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::ToneRow;
+    /// // Webern's Concerto, Op. 24
+    /// let row = ToneRow::from_chromas([11, 10, 2, 3, 7, 6, 8, 4, 5, 0, 1, 9]).unwrap();
+    ///
+    /// assert!(row.is_derived(3));
+    /// assert!(!row.is_derived(4));
+    /// ```
+    pub fn is_derived(&self, segment_len: usize) -> bool {
+        if segment_len == 0 || 12 % segment_len != 0 {
+            return false;
+        }
+
+        let mut segments = self.0.chunks(segment_len).map(|chunk| PitchClassSegment::new(chunk.iter().copied()));
+        let first = segments.next().expect("rows have 12 pitch classes");
+
+        segments.all(|segment| segment.is_form_of(&first))
+    }
+
+    /// Returns every row that starts with the generator and continues with forms of it.
+    ///
+    /// Returns no rows if the generator's length doesn't divide 12, or it repeats a pitch class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{PitchClassSegment, ToneRow};
+    /// let generator = PitchClassSegment::from_chromas([11, 10, 2]);
+    /// let rows = ToneRow::derived_from(&generator);
+    ///
+    /// // Webern's Concerto, Op. 24
+    /// let row = ToneRow::from_chromas([11, 10, 2, 3, 7, 6, 8, 4, 5, 0, 1, 9]).unwrap();
+    /// assert!(rows.contains(&row));
+    /// assert!(rows.iter().all(|row| row.is_derived(3)));
+    /// ```
+    pub fn derived_from(generator: &PitchClassSegment) -> Vec<Self> {
+        let len = generator.len();
+
+        if len == 0 || 12 % len != 0 || generator.pitch_class_set().len() as usize != len {
+            return Vec::new();
+        }
+
+        let mut forms = Vec::<PitchClassSegment>::new();
+
+        for form in generator.transformations() {
+            if !forms.contains(&form) {
+                forms.push(form);
+            }
+        }
+
+        fn extend(row: &mut Vec<PitchClass>, used: PitchClassSet, forms: &[PitchClassSegment], rows: &mut Vec<ToneRow>) {
+            if row.len() == 12 {
+                rows.push(ToneRow::new(row.iter().copied()).expect("segments are disjoint"));
+                return;
+            }
+
+            for form in forms {
+                let set = form.pitch_class_set();
+
+                if !(set & used).is_empty() {
+                    continue;
+                }
+
+                row.extend_from_slice(form.pitch_classes());
+                extend(row, used | set, forms, rows);
+                row.truncate(row.len() - form.len());
+            }
+        }
+
+        let mut rows = Vec::new();
+        extend(&mut generator.pitch_classes().to_vec(), generator.pitch_class_set(), &forms, &mut rows);
+        rows
+    }
+
+    /// Returns the row's twelve-tone matrix.
+    pub fn matrix(&self) -> RowMatrix {
+        RowMatrix(*self)
+    }
+}
+
+impl TryFrom<[PitchClass; 12]> for ToneRow {
+    type Error = ToneRowError;
+
+    fn try_from(pitch_classes: [PitchClass; 12]) -> Result<Self, Self::Error> {
+        Self::new(pitch_classes)
+    }
+}
+
+impl TryFrom<PitchClassSegment> for ToneRow {
+    type Error = ToneRowError;
+
+    fn try_from(segment: PitchClassSegment) -> Result<Self, Self::Error> {
+        Self::new(segment.pitch_classes().iter().copied())
+    }
+}
+
+impl fmt::Display for ToneRow {
+    /// Formats the row as its pitch classes in angle brackets, like [`PitchClassSegment`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segment().fmt(f)
+    }
+}
+
+/// The twelve-tone matrix of a [`ToneRow`].
+///
+/// The top row is the row itself, and the left column is its inversion starting on the same pitch
+/// class. The rows from left to right are the prime forms, and from right to left the retrogrades.
+/// The columns from top to bottom are the inversions, and from bottom to top the retrograde inversions.
+///
+/// The matrix is displayed with chromas, or with pitch class names using `{:#}`.
+///
+/// # Examples
+/// ```
+/// # use music_theory::PitchClass;
+/// # use music_theory::set::ToneRow;
+/// let row = ToneRow::from_chromas([0, 11, 7, 8, 3, 1, 2, 10, 6, 5, 4, 9]).unwrap();
+/// let matrix = row.matrix();
+///
+/// assert_eq!(matrix.get(1, 0), PitchClass::Cs);
+/// assert_eq!(matrix.row(1), row.form("P1".parse().unwrap()));
+/// assert_eq!(matrix.column(1), row.form("I11".parse().unwrap()));
+///
+/// assert_eq!(matrix.to_string().lines().nth(1).unwrap().trim_end(),
+///     "P0      0   11    7    8    3    1    2   10    6    5    4    9  R0");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RowMatrix(ToneRow);
+
+impl RowMatrix {
+    /// Returns the pitch class in the matrix's `row`th row and `column`th column, from zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `column` isn't in `[0, 11]`.
+    pub fn get(&self, row: usize, column: usize) -> PitchClass {
+        let pcs = (self.0).0;
+
+        pcs[column] + pcs[row].semitones_to(pcs[0])
+    }
+
+    /// Returns the matrix's `index`th row from the top, a prime form of the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't in `[0, 11]`.
+    pub fn row(&self, index: usize) -> ToneRow {
+        ToneRow(std::array::from_fn(|column| self.get(index, column)))
+    }
+
+    /// Returns the matrix's `index`th column from the left, an inversion of the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` isn't in `[0, 11]`.
+    pub fn column(&self, index: usize) -> ToneRow {
+        ToneRow(std::array::from_fn(|row| self.get(row, index)))
+    }
+
+    /// Returns the labels of the matrix's rows, from the top.
+    pub fn row_labels(&self) -> [RowForm; 12] {
+        std::array::from_fn(|i| RowForm::new(RowFormKind::Prime, self.get(i, 0).chroma()))
+    }
+
+    /// Returns the labels of the matrix's columns, from the left.
+    pub fn column_labels(&self) -> [RowForm; 12] {
+        std::array::from_fn(|i| RowForm::new(RowFormKind::Inversion, self.get(0, i).chroma()))
+    }
+}
+
+impl fmt::Display for RowMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = |kind, pcs: [RowForm; 12]| pcs.map(|label| RowForm { kind, ..label }.to_string());
+
+        write!(f, "{:4}", "")?;
+
+        for label in labels(RowFormKind::Inversion, self.column_labels()) {
+            write!(f, "{label:>5}")?;
+        }
+
+        for (i, (prime, retrograde)) in labels(RowFormKind::Prime, self.row_labels())
+            .into_iter()
+            .zip(labels(RowFormKind::Retrograde, self.row_labels()))
+            .enumerate()
+        {
+            write!(f, "\n{prime:4}")?;
+
+            for pc in self.row(i).0 {
+                if f.alternate() {
+                    write!(f, "{:>5}", pc.to_string())?;
+                } else {
+                    write!(f, "{:>5}", pc.chroma())?;
+                }
+            }
+
+            write!(f, "  {retrograde}")?;
+        }
+
+        write!(f, "\n{:4}", "")?;
+
+        for label in labels(RowFormKind::RetrogradeInversion, self.column_labels()) {
+            write!(f, "{label:>5}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forms() {
+        let row = ToneRow::from_chromas([0, 11, 7, 8, 3, 1, 2, 10, 6, 5, 4, 9]).unwrap();
+        let matrix = row.matrix();
+
+        for (i, (label, form)) in matrix.row_labels().into_iter().zip(matrix.column_labels()).enumerate() {
+            assert_eq!(row.form(label), matrix.row(i));
+            assert_eq!(row.form(form), matrix.column(i));
+
+            let retrograde = RowForm::new(RowFormKind::Retrograde, label.pitch_class().chroma());
+            assert_eq!(row.form(retrograde).segment(), matrix.row(i).segment().retrograde());
+        }
+
+        // the row has no symmetries, so every form is different
+        for (label, form) in row.forms() {
+            assert_eq!(row.find_form(&form), Some(label));
+            assert_eq!(label.to_string().parse(), Ok(label));
+        }
+
+        // the chromatic scale's retrograde inversions are also transpositions
+        let chromatic = ToneRow::from_chromas(0..12).unwrap();
+        let ri0 = chromatic.form("RI0".parse().unwrap());
+
+        assert_eq!(chromatic.find_form(&ri0), Some("P1".parse().unwrap()));
+        assert_eq!(chromatic.find_form(&chromatic.form("R0".parse().unwrap())), Some("I11".parse().unwrap()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, SeqDeserializer};
+
+        let deserialize = |names: &str| ToneRow::deserialize(
+            SeqDeserializer::<_, Error>::new(names.split(' '))
+        ).map_err(|e| e.to_string());
+
+        let row = ToneRow::from_chromas([0, 11, 7, 8, 3, 1, 2, 10, 6, 5, 4, 9]).unwrap();
+        assert_eq!(deserialize("C B G Gs Ds Cs D As Fs F E A"), Ok(row));
+
+        assert_eq!(
+            deserialize("C B G Gs Ds Cs D As Fs F E C"),
+            Err(ToneRowError::RepeatedPitchClass(PitchClass::C).to_string()),
+        );
+        assert!(deserialize("C B G Gs Ds Cs D As Fs F E").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+use crate::{PitchClass, Semitones};
+use crate::set::PitchClassSet;
+
+/// An ordered sequence of pitch classes, which may repeat and have any length.
+///
+/// Segments are the ordered counterpart of [`PitchClassSet`], and are used for motives,
+/// [tone rows](crate::set::ToneRow) and their parts.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{PitchClass, Semitones};
+/// # use music_theory::set::PitchClassSegment;
+/// let motive = PitchClassSegment::from_chromas([0, 11, 3]);
+///
+/// assert_eq!(motive.retrograde(), PitchClassSegment::from_chromas([3, 11, 0]));
+/// assert_eq!(motive.transpose(Semitones(4)), PitchClassSegment::from_chromas([4, 3, 7]));
+/// assert_eq!(motive.rotate(1), PitchClassSegment::from_chromas([11, 3, 0]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitchClassSegment(Vec<PitchClass>);
+
+impl PitchClassSegment {
+    /// Creates a segment from pitch classes, in order.
+    pub fn new(pitch_classes: impl IntoIterator<Item = PitchClass>) -> Self {
+        Self(pitch_classes.into_iter().collect())
+    }
+
+    /// Creates a segment from chroma values, where C is 0, ignoring chromas above 11.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::PitchClass;
+    /// # use music_theory::set::PitchClassSegment;
+    /// let segment = PitchClassSegment::from_chromas([0, 4, 7, 4]);
+    ///
+    /// assert_eq!(segment.pitch_classes(), [PitchClass::C, PitchClass::E, PitchClass::G, PitchClass::E]);
+    /// ```
+    pub fn from_chromas(chromas: impl IntoIterator<Item = u8>) -> Self {
+        Self(chromas.into_iter().filter_map(PitchClass::from_chroma).collect())
+    }
+
+    /// Returns the pitch classes of the segment, in order.
+    pub fn pitch_classes(&self) -> &[PitchClass] {
+        &self.0
+    }
+
+    /// Returns the number of pitch classes in the segment, counting repeats.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the segment has no pitch classes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the unordered set of the segment's pitch classes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{PitchClassSegment, PitchClassSet};
+    /// let segment = PitchClassSegment::from_chromas([7, 4, 0, 4]);
+    ///
+    /// assert_eq!(segment.pitch_class_set(), PitchClassSet::from_chromas([0, 4, 7]));
+    /// ```
+    pub fn pitch_class_set(&self) -> PitchClassSet {
+        self.0.iter().copied().collect()
+    }
+
+    /// Returns the ordered pitch class intervals between adjacent pitch classes, each in `[0, 11]`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Semitones;
+    /// # use music_theory::set::PitchClassSegment;
+    /// let segment = PitchClassSegment::from_chromas([0, 11, 3]);
+    ///
+    /// assert_eq!(segment.intervals(), [Semitones(11), Semitones(4)]);
+    /// ```
+    pub fn intervals(&self) -> Vec<Semitones> {
+        self.0.windows(2)
+            .map(|pair| pair[0].semitones_to(pair[1]))
+            .collect()
+    }
+
+    /// Transposes every pitch class in the segment by a number of semitones.
+    #[must_use = "This method returns a new PitchClassSegment instead of mutating the original"]
+    pub fn transpose(&self, semitones: Semitones) -> Self {
+        Self(self.0.iter().map(|&pc| pc + semitones).collect())
+    }
+
+    /// Inverts every pitch class in the segment around an axis.
+    ///
+    /// Like [`PitchClassSet::invert_around`], each pitch class `p` is mapped to `(2a - p) mod 12`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::PitchClass;
+    /// # use music_theory::set::PitchClassSegment;
+    /// let segment = PitchClassSegment::from_chromas([0, 4, 7]);
+    ///
+    /// assert_eq!(segment.invert_around(PitchClass::C), PitchClassSegment::from_chromas([0, 8, 5]));
+    /// assert_eq!(segment.invert_around(PitchClass::D), PitchClassSegment::from_chromas([4, 0, 9]));
+    /// ```
+    #[must_use = "This method returns a new PitchClassSegment instead of mutating the original"]
+    pub fn invert_around(&self, axis: PitchClass) -> Self {
+        let axis = 2 * axis.chroma() as i16;
+
+        Self(self.0.iter().map(|&pc| PitchClass::C + Semitones(axis - pc.chroma() as i16)).collect())
+    }
+
+    /// Reverses the order of the segment.
+    #[must_use = "This method returns a new PitchClassSegment instead of mutating the original"]
+    pub fn retrograde(&self) -> Self {
+        Self(self.0.iter().rev().copied().collect())
+    }
+
+    /// Multiplies every pitch class in the segment by a factor, modulo 12,
+    /// see [`PitchClassSet::multiply`].
+    #[must_use = "This method returns a new PitchClassSegment instead of mutating the original"]
+    pub fn multiply(&self, factor: u8) -> Self {
+        Self::from_chromas(self.0.iter().map(|pc| (pc.chroma() as u16 * factor as u16 % 12) as u8))
+    }
+
+    /// Rotates the segment `n` places to the left, so that it starts on its `n`th pitch class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSegment;
+    /// let segment = PitchClassSegment::from_chromas([0, 1, 2, 3]);
+    ///
+    /// assert_eq!(segment.rotate(1), PitchClassSegment::from_chromas([1, 2, 3, 0]));
+    /// assert_eq!(segment.rotate(6), PitchClassSegment::from_chromas([2, 3, 0, 1]));
+    /// ```
+    #[must_use = "This method returns a new PitchClassSegment instead of mutating the original"]
+    pub fn rotate(&self, n: usize) -> Self {
+        let mut pitch_classes = self.0.clone();
+
+        if !pitch_classes.is_empty() {
+            pitch_classes.rotate_left(n % self.len());
+        }
+
+        Self(pitch_classes)
+    }
+
+    /// Returns every rotation of the segment, starting with the segment itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSegment;
+    /// let segment = PitchClassSegment::from_chromas([0, 4, 7]);
+    ///
+    /// assert_eq!(segment.rotations().collect::<Vec<_>>(), [
+    ///     PitchClassSegment::from_chromas([0, 4, 7]),
+    ///     PitchClassSegment::from_chromas([4, 7, 0]),
+    ///     PitchClassSegment::from_chromas([7, 0, 4]),
+    /// ]);
+    /// ```
+    pub fn rotations(&self) -> impl Iterator<Item = Self> {
+        (0..self.len()).map(|n| self.rotate(n))
+    }
+
+    /// Returns every transposition, inversion, retrograde and retrograde inversion of the segment,
+    /// which may repeat if the segment is symmetrical.
+    pub(crate) fn transformations(&self) -> impl Iterator<Item = Self> {
+        let inversion = self.invert_around(PitchClass::C);
+
+        [self.clone(), inversion.clone(), self.retrograde(), inversion.retrograde()]
+            .into_iter()
+            .flat_map(|segment| (0..12).map(move |n| segment.transpose(Semitones(n))))
+    }
+
+    /// Returns `true` if the segment is a transposition, inversion, retrograde or retrograde
+    /// inversion of the other segment.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSegment;
+    /// let motive = PitchClassSegment::from_chromas([0, 11, 3]);
+    ///
+    /// assert!(PitchClassSegment::from_chromas([4, 8, 7]).is_form_of(&motive));
+    /// assert!(PitchClassSegment::from_chromas([5, 6, 2]).is_form_of(&motive));
+    ///
+    /// // the same set, in a different order
+    /// assert!(!PitchClassSegment::from_chromas([0, 3, 11]).is_form_of(&motive));
+    /// ```
+    pub fn is_form_of(&self, other: &Self) -> bool {
+        self.len() == other.len() && other.transformations().any(|form| form == *self)
+    }
+}
+
+impl FromIterator<PitchClass> for PitchClassSegment {
+    fn from_iter<T: IntoIterator<Item = PitchClass>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl fmt::Display for PitchClassSegment {
+    /// Formats the segment as its pitch classes in angle brackets.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSegment;
+    /// assert_eq!(PitchClassSegment::from_chromas([0, 11, 3]).to_string(), "<C, B, D♯>");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<")?;
+
+        for (i, pc) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{pc}")?;
+        }
+
+        f.write_str(">")
+    }
+}
@@ -0,0 +1,620 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use crate::{PitchClass, Semitones};
+use crate::set::{PitchClassSet, PrimeFormConvention};
+
+// Forte's names and prime forms of the set classes with up to six pitch classes,
+// where 'T' and 'E' are 10 and 11. Larger set classes are the complements of these.
+const FORTE_TABLE: [(&str, &str); 137] = [
+    ("0-1", ""),
+    ("1-1", "0"),
+    ("2-1", "01"), ("2-2", "02"), ("2-3", "03"), ("2-4", "04"), ("2-5", "05"), ("2-6", "06"),
+    ("3-1", "012"), ("3-2", "013"), ("3-3", "014"), ("3-4", "015"), ("3-5", "016"), ("3-6", "024"),
+    ("3-7", "025"), ("3-8", "026"), ("3-9", "027"), ("3-10", "036"), ("3-11", "037"), ("3-12", "048"),
+    ("4-1", "0123"), ("4-2", "0124"), ("4-3", "0134"), ("4-4", "0125"), ("4-5", "0126"),
+    ("4-6", "0127"), ("4-7", "0145"), ("4-8", "0156"), ("4-9", "0167"), ("4-10", "0235"),
+    ("4-11", "0135"), ("4-12", "0236"), ("4-13", "0136"), ("4-14", "0237"), ("4-Z15", "0146"),
+    ("4-16", "0157"), ("4-17", "0347"), ("4-18", "0147"), ("4-19", "0148"), ("4-20", "0158"),
+    ("4-21", "0246"), ("4-22", "0247"), ("4-23", "0257"), ("4-24", "0248"), ("4-25", "0268"),
+    ("4-26", "0358"), ("4-27", "0258"), ("4-28", "0369"), ("4-Z29", "0137"),
+    ("5-1", "01234"), ("5-2", "01235"), ("5-3", "01245"), ("5-4", "01236"), ("5-5", "01237"),
+    ("5-6", "01256"), ("5-7", "01267"), ("5-8", "02346"), ("5-9", "01246"), ("5-10", "01346"),
+    ("5-11", "02347"), ("5-Z12", "01356"), ("5-13", "01248"), ("5-14", "01257"), ("5-15", "01268"),
+    ("5-16", "01347"), ("5-Z17", "01348"), ("5-Z18", "01457"), ("5-19", "01367"), ("5-20", "01378"),
+    ("5-21", "01458"), ("5-22", "01478"), ("5-23", "02357"), ("5-24", "01357"), ("5-25", "02358"),
+    ("5-26", "02458"), ("5-27", "01358"), ("5-28", "02368"), ("5-29", "01368"), ("5-30", "01468"),
+    ("5-31", "01369"), ("5-32", "01469"), ("5-33", "02468"), ("5-34", "02469"), ("5-35", "02479"),
+    ("5-Z36", "01247"), ("5-Z37", "03458"), ("5-Z38", "01258"),
+    ("6-1", "012345"), ("6-2", "012346"), ("6-Z3", "012356"), ("6-Z4", "012456"), ("6-5", "012367"),
+    ("6-Z6", "012567"), ("6-7", "012678"), ("6-8", "023457"), ("6-9", "012357"), ("6-Z10", "013457"),
+    ("6-Z11", "012457"), ("6-Z12", "012467"), ("6-Z13", "013467"), ("6-14", "013458"), ("6-15", "012458"),
+    ("6-16", "014568"), ("6-Z17", "012478"), ("6-18", "012578"), ("6-Z19", "013478"), ("6-20", "014589"),
+    ("6-21", "023468"), ("6-22", "012468"), ("6-Z23", "023568"), ("6-Z24", "013468"), ("6-Z25", "013568"),
+    ("6-Z26", "013578"), ("6-27", "013469"), ("6-Z28", "013569"), ("6-Z29", "013689"), ("6-30", "013679"),
+    ("6-31", "013589"), ("6-32", "024579"), ("6-33", "023579"), ("6-34", "013579"), ("6-35", "02468T"),
+    ("6-Z36", "012347"), ("6-Z37", "012348"), ("6-Z38", "012378"), ("6-Z39", "023458"), ("6-Z40", "012358"),
+    ("6-Z41", "012368"), ("6-Z42", "012369"), ("6-Z43", "012568"), ("6-Z44", "012569"), ("6-Z45", "023469"),
+    ("6-Z46", "012469"), ("6-Z47", "012479"), ("6-Z48", "012579"), ("6-Z49", "013479"), ("6-Z50", "014679"),
+];
+
+// splits a name like "4-Z15" into its cardinality, number, and whether it has a 'Z'
+fn parse_name(s: &str) -> Option<(u8, u8, bool)> {
+    let (cardinality, number) = s.split_once('-')?;
+
+    let (z, number) = match number.strip_prefix(['Z', 'z']) {
+        Some(number) => (true, number),
+        None => (false, number),
+    };
+
+    // reject signs, which parse allows
+    if !number.starts_with(|c: char| c.is_ascii_digit()) || !cardinality.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((cardinality.parse().ok()?, number.parse().ok()?, z))
+}
+
+struct Table {
+    // the prime form and Z tag of each set class, indexed by cardinality then number
+    classes: Vec<Vec<(PitchClassSet, bool)>>,
+    // the set class of each prime form
+    by_prime_form: HashMap<u16, SetClass>,
+}
+
+static TABLE: LazyLock<Table> = LazyLock::new(|| {
+    let mut classes = vec![Vec::new(); 13];
+
+    for (name, prime) in FORTE_TABLE {
+        let (cardinality, _, z) = parse_name(name).expect("table names are valid");
+
+        let chromas = prime.chars().map(|c| match c {
+            'T' => 10,
+            'E' => 11,
+            c => c.to_digit(10).expect("table prime forms are valid") as u8,
+        });
+
+        classes[cardinality as usize].push((PitchClassSet::from_chromas(chromas), z));
+    }
+
+    // larger set classes have the same numbers as their complements
+    for cardinality in 7..=12 {
+        classes[cardinality] = classes[12 - cardinality].iter()
+            .map(|&(prime, z)| (prime.complement().prime_form(PrimeFormConvention::Forte), z))
+            .collect();
+    }
+
+    let by_prime_form = classes.iter()
+        .enumerate()
+        .flat_map(|(cardinality, classes)| classes.iter()
+            .enumerate()
+            .map(move |(i, &(prime, _))| (prime.bits(), SetClass { cardinality: cardinality as u8, number: i as u8 + 1 })))
+        .collect();
+
+    Table { classes, by_prime_form }
+});
+
+/// A set class, the pitch class sets that are transpositions or inversions of each other,
+/// named by its Forte number.
+///
+/// Forte numbers are written as the cardinality, a dash, and the set class's position in
+/// Allen Forte's list, such as `3-11` for major and minor triads. Set classes that share
+/// their [interval class vector](crate::set::IntervalClassVector) with another set class have
+/// a `Z`, such as `4-Z15`.
+///
+/// There are 224 set classes, from `0-1` for the empty set to `12-1` for the chromatic aggregate.
+///
+/// # Examples
+/// ```
+/// # use music_theory::set::{PitchClassSet, SetClass};
+/// let minor = PitchClassSet::from_chromas([9, 0, 4]);
+///
+/// let set_class = minor.set_class();
+/// assert_eq!(set_class.to_string(), "3-11");
+/// assert_eq!(set_class.prime_form(), PitchClassSet::from_chromas([0, 3, 7]));
+///
+/// let all_interval: SetClass = "4-Z15".parse().unwrap();
+/// assert!(all_interval.is_z());
+/// assert_eq!(all_interval.prime_form(), PitchClassSet::from_chromas([0, 1, 4, 6]));
+///
+/// assert_eq!(SetClass::all().count(), 224);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedSetClass"))]
+pub struct SetClass {
+    cardinality: u8,
+    number: u8,
+}
+
+// a set class as it's deserialized, before checking that it exists
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedSetClass {
+    cardinality: u8,
+    number: u8,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedSetClass> for SetClass {
+    type Error = ParseSetClassError;
+
+    fn try_from(unchecked: UncheckedSetClass) -> Result<Self, Self::Error> {
+        Self::new(unchecked.cardinality, unchecked.number).ok_or(ParseSetClassError::UnknownSetClass)
+    }
+}
+
+impl SetClass {
+    /// Creates a set class from its cardinality and number,
+    /// or returns `None` if there's no such set class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// assert_eq!(SetClass::new(6, 44).map(|c| c.to_string()), Some("6-Z44".to_owned()));
+    /// assert_eq!(SetClass::new(3, 13), None);
+    /// ```
+    pub fn new(cardinality: u8, number: u8) -> Option<Self> {
+        let count = TABLE.classes.get(cardinality as usize)?.len();
+
+        (1..=count).contains(&(number as usize)).then_some(Self { cardinality, number })
+    }
+
+    /// Returns the set class of a pitch class set.
+    ///
+    /// This is the same as [`PitchClassSet::set_class`].
+    pub fn of(set: PitchClassSet) -> Self {
+        TABLE.by_prime_form[&set.prime_form(PrimeFormConvention::Forte).bits()]
+    }
+
+    /// Returns all 224 set classes, ordered by cardinality and then number.
+    pub fn all() -> impl Iterator<Item = Self> {
+        TABLE.classes.iter()
+            .enumerate()
+            .flat_map(|(cardinality, classes)| (1..=classes.len())
+                .map(move |number| Self { cardinality: cardinality as u8, number: number as u8 }))
+    }
+
+    /// Returns the number of pitch classes in the set class's sets.
+    pub fn cardinality(self) -> u8 {
+        self.cardinality
+    }
+
+    /// Returns the set class's position in Forte's list, starting from 1.
+    pub fn number(self) -> u8 {
+        self.number
+    }
+
+    /// Returns `true` if the set class's name has a `Z`, because another set class
+    /// has the same interval class vector.
+    pub fn is_z(self) -> bool {
+        self.entry().1
+    }
+
+    /// Returns the prime form of the set class, using [Forte's convention](PrimeFormConvention::Forte).
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::{PitchClassSet, SetClass};
+    /// let set_class = SetClass::new(7, 35).unwrap();
+    ///
+    /// // the diatonic collection
+    /// assert_eq!(set_class.prime_form(), PitchClassSet::from_chromas([0, 1, 3, 5, 6, 8, 10]));
+    /// ```
+    pub fn prime_form(self) -> PitchClassSet {
+        self.entry().0
+    }
+
+    fn entry(self) -> (PitchClassSet, bool) {
+        TABLE.classes[self.cardinality as usize][self.number as usize - 1]
+    }
+
+    /// Returns the set class of the complements of the set class's sets,
+    /// which has the same number unless the set class is a Z related hexachord.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let pentatonic = "5-35".parse::<SetClass>().unwrap();
+    /// assert_eq!(pentatonic.complement().to_string(), "7-35");
+    ///
+    /// let hexachord = "6-Z3".parse::<SetClass>().unwrap();
+    /// assert_eq!(hexachord.complement().to_string(), "6-Z36");
+    /// ```
+    pub fn complement(self) -> Self {
+        self.prime_form().complement().set_class()
+    }
+
+    /// Returns the other set class with the same interval class vector, if the set class is Z related.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let all_interval = "4-Z15".parse::<SetClass>().unwrap();
+    /// assert_eq!(all_interval.z_correspondent().map(|c| c.to_string()), Some("4-Z29".to_owned()));
+    ///
+    /// let triad = "3-11".parse::<SetClass>().unwrap();
+    /// assert_eq!(triad.z_correspondent(), None);
+    /// ```
+    pub fn z_correspondent(self) -> Option<Self> {
+        if !self.is_z() {
+            return None;
+        }
+
+        let icv = self.prime_form().interval_class_vector();
+
+        Self::all().find(|&other| {
+            other != self
+                && other.cardinality == self.cardinality
+                && other.prime_form().interval_class_vector() == icv
+        })
+    }
+
+    /// Returns `true` if some set in this set class is a subset of some set in the other set class,
+    /// which is called abstract inclusion.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let triad = "3-11".parse::<SetClass>().unwrap();
+    /// let diatonic = "7-35".parse::<SetClass>().unwrap();
+    /// let whole_tone = "6-35".parse::<SetClass>().unwrap();
+    ///
+    /// assert!(triad.is_abstract_subset_of(diatonic));
+    /// assert!(!triad.is_abstract_subset_of(whole_tone));
+    /// assert!(triad.is_abstract_subset_of(triad));
+    /// ```
+    pub fn is_abstract_subset_of(self, other: Self) -> bool {
+        let (prime, other) = (self.prime_form(), other.prime_form());
+
+        self.cardinality <= other.len()
+            && [prime, prime.invert_around(PitchClass::C)]
+                .into_iter()
+                .any(|set| (0..12).any(|t| (set + Semitones(t)).is_subset_of(other)))
+    }
+
+    /// Returns the set classes of every subset of the set class's sets, including itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let triad = "3-11".parse::<SetClass>().unwrap();
+    ///
+    /// let subsets = triad.abstract_subsets().iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(subsets, ["0-1", "1-1", "2-3", "2-4", "2-5", "3-11"]);
+    /// ```
+    pub fn abstract_subsets(self) -> Vec<Self> {
+        let mut subsets = self.prime_form()
+            .subsets()
+            .map(PitchClassSet::set_class)
+            .collect::<Vec<_>>();
+
+        subsets.sort();
+        subsets.dedup();
+        subsets
+    }
+
+    /// Returns every set class with a set that contains one of this set class's sets, including itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let whole_tone = "6-35".parse::<SetClass>().unwrap();
+    ///
+    /// let supersets = whole_tone.abstract_supersets().iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(supersets[..3], ["6-35", "7-33", "8-21"]);
+    /// ```
+    pub fn abstract_supersets(self) -> Vec<Self> {
+        Self::all()
+            .filter(|&other| self.is_abstract_subset_of(other))
+            .collect()
+    }
+
+    // whether one set class includes the other, in either direction
+    fn is_included(self, other: Self) -> bool {
+        self.is_abstract_subset_of(other) || other.is_abstract_subset_of(self)
+    }
+
+    /// Returns `true` if the set class is in the set complex K about the nexus set class.
+    ///
+    /// A set class is in K about a nexus if it includes or is included in the nexus or its complement.
+    /// Like Forte, the set class must have a cardinality from 3 to 9, and can't be the nexus or its
+    /// complement.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let nexus = "6-35".parse::<SetClass>().unwrap();
+    ///
+    /// // included in the whole tone scale
+    /// assert!("3-12".parse::<SetClass>().unwrap().is_in_k_complex(nexus));
+    /// // not included in the whole tone scale or containing it
+    /// assert!(!"3-11".parse::<SetClass>().unwrap().is_in_k_complex(nexus));
+    /// ```
+    pub fn is_in_k_complex(self, nexus: Self) -> bool {
+        let complement = nexus.complement();
+
+        (3..=9).contains(&self.cardinality)
+            && self != nexus
+            && self != complement
+            && (self.is_included(nexus) || self.is_included(complement))
+    }
+
+    /// Returns `true` if the set class is in the set complex Kh about the nexus set class.
+    ///
+    /// Kh is the subcomplex of [K](Self::is_in_k_complex) where the set class includes or is included
+    /// in both the nexus and its complement.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::SetClass;
+    /// let nexus = "4-28".parse::<SetClass>().unwrap();
+    ///
+    /// // diminished triads are in both the diminished seventh and the octatonic scale
+    /// assert!("3-10".parse::<SetClass>().unwrap().is_in_kh_complex(nexus));
+    /// assert!("5-31".parse::<SetClass>().unwrap().is_in_kh_complex(nexus));
+    ///
+    /// // major triads are only in the octatonic scale
+    /// let triad = "3-11".parse::<SetClass>().unwrap();
+    /// assert!(triad.is_in_k_complex(nexus));
+    /// assert!(!triad.is_in_kh_complex(nexus));
+    /// ```
+    pub fn is_in_kh_complex(self, nexus: Self) -> bool {
+        let complement = nexus.complement();
+
+        self.is_in_k_complex(nexus) && self.is_included(nexus) && self.is_included(complement)
+    }
+
+    /// Returns the set classes in the set complex K about this nexus set class,
+    /// see [`Self::is_in_k_complex`].
+    pub fn k_complex(self) -> Vec<Self> {
+        Self::all().filter(|c| c.is_in_k_complex(self)).collect()
+    }
+
+    /// Returns the set classes in the set complex Kh about this nexus set class,
+    /// see [`Self::is_in_kh_complex`].
+    pub fn kh_complex(self) -> Vec<Self> {
+        Self::all().filter(|c| c.is_in_kh_complex(self)).collect()
+    }
+}
+
+impl PitchClassSet {
+    /// Returns the set class of the set, named by its Forte number.
+    ///
+    /// For more information, see [`SetClass`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let dominant_seventh = PitchClassSet::from_chromas([7, 11, 2, 5]);
+    /// assert_eq!(dominant_seventh.set_class().to_string(), "4-27");
+    /// ```
+    pub fn set_class(self) -> SetClass {
+        SetClass::of(self)
+    }
+
+    /// Returns `true` if the sets are Z related, meaning they have the same interval class vector,
+    /// but aren't transpositions or inversions of each other.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let a = PitchClassSet::from_chromas([0, 1, 4, 6]);
+    /// let b = PitchClassSet::from_chromas([2, 3, 5, 9]);
+    ///
+    /// assert!(a.is_z_related_to(b));
+    /// assert!(!a.is_z_related_to(a + music_theory::Semitones(3)));
+    /// ```
+    pub fn is_z_related_to(self, other: Self) -> bool {
+        self.interval_class_vector() == other.interval_class_vector() && self.set_class() != other.set_class()
+    }
+}
+
+impl fmt::Display for SetClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let z = if self.is_z() { "Z" } else { "" };
+
+        write!(f, "{}-{z}{}", self.cardinality, self.number)
+    }
+}
+
+/// Error returned when parsing a [`SetClass`] from a Forte number fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::set::{ParseSetClassError, SetClass};
+/// assert_eq!("3 11".parse::<SetClass>(), Err(ParseSetClassError::InvalidFormat));
+/// assert_eq!("3-13".parse::<SetClass>(), Err(ParseSetClassError::UnknownSetClass));
+/// assert_eq!("3-Z11".parse::<SetClass>(), Err(ParseSetClassError::UnknownSetClass));
+/// ```
+#[derive(Debug, thiserror::Error, Eq, PartialEq, Copy, Clone)]
+pub enum ParseSetClassError {
+    /// The input wasn't a cardinality and a number separated by a dash.
+    #[error("Expected a Forte number like '4-Z15'")]
+    InvalidFormat,
+    /// There's no set class with that name.
+    #[error("There's no set class with that Forte number")]
+    UnknownSetClass,
+}
+
+impl FromStr for SetClass {
+    type Err = ParseSetClassError;
+
+    /// Parses a set class from its Forte number, such as `3-11` or `6-Z44`.
+    ///
+    /// The `Z` can be left out, but can't be added to set classes without one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (cardinality, number, z) = parse_name(s.trim()).ok_or(ParseSetClassError::InvalidFormat)?;
+
+        Self::new(cardinality, number)
+            .filter(|set_class| !z || set_class.is_z())
+            .ok_or(ParseSetClassError::UnknownSetClass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::set::IntervalClassVector;
+
+    #[test]
+    fn all_set_classes() {
+        let counts = [1, 1, 6, 12, 29, 38, 50, 38, 29, 12, 6, 1, 1];
+
+        for (cardinality, count) in counts.into_iter().enumerate() {
+            let classes = SetClass::all().filter(|c| c.cardinality() as usize == cardinality);
+            assert_eq!(classes.count(), count, "{cardinality}");
+        }
+
+        for set_class in SetClass::all() {
+            let prime = set_class.prime_form();
+
+            assert_eq!(prime.len(), set_class.cardinality());
+            assert_eq!(prime.prime_form(PrimeFormConvention::Forte), prime, "{set_class}");
+            assert_eq!(prime.set_class(), set_class);
+            assert_eq!(set_class.to_string().parse(), Ok(set_class));
+
+            // complements keep their number, and hexachords without a Z are their own complement
+            let complement = prime.complement().set_class();
+
+            if set_class.cardinality() != 6 || !set_class.is_z() {
+                assert_eq!(complement.number(), set_class.number(), "{set_class}");
+            }
+        }
+
+        // every set is in a set class
+        let mut seen = HashMap::new();
+
+        for bits in 0..4096 {
+            let set = PitchClassSet::from_bits(bits).expect("12 bits");
+            *seen.entry(set.set_class()).or_insert(0) += 1;
+        }
+
+        assert_eq!(seen.len(), 224);
+        assert_eq!(seen.values().sum::<i32>(), 4096);
+    }
+
+    #[test]
+    fn z_relations() {
+        let mut by_icv = HashMap::<(u8, IntervalClassVector), Vec<SetClass>>::new();
+
+        for set_class in SetClass::all() {
+            by_icv.entry((set_class.cardinality(), set_class.prime_form().interval_class_vector())).or_default().push(set_class);
+        }
+
+        // set classes have a Z exactly when they share their interval class vector
+        for classes in by_icv.values() {
+            assert!(classes.len() <= 2);
+
+            for set_class in classes {
+                assert_eq!(set_class.is_z(), classes.len() == 2, "{set_class}");
+            }
+        }
+
+        // Z related hexachords are each other's complements
+        for set_class in SetClass::all().filter(|c| c.cardinality() == 6 && c.is_z()) {
+            let complement = set_class.prime_form().complement().set_class();
+
+            assert_ne!(complement, set_class);
+            assert_eq!(complement.prime_form().interval_class_vector(), set_class.prime_form().interval_class_vector());
+        }
+    }
+
+    #[test]
+    fn names() {
+        let cases = [
+            ("3-11", &[0, 3, 7][..]),
+            ("4-Z15", &[0, 1, 4, 6]),
+            ("4-28", &[0, 3, 6, 9]),
+            ("5-20", &[0, 1, 3, 7, 8]),
+            ("6-Z44", &[0, 1, 2, 5, 6, 9]),
+            ("6-35", &[0, 2, 4, 6, 8, 10]),
+            ("7-35", &[0, 1, 3, 5, 6, 8, 10]),
+            ("8-28", &[0, 1, 3, 4, 6, 7, 9, 10]),
+            ("0-1", &[]),
+            ("12-1", &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+        ];
+
+        for (name, prime) in cases {
+            let set_class = name.parse::<SetClass>().unwrap();
+            assert_eq!(set_class.prime_form(), PitchClassSet::from_chromas(prime.iter().copied()), "{name}");
+        }
+
+        assert_eq!("4-15".parse::<SetClass>().map(|c| c.to_string()), Ok("4-Z15".to_owned()));
+        assert_eq!("-3-11".parse::<SetClass>(), Err(ParseSetClassError::InvalidFormat));
+        assert_eq!("3-+11".parse::<SetClass>(), Err(ParseSetClassError::InvalidFormat));
+        assert_eq!("13-1".parse::<SetClass>(), Err(ParseSetClassError::UnknownSetClass));
+    }
+
+    #[test]
+    fn z_correspondents() {
+        let name = |c: Option<SetClass>| c.map(|c| c.to_string());
+
+        for set_class in SetClass::all() {
+            let correspondent = set_class.z_correspondent();
+
+            assert_eq!(correspondent.is_some(), set_class.is_z(), "{set_class}");
+
+            if let Some(correspondent) = correspondent {
+                assert_eq!(correspondent.z_correspondent(), Some(set_class));
+                assert!(set_class.prime_form().is_z_related_to(correspondent.prime_form()));
+            }
+        }
+
+        let parse = |s: &str| s.parse::<SetClass>().unwrap();
+
+        assert_eq!(name(parse("6-Z44").z_correspondent()), Some("6-Z19".to_owned()));
+        assert_eq!(name(parse("5-Z12").z_correspondent()), Some("5-Z36".to_owned()));
+        assert_eq!(name(parse("7-Z12").z_correspondent()), Some("7-Z36".to_owned()));
+    }
+
+    #[test]
+    fn inclusion_and_complexes() {
+        let parse = |s: &str| s.parse::<SetClass>().unwrap();
+
+        for set_class in SetClass::all() {
+            assert_eq!(set_class.complement().complement(), set_class);
+
+            let subsets = set_class.abstract_subsets();
+
+            assert!(subsets.iter().all(|c| c.is_abstract_subset_of(set_class)));
+            assert!(subsets.contains(&set_class));
+        }
+
+        // every set class except the empty set includes a single pitch class
+        assert_eq!(parse("1-1").abstract_supersets().len(), 223);
+        assert_eq!(parse("12-1").abstract_subsets().len(), 224);
+
+        // the diatonic collection is an abstract superset of the pentatonic, but not of the whole tone scale
+        assert!(parse("5-35").is_abstract_subset_of(parse("7-35")));
+        assert!(!parse("6-35").is_abstract_subset_of(parse("7-35")));
+
+        let nexus = parse("6-Z44");
+        let k = nexus.k_complex();
+        let kh = nexus.kh_complex();
+
+        assert!(kh.iter().all(|c| k.contains(c)));
+        assert!(!k.contains(&nexus) && !k.contains(&nexus.complement()));
+        assert!(k.iter().all(|c| (3..=9).contains(&c.cardinality())));
+
+        // the complement of a self complementary hexachord is itself, so K and Kh are the same
+        let nexus = parse("6-35");
+        assert_eq!(nexus.k_complex(), nexus.kh_complex());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, MapDeserializer};
+
+        let deserialize = |cardinality: u8, number: u8| SetClass::deserialize(
+            MapDeserializer::<_, Error>::new([("cardinality", cardinality), ("number", number)].into_iter())
+        ).map_err(|e| e.to_string());
+
+        assert_eq!(deserialize(3, 11), Ok(SetClass::new(3, 11).unwrap()));
+        assert_eq!(deserialize(3, 13), Err(ParseSetClassError::UnknownSetClass.to_string()));
+        assert_eq!(deserialize(13, 1), Err(ParseSetClassError::UnknownSetClass.to_string()));
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use crate::set::{PitchClassSet, SetClass};
+
+// how many subsets with at least two pitch classes are in each set class
+fn embeddings(set: PitchClassSet) -> HashMap<SetClass, u32> {
+    let mut counts = HashMap::new();
+
+    for subset in set.subsets().filter(|s| s.len() >= 2) {
+        *counts.entry(subset.set_class()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// the number of subsets with at least two pitch classes
+fn total_embeddings(set: PitchClassSet) -> u32 {
+    let len = set.len() as u32;
+
+    (1 << len) - len - 1
+}
+
+impl PitchClassSet {
+    /// Returns the number of subsets of the set that are in a set class, called EMB by Lewin and Rahn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::{PitchClassSet, SetClass};
+    /// let diatonic = PitchClassSet::from_chromas([0, 2, 4, 5, 7, 9, 11]);
+    /// let triad = "3-11".parse::<SetClass>().unwrap();
+    ///
+    /// // three major and three minor triads
+    /// assert_eq!(diatonic.embedding_count(triad), 6);
+    /// ```
+    pub fn embedding_count(self, set_class: SetClass) -> u32 {
+        self.subsets()
+            .filter(|s| s.len() == set_class.cardinality() && s.set_class() == set_class)
+            .count() as _
+    }
+
+    /// Morris's SIM, the sum of the differences between the sets' interval class vectors.
+    ///
+    /// This is the [Manhattan distance](crate::set::IntervalClassVector::manhattan_distance) between
+    /// the interval class vectors, so identical sets have a SIM of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let major = PitchClassSet::from_chromas([0, 4, 7]);
+    /// let augmented = PitchClassSet::from_chromas([0, 4, 8]);
+    ///
+    /// assert_eq!(major.sim(augmented), 4);
+    ///
+    /// // Z related sets have the same intervals
+    /// let all_interval_1 = PitchClassSet::from_chromas([0, 1, 4, 6]);
+    /// let all_interval_2 = PitchClassSet::from_chromas([0, 1, 3, 7]);
+    /// assert_eq!(all_interval_1.sim(all_interval_2), 0);
+    /// ```
+    pub fn sim(self, other: Self) -> u8 {
+        self.interval_class_vector().manhattan_distance(other.interval_class_vector())
+    }
+
+    /// Morris's ASIM, [SIM](Self::sim) divided by the total number of intervals in both sets.
+    ///
+    /// Returns a value in `[0.0, 1.0]`, where 0.0 means the sets have the same interval content.
+    /// Returns `None` if neither set has an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let major = PitchClassSet::from_chromas([0, 4, 7]);
+    /// let augmented = PitchClassSet::from_chromas([0, 4, 8]);
+    ///
+    /// assert_eq!(major.asim(augmented), Some(4.0 / 6.0));
+    /// assert_eq!(major.asim(major), Some(0.0));
+    /// ```
+    pub fn asim(self, other: Self) -> Option<f32> {
+        let total = self.interval_class_vector().total() as u32 + other.interval_class_vector().total() as u32;
+
+        (total != 0).then(|| self.sim(other) as f32 / total as f32)
+    }
+
+    /// Rahn's ATMEMB, the proportion of the sets' subsets that are in set classes both sets share.
+    ///
+    /// Every subset with at least two pitch classes is counted, in both sets. Returns a value in
+    /// `[0.0, 1.0]`, where 1.0 means the sets have the same subsets, or `None` if neither set has a
+    /// subset with two pitch classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let major = PitchClassSet::from_chromas([0, 4, 7]);
+    /// let augmented = PitchClassSet::from_chromas([0, 4, 8]);
+    /// let dominant = PitchClassSet::from_chromas([7, 11, 2, 5]);
+    ///
+    /// // major and augmented triads only share major thirds
+    /// assert_eq!(major.atmemb(augmented), Some(4.0 / 8.0));
+    ///
+    /// // the dominant seventh has all of a major triad's subsets, and more
+    /// assert!(major.atmemb(dominant).unwrap() > major.atmemb(augmented).unwrap());
+    /// ```
+    pub fn atmemb(self, other: Self) -> Option<f32> {
+        let total = total_embeddings(self) + total_embeddings(other);
+
+        if total == 0 {
+            return None;
+        }
+
+        let (embeddings, other_embeddings) = (embeddings(self), embeddings(other));
+
+        let mutual = embeddings.iter()
+            .filter_map(|(set_class, count)| Some(count + other_embeddings.get(set_class)?))
+            .sum::<u32>();
+
+        Some(mutual as f32 / total as f32)
+    }
+
+    /// Lewin's REL, which compares how many subsets each set has in every set class.
+    ///
+    /// Every subset with at least two pitch classes is counted. Returns a value in `[0.0, 1.0]`,
+    /// where 1.0 means the sets have the same subsets, or `None` if either set doesn't have a subset
+    /// with two pitch classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use music_theory::set::PitchClassSet;
+    /// let major = PitchClassSet::from_chromas([0, 4, 7]);
+    /// let minor = PitchClassSet::from_chromas([0, 3, 7]);
+    /// let chromatic = PitchClassSet::from_chromas([0, 1, 2]);
+    ///
+    /// assert_eq!(major.rel(minor), Some(1.0));
+    /// assert!(major.rel(chromatic).unwrap() < 0.5);
+    /// ```
+    pub fn rel(self, other: Self) -> Option<f32> {
+        let total = total_embeddings(self) * total_embeddings(other);
+
+        if total == 0 {
+            return None;
+        }
+
+        let (embeddings, other_embeddings) = (embeddings(self), embeddings(other));
+
+        let shared = embeddings.iter()
+            .filter_map(|(set_class, &count)| Some(((count * other_embeddings.get(set_class)?) as f32).sqrt()))
+            .sum::<f32>();
+
+        Some(shared / (total as f32).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures() {
+        let sets = [
+            PitchClassSet::from_chromas([0, 4, 7]),
+            PitchClassSet::from_chromas([0, 1, 4, 6]),
+            PitchClassSet::from_chromas([0, 2, 4, 5, 7, 9, 11]),
+            PitchClassSet::from_chromas([1, 3, 6, 8, 10]),
+            PitchClassSet::CHROMATIC_AGGREGATE,
+        ];
+
+        for a in sets {
+            assert_eq!(a.sim(a), 0);
+            assert_eq!(a.asim(a), Some(0.0));
+            assert_eq!(a.atmemb(a), Some(1.0));
+            assert!((a.rel(a).unwrap() - 1.0).abs() < 1e-6);
+
+            for b in sets {
+                assert_eq!(a.sim(b), b.sim(a));
+                assert_eq!(a.atmemb(b), b.atmemb(a));
+
+                for value in [a.asim(b), a.atmemb(b), a.rel(b)] {
+                    assert!((0.0..=1.0 + 1e-6).contains(&value.unwrap()), "{a} {b}");
+                }
+            }
+        }
+
+        // the black key pentatonic is a transposed subset of the white keys, so it shares every subset class
+        let (diatonic, pentatonic) = (sets[2], sets[3]);
+        let aggregate = sets[4];
+        assert!(pentatonic.atmemb(diatonic) > pentatonic.atmemb(aggregate));
+
+        let dyad = PitchClassSet::from_chromas([0, 6]);
+        let single = PitchClassSet::from_chromas([3]);
+
+        assert_eq!(dyad.asim(single), Some(1.0));
+        assert_eq!(dyad.atmemb(single), Some(0.0));
+        assert_eq!(dyad.rel(single), None);
+        assert_eq!(single.atmemb(PitchClassSet::EMPTY), None);
+    }
+
+    #[test]
+    fn embedding_counts() {
+        let diatonic = PitchClassSet::from_chromas([0, 2, 4, 5, 7, 9, 11]);
+
+        let total = SetClass::all()
+            .map(|set_class| diatonic.embedding_count(set_class))
+            .sum::<u32>();
+
+        assert_eq!(total, 128);
+
+        // each dyad is embedded once per interval of its class, so this is the interval class vector
+        let dyads = SetClass::all()
+            .filter(|c| c.cardinality() == 2)
+            .map(|c| diatonic.embedding_count(c))
+            .collect::<Vec<_>>();
+
+        assert_eq!(dyads, [2, 5, 4, 3, 6, 1]);
+    }
+}
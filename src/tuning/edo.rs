@@ -0,0 +1,517 @@
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+use crate::{Interval, Letter, Note};
+use super::{MicrotonalAccidental, ParseMicrotonalError};
+
+/// An interval in `N` equal divisions of the octave, measured in steps.
+///
+/// # Examples
+/// ```
+/// # use music_theory::Interval;
+/// # use music_theory::tuning::EdoInterval;
+/// // in 31-EDO, major thirds are almost pure
+/// let third = EdoInterval::<31>::from_interval(Interval::MAJOR_THIRD);
+/// assert_eq!(third.steps(), 10);
+/// assert!((third.cents() - 387.1).abs() < 0.1);
+///
+/// assert_eq!(EdoInterval::<31>::from_ratio(5.0 / 4.0), Some(third));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdoInterval<const N: u16> {
+    steps: i32,
+}
+
+impl<const N: u16> EdoInterval<N> {
+    // panics at compile time for 0-EDO
+    fn divisions() -> i32 {
+        const { assert!(N > 0, "there must be at least one division of the octave") };
+        N as i32
+    }
+
+    /// Creates an interval from a number of steps, which is descending if negative.
+    pub const fn from_steps(steps: i32) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the number of steps in the interval.
+    pub const fn steps(self) -> i32 {
+        self.steps
+    }
+
+    /// Returns the size of the interval in cents.
+    pub fn cents(self) -> f64 {
+        self.steps as f64 * 1200.0 / Self::divisions() as f64
+    }
+
+    /// Returns the interval closest to a frequency ratio,
+    /// or `None` if the ratio isn't positive or is too large.
+    pub fn from_ratio(ratio: f64) -> Option<Self> {
+        let steps = (ratio.log2() * Self::divisions() as f64).round();
+
+        (ratio > 0.0 && steps.abs() <= i32::MAX as f64).then_some(Self::from_steps(steps as i32))
+    }
+
+    /// Returns the closest fifth to a pure fifth (3/2), which generates the letters of the notation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::EdoInterval;
+    /// assert_eq!(EdoInterval::<12>::fifth().steps(), 7);
+    /// assert_eq!(EdoInterval::<19>::fifth().steps(), 11);
+    /// assert_eq!(EdoInterval::<53>::fifth().steps(), 31);
+    /// ```
+    pub fn fifth() -> Self {
+        Self::from_ratio(1.5).expect("3/2 is a valid ratio")
+    }
+
+    /// Returns the size of a sharp, which is seven fifths less four octaves.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::EdoInterval;
+    /// assert_eq!(EdoInterval::<12>::sharp().steps(), 1);
+    /// assert_eq!(EdoInterval::<24>::sharp().steps(), 2);
+    /// assert_eq!(EdoInterval::<31>::sharp().steps(), 2);
+    /// assert_eq!(EdoInterval::<53>::sharp().steps(), 5);
+    /// ```
+    pub fn sharp() -> Self {
+        Self::from_steps(Self::fifth().steps * 7 - Self::divisions() * 4)
+    }
+
+    /// Returns the size of a syntonic comma, the difference between four fifths and a major third
+    /// closest to a pure one (5/4), which is the size of an arrow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::EdoInterval;
+    /// // meantone tunings temper out the comma
+    /// assert_eq!(EdoInterval::<31>::comma().steps(), 0);
+    /// assert_eq!(EdoInterval::<53>::comma().steps(), 1);
+    /// ```
+    pub fn comma() -> Self {
+        let third = Self::from_ratio(1.25).expect("5/4 is a valid ratio");
+
+        Self::from_steps(Self::fifth().steps * 4 - Self::divisions() * 2 - third.steps)
+    }
+
+    /// Returns the number of steps an accidental alters a pitch by, or `None` if it can't be
+    /// written in this EDO.
+    ///
+    /// Quarter tones need a sharp of an even number of steps, and arrows need a comma of at least
+    /// one step.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::{EdoInterval, MicrotonalAccidental};
+    /// let half_sharp = MicrotonalAccidental::HALF_SHARP;
+    ///
+    /// assert_eq!(EdoInterval::<24>::from_accidental(half_sharp), Some(EdoInterval::from_steps(1)));
+    /// assert_eq!(EdoInterval::<19>::from_accidental(half_sharp), None);
+    /// assert_eq!(EdoInterval::<12>::from_accidental(MicrotonalAccidental::NATURAL.with_arrows(1)), None);
+    /// ```
+    pub fn from_accidental(accidental: MicrotonalAccidental) -> Option<Self> {
+        let (sharp, comma) = (Self::sharp().steps, Self::comma().steps);
+
+        if accidental.quarter_tones % 2 != 0 && sharp % 2 != 0 {
+            return None;
+        }
+
+        if accidental.arrows != 0 && comma == 0 {
+            return None;
+        }
+
+        let quarter_tones = accidental.quarter_tones as i32 * sharp / 2;
+
+        Some(Self::from_steps(quarter_tones + accidental.arrows as i32 * comma))
+    }
+
+    /// Returns the closest interval in this EDO to a 12-tone interval, keeping its spelling,
+    /// so that an augmented second and a minor third can differ.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Interval;
+    /// # use music_theory::tuning::EdoInterval;
+    /// let augmented_second = EdoInterval::<19>::from_interval(Interval::AUGMENTED_SECOND);
+    /// let minor_third = EdoInterval::<19>::from_interval(Interval::MINOR_THIRD);
+    ///
+    /// assert_eq!(augmented_second.steps(), 4);
+    /// assert_eq!(minor_third.steps(), 5);
+    /// ```
+    pub fn from_interval(interval: Interval) -> Self {
+        EdoPitch::from(Note::MIDDLE_C + interval) - EdoPitch::from(Note::MIDDLE_C)
+    }
+}
+
+impl<const N: u16> Add for EdoInterval<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_steps(self.steps + rhs.steps)
+    }
+}
+
+impl<const N: u16> Sub for EdoInterval<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_steps(self.steps - rhs.steps)
+    }
+}
+
+impl<const N: u16> Neg for EdoInterval<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_steps(-self.steps)
+    }
+}
+
+/// A pitch in `N` equal divisions of the octave (N-EDO), such as 19-, 24-, 31-, or 53-EDO.
+///
+/// Pitches are written with the letters generated by the EDO's [fifth](EdoInterval::fifth), a
+/// [`MicrotonalAccidental`], and an octave, where octaves start on C. When the pitch can be
+/// spelled in several ways, [`spell`](Self::spell) picks the simplest accidental.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Interval, Letter, Note};
+/// # use music_theory::tuning::{EdoInterval, EdoPitch, MicrotonalAccidental};
+/// let c4 = EdoPitch::<24>::from(Note::MIDDLE_C);
+///
+/// // transpose up a quarter tone
+/// let c_half_sharp = c4 + EdoInterval::from_steps(1);
+/// assert_eq!(c_half_sharp.spell(), Some((Letter::C, MicrotonalAccidental::HALF_SHARP, 4)));
+/// assert_eq!(c_half_sharp.to_string(), "C\u{E282}4");
+///
+/// // and up a major third
+/// let e_half_sharp = c_half_sharp + EdoInterval::from_interval(Interval::MAJOR_THIRD);
+/// assert_eq!(e_half_sharp, "E\u{E282}4".parse().unwrap());
+///
+/// assert!((e_half_sharp.frequency(440.0) - 339.29).abs() < 0.01);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdoPitch<const N: u16> {
+    steps: i32,
+}
+
+impl<const N: u16> EdoPitch<N> {
+    /// Creates a pitch from the number of steps above C0.
+    pub const fn from_steps(steps: i32) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the number of steps above C0.
+    pub const fn steps(self) -> i32 {
+        self.steps
+    }
+
+    /// Creates a pitch from a letter, accidental, and octave,
+    /// or returns `None` if the accidental can't be written in this EDO.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Letter;
+    /// # use music_theory::tuning::{EdoPitch, MicrotonalAccidental};
+    /// let d_sharp = EdoPitch::<31>::new(Letter::D, MicrotonalAccidental::SHARP, 4).unwrap();
+    /// let e_flat = EdoPitch::<31>::new(Letter::E, MicrotonalAccidental::FLAT, 4).unwrap();
+    ///
+    /// // D♯ and E♭ are different in 31-EDO
+    /// assert_eq!(e_flat.steps() - d_sharp.steps(), 1);
+    ///
+    /// // C♭ is in the octave below C
+    /// let c_flat = EdoPitch::<31>::new(Letter::C, MicrotonalAccidental::FLAT, 4).unwrap();
+    /// assert_eq!(c_flat.steps(), 31 * 4 - 2);
+    /// ```
+    pub fn new(letter: Letter, accidental: MicrotonalAccidental, octave: i16) -> Option<Self> {
+        let accidental = EdoInterval::<N>::from_accidental(accidental)?;
+
+        Some(Self::from_steps(octave as i32 * EdoInterval::<N>::divisions() + Self::letter_steps(letter) + accidental.steps))
+    }
+
+    // the steps from C to a natural letter
+    fn letter_steps(letter: Letter) -> i32 {
+        (letter.fifths_from_c() as i32 * EdoInterval::<N>::fifth().steps).rem_euclid(EdoInterval::<N>::divisions())
+    }
+
+    /// Returns the simplest spelling of the pitch as a letter, accidental, and octave, or `None` if
+    /// the pitch can't be written with at most a double sharp or flat and three arrows.
+    ///
+    /// Accidentals are simpler with fewer quarter tones and arrows, and sharps are preferred over flats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Letter;
+    /// # use music_theory::tuning::{EdoPitch, MicrotonalAccidental};
+    /// // in 53-EDO, the step above C is C raised by a comma
+    /// let pitch = EdoPitch::<53>::from_steps(53 * 4 + 1);
+    /// assert_eq!(pitch.spell(), Some((Letter::C, MicrotonalAccidental::NATURAL.with_arrows(1), 4)));
+    ///
+    /// // in 19-EDO, E♯ is between E and F
+    /// let pitch = EdoPitch::<19>::from_steps(19 * 3 + 7);
+    /// assert_eq!(pitch.spell(), Some((Letter::E, MicrotonalAccidental::SHARP, 3)));
+    /// ```
+    pub fn spell(self) -> Option<(Letter, MicrotonalAccidental, i16)> {
+        let divisions = EdoInterval::<N>::divisions();
+
+        let mut candidates = Vec::new();
+
+        for letter in [Letter::C, Letter::D, Letter::E, Letter::F, Letter::G, Letter::A, Letter::B] {
+            for quarter_tones in -4..=4 {
+                for arrows in -3..=3 {
+                    let accidental = MicrotonalAccidental { quarter_tones, arrows };
+
+                    let Some(altered) = EdoInterval::<N>::from_accidental(accidental) else {
+                        continue;
+                    };
+
+                    let from_c = self.steps - Self::letter_steps(letter) - altered.steps;
+
+                    if from_c.rem_euclid(divisions) != 0 {
+                        continue;
+                    }
+
+                    let Ok(octave) = i16::try_from(from_c.div_euclid(divisions)) else {
+                        continue;
+                    };
+
+                    let complexity = quarter_tones.abs() + arrows.abs() * 2;
+                    candidates.push(((complexity, quarter_tones < 0, arrows < 0), (letter, accidental, octave)));
+                }
+            }
+        }
+
+        candidates.into_iter()
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, spelling)| spelling)
+    }
+
+    /// Returns the pitch transposed by an interval.
+    pub fn transpose(self, interval: EdoInterval<N>) -> Self {
+        Self::from_steps(self.steps + interval.steps)
+    }
+
+    /// Returns the frequency of the pitch in Hz, with A4 tuned to `a4_hz`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Letter;
+    /// # use music_theory::tuning::{EdoPitch, MicrotonalAccidental};
+    /// let a4 = EdoPitch::<19>::new(Letter::A, MicrotonalAccidental::NATURAL, 4).unwrap();
+    /// assert_eq!(a4.frequency(440.0), 440.0);
+    ///
+    /// let a5 = EdoPitch::<19>::from_steps(a4.steps() + 19);
+    /// assert_eq!(a5.frequency(440.0), 880.0);
+    /// ```
+    pub fn frequency(self, a4_hz: f64) -> f64 {
+        let steps = (self - Self::a4()).steps as f64;
+
+        a4_hz * f64::exp2(steps / EdoInterval::<N>::divisions() as f64)
+    }
+
+    /// Returns the pitch closest to a frequency in Hz, with A4 tuned to `a4_hz`,
+    /// or `None` if the frequency isn't positive or is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::EdoPitch;
+    /// let pitch = EdoPitch::<24>::from_frequency(453.0, 440.0).unwrap();
+    /// assert_eq!(pitch.to_string(), "A\u{E282}4");
+    ///
+    /// assert_eq!(EdoPitch::<24>::from_frequency(0.0, 440.0), None);
+    /// ```
+    pub fn from_frequency(hz: f64, a4_hz: f64) -> Option<Self> {
+        let interval = EdoInterval::<N>::from_ratio(hz / a4_hz)?;
+
+        Self::a4().steps.checked_add(interval.steps).map(Self::from_steps)
+    }
+
+    fn a4() -> Self {
+        Self::from_steps(4 * EdoInterval::<N>::divisions() + Self::letter_steps(Letter::A))
+    }
+}
+
+impl<const N: u16> From<Note> for EdoPitch<N> {
+    /// Converts a note to the EDO, where each sharp or flat is the size of the EDO's [sharp](EdoInterval::sharp).
+    fn from(note: Note) -> Self {
+        let accidental = note.pitch.accidental().offset as i32 * EdoInterval::<N>::sharp().steps;
+        let letter = Self::letter_steps(note.pitch.letter());
+
+        Self::from_steps(note.octave as i32 * EdoInterval::<N>::divisions() + letter + accidental)
+    }
+}
+
+impl<const N: u16> Add<EdoInterval<N>> for EdoPitch<N> {
+    type Output = Self;
+
+    fn add(self, rhs: EdoInterval<N>) -> Self::Output {
+        self.transpose(rhs)
+    }
+}
+
+impl<const N: u16> Sub<EdoInterval<N>> for EdoPitch<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: EdoInterval<N>) -> Self::Output {
+        self.transpose(-rhs)
+    }
+}
+
+impl<const N: u16> Sub for EdoPitch<N> {
+    type Output = EdoInterval<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        EdoInterval::from_steps(self.steps - rhs.steps)
+    }
+}
+
+impl<const N: u16> fmt::Display for EdoPitch<N> {
+    /// Formats the pitch with its [simplest spelling](Self::spell), using SMuFL glyphs for
+    /// the accidental, which is left out for naturals.
+    ///
+    /// Pitches without a spelling are written as the steps above C0 and the number of divisions,
+    /// like `7\8`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.spell() {
+            Some((letter, accidental, octave)) if accidental == MicrotonalAccidental::NATURAL => write!(f, "{letter}{octave}"),
+            Some((letter, accidental, octave)) => write!(f, "{letter}{accidental}{octave}"),
+            None => write!(f, "{}\\{N}", self.steps),
+        }
+    }
+}
+
+impl<const N: u16> FromStr for EdoPitch<N> {
+    type Err = ParseMicrotonalError;
+
+    /// Parses a pitch from a letter, an optional [`MicrotonalAccidental`], and an octave,
+    /// such as `E𝄳4`, or from steps above C0 written like `7\8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ParseMicrotonalError as E;
+
+        if let Some((steps, divisions)) = s.split_once('\\') {
+            let steps = steps.parse().map_err(|_| E::InvalidOctave)?;
+
+            return match divisions.parse::<u16>() {
+                Ok(divisions) if divisions == N => Ok(Self::from_steps(steps)),
+                _ => Err(E::NotInEdo),
+            };
+        }
+
+        let mut chars = s.chars();
+
+        let letter = match chars.next().ok_or(E::Empty)? {
+            'C' | 'c' => Letter::C,
+            'D' | 'd' => Letter::D,
+            'E' | 'e' => Letter::E,
+            'F' | 'f' => Letter::F,
+            'G' | 'g' => Letter::G,
+            'A' | 'a' => Letter::A,
+            'B' | 'b' => Letter::B,
+            _ => return Err(E::InvalidLetter),
+        };
+
+        let rest = chars.as_str();
+        let (accidental, octave) = rest.split_at(rest.find(|c: char| c.is_ascii_digit() || c == '-').unwrap_or(rest.len()));
+
+        let accidental = match accidental {
+            "" => MicrotonalAccidental::NATURAL,
+            accidental => accidental.parse()?,
+        };
+
+        if octave.is_empty() {
+            return Err(E::MissingOctave);
+        }
+
+        let octave = octave.parse().map_err(|_| E::InvalidOctave)?;
+
+        Self::new(letter, accidental, octave).ok_or(E::NotInEdo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pitch;
+    use crate::tuning::Tuning;
+
+    fn round_trip<const N: u16>() {
+        for steps in -(N as i32)..N as i32 * 9 {
+            let pitch = EdoPitch::<N>::from_steps(steps);
+            let (letter, accidental, octave) = pitch.spell().unwrap_or_else(|| panic!("{steps}\\{N}"));
+
+            assert_eq!(EdoPitch::new(letter, accidental, octave), Some(pitch));
+            assert_eq!(pitch.to_string().parse(), Ok(pitch), "{pitch}");
+        }
+    }
+
+    #[test]
+    fn spelling_round_trips() {
+        round_trip::<12>();
+        round_trip::<17>();
+        round_trip::<19>();
+        round_trip::<22>();
+        round_trip::<24>();
+        round_trip::<31>();
+        round_trip::<41>();
+        round_trip::<53>();
+        round_trip::<72>();
+    }
+
+    #[test]
+    fn twelve_matches_notes() {
+        for midi in 0..128 {
+            let note = Note::from_midi(midi);
+            let pitch = EdoPitch::<12>::from(note);
+
+            assert_eq!(pitch.to_string(), note.to_string());
+            assert!((pitch.frequency(440.0) - Tuning::A440.frequency(note)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn spellings() {
+        let spell = |s: &str| s.parse::<EdoPitch<31>>().unwrap().to_string();
+
+        // 31-EDO uses half sharps and flats between the sharps and flats
+        assert_eq!(spell("C#4"), "C♯4");
+        assert_eq!(spell("Db4"), "D♭4");
+        assert_eq!(spell("C\u{E283}4"), "D♭4");
+        assert_eq!(spell("E#4"), "F\u{E280}4");
+        assert_eq!(spell("F\u{E281}4"), "E4");
+        assert_eq!(spell("F♭4"), "E\u{E282}4");
+
+        // 53-EDO uses arrows
+        let spell = |s: &str| s.parse::<EdoPitch<53>>().unwrap().to_string();
+
+        assert_eq!(spell("E𝄯4"), "E\u{E2C2}4");
+        assert_eq!(spell("C#𝄯4"), "D♭4");
+
+        assert_eq!("2\\19".parse::<EdoPitch<19>>(), Ok(EdoPitch::from(Note::new(Pitch::D_FLAT, 0))));
+        assert_eq!("4\\31".parse::<EdoPitch<19>>(), Err(ParseMicrotonalError::NotInEdo));
+        assert_eq!("C4\u{E282}".parse::<EdoPitch<24>>(), Err(ParseMicrotonalError::InvalidOctave));
+    }
+
+    #[test]
+    fn intervals() {
+        let c4 = EdoPitch::<31>::from(Note::MIDDLE_C);
+
+        let g4 = c4 + EdoInterval::from_interval(Interval::PERFECT_FIFTH);
+        assert_eq!(g4.to_string(), "G4");
+        assert_eq!(g4 - c4, EdoInterval::fifth());
+        assert_eq!(g4 - EdoInterval::fifth(), c4);
+
+        let octave = EdoInterval::<31>::from_interval(Interval::PERFECT_OCTAVE);
+        assert_eq!(octave.steps(), 31);
+        assert_eq!(octave.cents(), 1200.0);
+
+        // descending intervals
+        let down = EdoInterval::<31>::from_interval(-Interval::MAJOR_SECOND);
+        assert_eq!((c4 + down).to_string(), "B♭3");
+
+        assert_eq!(EdoInterval::<31>::from_ratio(-1.0), None);
+        assert_eq!(EdoInterval::<31>::from_ratio(7.0 / 4.0).map(EdoInterval::steps), Some(25));
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::AccidentalSign;
+
+// a syntonic comma (81/80) in cents
+const SYNTONIC_COMMA: f64 = 21.506_289_596_157_24;
+
+/// An accidental that can alter a pitch by quarter tones and syntonic commas.
+///
+/// Quarter tones are written with Stein-Zimmermann accidentals (half sharp, sesquisharp, and so on),
+/// and commas with the arrows of the Helmholtz-Ellis notation for just intonation,
+/// attached to a standard accidental. Unlike [`AccidentalSign`], which only models whole
+/// semitones, the size of a microtonal accidental depends on the tuning it's used in, see
+/// [`EdoPitch`](super::EdoPitch).
+///
+/// The accidental is written with [SMuFL](https://w3c.github.io/smufl/latest/) glyphs, which need a
+/// music font such as Bravura to display, and can be parsed from them, or from the
+/// Unicode and ASCII symbols used for [`Note`](crate::Note).
+///
+/// # Examples
+/// ```
+/// # use music_theory::AccidentalSign;
+/// # use music_theory::tuning::MicrotonalAccidental;
+/// let half_sharp = MicrotonalAccidental::HALF_SHARP;
+/// assert_eq!(half_sharp.cents(), 50.0);
+/// assert_eq!(half_sharp.to_string(), "\u{E282}");
+///
+/// // a sharp raised by a syntonic comma
+/// let sharp_up = MicrotonalAccidental::SHARP.with_arrows(1);
+/// assert!((sharp_up.cents() - 121.506).abs() < 0.001);
+/// assert_eq!(sharp_up.to_string(), "\u{E2C8}");
+/// assert_eq!("\u{E2C8}".parse(), Ok(sharp_up));
+///
+/// // standard accidentals convert both ways
+/// assert_eq!(MicrotonalAccidental::from(AccidentalSign::DOUBLE_FLAT), MicrotonalAccidental::DOUBLE_FLAT);
+/// assert_eq!(MicrotonalAccidental::SESQUISHARP.as_accidental_sign(), None);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MicrotonalAccidental {
+    /// The number of quarter tones the pitch is raised by, or lowered by if negative.
+    ///
+    /// Sharps are two quarter tones.
+    pub quarter_tones: i16,
+    /// The number of syntonic commas the pitch is raised by, or lowered by if negative.
+    pub arrows: i16,
+}
+
+impl MicrotonalAccidental {
+    /// Double flat, lowers the pitch by four quarter tones.
+    pub const DOUBLE_FLAT: Self = Self::from_quarter_tones(-4);
+    /// Sesquiflat, or three quarter tones flat.
+    pub const SESQUIFLAT: Self = Self::from_quarter_tones(-3);
+    /// Flat, lowers the pitch by two quarter tones.
+    pub const FLAT: Self = Self::from_quarter_tones(-2);
+    /// Half flat, or a quarter tone flat.
+    pub const HALF_FLAT: Self = Self::from_quarter_tones(-1);
+    /// Natural, no alteration.
+    pub const NATURAL: Self = Self::from_quarter_tones(0);
+    /// Half sharp, or a quarter tone sharp.
+    pub const HALF_SHARP: Self = Self::from_quarter_tones(1);
+    /// Sharp, raises the pitch by two quarter tones.
+    pub const SHARP: Self = Self::from_quarter_tones(2);
+    /// Sesquisharp, or three quarter tones sharp.
+    pub const SESQUISHARP: Self = Self::from_quarter_tones(3);
+    /// Double sharp, raises the pitch by four quarter tones.
+    pub const DOUBLE_SHARP: Self = Self::from_quarter_tones(4);
+
+    /// Creates an accidental from a number of quarter tones, without arrows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// assert_eq!(MicrotonalAccidental::from_quarter_tones(-3), MicrotonalAccidental::SESQUIFLAT);
+    /// ```
+    pub const fn from_quarter_tones(quarter_tones: i16) -> Self {
+        Self { quarter_tones, arrows: 0 }
+    }
+
+    /// Returns the accidental with its arrows replaced, where each arrow raises the pitch
+    /// by a syntonic comma, or lowers it if negative.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// let flat_down = MicrotonalAccidental::FLAT.with_arrows(-2);
+    /// assert_eq!(flat_down.arrows, -2);
+    /// assert_eq!(flat_down.quarter_tones, -2);
+    /// ```
+    pub const fn with_arrows(self, arrows: i16) -> Self {
+        Self { arrows, ..self }
+    }
+
+    /// Returns how many cents the accidental alters a pitch by, where quarter tones are
+    /// 50 cents and arrows are syntonic commas, about 21.5 cents.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// assert_eq!(MicrotonalAccidental::SESQUIFLAT.cents(), -150.0);
+    /// assert!((MicrotonalAccidental::NATURAL.with_arrows(-1).cents() + 21.506).abs() < 0.001);
+    /// ```
+    pub fn cents(self) -> f64 {
+        self.quarter_tones as f64 * 50.0 + self.arrows as f64 * SYNTONIC_COMMA
+    }
+
+    /// Returns the accidental as an [`AccidentalSign`], if it's a whole number of semitones
+    /// without arrows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::AccidentalSign;
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// assert_eq!(MicrotonalAccidental::FLAT.as_accidental_sign(), Some(AccidentalSign::FLAT));
+    /// assert_eq!(MicrotonalAccidental::HALF_FLAT.as_accidental_sign(), None);
+    /// assert_eq!(MicrotonalAccidental::FLAT.with_arrows(1).as_accidental_sign(), None);
+    /// ```
+    pub fn as_accidental_sign(self) -> Option<AccidentalSign> {
+        (self.quarter_tones % 2 == 0 && self.arrows == 0)
+            .then_some(AccidentalSign { offset: self.quarter_tones / 2 })
+    }
+}
+
+impl From<AccidentalSign> for MicrotonalAccidental {
+    fn from(value: AccidentalSign) -> Self {
+        Self::from_quarter_tones(value.offset * 2)
+    }
+}
+
+// the first Helmholtz-Ellis glyph, a double flat with one arrow down, followed by the flat,
+// natural, sharp, and double sharp, then the same with one arrow up, two arrows down, and so on
+const HEJI_START: u32 = 0xE2C0;
+
+// the Helmholtz-Ellis glyph for a standard accidental with one to three arrows
+fn heji_glyph(quarter_tones: i16, arrows: i16) -> char {
+    let accidental = (quarter_tones / 2 + 2) as u32;
+    let group = ((arrows.abs() - 1) * 2 + (arrows > 0) as i16) as u32;
+
+    char::from_u32(HEJI_START + group * 5 + accidental).expect("Helmholtz-Ellis glyphs are valid chars")
+}
+
+// the quarter tones and arrows of a glyph
+fn parse_glyph(c: char) -> Option<(i16, i16)> {
+    let glyph = match c {
+        '𝄫' | '\u{E264}' => (-4, 0),
+        '♭' | 'b' | '\u{E260}' => (-2, 0),
+        '♮' | '\u{E261}' => (0, 0),
+        '♯' | '#' | '\u{E262}' => (2, 0),
+        '𝄪' | 'x' | '\u{E263}' => (4, 0),
+        '\u{E265}' => (6, 0),
+        '\u{E266}' => (-6, 0),
+        // Stein-Zimmermann
+        '\u{E280}' | '𝄳' => (-1, 0),
+        '\u{E281}' => (-3, 0),
+        '\u{E282}' | '𝄲' => (1, 0),
+        '\u{E283}' => (3, 0),
+        // Unicode arrows
+        '𝄬' => (-2, 1),
+        '𝄭' => (-2, -1),
+        '𝄮' => (0, 1),
+        '𝄯' => (0, -1),
+        '𝄰' => (2, 1),
+        '𝄱' => (2, -1),
+        // Helmholtz-Ellis
+        c @ '\u{E2C0}'..='\u{E2DD}' => {
+            let i = (c as u32 - HEJI_START) as i16;
+            let (group, accidental) = (i / 5, i % 5);
+            let arrows = group / 2 + 1;
+
+            ((accidental - 2) * 2, if group % 2 == 0 { -arrows } else { arrows })
+        }
+        _ => return None,
+    };
+
+    Some(glyph)
+}
+
+impl fmt::Display for MicrotonalAccidental {
+    /// Formats the accidental with SMuFL glyphs.
+    ///
+    /// Standard accidentals without arrows use the same Unicode symbols as [`AccidentalSign`],
+    /// quarter tones use Stein-Zimmermann glyphs, and arrows use Helmholtz-Ellis glyphs.
+    /// Accidentals without a single glyph are written as several glyphs, which parse back
+    /// into the same accidental.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// assert_eq!(MicrotonalAccidental::NATURAL.to_string(), "♮");
+    /// assert_eq!(MicrotonalAccidental::DOUBLE_SHARP.to_string(), "𝄪");
+    /// assert_eq!(MicrotonalAccidental::SESQUIFLAT.to_string(), "\u{E281}");
+    /// assert_eq!(MicrotonalAccidental::NATURAL.with_arrows(-3).to_string(), "\u{E2D6}");
+    ///
+    /// // a double sharp and a half sharp
+    /// assert_eq!(MicrotonalAccidental::from_quarter_tones(5).to_string(), "𝄪\u{E282}");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { quarter_tones, mut arrows } = *self;
+
+        if quarter_tones == 0 && arrows == 0 {
+            return write!(f, "♮");
+        }
+
+        // split off the quarter tone, which only sesquisharps and sesquiflats write on their own
+        let quarter = match quarter_tones {
+            q if q % 2 == 0 => 0,
+            q if q.abs() == 3 => q,
+            q => q.signum(),
+        };
+
+        let standard = quarter_tones - quarter;
+
+        if quarter == 0 && arrows != 0 && standard.abs() <= 4 {
+            let first = arrows.clamp(-3, 3);
+            write!(f, "{}", heji_glyph(standard, first))?;
+            arrows -= first;
+        } else if standard != 0 {
+            write!(f, "{}", AccidentalSign { offset: standard / 2 })?;
+        }
+
+        match quarter {
+            -3 => write!(f, "\u{E281}")?,
+            -1 => write!(f, "\u{E280}")?,
+            1 => write!(f, "\u{E282}")?,
+            3 => write!(f, "\u{E283}")?,
+            _ => {}
+        }
+
+        // any remaining arrows are written on naturals
+        while arrows != 0 {
+            let next = arrows.clamp(-3, 3);
+            write!(f, "{}", heji_glyph(0, next))?;
+            arrows -= next;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`MicrotonalAccidental`] or an [`EdoPitch`](super::EdoPitch) fails.
+///
+/// # Examples
+/// ```
+/// # use music_theory::tuning::{EdoPitch, MicrotonalAccidental, ParseMicrotonalError};
+/// assert_eq!("".parse::<MicrotonalAccidental>(), Err(ParseMicrotonalError::Empty));
+/// assert_eq!("♯?".parse::<MicrotonalAccidental>(), Err(ParseMicrotonalError::InvalidAccidental));
+/// assert_eq!("H4".parse::<EdoPitch<24>>(), Err(ParseMicrotonalError::InvalidLetter));
+/// assert_eq!("C\u{E282}".parse::<EdoPitch<24>>(), Err(ParseMicrotonalError::MissingOctave));
+///
+/// // 19-EDO has no quarter tones
+/// assert_eq!("C\u{E282}4".parse::<EdoPitch<19>>(), Err(ParseMicrotonalError::NotInEdo));
+/// ```
+#[derive(Debug, thiserror::Error, Eq, PartialEq, Copy, Clone)]
+pub enum ParseMicrotonalError {
+    /// The input string was empty.
+    #[error("The input was empty")]
+    Empty,
+    /// The pitch didn't start with a valid letter.
+    #[error("Letter must be A, B, C, D, E, F, or G")]
+    InvalidLetter,
+    /// The accidental had an unknown glyph, or a natural that wasn't on its own.
+    #[error("The accidental was invalid")]
+    InvalidAccidental,
+    /// The pitch had no octave.
+    #[error("The pitch didn't have an octave")]
+    MissingOctave,
+    /// The octave was invalid, or out of range.
+    #[error("The octave was invalid or out of range")]
+    InvalidOctave,
+    /// The pitch can't be written in this equal division of the octave,
+    /// such as a quarter tone in 19-EDO.
+    #[error("The pitch isn't in this equal division of the octave")]
+    NotInEdo,
+}
+
+impl FromStr for MicrotonalAccidental {
+    type Err = ParseMicrotonalError;
+
+    /// Parses an accidental from one or more glyphs, which are added together.
+    ///
+    /// Accepts the symbols that [`Note`](crate::Note) accepts, the Unicode quarter tone and arrow
+    /// accidentals (`𝄲`, `𝄳`, `𝄬`-`𝄱`), and the SMuFL standard, Stein-Zimmermann, and
+    /// Helmholtz-Ellis glyphs. A natural sign can only be written on its own.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::MicrotonalAccidental;
+    /// assert_eq!("𝄲".parse(), Ok(MicrotonalAccidental::HALF_SHARP));
+    /// assert_eq!("b\u{E280}".parse(), Ok(MicrotonalAccidental::SESQUIFLAT));
+    /// assert_eq!("𝄰".parse(), Ok(MicrotonalAccidental::SHARP.with_arrows(1)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseMicrotonalError::Empty);
+        }
+
+        let mut accidental = Self::NATURAL;
+        let mut natural = false;
+
+        for c in s.chars() {
+            let (quarter_tones, arrows) = parse_glyph(c).ok_or(ParseMicrotonalError::InvalidAccidental)?;
+
+            natural |= c == '♮' || c == '\u{E261}';
+
+            accidental.quarter_tones = accidental.quarter_tones.checked_add(quarter_tones)
+                .ok_or(ParseMicrotonalError::InvalidAccidental)?;
+
+            accidental.arrows = accidental.arrows.checked_add(arrows)
+                .ok_or(ParseMicrotonalError::InvalidAccidental)?;
+        }
+
+        if natural && s.chars().count() > 1 {
+            return Err(ParseMicrotonalError::InvalidAccidental);
+        }
+
+        Ok(accidental)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for quarter_tones in -9..=9 {
+            for arrows in -7..=7 {
+                let accidental = MicrotonalAccidental { quarter_tones, arrows };
+                assert_eq!(accidental.to_string().parse(), Ok(accidental), "{accidental:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn glyphs() {
+        let heji = [
+            ("\u{E2C0}", -4, -1),
+            ("\u{E2C4}", 4, -1),
+            ("\u{E2C7}", 0, 1),
+            ("\u{E2CB}", -2, -2),
+            ("\u{E2D3}", 4, 2),
+            ("\u{E2D4}", -4, -3),
+            ("\u{E2DD}", 4, 3),
+        ];
+
+        for (glyph, quarter_tones, arrows) in heji {
+            let accidental = MicrotonalAccidental { quarter_tones, arrows };
+
+            assert_eq!(glyph.parse(), Ok(accidental));
+            assert_eq!(accidental.to_string(), glyph);
+        }
+
+        assert_eq!("𝄳".parse(), Ok(MicrotonalAccidental::HALF_FLAT));
+        assert_eq!("\u{E262}\u{E282}".parse(), Ok(MicrotonalAccidental::SESQUISHARP));
+        assert_eq!("𝄯".parse(), Ok(MicrotonalAccidental::NATURAL.with_arrows(-1)));
+        assert_eq!("\u{E266}".parse(), Ok(MicrotonalAccidental::from_quarter_tones(-6)));
+
+        // too many arrows for one glyph
+        assert_eq!(MicrotonalAccidental::FLAT.with_arrows(5).to_string(), "\u{E2DA}\u{E2D1}");
+        // no glyph for a quarter tone with arrows
+        assert_eq!(MicrotonalAccidental::HALF_SHARP.with_arrows(1).to_string(), "\u{E282}\u{E2C7}");
+
+        assert_eq!("♮♯".parse::<MicrotonalAccidental>(), Err(ParseMicrotonalError::InvalidAccidental));
+    }
+}
@@ -0,0 +1,551 @@
+//! Tuning systems, for converting between notes and frequencies.
+//!
+//! A [`Tuning`] combines a [`Temperament`], which decides how far each pitch is from
+//! 12-tone equal temperament, with a reference note and its frequency, such as A4 = 440 Hz.
+//!
+//! Scales and keyboard mappings from Scala `.scl` and `.kbm` files can be read and written
+//! with [`ScalaScale`] and [`KeyboardMapping`], and combined into a [`ScalaTuning`].
+//!
+//! For tunings beyond twelve notes, [`EdoPitch`] is a pitch in any equal division of the octave,
+//! written with [`MicrotonalAccidental`]s such as half sharps and comma arrows.
+//!
+//! # Examples
+//! ```
+//! # use music_theory::{Note, Pitch};
+//! # use music_theory::tuning::{Temperament, Tuning};
+//! let e4 = Note::new(Pitch::E, 4);
+//!
+//! // baroque pitch
+//! let a415 = Tuning::equal(415.0);
+//! assert!((e4.as_frequency_hz_with(&a415) - 310.90).abs() < 0.01);
+//!
+//! // a just major third above C4 is exactly 5/4 of it
+//! let just = Tuning::new(Temperament::JustIntonation { tonic: Pitch::C }, 440.0);
+//! let ratio = just.frequency(e4) / just.frequency(Note::MIDDLE_C);
+//! assert!((ratio - 5.0 / 4.0).abs() < 1e-12);
+//! ```
+
+use crate::harmony::Key;
+use crate::pitch::Spelling;
+use crate::{Note, Pitch, PitchClass};
+
+mod scala;
+pub use scala::*;
+
+mod microtonal;
+pub use microtonal::*;
+
+mod edo;
+pub use edo::*;
+
+// a pure fifth (3/2) is this many cents wider than an equal tempered one
+const PURE_FIFTH: f64 = 1.955_000_865_387_433;
+// a pure major third (5/4) is this many cents narrower than an equal tempered one
+const PURE_THIRD: f64 = -13.686_286_135_165_3;
+// a quarter-comma meantone fifth is this many cents narrower than an equal tempered one
+const MEANTONE_FIFTH: f64 = -3.421_571_533_791_3;
+
+/// How far each pitch is from 12-tone equal temperament.
+///
+/// Temperaments built from a chain of fifths, like [`Pythagorean`](Self::Pythagorean), are
+/// spelling-aware, so C♯ and D♭ are tuned differently. The well temperaments and custom tables
+/// only depend on the [`PitchClass`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Temperament {
+    /// 12-tone equal temperament, where every semitone is the same size.
+    #[default]
+    Equal,
+    /// Every fifth from the tonic is pure (3/2), so the major thirds are wide.
+    Pythagorean {
+        /// The pitch the chain of fifths starts from.
+        tonic: Pitch,
+    },
+    /// Quarter-comma meantone, where every fifth from the tonic is narrowed by a quarter of a
+    /// syntonic comma, so that the major thirds are pure (5/4).
+    QuarterCommaMeantone {
+        /// The pitch the chain of fifths starts from.
+        tonic: Pitch,
+    },
+    /// 5-limit just intonation, where each pitch is tuned to a ratio of the tonic made from
+    /// pure fifths and major thirds, such as 9/8 for the second degree and 15/8 for the seventh.
+    ///
+    /// Each pitch is reached with at most two fifths up or one fifth down from the tonic, and
+    /// then by major thirds, so a major scale is tuned with the ratios
+    /// 1/1, 9/8, 5/4, 4/3, 3/2, 5/3, and 15/8.
+    JustIntonation {
+        /// The pitch the ratios are relative to.
+        tonic: Pitch,
+    },
+    /// Werckmeister III, where the fifths C–G–D–A and B–F♯ are narrowed by a quarter of a
+    /// Pythagorean comma, and the rest are pure.
+    WerckmeisterIII,
+    /// Kirnberger III, where the fifths C–G–D–A–E are narrowed by a quarter of a syntonic comma,
+    /// F♯–C♯ by a schisma, and the rest are pure.
+    KirnbergerIII,
+    /// Vallotti, where the fifths F–C–G–D–A–E–B are narrowed by a sixth of a Pythagorean comma,
+    /// and the rest are pure.
+    Vallotti,
+    /// A custom table of how many cents each pitch class is from equal temperament,
+    /// starting from C.
+    Offsets([f64; 12]),
+}
+
+impl Temperament {
+    /// Returns how many cents a pitch is from its equal tempered tuning, relative to the
+    /// temperament's tonic, or C if it doesn't have one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Pitch;
+    /// # use music_theory::tuning::Temperament;
+    /// let pythagorean = Temperament::Pythagorean { tonic: Pitch::C };
+    ///
+    /// // sharps are higher than their enharmonic flats
+    /// assert!(pythagorean.offset(Pitch::C_SHARP) > pythagorean.offset(Pitch::D_FLAT));
+    ///
+    /// assert_eq!(Temperament::Equal.offset(Pitch::C_SHARP), 0.0);
+    /// ```
+    pub fn offset(&self, pitch: Pitch) -> f64 {
+        let fifths_from = |tonic: Pitch| (pitch.as_fifths_from_c() - tonic.as_fifths_from_c()) as f64;
+
+        match *self {
+            Self::Equal => 0.0,
+            Self::Pythagorean { tonic } => fifths_from(tonic) * PURE_FIFTH,
+            Self::QuarterCommaMeantone { tonic } => fifths_from(tonic) * MEANTONE_FIFTH,
+            Self::JustIntonation { tonic } => {
+                let fifths = pitch.as_fifths_from_c() - tonic.as_fifths_from_c();
+
+                // four fifths up is a major third and two octaves up, so keep the fifths in [-1, 2]
+                let thirds = (fifths + 1).div_euclid(4);
+                let fifths = fifths - 4 * thirds;
+
+                fifths as f64 * PURE_FIFTH + thirds as f64 * PURE_THIRD
+            }
+            Self::WerckmeisterIII => Self::table(pitch, [
+                0.0, -9.775, -7.820, -5.865, -9.775, -1.955, -11.730, -3.910, -7.820, -11.730, -3.910, -7.820,
+            ]),
+            Self::KirnbergerIII => Self::table(pitch, [
+                0.0, -9.775, -6.843, -5.865, -13.686, -1.955, -9.776, -3.422, -7.820, -10.265, -3.910, -11.731,
+            ]),
+            Self::Vallotti => Self::table(pitch, [
+                0.0, -5.865, -3.910, -1.955, -7.820, 1.955, -7.820, -1.955, -3.910, -5.865, 0.0, -9.775,
+            ]),
+            Self::Offsets(offsets) => Self::table(pitch, offsets),
+        }
+    }
+
+    fn table(pitch: Pitch, offsets: [f64; 12]) -> f64 {
+        offsets[pitch.as_pitch_class() as usize]
+    }
+}
+
+/// A tuning system, which gives the frequency of every note.
+///
+/// The frequency of the [reference](Self::reference) note is fixed, and every other note is
+/// tuned relative to it with the [temperament](Self::temperament).
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// # use music_theory::tuning::{Temperament, Tuning};
+/// let meantone = Tuning::new(Temperament::QuarterCommaMeantone { tonic: Pitch::C }, 440.0);
+///
+/// // A4 is still 440 Hz
+/// assert_eq!(meantone.frequency(Note::A4), 440.0);
+///
+/// // G♯ and A♭ are different notes
+/// let g_sharp = meantone.frequency(Note::new(Pitch::G_SHARP, 4));
+/// let a_flat = meantone.frequency(Note::new(Pitch::A_FLAT, 4));
+/// assert!(a_flat - g_sharp > 5.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuning {
+    /// How far each pitch is from equal temperament.
+    pub temperament: Temperament,
+    /// The note the tuning is anchored to.
+    pub reference: Note,
+    /// The frequency of the reference note, in Hz.
+    pub reference_hz: f64,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::A440
+    }
+}
+
+impl Tuning {
+    /// Standard tuning: 12-tone equal temperament with A4 = 440 Hz.
+    pub const A440: Self = Self {
+        temperament: Temperament::Equal,
+        reference: Note::A4,
+        reference_hz: 440.0,
+    };
+
+    /// Creates a tuning with the given temperament, where A4 is `a4_hz`.
+    pub fn new(temperament: Temperament, a4_hz: f64) -> Self {
+        Self { temperament, reference: Note::A4, reference_hz: a4_hz }
+    }
+
+    /// Creates an equal tempered tuning where A4 is `a4_hz`, such as 415, 432, or 443 Hz.
+    pub fn equal(a4_hz: f64) -> Self {
+        Self::new(Temperament::Equal, a4_hz)
+    }
+
+    /// Returns the same tuning, anchored to a different reference note and frequency.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::tuning::Tuning;
+    /// // scientific pitch, with C4 = 256 Hz
+    /// let scientific = Tuning::A440.with_reference(Note::MIDDLE_C, 256.0);
+    /// assert_eq!(scientific.frequency(Note::MIDDLE_C), 256.0);
+    /// assert_eq!(scientific.frequency("C5".parse().unwrap()), 512.0);
+    /// ```
+    pub fn with_reference(self, reference: Note, reference_hz: f64) -> Self {
+        Self { reference, reference_hz, .. self }
+    }
+
+    // how many cents the note is above C0, in this temperament
+    fn cents_from_c0(&self, note: Note) -> f64 {
+        let semitones = Note::new(Pitch::C, 0).semitones_to(note).0 as f64;
+
+        semitones * 100.0 + self.temperament.offset(note.pitch)
+    }
+
+    /// Returns how many cents `note` is above the reference note. Negative if it's below.
+    pub fn cents_from_reference(&self, note: Note) -> f64 {
+        self.cents_from_c0(note) - self.cents_from_c0(self.reference)
+    }
+
+    /// Returns the frequency of a note, in Hz.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::tuning::{Temperament, Tuning};
+    /// let pythagorean = Tuning::new(Temperament::Pythagorean { tonic: Pitch::D }, 440.0);
+    ///
+    /// // a pure fifth above A4
+    /// let e5 = pythagorean.frequency(Note::new(Pitch::E, 5));
+    /// assert!((e5 - 660.0).abs() < 1e-9);
+    /// ```
+    pub fn frequency(&self, note: Note) -> f64 {
+        self.frequency_with_cents(note, 0.0)
+    }
+
+    /// Returns the frequency of a note raised by `cents`, or lowered if `cents` is negative, in Hz.
+    ///
+    /// This is the inverse of [`deviation`](Self::deviation).
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::tuning::Tuning;
+    /// // a quarter tone above A4
+    /// let hz = Tuning::A440.frequency_with_cents(Note::A4, 50.0);
+    /// assert!((hz - 452.893).abs() < 1e-3);
+    ///
+    /// // an octave is 1200 cents
+    /// assert_eq!(Tuning::A440.frequency_with_cents(Note::A4, -1200.0), 220.0);
+    /// ```
+    pub fn frequency_with_cents(&self, note: Note, cents: f64) -> f64 {
+        self.reference_hz * f64::exp2((self.cents_from_reference(note) + cents) / 1200.0)
+    }
+
+    /// Returns the note closest to a frequency in this tuning, spelled with sharps.
+    ///
+    /// Returns `None` if the frequency is non-positive or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::tuning::Tuning;
+    /// let a432 = Tuning::equal(432.0);
+    ///
+    /// assert_eq!(a432.nearest_note(432.0), Some(Note::A4));
+    /// assert_eq!(a432.nearest_note(440.0), Some(Note::A4));
+    /// assert_eq!(a432.nearest_note(458.0), Some(Note::new(Pitch::A_SHARP, 4)));
+    /// assert_eq!(a432.nearest_note(0.0), None);
+    /// ```
+    pub fn nearest_note(&self, hz: f64) -> Option<Note> {
+        self.deviation(hz, Spelling::Sharps).map(|d| d.note)
+    }
+
+    /// Returns the note closest to a frequency in this tuning, and how many cents the frequency
+    /// is from it.
+    ///
+    /// Notes that aren't natural are spelled with `spelling`. In spelling-aware temperaments,
+    /// this changes which notes there are to choose from, since C♯ and D♭ aren't tuned the same.
+    ///
+    /// Returns `None` if the frequency is non-positive or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::pitch::Spelling;
+    /// # use music_theory::tuning::Tuning;
+    /// let deviation = Tuning::A440.deviation(450.0, Spelling::Sharps).unwrap();
+    ///
+    /// assert_eq!(deviation.note, Note::A4);
+    /// assert!((deviation.cents - 38.906).abs() < 1e-3);
+    ///
+    /// // 23 cents flat
+    /// let deviation = Tuning::A440.deviation(460.0, Spelling::Flats).unwrap();
+    ///
+    /// assert_eq!(deviation.note, Note::new(Pitch::B_FLAT, 4));
+    /// assert!((deviation.cents + 23.04).abs() < 1e-2);
+    /// ```
+    pub fn deviation(&self, hz: f64, spelling: Spelling) -> Option<NoteDeviation> {
+        self.nearest(hz, |pc| pc.spell_with(spelling))
+    }
+
+    /// Returns the note closest to a frequency in this tuning, spelled in `key`, and how many cents
+    /// the frequency is from it.
+    ///
+    /// Notes in the key are spelled as they are in its scale, and other notes with the key's
+    /// accidentals, as with [`PitchClass::spell_in_key`].
+    ///
+    /// Returns `None` if the frequency is non-positive or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::{Note, Pitch};
+    /// # use music_theory::harmony::Key;
+    /// # use music_theory::tuning::Tuning;
+    /// // the leading tone of C♯ major
+    /// let deviation = Tuning::A440.deviation_in_key(523.0, Key::major(Pitch::C_SHARP)).unwrap();
+    ///
+    /// assert_eq!(deviation.note, Note::new(Pitch::B_SHARP, 4));
+    /// assert!(deviation.cents < 0.0);
+    /// ```
+    pub fn deviation_in_key(&self, hz: f64, key: Key) -> Option<NoteDeviation> {
+        self.nearest(hz, |pc| pc.spell_in_key(key))
+    }
+
+    // the closest note, trying every pitch class within half an octave of the equal tempered estimate
+    fn nearest(&self, hz: f64, spell: impl Fn(PitchClass) -> Pitch) -> Option<NoteDeviation> {
+        if hz <= 0.0 || !hz.is_finite() {
+            return None;
+        }
+
+        let cents = 1200.0 * (hz / self.reference_hz).log2() + self.cents_from_c0(self.reference);
+
+        if !cents.is_finite() {
+            return None;
+        }
+
+        let estimate = (cents / 100.0).round() as i32;
+
+        (estimate - 6..=estimate + 6)
+            .map(|semitones| {
+                let pitch_class = PitchClass::from_chroma(semitones.rem_euclid(12) as u8).expect("in range");
+                let pitch = spell(pitch_class);
+
+                // the octave of a spelled pitch can differ from the octave of its pitch class, as with B♯
+                let pitch_semitones = Note::new(Pitch::C, 0).semitones_to(Note::new(pitch, 0)).0 as i32;
+                let octave = (semitones - pitch_semitones).div_euclid(12) as i16;
+
+                let note = Note::new(pitch, octave);
+
+                NoteDeviation { note, cents: cents - self.cents_from_c0(note) }
+            })
+            .min_by(|a, b| a.cents.abs().total_cmp(&b.cents.abs()))
+    }
+}
+
+/// A note, and how far a frequency is from it, as returned by [`Tuning::deviation`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteDeviation {
+    /// The closest note.
+    pub note: Note,
+    /// How many cents the frequency is above the note, or below if negative.
+    /// Always between `-50.0` and `50.0` in equal temperament.
+    pub cents: f64,
+}
+
+impl NoteDeviation {
+    /// Returns the frequency this deviation is from, in Hz.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::pitch::Spelling;
+    /// # use music_theory::tuning::Tuning;
+    /// let deviation = Tuning::A440.deviation(450.0, Spelling::Sharps).unwrap();
+    /// assert!((deviation.frequency(&Tuning::A440) - 450.0).abs() < 1e-9);
+    /// ```
+    pub fn frequency(&self, tuning: &Tuning) -> f64 {
+        tuning.frequency_with_cents(self.note, self.cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn ratio(tuning: &Tuning, low: &str, high: &str) -> f64 {
+        tuning.frequency(note(high)) / tuning.frequency(note(low))
+    }
+
+    const TEMPERAMENTS: [Temperament; 8] = [
+        Temperament::Equal,
+        Temperament::Pythagorean { tonic: Pitch::C },
+        Temperament::QuarterCommaMeantone { tonic: Pitch::C },
+        Temperament::JustIntonation { tonic: Pitch::C },
+        Temperament::WerckmeisterIII,
+        Temperament::KirnbergerIII,
+        Temperament::Vallotti,
+        Temperament::Offsets([0.0, 10.0, -10.0, 20.0, -20.0, 30.0, -30.0, 40.0, -40.0, 45.0, -45.0, 5.0]),
+    ];
+
+    #[test]
+    fn reference_is_fixed() {
+        for temperament in TEMPERAMENTS {
+            for hz in [415.0, 432.0, 440.0, 443.0] {
+                let tuning = Tuning::new(temperament, hz);
+
+                assert_eq!(tuning.frequency(Note::A4), hz, "{temperament:?}");
+                assert!((ratio(&tuning, "A3", "A4") - 2.0).abs() < 1e-12, "{temperament:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn pure_intervals() {
+        let pythagorean = Tuning::new(Temperament::Pythagorean { tonic: Pitch::E_FLAT }, 440.0);
+
+        for (low, high) in [("C4", "G4"), ("B♭3", "F4"), ("F♯4", "C♯5"), ("D♭4", "A♭4")] {
+            assert!((ratio(&pythagorean, low, high) - 1.5).abs() < 1e-12, "{low}-{high}");
+        }
+
+        let meantone = Tuning::new(Temperament::QuarterCommaMeantone { tonic: Pitch::C }, 440.0);
+
+        for (low, high) in [("C4", "E4"), ("E♭4", "G4"), ("F♯4", "A♯4")] {
+            assert!((ratio(&meantone, low, high) - 1.25).abs() < 1e-12, "{low}-{high}");
+        }
+
+        // the quarter-comma meantone fifth is the fourth root of 5
+        assert!((ratio(&meantone, "C4", "G4") - 5f64.powf(0.25)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn just_intonation() {
+        let just = Tuning::new(Temperament::JustIntonation { tonic: Pitch::G }, 440.0);
+
+        let ratios = [
+            ("G4", 1.0), ("A4", 9.0 / 8.0), ("B4", 5.0 / 4.0), ("C5", 4.0 / 3.0), ("D5", 3.0 / 2.0),
+            ("E5", 5.0 / 3.0), ("F♯5", 15.0 / 8.0), ("B♭4", 6.0 / 5.0), ("E♭5", 8.0 / 5.0),
+            ("F5", 9.0 / 5.0), ("A♭4", 16.0 / 15.0), ("G♯4", 25.0 / 24.0), ("C♯5", 45.0 / 32.0),
+        ];
+
+        for (high, expected) in ratios {
+            assert!((ratio(&just, "G4", high) - expected).abs() < 1e-12, "{high}");
+        }
+    }
+
+    #[test]
+    fn well_temperaments() {
+        let werckmeister = Tuning::new(Temperament::WerckmeisterIII, 440.0);
+
+        // pure fifths outside of C-G-D-A and B-F♯
+        assert!((ratio(&werckmeister, "E4", "B4") - 1.5).abs() < 1e-4);
+        assert!((ratio(&werckmeister, "C4", "G4") - 1.5).abs() > 1e-3);
+
+        let kirnberger = Tuning::new(Temperament::KirnbergerIII, 440.0);
+
+        // C-E is a pure major third
+        assert!((ratio(&kirnberger, "C4", "E4") - 1.25).abs() < 1e-4);
+
+        let vallotti = Tuning::new(Temperament::Vallotti, 440.0);
+
+        assert!((ratio(&vallotti, "B4", "F♯5") - 1.5).abs() < 1e-4);
+        assert!((ratio(&vallotti, "F4", "C5") - 1.5).abs() > 1e-3);
+
+        // not spelling-aware
+        for tuning in [werckmeister, kirnberger, vallotti] {
+            assert_eq!(tuning.frequency(note("C♯4")), tuning.frequency(note("D♭4")));
+        }
+    }
+
+    #[test]
+    fn nearest_note() {
+        for temperament in TEMPERAMENTS {
+            for hz in [415.0, 440.0] {
+                let tuning = Tuning::new(temperament, hz);
+
+                for midi in 12..120 {
+                    let note = Note::from_midi(midi);
+
+                    assert_eq!(tuning.nearest_note(tuning.frequency(note)), Some(note), "{temperament:?} {hz}");
+                }
+            }
+        }
+
+        let tuning = Tuning::A440;
+        assert_eq!(tuning.nearest_note(f64::NAN), None);
+        assert_eq!(tuning.deviation(f64::INFINITY, Spelling::Flats), None);
+        assert_eq!(tuning.nearest_note(-1.0), None);
+        assert!(tuning.nearest_note(f64::MIN_POSITIVE).is_some());
+    }
+
+    #[test]
+    fn deviation() {
+        for temperament in TEMPERAMENTS {
+            let tuning = Tuning::new(temperament, 442.0);
+
+            for midi in 24..108 {
+                for cents in [-45.0, -12.5, 0.0, 3.0, 49.0] {
+                    for spelling in [Spelling::Sharps, Spelling::Flats] {
+                        let note = Note::from_midi(midi).respell_with(spelling);
+                        let hz = tuning.frequency_with_cents(note, cents);
+
+                        let deviation = tuning.deviation(hz, spelling).unwrap();
+
+                        // custom offsets can make other notes closer
+                        if temperament == Temperament::Equal {
+                            assert_eq!(deviation.note, note);
+                            assert!((deviation.cents - cents).abs() < 1e-6);
+                        }
+
+                        assert!((deviation.frequency(&tuning) - hz).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spelling_aware_deviation() {
+        let pythagorean = Tuning::new(Temperament::Pythagorean { tonic: Pitch::C }, 440.0);
+
+        let c_sharp = note("C♯5");
+        let hz = pythagorean.frequency(c_sharp);
+
+        let sharp = pythagorean.deviation(hz, Spelling::Sharps).unwrap();
+        assert_eq!(sharp.note, c_sharp);
+        assert!(sharp.cents.abs() < 1e-9);
+
+        // D♭ is a Pythagorean comma lower than C♯
+        let flat = pythagorean.deviation(hz, Spelling::Flats).unwrap();
+        assert_eq!(flat.note, note("D♭5"));
+        assert!((flat.cents - 23.46).abs() < 1e-2);
+
+        let key = Key::minor(Pitch::D_SHARP);
+        let deviation = pythagorean.deviation_in_key(pythagorean.frequency(note("C♯5")), key).unwrap();
+        assert_eq!(deviation.note, note("C♯5"));
+
+        let deviation = Tuning::A440.deviation_in_key(Tuning::A440.frequency(note("F4")), Key::major(Pitch::C_SHARP)).unwrap();
+        assert_eq!(deviation.note, note("E♯4"));
+
+        let deviation = Tuning::A440.deviation_in_key(Tuning::A440.frequency(note("B3")), Key::major(Pitch::G_FLAT)).unwrap();
+        assert_eq!(deviation.note, note("C♭4"));
+    }
+}
@@ -0,0 +1,715 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::Note;
+
+/// An error returned when a Scala `.scl` or `.kbm` file can't be parsed.
+///
+/// The [`line`](Self::line) is the 1-based line number responsible for the error,
+/// or one past the last line if the file ended too early.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+#[error("{kind} (on line {line})")]
+pub struct ParseScalaError {
+    pub kind: ParseScalaErrorKind,
+    pub line: usize,
+}
+
+/// The reason a Scala file couldn't be parsed.
+#[derive(Copy, Clone, Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ParseScalaErrorKind {
+    /// The file ended before every required line was read.
+    #[error("The file ended too early")]
+    UnexpectedEnd,
+    /// The number of pitches wasn't a non-negative integer.
+    #[error("The number of pitches wasn't a non-negative integer")]
+    InvalidCount,
+    /// A pitch was neither a number of cents, which must contain a `.`, nor a ratio.
+    #[error("The pitch wasn't a number of cents or a ratio")]
+    InvalidPitch,
+    /// A ratio had a zero numerator or denominator.
+    #[error("Ratios must be positive")]
+    NonPositiveRatio,
+    /// There were more pitches than the number of pitches given.
+    #[error("There were more pitches than the number of pitches given")]
+    TooManyPitches,
+    /// A keyboard mapping field wasn't a valid MIDI note number, from 0 to 127.
+    #[error("Expected a MIDI note number")]
+    InvalidNote,
+    /// A keyboard mapping field wasn't a non-negative integer.
+    #[error("Expected a non-negative integer")]
+    InvalidInteger,
+    /// The reference frequency wasn't a positive number.
+    #[error("Expected a positive frequency")]
+    InvalidFrequency,
+    /// A keyboard mapping entry was neither a scale degree nor `x`.
+    #[error("The mapping entry wasn't a scale degree or 'x'")]
+    InvalidMapping,
+    /// There were more mapping entries than the size of the map.
+    #[error("There were more mapping entries than the size of the map")]
+    TooManyMappings,
+}
+
+/// A pitch in a [`ScalaScale`], measured from the first degree of the scale.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScalaPitch {
+    /// A number of cents, written with a `.`, such as `701.955`.
+    Cents(f64),
+    /// A frequency ratio, written as `3/2`, or as a whole number such as `2`.
+    Ratio(u64, u64),
+}
+
+impl ScalaPitch {
+    /// Returns the size of the pitch in cents.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::ScalaPitch;
+    /// assert_eq!(ScalaPitch::Ratio(2, 1).cents(), 1200.0);
+    /// assert_eq!(ScalaPitch::Cents(701.955).cents(), 701.955);
+    /// ```
+    pub fn cents(self) -> f64 {
+        match self {
+            Self::Cents(cents) => cents,
+            Self::Ratio(numerator, denominator) => 1200.0 * (numerator as f64 / denominator as f64).log2(),
+        }
+    }
+}
+
+impl fmt::Display for ScalaPitch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            // cents must contain a '.' to not be read as a ratio
+            Self::Cents(cents) if cents.fract() == 0.0 => write!(f, "{cents}.0"),
+            Self::Cents(cents) => write!(f, "{cents}"),
+            Self::Ratio(numerator, 1) => write!(f, "{numerator}"),
+            Self::Ratio(numerator, denominator) => write!(f, "{numerator}/{denominator}"),
+        }
+    }
+}
+
+impl FromStr for ScalaPitch {
+    type Err = ParseScalaErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ParseScalaErrorKind as E;
+
+        // anything after the pitch is a label
+        let s = s.split_whitespace().next().ok_or(E::InvalidPitch)?;
+
+        if s.contains('.') {
+            return s.parse::<f64>()
+                .ok()
+                .filter(|c| c.is_finite())
+                .map(Self::Cents)
+                .ok_or(E::InvalidPitch);
+        }
+
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+
+        let parse = |n: &str| n.parse::<u64>().map_err(|_| E::InvalidPitch);
+        let (numerator, denominator) = (parse(numerator)?, parse(denominator)?);
+
+        if numerator == 0 || denominator == 0 {
+            return Err(E::NonPositiveRatio);
+        }
+
+        Ok(Self::Ratio(numerator, denominator))
+    }
+}
+
+// the lines of a file that aren't comments, with their line numbers
+fn lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.starts_with('!'))
+}
+
+/// A scale from a Scala `.scl` file.
+///
+/// The first degree of the scale, `1/1`, is implied, so [`pitches`](Self::pitches) starts from the
+/// second degree. The last pitch is the period the scale repeats at, usually an octave (`2/1`).
+///
+/// The scale can be parsed with [`FromStr`], and written back with [`Display`](fmt::Display).
+///
+/// # Examples
+/// ```
+/// # use music_theory::tuning::{ScalaPitch, ScalaScale};
+/// let scale = "\
+/// ! pentatonic.scl
+/// !
+/// A just pentatonic scale
+///  5
+/// !
+///  9/8
+///  5/4
+///  3/2
+///  5/3
+///  2/1
+/// ".parse::<ScalaScale>().unwrap();
+///
+/// assert_eq!(scale.description, "A just pentatonic scale");
+/// assert_eq!(scale.pitches[2], ScalaPitch::Ratio(3, 2));
+/// assert_eq!(scale.period(), 1200.0);
+///
+/// assert_eq!(scale.to_string().parse::<ScalaScale>(), Ok(scale));
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalaScale {
+    /// A one line description of the scale.
+    pub description: String,
+    /// The pitches of the scale after the first degree, ending with the period.
+    pub pitches: Vec<ScalaPitch>,
+}
+
+impl ScalaScale {
+    /// Returns the period of the scale in cents, which is the last pitch,
+    /// or `0.0` if the scale has no pitches.
+    pub fn period(&self) -> f64 {
+        self.pitches.last().map_or(0.0, |p| p.cents())
+    }
+
+    /// Returns the number of cents a scale degree is above the first degree.
+    ///
+    /// Degrees past the end of the scale continue in the next period, and
+    /// negative degrees are in the previous period.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::tuning::{ScalaPitch, ScalaScale};
+    /// let scale = ScalaScale {
+    ///     description: "Three notes".to_owned(),
+    ///     pitches: vec![ScalaPitch::Cents(400.0), ScalaPitch::Cents(700.0), ScalaPitch::Ratio(2, 1)],
+    /// };
+    ///
+    /// assert_eq!(scale.degree_cents(0), 0.0);
+    /// assert_eq!(scale.degree_cents(2), 700.0);
+    /// assert_eq!(scale.degree_cents(4), 1600.0);
+    /// assert_eq!(scale.degree_cents(-1), -500.0);
+    /// ```
+    pub fn degree_cents(&self, degree: i32) -> f64 {
+        let len = self.pitches.len() as i32;
+
+        if len == 0 {
+            return 0.0;
+        }
+
+        let periods = degree.div_euclid(len) as f64;
+
+        let cents = match degree.rem_euclid(len) {
+            0 => 0.0,
+            i => self.pitches[i as usize - 1].cents(),
+        };
+
+        periods * self.period() + cents
+    }
+}
+
+impl fmt::Display for ScalaScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.description)?;
+        writeln!(f, " {}", self.pitches.len())?;
+        writeln!(f, "!")?;
+
+        for pitch in &self.pitches {
+            writeln!(f, " {pitch}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for ScalaScale {
+    type Err = ParseScalaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ParseScalaErrorKind as E;
+
+        let end = s.lines().count() + 1;
+        let mut lines = lines(s);
+
+        let error = |kind, line| ParseScalaError { kind, line };
+
+        let (_, description) = lines.next().ok_or(error(E::UnexpectedEnd, end))?;
+
+        let (line, count) = lines.next().ok_or(error(E::UnexpectedEnd, end))?;
+
+        let count = count.split_whitespace()
+            .next()
+            .and_then(|c| c.parse::<usize>().ok())
+            .ok_or(error(E::InvalidCount, line))?;
+
+        let pitches = lines.by_ref()
+            .take(count)
+            .map(|(line, pitch)| pitch.parse().map_err(|kind| error(kind, line)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if pitches.len() < count {
+            return Err(error(E::UnexpectedEnd, end));
+        }
+
+        if let Some((line, _)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+            return Err(error(E::TooManyPitches, line));
+        }
+
+        Ok(Self { description: description.trim().to_owned(), pitches })
+    }
+}
+
+// parses the next header field of a keyboard mapping
+fn parse_field<'a, T>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    end: usize,
+    parse: impl Fn(&str) -> Option<T>,
+    kind: ParseScalaErrorKind,
+) -> Result<T, ParseScalaError> {
+    let (line, value) = lines.next().ok_or(ParseScalaError { kind: ParseScalaErrorKind::UnexpectedEnd, line: end })?;
+    parse(value).ok_or(ParseScalaError { kind, line })
+}
+
+/// A keyboard mapping from a Scala `.kbm` file, which decides which scale degree each MIDI
+/// note plays, and the frequency the scale is tuned to.
+///
+/// The [`Default`] mapping is the usual one for 12 note scales, with middle C (60) on the first
+/// degree, every note mapped to the next degree, and A4 (69) at 440 Hz.
+///
+/// The mapping can be parsed with [`FromStr`], and written back with [`Display`](fmt::Display).
+///
+/// # Examples
+/// ```
+/// # use music_theory::tuning::KeyboardMapping;
+/// let mapping = "\
+/// ! white keys only
+/// 12
+/// 0
+/// 127
+/// 60
+/// 69
+/// 440.0
+/// 7
+/// 0
+/// x
+/// 1
+/// x
+/// 2
+/// 3
+/// x
+/// 4
+/// x
+/// 5
+/// x
+/// 6
+/// ".parse::<KeyboardMapping>().unwrap();
+///
+/// assert_eq!(mapping.mapping[1], None);
+/// assert_eq!(mapping.mapping[2], Some(1));
+/// assert_eq!(mapping.to_string().parse::<KeyboardMapping>(), Ok(mapping));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyboardMapping {
+    /// The lowest MIDI note that's tuned.
+    pub first_note: u8,
+    /// The highest MIDI note that's tuned.
+    pub last_note: u8,
+    /// The MIDI note the first entry of the mapping is for.
+    pub middle_note: u8,
+    /// The MIDI note [`reference_hz`](Self::reference_hz) is the frequency of.
+    pub reference_note: u8,
+    /// The frequency of the reference note, in Hz.
+    pub reference_hz: f64,
+    /// The scale degree that the mapping repeats at, or `0` to use the period of the scale.
+    pub octave_degree: usize,
+    /// The scale degree each MIDI note is mapped to, starting from the middle note and repeating
+    /// every `mapping.len()` notes, with `None` for notes that aren't tuned.
+    ///
+    /// If this is empty, every note is mapped to the next scale degree.
+    pub mapping: Vec<Option<usize>>,
+}
+
+impl Default for KeyboardMapping {
+    fn default() -> Self {
+        Self {
+            first_note: 0,
+            last_note: 127,
+            middle_note: 60,
+            reference_note: 69,
+            reference_hz: 440.0,
+            octave_degree: 0,
+            mapping: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for KeyboardMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reference_hz = ScalaPitch::Cents(self.reference_hz);
+
+        writeln!(f, "! Size of map:")?;
+        writeln!(f, "{}", self.mapping.len())?;
+        writeln!(f, "! First MIDI note number to retune:")?;
+        writeln!(f, "{}", self.first_note)?;
+        writeln!(f, "! Last MIDI note number to retune:")?;
+        writeln!(f, "{}", self.last_note)?;
+        writeln!(f, "! Middle note where the first entry of the mapping is mapped to:")?;
+        writeln!(f, "{}", self.middle_note)?;
+        writeln!(f, "! Reference note for which frequency is given:")?;
+        writeln!(f, "{}", self.reference_note)?;
+        writeln!(f, "! Frequency to tune the above note to:")?;
+        // written like cents, so it always has a decimal point
+        writeln!(f, "{reference_hz}")?;
+        writeln!(f, "! Scale degree to consider as formal octave:")?;
+        writeln!(f, "{}", self.octave_degree)?;
+        writeln!(f, "! Mapping:")?;
+
+        for degree in &self.mapping {
+            match degree {
+                Some(degree) => writeln!(f, "{degree}")?,
+                None => writeln!(f, "x")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for KeyboardMapping {
+    type Err = ParseScalaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ParseScalaErrorKind as E;
+
+        let end = s.lines().count() + 1;
+
+        // blank lines aren't allowed in the header, but can be left at the end
+        let mut lines = lines(s).map(|(line, s)| (line, s.split_whitespace().next().unwrap_or("")));
+
+        let error = |kind, line| ParseScalaError { kind, line };
+
+        let integer = |s: &str| s.parse::<usize>().ok();
+        let note = |s: &str| s.parse::<u8>().ok().filter(|&n| n < 128);
+        let frequency = |s: &str| s.parse::<f64>().ok().filter(|hz| *hz > 0.0 && hz.is_finite());
+
+        let size = parse_field(&mut lines, end, integer, E::InvalidInteger)?;
+        let first_note = parse_field(&mut lines, end, note, E::InvalidNote)?;
+        let last_note = parse_field(&mut lines, end, note, E::InvalidNote)?;
+        let middle_note = parse_field(&mut lines, end, note, E::InvalidNote)?;
+        let reference_note = parse_field(&mut lines, end, note, E::InvalidNote)?;
+        let reference_hz = parse_field(&mut lines, end, frequency, E::InvalidFrequency)?;
+        let octave_degree = parse_field(&mut lines, end, integer, E::InvalidInteger)?;
+
+        let mut mapping = Vec::new();
+
+        for (line, entry) in lines.filter(|(_, entry)| !entry.is_empty()) {
+            if mapping.len() == size {
+                return Err(error(E::TooManyMappings, line));
+            }
+
+            let degree = match entry {
+                "x" | "X" => None,
+                n => Some(n.parse::<usize>().map_err(|_| error(E::InvalidMapping, line))?),
+            };
+
+            mapping.push(degree);
+        }
+
+        // unmapped notes at the end can be left out
+        mapping.resize(size, None);
+
+        Ok(Self { first_note, last_note, middle_note, reference_note, reference_hz, octave_degree, mapping })
+    }
+}
+
+/// A tuning made from a Scala scale and keyboard mapping, which gives the frequency of each MIDI note.
+///
+/// # Examples
+/// ```
+/// # use music_theory::{Note, Pitch};
+/// # use music_theory::tuning::{KeyboardMapping, ScalaPitch, ScalaScale, ScalaTuning};
+/// // 19 tone equal temperament
+/// let scale = ScalaScale {
+///     description: "19-EDO".to_owned(),
+///     pitches: (1..=19).map(|i| ScalaPitch::Cents(i as f64 * 1200.0 / 19.0)).collect(),
+/// };
+///
+/// let mapping = KeyboardMapping { reference_note: 60, reference_hz: 261.0, ..Default::default() };
+/// let tuning = ScalaTuning::new(scale, mapping);
+///
+/// assert_eq!(tuning.frequency(60), Some(261.0));
+///
+/// // 19 keys up is an octave
+/// assert!((tuning.frequency(79).unwrap() - 522.0).abs() < 1e-9);
+///
+/// // MIDI notes can come from notes
+/// let c5 = Note::new(Pitch::C, 5);
+/// assert!(tuning.note_frequency(c5).unwrap() < 522.0);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalaTuning {
+    /// The scale.
+    pub scale: ScalaScale,
+    /// How MIDI notes are mapped to the scale.
+    pub mapping: KeyboardMapping,
+}
+
+impl ScalaTuning {
+    /// Creates a tuning from a scale and keyboard mapping.
+    pub fn new(scale: ScalaScale, mapping: KeyboardMapping) -> Self {
+        Self { scale, mapping }
+    }
+
+    // how many cents a MIDI note is above the middle note, or None if it isn't mapped
+    fn cents(&self, midi: u8) -> Option<f64> {
+        let steps = midi as i32 - self.mapping.middle_note as i32;
+
+        if self.mapping.mapping.is_empty() {
+            return Some(self.scale.degree_cents(steps));
+        }
+
+        let size = self.mapping.mapping.len() as i32;
+        let degree = self.mapping.mapping[steps.rem_euclid(size) as usize]?;
+
+        let octave = match self.mapping.octave_degree {
+            0 => self.scale.period(),
+            degree => self.scale.degree_cents(degree as i32),
+        };
+
+        Some(steps.div_euclid(size) as f64 * octave + self.scale.degree_cents(degree as i32))
+    }
+
+    /// Returns the frequency of a MIDI note in Hz.
+    ///
+    /// Returns `None` if the note is outside of the mapping's range, isn't mapped to a scale degree,
+    /// or if the reference note isn't mapped, so there's nothing to tune the scale to.
+    ///
+    /// # Examples
+    /// ```
+    /// # use music_theory::Note;
+    /// # use music_theory::tuning::{KeyboardMapping, ScalaTuning};
+    /// # use music_theory::tuning::ScalaScale;
+    /// let scale = "Pythagorean pentatonic\n5\n9/8\n81/64\n3/2\n27/16\n2/1".parse::<ScalaScale>().unwrap();
+    ///
+    /// let mapping = KeyboardMapping { first_note: 21, last_note: 108, ..Default::default() };
+    /// let tuning = ScalaTuning::new(scale, mapping);
+    ///
+    /// // A4 is 9 keys above middle C, so the fourth degree of the second octave
+    /// assert_eq!(tuning.frequency(69), Some(440.0));
+    /// assert!((tuning.frequency(70).unwrap() - 440.0 * 32.0 / 27.0).abs() < 1e-9);
+    ///
+    /// // below the lowest key of a piano
+    /// assert_eq!(tuning.frequency(20), None);
+    /// ```
+    pub fn frequency(&self, midi: u8) -> Option<f64> {
+        if !(self.mapping.first_note..=self.mapping.last_note).contains(&midi) {
+            return None;
+        }
+
+        let cents = self.cents(midi)? - self.cents(self.mapping.reference_note)?;
+
+        Some(self.mapping.reference_hz * f64::exp2(cents / 1200.0))
+    }
+
+    /// Returns the frequency of a note in Hz, using its MIDI note number.
+    ///
+    /// Returns `None` if the note is outside of MIDI range, or for the same reasons as
+    /// [`frequency`](Self::frequency).
+    pub fn note_frequency(&self, note: Note) -> Option<f64> {
+        self.frequency(note.as_midi_strict()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pitch;
+    use crate::tuning::{Temperament, Tuning};
+
+    const MEANTONE: &str = "\
+! meanquar.scl
+!
+1/4-comma meantone scale. Pietro Aaron's temperament (1523)
+ 12
+!
+ 76.04900
+ 193.15686
+ 310.26471
+ 5/4
+ 503.42157
+ 579.47057
+ 696.57843
+ 25/16
+ 889.73529
+ 1006.84314
+ 1082.89214
+ 2/1
+";
+
+    #[test]
+    fn parse_scale() {
+        let scale = MEANTONE.parse::<ScalaScale>().unwrap();
+
+        assert_eq!(scale.description, "1/4-comma meantone scale. Pietro Aaron's temperament (1523)");
+        assert_eq!(scale.pitches.len(), 12);
+        assert_eq!(scale.pitches[0], ScalaPitch::Cents(76.049));
+        assert_eq!(scale.pitches[3], ScalaPitch::Ratio(5, 4));
+        assert_eq!(scale.pitches[11], ScalaPitch::Ratio(2, 1));
+
+        // labels after pitches, negative cents, and whole number ratios
+        let scale = "\n3\n-5.5 flat unison\n  3 a twelfth\n2/1\n\n".parse::<ScalaScale>().unwrap();
+
+        assert_eq!(scale.description, "");
+        assert_eq!(scale.pitches, [ScalaPitch::Cents(-5.5), ScalaPitch::Ratio(3, 1), ScalaPitch::Ratio(2, 1)]);
+
+        // no pitches
+        assert_eq!("empty\n0".parse::<ScalaScale>().unwrap().pitches, []);
+    }
+
+    #[test]
+    fn round_trip() {
+        let scales = [
+            MEANTONE.parse::<ScalaScale>().unwrap(),
+            ScalaScale {
+                description: "odd cents".to_owned(),
+                pitches: vec![
+                    ScalaPitch::Cents(100.0),
+                    ScalaPitch::Cents(-0.000_001),
+                    ScalaPitch::Cents(1.0 / 3.0),
+                    ScalaPitch::Ratio(81, 80),
+                    ScalaPitch::Ratio(3, 1),
+                    ScalaPitch::Cents(1e10),
+                ],
+            },
+            ScalaScale::default(),
+        ];
+
+        for scale in scales {
+            assert_eq!(scale.to_string().parse::<ScalaScale>(), Ok(scale.clone()), "{scale}");
+        }
+
+        let mappings = [
+            KeyboardMapping::default(),
+            KeyboardMapping {
+                first_note: 12,
+                last_note: 100,
+                middle_note: 48,
+                reference_note: 57,
+                reference_hz: 415.3,
+                octave_degree: 7,
+                mapping: vec![Some(0), None, Some(1), Some(12), None],
+            },
+        ];
+
+        for mapping in mappings {
+            assert_eq!(mapping.to_string().parse::<KeyboardMapping>(), Ok(mapping.clone()), "{mapping}");
+        }
+    }
+
+    #[test]
+    fn scale_errors() {
+        use ParseScalaErrorKind as E;
+
+        let cases = [
+            ("", E::UnexpectedEnd, 1),
+            ("! only a comment\n", E::UnexpectedEnd, 2),
+            ("description\n", E::UnexpectedEnd, 2),
+            ("description\n!\ntwelve\n", E::InvalidCount, 3),
+            ("description\n-1\n", E::InvalidCount, 2),
+            ("description\n2\n100.0\n", E::UnexpectedEnd, 4),
+            ("description\n2\n100.0\n!\nthree halves\n", E::InvalidPitch, 5),
+            ("description\n2\n100.0\n3/0\n", E::NonPositiveRatio, 4),
+            ("description\n2\n100.0\n-3/2\n", E::InvalidPitch, 4),
+            ("description\n2\n100.0\n1.2.3\n", E::InvalidPitch, 4),
+            ("description\n1\n2/1\n\n3/1\n", E::TooManyPitches, 5),
+        ];
+
+        for (input, kind, line) in cases {
+            assert_eq!(input.parse::<ScalaScale>(), Err(ParseScalaError { kind, line }), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn mapping_errors() {
+        use ParseScalaErrorKind as E;
+
+        let header = |lines: &[&str]| lines.join("\n");
+
+        let cases = [
+            (header(&["12", "0", "127", "60"]), E::UnexpectedEnd, 5),
+            (header(&["twelve"]), E::InvalidInteger, 1),
+            (header(&["12", "0", "128"]), E::InvalidNote, 3),
+            (header(&["12", "0", "127", "60", "69", "-440.0"]), E::InvalidFrequency, 6),
+            (header(&["12", "0", "127", "60", "69", "", "0"]), E::InvalidFrequency, 6),
+            (header(&["2", "0", "127", "60", "69", "440", "0", "0", "y"]), E::InvalidMapping, 9),
+            (header(&["1", "0", "127", "60", "69", "440", "0", "0", "!", "1"]), E::TooManyMappings, 10),
+        ];
+
+        for (input, kind, line) in cases {
+            assert_eq!(input.parse::<KeyboardMapping>(), Err(ParseScalaError { kind, line }), "{input:?}");
+        }
+
+        // unmapped keys at the end can be left out
+        let mapping = header(&["3", "0", "127", "60", "69", "440", "0", "0"]).parse::<KeyboardMapping>().unwrap();
+        assert_eq!(mapping.mapping, [Some(0), None, None]);
+    }
+
+    #[test]
+    fn frequencies() {
+        let equal = ScalaScale {
+            description: "12-EDO".to_owned(),
+            pitches: (1..=12).map(|i| ScalaPitch::Cents(i as f64 * 100.0)).collect(),
+        };
+
+        let tuning = ScalaTuning::new(equal, KeyboardMapping::default());
+
+        for midi in 0..128 {
+            let expected = Tuning::A440.frequency(Note::from_midi(midi));
+            assert!((tuning.frequency(midi).unwrap() - expected).abs() < 1e-9, "{midi}");
+        }
+
+        // meantone matches the temperament, with C on middle C
+        let meantone = ScalaTuning::new(MEANTONE.parse().unwrap(), KeyboardMapping::default());
+        let temperament = Tuning::new(Temperament::QuarterCommaMeantone { tonic: Pitch::C }, 440.0);
+
+        for note in ["C4", "E♭4", "F♯3", "G♯5", "B♭2"] {
+            let note = note.parse::<Note>().unwrap();
+            let expected = temperament.frequency(note);
+
+            assert!((meantone.note_frequency(note).unwrap() - expected).abs() < 1e-3, "{note:?}");
+        }
+    }
+
+    #[test]
+    fn mapped_frequencies() {
+        // a 7 note scale on the white keys
+        let scale = "just major\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1".parse::<ScalaScale>().unwrap();
+
+        let mapping = KeyboardMapping {
+            first_note: 36,
+            last_note: 96,
+            middle_note: 60,
+            reference_note: 60,
+            reference_hz: 264.0,
+            octave_degree: 7,
+            mapping: vec![Some(0), None, Some(1), None, Some(2), Some(3), None, Some(4), None, Some(5), None, Some(6)],
+        };
+
+        let tuning = ScalaTuning::new(scale, mapping);
+        let hz = |s: &str| tuning.note_frequency(s.parse().unwrap());
+
+        assert_eq!(hz("C4"), Some(264.0));
+        assert_eq!(hz("C♯4"), None);
+        assert!((hz("A4").unwrap() - 440.0).abs() < 1e-9);
+        assert!((hz("B3").unwrap() - 247.5).abs() < 1e-9);
+        assert!((hz("E5").unwrap() - 660.0).abs() < 1e-9);
+
+        // outside of the mapped range
+        assert_eq!(hz("B1"), None);
+        assert_eq!(tuning.note_frequency(Note::new(Pitch::C, 10)), None);
+
+        // the reference note must be mapped
+        let tuning = ScalaTuning::new(tuning.scale, KeyboardMapping { reference_note: 61, ..tuning.mapping });
+        assert_eq!(tuning.frequency(60), None);
+    }
+}